@@ -5,6 +5,7 @@ use crate::{
     Session,
     Value,
     lower_expr,
+    mark_in_place_updates,
 };
 use sodigy_mir::{self as mir, Intrinsic};
 use sodigy_span::Span;
@@ -126,6 +127,7 @@ impl Assert {
                 dst: Memory::Return,  // don't care
                 debug_info: None,
             });
+            mark_in_place_updates(&mut bytecodes);
         }
 
         Assert {
@@ -96,9 +96,10 @@ impl Display for Bytecode {
             ),
             Bytecode::Label(label) => write!(fmt, "label {label}:"),
             Bytecode::Return(ssa) => write!(fmt, "return {ssa};"),
-            Bytecode::Update { src, size: _, index, value, dst } => write!(
+            Bytecode::Update { src, size: _, index, value, dst, in_place } => write!(
                 fmt,
-                "{dst} = {src} `{index} {value};",
+                "{dst} = {src} `{index} {value};{}",
+                if *in_place { "  // in place" } else { "" },
             ),
             Bytecode::Intrinsic { intrinsic, args, dst, debug_info } => write!(
                 fmt,
@@ -166,6 +167,13 @@ impl Display for Value {
                 None => write!(fmt, "%f(@S{:09x})", def_span.hash() & 0xfff_fff_fff),
             },
             Value::Span(s) => write!(fmt, "%sp({:09x})", s.hash() & 0xfff_fff_fff),
+            Value::String { binary, bytes } => write!(
+                fmt,
+                "%{}({:?})",
+                if *binary { "b" } else { "str" },
+                String::from_utf8_lossy(bytes),
+            ),
+            Value::Pooled(i) => write!(fmt, "%p({i})"),
         }
     }
 }
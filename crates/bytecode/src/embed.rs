@@ -0,0 +1,47 @@
+use crate::{Bytecode, Memory, Session, Value};
+use sodigy_span::Span;
+use std::collections::HashMap;
+
+impl Session<'_, '_> {
+    // The code generator evaluates top-level `let`s at compile time (if it can), and
+    // this function embeds the values.
+    //
+    // A use of a top-level `let` is lowered to
+    //
+    // ```
+    // init_or_jump(def_span, @let, @L0);
+    // global(def_span) = return;
+    // label @L0;
+    // ```
+    //
+    // , and the first 2 bytecodes become `global(def_span) = <value>;`. Then nobody calls
+    // the `let`, so the linker doesn't link it.
+    pub fn embed_const_lets(&mut self, values: &HashMap<Span, Value>) {
+        for bytecodes in self.funcs.iter_mut().map(
+            |func| &mut func.bytecodes
+        ).chain(
+            self.lets.iter_mut().map(|r#let| &mut r#let.bytecodes)
+        ).chain(
+            self.asserts.iter_mut().map(|assert| &mut assert.bytecodes)
+        ) {
+            let mut i = 0;
+
+            while i + 1 < bytecodes.len() {
+                if let Bytecode::InitOrJump { def_span, .. } = &bytecodes[i] &&
+                    let Some(value) = values.get(def_span) &&
+                    let Bytecode::Move { src: Memory::Return, dst: Memory::Global(dst) } = &bytecodes[i + 1] &&
+                    dst == def_span
+                {
+                    bytecodes[i] = Bytecode::Const {
+                        value: value.clone(),
+                        dst: Memory::Global(def_span.clone()),
+                        debug_info: None,
+                    };
+                    bytecodes.remove(i + 1);
+                }
+
+                i += 1;
+            }
+        }
+    }
+}
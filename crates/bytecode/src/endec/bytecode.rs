@@ -71,13 +71,14 @@ impl Endec for Bytecode {
                 buffer.push(9);
                 ssa.encode_impl(buffer);
             },
-            Bytecode::Update { src, size, index, value, dst } => {
+            Bytecode::Update { src, size, index, value, dst, in_place } => {
                 buffer.push(10);
                 src.encode_impl(buffer);
                 size.encode_impl(buffer);
                 index.encode_impl(buffer);
                 value.encode_impl(buffer);
                 dst.encode_impl(buffer);
+                in_place.encode_impl(buffer);
             },
             Bytecode::Intrinsic { intrinsic, args, dst, debug_info } => {
                 buffer.push(11);
@@ -173,7 +174,8 @@ impl Endec for Bytecode {
                 let (index, cursor) = usize::decode_impl(buffer, cursor)?;
                 let (value, cursor) = SSA::decode_impl(buffer, cursor)?;
                 let (dst, cursor) = Memory::decode_impl(buffer, cursor)?;
-                Ok((Bytecode::Update { src, size, index, value, dst }, cursor))
+                let (in_place, cursor) = bool::decode_impl(buffer, cursor)?;
+                Ok((Bytecode::Update { src, size, index, value, dst, in_place }, cursor))
             },
             Some(11) => {
                 let (intrinsic, cursor) = Intrinsic::decode_impl(buffer, cursor + 1)?;
@@ -1,20 +1,27 @@
-use crate::{Bytecode, Executable};
+use crate::{Bytecode, Executable, Memoize, Value};
 use sodigy_endec::{DecodeError, Endec};
+use std::collections::HashMap;
 
 impl Endec for Executable {
     fn encode_impl(&self, buffer: &mut Vec<u8>) {
         self.asserts.encode_impl(buffer);
         self.bytecodes.encode_impl(buffer);
+        self.constants.encode_impl(buffer);
+        self.memoized.encode_impl(buffer);
     }
 
     fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
         let (asserts, cursor) = Vec::<(String, usize)>::decode_impl(buffer, cursor)?;
         let (bytecodes, cursor) = Vec::<Bytecode>::decode_impl(buffer, cursor)?;
+        let (constants, cursor) = Vec::<Value>::decode_impl(buffer, cursor)?;
+        let (memoized, cursor) = HashMap::<usize, Memoize>::decode_impl(buffer, cursor)?;
 
         Ok((
             Executable {
                 asserts,
                 bytecodes,
+                constants,
+                memoized,
             },
             cursor,
         ))
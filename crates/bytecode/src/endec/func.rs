@@ -1,4 +1,4 @@
-use crate::{Bytecode, Func};
+use crate::{Bytecode, Func, Memoize, ValueShape};
 use sodigy_endec::{DecodeError, Endec};
 use sodigy_error::FuncEffect;
 use sodigy_span::Span;
@@ -11,6 +11,7 @@ impl Endec for Func {
         self.name_span.encode_impl(buffer);
         self.params.encode_impl(buffer);
         self.bytecodes.encode_impl(buffer);
+        self.memoize.encode_impl(buffer);
     }
 
     fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
@@ -19,7 +20,21 @@ impl Endec for Func {
         let (name_span, cursor) = Span::decode_impl(buffer, cursor)?;
         let (params, cursor) = usize::decode_impl(buffer, cursor)?;
         let (bytecodes, cursor) = Vec::<Bytecode>::decode_impl(buffer, cursor)?;
+        let (memoize, cursor) = Option::<Memoize>::decode_impl(buffer, cursor)?;
 
-        Ok((Func { effect, name, name_span, params, bytecodes }, cursor))
+        Ok((Func { effect, name, name_span, params, bytecodes, memoize }, cursor))
+    }
+}
+
+impl Endec for Memoize {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        self.params.encode_impl(buffer);
+        self.max.encode_impl(buffer);
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        let (params, cursor) = Vec::<ValueShape>::decode_impl(buffer, cursor)?;
+        let (max, cursor) = Option::<usize>::decode_impl(buffer, cursor)?;
+        Ok((Memoize { params, max }, cursor))
     }
 }
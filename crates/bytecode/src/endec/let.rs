@@ -1,4 +1,4 @@
-use crate::{Bytecode, Let};
+use crate::{Bytecode, Let, ValueShape};
 use sodigy_endec::{DecodeError, Endec};
 use sodigy_span::Span;
 use sodigy_string::InternedString;
@@ -8,15 +8,17 @@ impl Endec for Let {
         self.name.encode_impl(buffer);
         self.name_span.encode_impl(buffer);
         self.bytecodes.encode_impl(buffer);
+        self.shape.encode_impl(buffer);
     }
 
     fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
         let (name, cursor) = InternedString::decode_impl(buffer, cursor)?;
         let (name_span, cursor) = Span::decode_impl(buffer, cursor)?;
         let (bytecodes, cursor) = Vec::<Bytecode>::decode_impl(buffer, cursor)?;
+        let (shape, cursor) = Option::<ValueShape>::decode_impl(buffer, cursor)?;
 
         Ok((
-            Let { name, name_span, bytecodes },
+            Let { name, name_span, bytecodes, shape },
             cursor,
         ))
     }
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Value, ValueShape};
 use sodigy_endec::{DecodeError, Endec};
 use sodigy_number::BigInt;
 use sodigy_span::Span;
@@ -31,6 +31,15 @@ impl Endec for Value {
                 buffer.push(5);
                 span.encode_impl(buffer);
             },
+            Value::String { binary, bytes } => {
+                buffer.push(6);
+                binary.encode_impl(buffer);
+                bytes.encode_impl(buffer);
+            },
+            Value::Pooled(i) => {
+                buffer.push(7);
+                i.encode_impl(buffer);
+            },
         }
     }
 
@@ -61,7 +70,62 @@ impl Endec for Value {
                 let (span, cursor) = Span::decode_impl(buffer, cursor + 1)?;
                 Ok((Value::Span(span), cursor))
             },
-            Some(n @ 6..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            Some(6) => {
+                let (binary, cursor) = bool::decode_impl(buffer, cursor + 1)?;
+                let (bytes, cursor) = Vec::<u8>::decode_impl(buffer, cursor)?;
+                Ok((Value::String { binary, bytes }, cursor))
+            },
+            Some(7) => {
+                let (i, cursor) = usize::decode_impl(buffer, cursor + 1)?;
+                Ok((Value::Pooled(i), cursor))
+            },
+            Some(n @ 8..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            None => Err(DecodeError::UnexpectedEof),
+        }
+    }
+}
+
+impl Endec for ValueShape {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        match self {
+            ValueShape::Scalar => {
+                buffer.push(0);
+            },
+            ValueShape::Int => {
+                buffer.push(1);
+            },
+            ValueShape::List(element) => {
+                buffer.push(2);
+                element.encode_impl(buffer);
+            },
+            ValueShape::Compound(fields) => {
+                buffer.push(3);
+                fields.encode_impl(buffer);
+            },
+            ValueShape::Nullable(value) => {
+                buffer.push(4);
+                value.encode_impl(buffer);
+            },
+        }
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        match buffer.get(cursor) {
+            Some(0) => Ok((ValueShape::Scalar, cursor + 1)),
+            Some(1) => Ok((ValueShape::Int, cursor + 1)),
+            Some(2) => {
+                let (element, cursor) = ValueShape::decode_impl(buffer, cursor + 1)?;
+                Ok((ValueShape::List(Box::new(element)), cursor))
+            },
+            Some(3) => {
+                let (fields, cursor) = Vec::<ValueShape>::decode_impl(buffer, cursor + 1)?;
+                Ok((ValueShape::Compound(fields), cursor))
+            },
+            Some(4) => {
+                let (value, cursor) = ValueShape::decode_impl(buffer, cursor + 1)?;
+                Ok((ValueShape::Nullable(Box::new(value)), cursor))
+            },
+            Some(n @ 5..) => Err(DecodeError::InvalidEnumVariant(*n)),
             None => Err(DecodeError::UnexpectedEof),
         }
     }
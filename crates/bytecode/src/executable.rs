@@ -1,8 +1,16 @@
-use crate::Bytecode;
+use crate::{Bytecode, Memoize, Value};
+use std::collections::HashMap;
 
 pub struct Executable {
     pub asserts: Vec<(/* name: */ String, /* bytecode offset: */ usize)>,
     pub bytecodes: Vec<Bytecode>,
+
+    // Deduplicated constants. `Value::Pooled(i)` is `constants[i]`.
+    // They're allocated before the program starts, and are read-only.
+    pub constants: Vec<Value>,
+
+    // key: entry point (bytecode offset) of a memoized function
+    pub memoized: HashMap<usize, Memoize>,
 }
 
 impl Executable {}
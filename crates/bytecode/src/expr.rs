@@ -9,9 +9,10 @@ use crate::{
     Value,
 };
 use sodigy_hir::{EnumRepr, FuncShape};
-use sodigy_mir::{Block, Callable, Expr, If, Match, Type, type_of};
+use sodigy_mir::{Block, Callable, Expr, If, Intrinsic, Match, Type, get_def_span_from_id, type_of};
 use sodigy_name_analysis::{NameKind, NameOrigin};
 use sodigy_parse::Field;
+use sodigy_span::{Span, SpanDeriveKind};
 
 // It generates bytecodes that
 //    1) evaluates the expr
@@ -173,7 +174,7 @@ pub fn lower_expr(
 
             for field in fields.iter() {
                 let ssa_reg = session.get_ssa();
-                lower_field_read(curr_ssa_reg, field, ssa_reg, bytecodes);
+                lower_field_read(curr_ssa_reg, field, ssa_reg, session, bytecodes);
                 curr_ssa_reg = ssa_reg;
             }
 
@@ -228,7 +229,7 @@ pub fn lower_expr(
                 }
 
                 let ssa_reg = session.get_ssa();
-                lower_field_read(curr_ssa_reg, field, ssa_reg, bytecodes);
+                lower_field_read(curr_ssa_reg, field, ssa_reg, session, bytecodes);
                 sources.push(ssa_reg);
                 curr_ssa_reg = ssa_reg;
             }
@@ -284,7 +285,13 @@ pub fn lower_expr(
                             },
                             None => {
                                 let func = Label::Global(def_span.clone());
-                                let effect = match session.global_context.func_shapes.unwrap().get(def_span) {
+                                // The MIR optimizer adds an accumulator to some functions (see `SpanDeriveKind::AccumulatorFunc`).
+                                // They're not in `func_shapes`, but have the same effect as the original functions.
+                                let shape_span = match def_span {
+                                    Span::Derived { kind: SpanDeriveKind::AccumulatorFunc, span } => span,
+                                    _ => def_span,
+                                };
+                                let effect = match session.global_context.func_shapes.unwrap().get(shape_span) {
                                     Some(FuncShape { effect, .. }) => Box::new(effect.clone()),
                                     _ => unreachable!(),
                                 };
@@ -362,10 +369,18 @@ pub fn lower_expr(
                     }
                 },
                 Callable::EnumInit { enum_def_span, variant_def_span, .. } => {
-                    let enum_shape = session.global_context.enum_shapes.unwrap().get(enum_def_span).unwrap();
+                    let enum_shapes = session.global_context.enum_shapes.unwrap();
+                    let enum_shape = enum_shapes.get(enum_def_span).unwrap();
                     let variant_index = *enum_shape.variant_index.get(&variant_def_span.id().unwrap()).unwrap();
 
-                    match enum_shape.representation {
+                    // `enum_def_span` is the def_span of the generic enum, but a monomorphized
+                    // enum may have a different representation (e.g. niche optimization).
+                    let representation = match type_of(expr, session.global_context.clone()) {
+                        Some(Type::Data { constructor_def_span, args, .. }) => enum_shapes.get(&get_def_span_from_id(constructor_def_span, &args)).unwrap_or(enum_shape).representation,
+                        _ => enum_shape.representation,
+                    };
+
+                    match representation {
                         EnumRepr::Scalar => {
                             assert!(args.is_empty());
                             bytecodes.push(Bytecode::Const {
@@ -403,7 +418,35 @@ pub fn lower_expr(
                                 );
                             }
                         },
-                        EnumRepr::Niche => todo!(),
+                        EnumRepr::Niche { null_variant } => {
+                            if variant_index == null_variant {
+                                assert!(args.is_empty());
+                                bytecodes.push(Bytecode::Const {
+                                    value: Value::Scalar(0),
+                                    dst: dst.clone(),
+                                    debug_info: None,
+                                });
+                            }
+
+                            else {
+                                lower_expr(
+                                    &args[0],
+                                    session,
+                                    bytecodes,
+                                    dst.clone(),
+                                    /* is_tail_call: */ false,
+                                );
+                            }
+                        },
+                        EnumRepr::Unboxed => {
+                            lower_expr(
+                                &args[0],
+                                session,
+                                bytecodes,
+                                dst.clone(),
+                                /* is_tail_call: */ false,
+                            );
+                        },
                     }
 
                     if is_tail_call {
@@ -452,6 +495,7 @@ fn lower_field_read(
     src: SSA,
     field: &Field,
     dst: SSA,
+    session: &mut Session,
     bytecodes: &mut Vec<Bytecode>,
 ) {
     match field {
@@ -478,7 +522,7 @@ fn lower_field_read(
                 dst: Memory::SSA(dst),
             });
         },
-        Field::SelfAsScalar => {
+        Field::SelfAsScalar | Field::UnboxedPayload { .. } => {
             if src != dst {
                 bytecodes.push(Bytecode::Move {
                     src: Memory::SSA(src),
@@ -486,6 +530,30 @@ fn lower_field_read(
                 });
             }
         },
+        Field::NicheDiscriminant { null_variant } => {
+            let null_ssa = session.get_ssa();
+            bytecodes.push(Bytecode::Const {
+                value: Value::Scalar(0),
+                dst: Memory::SSA(null_ssa),
+                debug_info: None,
+            });
+
+            // There are only 2 variants, so the discriminant is either 0 or 1.
+            // A non-null value is always greater than 0.
+            bytecodes.push(Bytecode::Intrinsic {
+                intrinsic: if *null_variant == 0 { Intrinsic::GtScalar } else { Intrinsic::EqScalar },
+                args: vec![src, null_ssa],
+                dst: Memory::SSA(dst),
+                debug_info: None,
+            });
+        },
+        Field::UnboxedDiscriminant => {
+            bytecodes.push(Bytecode::Const {
+                value: Value::Scalar(0),
+                dst: Memory::SSA(dst),
+                debug_info: None,
+            });
+        },
         _ => panic!("TODO: {field:?}"),
     }
 }
@@ -505,6 +573,7 @@ fn lower_field_update(
                 index: *i as usize,
                 value,
                 dst,
+                in_place: false,
             });
         },
         Field::EnumPayload { payload, .. } => {
@@ -514,6 +583,14 @@ fn lower_field_update(
                 index: *payload as usize + 1,
                 value,
                 dst,
+                in_place: false,
+            });
+        },
+        // The payload is the value itself, so the new value is the new payload.
+        Field::UnboxedPayload { .. } => {
+            bytecodes.push(Bytecode::Move {
+                src: Memory::SSA(value),
+                dst,
             });
         },
         _ => panic!("TODO: {field:?}"),
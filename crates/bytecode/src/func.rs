@@ -1,7 +1,8 @@
-use crate::{Bytecode, Memory, Session, SSA, lower_expr};
-use sodigy_error::FuncEffect;
+use crate::{Bytecode, Memory, Session, SSA, ValueShape, lower_expr, mark_in_place_updates, value_shape};
+use sodigy_error::{FuncEffect, Warning, WarningKind};
+use sodigy_hir as hir;
 use sodigy_mir as mir;
-use sodigy_span::Span;
+use sodigy_span::{RenderableSpan, Span};
 use sodigy_string::InternedString;
 use std::collections::HashMap;
 
@@ -12,6 +13,19 @@ pub struct Func {
     pub name_span: Span,
     pub params: usize,
     pub bytecodes: Vec<Bytecode>,
+    pub memoize: Option<Memoize>,
+}
+
+/// `#[memoize]`: the runtime caches the results of the function. The arguments are
+/// the key of the cache, and they're compared by value, not by pointer.
+#[derive(Clone, Debug)]
+pub struct Memoize {
+    /// The runtime doesn't know the types of the arguments, so it needs this to
+    /// read the arguments from the heap.
+    pub params: Vec<ValueShape>,
+
+    /// If the cache is full, the oldest entry is evicted.
+    pub max: Option<usize>,
 }
 
 impl Func {
@@ -35,6 +49,13 @@ impl Func {
             Memory::Return,
             /* is_tail_call: */ true,
         );
+        mark_in_place_updates(&mut bytecodes);
+
+        // A generic function is not called at runtime. Only its monomorphized copies are.
+        let memoize = match (&mir_func.memoize, &session.global_context.types) {
+            (Some(memoize), Some(_)) if mir_func.generics.is_empty() => Memoize::from_mir(mir_func, memoize, session),
+            _ => None,
+        };
 
         Func {
             effect: mir_func.effect.clone(),
@@ -42,6 +63,49 @@ impl Func {
             name_span: mir_func.name_span.clone(),
             params: mir_func.params.len(),
             bytecodes,
+            memoize,
         }
     }
 }
+
+impl Memoize {
+    // If a parameter doesn't have a shape (e.g. a function), the function is not memoized.
+    fn from_mir(mir_func: &mir::Func, memoize: &hir::Memoize, session: &mut Session) -> Option<Memoize> {
+        let mut params = Vec::with_capacity(mir_func.params.len());
+
+        for param in mir_func.params.iter() {
+            match session.global_context.get_type(&param.name_span).and_then(
+                |r#type| value_shape(&r#type, session)
+            ) {
+                Some(shape) => {
+                    params.push(shape);
+                },
+                None => {
+                    session.warnings.push(Warning {
+                        kind: WarningKind::CannotMemoize { param: param.name },
+                        spans: vec![
+                            RenderableSpan {
+                                span: param.name_span.clone(),
+                                auxiliary: false,
+                                note: None,
+                            },
+                            RenderableSpan {
+                                span: memoize.decorator_span.clone(),
+                                auxiliary: true,
+                                note: Some(String::from("This decorator is ignored.")),
+                            },
+                        ],
+                        note: Some(String::from("Functions, enums with payloads and numbers that are not integers cannot be compared by value.")),
+                        suggestions: vec![],
+                    });
+                    return None;
+                },
+            }
+        }
+
+        Some(Memoize {
+            params,
+            max: memoize.max,
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{Bytecode, Memory, Offset, SSA};
+use std::collections::HashMap;
+
+// `Bytecode::Update` clones the tuple and updates the clone. If nobody can see the
+// tuple after the update, the runtime can update the tuple in place.
+//
+// A tuple is unique if
+//   1. it's initialized by `InitTuple` or `Update` in this function, or it's moved from a unique tuple.
+//   2. its SSA is used only once (by the update or the move), except the reads and writes of its
+//      elements (`*(_2 + 1)`) before the use in the same basic block.
+//
+// The runtime doesn't maintain ref counts (nobody calls `Heap::inc_rc`), so it can't tell
+// whether a block is shared or not. The compiler has to prove it.
+pub(crate) fn mark_in_place_updates(bytecodes: &mut [Bytecode]) {
+    let mut defs: HashMap<SSA, Option<usize>> = HashMap::new();
+    let mut uses: HashMap<SSA, Vec<(usize, Use)>> = HashMap::new();
+
+    for (i, bytecode) in bytecodes.iter().enumerate() {
+        if let Some(Memory::SSA(dst)) = bytecode.get_dst() {
+            // `None` if it's defined more than once.
+            defs.entry(*dst).and_modify(|def| { *def = None; }).or_insert(Some(i));
+        }
+
+        for (ssa, r#use) in collect_uses(bytecode) {
+            uses.entry(ssa).or_default().push((i, r#use));
+        }
+    }
+
+    let context = Context { bytecodes, defs, uses };
+    let mut in_place_updates = vec![];
+
+    for (i, bytecode) in context.bytecodes.iter().enumerate() {
+        if let Bytecode::Update { src, .. } = bytecode && context.is_last_use(*src, i) && context.is_unique(*src) {
+            in_place_updates.push(i);
+        }
+    }
+
+    for i in in_place_updates.into_iter() {
+        if let Bytecode::Update { in_place, .. } = &mut bytecodes[i] {
+            *in_place = true;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Use {
+    // `*(_2 + 1)` reads or writes an element of `_2`.
+    Element,
+
+    // Any other use. `_2` might be stored somewhere else.
+    Whole,
+}
+
+struct Context<'b> {
+    bytecodes: &'b [Bytecode],
+    defs: HashMap<SSA, Option<usize>>,
+    uses: HashMap<SSA, Vec<(usize, Use)>>,
+}
+
+impl Context<'_> {
+    fn is_unique(&self, ssa: SSA) -> bool {
+        let mut ssa = ssa;
+
+        loop {
+            let Some(Some(def)) = self.defs.get(&ssa) else {
+                return false;
+            };
+
+            match &self.bytecodes[*def] {
+                Bytecode::InitTuple { .. } | Bytecode::Update { .. } => {
+                    return true;
+                },
+                Bytecode::Move { src: Memory::SSA(src), .. } if self.is_last_use(*src, *def) => {
+                    ssa = *src;
+                },
+                _ => {
+                    return false;
+                },
+            }
+        }
+    }
+
+    // Whether the bytecode at `index` is the only `Use::Whole` of `ssa`, and the
+    // other uses are in the same basic block before the bytecode.
+    fn is_last_use(&self, ssa: SSA, index: usize) -> bool {
+        let Some(uses) = self.uses.get(&ssa) else {
+            return false;
+        };
+
+        uses.iter().all(
+            |(i, r#use)| match r#use {
+                Use::Whole => *i == index,
+                Use::Element => *i < index && !self.bytecodes[(*i + 1)..index].iter().any(is_block_boundary),
+            }
+        ) && uses.iter().filter(|(i, _)| *i == index).count() == 1
+    }
+}
+
+fn is_block_boundary(bytecode: &Bytecode) -> bool {
+    matches!(
+        bytecode,
+        Bytecode::Label(_) |
+        Bytecode::Jump(_) |
+        Bytecode::JumpIf { .. } |
+        Bytecode::InitOrJump { .. } |
+        Bytecode::Return(_) |
+        Bytecode::Call { dst: None, .. } |
+        Bytecode::CallDynamic { dst: None, .. },
+    )
+}
+
+fn collect_uses(bytecode: &Bytecode) -> Vec<(SSA, Use)> {
+    fn memory_uses(memory: &Memory, is_dst: bool, uses: &mut Vec<(SSA, Use)>) {
+        match memory {
+            Memory::SSA(ssa) if !is_dst => {
+                uses.push((*ssa, Use::Whole));
+            },
+            Memory::Heap { ptr, offset: Offset::Static(_) } |
+            Memory::List { ptr, offset: Offset::Static(_) } => {
+                uses.push((*ptr, Use::Element));
+            },
+            Memory::Heap { ptr, offset: Offset::Dynamic(offset) } |
+            Memory::List { ptr, offset: Offset::Dynamic(offset) } => {
+                uses.push((*ptr, Use::Whole));
+                memory_uses(offset, false, uses);
+            },
+            Memory::SSA(_) | Memory::Return | Memory::Global(_) => {},
+        }
+    }
+
+    let mut uses = vec![];
+
+    match bytecode {
+        Bytecode::Const { dst, .. } |
+        Bytecode::InitTuple { dst, .. } |
+        Bytecode::InitList { dst, .. } => {
+            memory_uses(dst, true, &mut uses);
+        },
+        Bytecode::Move { src, dst } => {
+            memory_uses(src, false, &mut uses);
+            memory_uses(dst, true, &mut uses);
+        },
+        Bytecode::Phi { pair: (a, b), dst } => {
+            uses.push((*a, Use::Whole));
+            uses.push((*b, Use::Whole));
+            memory_uses(dst, true, &mut uses);
+        },
+        Bytecode::Call { args, dst, .. } => {
+            uses.extend(args.iter().map(|arg| (*arg, Use::Whole)));
+
+            if let Some(dst) = dst {
+                memory_uses(dst, true, &mut uses);
+            }
+        },
+        Bytecode::CallDynamic { func, args, dst, .. } => {
+            memory_uses(func, false, &mut uses);
+            uses.extend(args.iter().map(|arg| (*arg, Use::Whole)));
+
+            if let Some(dst) = dst {
+                memory_uses(dst, true, &mut uses);
+            }
+        },
+        Bytecode::JumpIf { value, .. } => {
+            memory_uses(value, false, &mut uses);
+        },
+        Bytecode::Return(a) => {
+            uses.push((*a, Use::Whole));
+        },
+        Bytecode::Update { src, value, dst, .. } => {
+            uses.push((*src, Use::Whole));
+            uses.push((*value, Use::Whole));
+            memory_uses(dst, true, &mut uses);
+        },
+        Bytecode::Intrinsic { args, dst, .. } => {
+            uses.extend(args.iter().map(|arg| (*arg, Use::Whole)));
+            memory_uses(dst, true, &mut uses);
+        },
+        Bytecode::PushDebugInfo { src, .. } => {
+            memory_uses(src, false, &mut uses);
+        },
+        Bytecode::Jump(_) |
+        Bytecode::InitOrJump { .. } |
+        Bytecode::Label(_) |
+        Bytecode::PopDebugInfo => {},
+    }
+
+    uses
+}
@@ -1,5 +1,6 @@
-use crate::{Bytecode, Memory, Session, lower_expr};
-use sodigy_mir as mir;
+use crate::{Bytecode, Memory, Session, ValueShape, lower_expr, mark_in_place_updates};
+use sodigy_hir::EnumRepr;
+use sodigy_mir::{self as mir, Type, get_def_span_from_id};
 use sodigy_span::Span;
 use sodigy_string::InternedString;
 
@@ -12,6 +13,10 @@ pub struct Let {
     pub name: InternedString,
     pub name_span: Span,
     pub bytecodes: Vec<Bytecode>,
+
+    /// If it's set, the code generator may evaluate the value at compile time
+    /// and embed it as a constant. It's used to read the value back from the runtime.
+    pub shape: Option<ValueShape>,
 }
 
 impl Let {
@@ -29,11 +34,95 @@ impl Let {
             /* is_tail_call: */ false,
         );
         bytecodes.push(Bytecode::Return(return_ssa));
+        mark_in_place_updates(&mut bytecodes);
+
+        let shape = match &session.global_context.types {
+            Some(_) => session.global_context.get_type(&mir_let.name_span).and_then(
+                |r#type| value_shape(&r#type, session)
+            ),
+            None => None,
+        };
 
         Let {
             name: mir_let.name,
             name_span: mir_let.name_span.clone(),
             bytecodes,
+            shape,
+        }
+    }
+}
+
+// Functions, enums with payloads (unless they're unboxed or niche-optimized) and `Number`s
+// (which can be either an integer or a ratio) don't have a shape.
+pub(crate) fn value_shape(r#type: &Type, session: &Session) -> Option<ValueShape> {
+    value_shape_worker(r#type, session, &mut vec![])
+}
+
+// A self-referential type (e.g. `struct Person = { friends: [Person] }`) doesn't have a shape.
+// `visiting` is the def_spans of the structs and enums that are being visited.
+fn value_shape_worker(r#type: &Type, session: &Session, visiting: &mut Vec<Span>) -> Option<ValueShape> {
+    let Type::Data { constructor_def_span, args, .. } = r#type else {
+        return None;
+    };
+    let is_lang_item = |lang_item: &str| session.get_lang_item_span(lang_item).id() == Some(*constructor_def_span);
+
+    if is_lang_item("type.Int") {
+        Some(ValueShape::Int)
+    }
+
+    else if is_lang_item("type.Char") || is_lang_item("type.Byte") || is_lang_item("type.Scalar") {
+        Some(ValueShape::Scalar)
+    }
+
+    else if is_lang_item("type.List") {
+        Some(ValueShape::List(Box::new(value_shape_worker(args.as_ref()?.first()?, session, visiting)?)))
+    }
+
+    else if is_lang_item("type.Tuple") {
+        Some(ValueShape::Compound(
+            args.as_ref()?.iter().map(|arg| value_shape_worker(arg, session, visiting)).collect::<Option<Vec<_>>>()?,
+        ))
+    }
+
+    else {
+        let def_span = get_def_span_from_id(*constructor_def_span, args);
+
+        if visiting.contains(&def_span) {
+            return None;
+        }
+
+        visiting.push(def_span.clone());
+        let shape = data_value_shape(&def_span, session, visiting);
+        visiting.pop();
+        shape
+    }
+}
+
+fn data_value_shape(def_span: &Span, session: &Session, visiting: &mut Vec<Span>) -> Option<ValueShape> {
+    if let Some(struct_shape) = session.global_context.struct_shapes?.get(def_span) && struct_shape.from_enum.is_none() {
+        Some(ValueShape::Compound(
+            struct_shape.fields.iter().map(
+                |field| value_shape_worker(&session.global_context.get_type(&field.name_span)?, session, visiting)
+            ).collect::<Option<Vec<_>>>()?,
+        ))
+    }
+
+    else if let Some(enum_shape) = session.global_context.enum_shapes?.get(def_span) {
+        // The payload of an unboxed or niche-optimized enum is the enum value itself.
+        let mut payload_shape = |variant: usize| match session.global_context.get_type(&enum_shape.variants[variant].name_span)? {
+            Type::Func { params, .. } => value_shape_worker(&params[0], session, visiting),
+            _ => None,
+        };
+
+        match enum_shape.representation {
+            EnumRepr::Scalar => Some(ValueShape::Scalar),
+            EnumRepr::Compound => None,
+            EnumRepr::Niche { null_variant } => Some(ValueShape::Nullable(Box::new(payload_shape(1 - null_variant)?))),
+            EnumRepr::Unboxed => payload_shape(0),
         }
     }
+
+    else {
+        None
+    }
 }
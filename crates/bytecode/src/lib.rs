@@ -5,10 +5,12 @@ use std::collections::HashMap;
 
 mod assert;
 mod dump;
+mod embed;
 mod endec;
 mod executable;
 mod expr;
 mod func;
+mod in_place;
 mod r#let;
 mod link;
 mod session;
@@ -21,10 +23,12 @@ pub use assert::Assert;
 pub(crate) use dump::dump_bytecodes;
 pub use executable::Executable;
 pub(crate) use expr::lower_expr;
-pub use func::Func;
+pub use func::{Func, Memoize};
+pub(crate) use in_place::mark_in_place_updates;
 pub use r#let::Let;
+pub(crate) use r#let::value_shape;
 pub use session::{LocalValue, Session};
-pub use value::Value;
+pub use value::{Value, ValueShape};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct SSA(u32);
@@ -123,6 +127,10 @@ pub enum Bytecode {
 
         value: SSA,
         dst: Memory,
+
+        // If it's set, nobody can see `src` after this update, so the runtime
+        // can update `src` in place instead of cloning it. See `in_place.rs`.
+        in_place: bool,
     },
 
     Intrinsic {
@@ -366,7 +374,10 @@ impl Bytecode {
             Bytecode::Return(a) => {
                 *a = *ssa_alias.get(a).unwrap_or(a);
             },
-            Bytecode::Update { src, value, .. } => todo!(),
+            Bytecode::Update { src, value, .. } => {
+                *src = *ssa_alias.get(src).unwrap_or(src);
+                *value = *ssa_alias.get(value).unwrap_or(value);
+            },
             Bytecode::Intrinsic { args, .. } => {
                 apply_ssa_alias_args(args, ssa_alias, heap_ssa_alias);
             },
@@ -379,6 +390,80 @@ impl Bytecode {
         }
     }
 
+    // Unlike `apply_ssa_alias`, it renames every SSA in the bytecode, including the `dst`s.
+    pub fn rename_ssa(&mut self, f: &mut impl FnMut(SSA) -> SSA) {
+        fn rename_memory(memory: &mut Memory, f: &mut impl FnMut(SSA) -> SSA) {
+            match memory {
+                Memory::SSA(ssa) => {
+                    *ssa = f(*ssa);
+                },
+                Memory::Heap { ptr, offset } |
+                Memory::List { ptr, offset } => {
+                    *ptr = f(*ptr);
+
+                    if let Offset::Dynamic(offset) = offset {
+                        rename_memory(offset, f);
+                    }
+                },
+                Memory::Return | Memory::Global(_) => {},
+            }
+        }
+
+        match self {
+            Bytecode::Const { dst, .. } |
+            Bytecode::InitTuple { dst, .. } |
+            Bytecode::InitList { dst, .. } => {
+                rename_memory(dst, f);
+            },
+            Bytecode::Move { src, dst } => {
+                rename_memory(src, f);
+                rename_memory(dst, f);
+            },
+            Bytecode::Phi { pair: (a, b), dst } => {
+                *a = f(*a);
+                *b = f(*b);
+                rename_memory(dst, f);
+            },
+            Bytecode::Call { args, dst, .. } => {
+                args.iter_mut().for_each(|arg| { *arg = f(*arg); });
+
+                if let Some(dst) = dst {
+                    rename_memory(dst, f);
+                }
+            },
+            Bytecode::CallDynamic { func, args, dst, .. } => {
+                rename_memory(func, f);
+                args.iter_mut().for_each(|arg| { *arg = f(*arg); });
+
+                if let Some(dst) = dst {
+                    rename_memory(dst, f);
+                }
+            },
+            Bytecode::JumpIf { value, .. } => {
+                rename_memory(value, f);
+            },
+            Bytecode::Return(a) => {
+                *a = f(*a);
+            },
+            Bytecode::Update { src, value, dst, .. } => {
+                *src = f(*src);
+                *value = f(*value);
+                rename_memory(dst, f);
+            },
+            Bytecode::Intrinsic { args, dst, .. } => {
+                args.iter_mut().for_each(|arg| { *arg = f(*arg); });
+                rename_memory(dst, f);
+            },
+            Bytecode::PushDebugInfo { src, .. } => {
+                rename_memory(src, f);
+            },
+            Bytecode::Jump(_) |
+            Bytecode::InitOrJump { .. } |
+            Bytecode::Label(_) |
+            Bytecode::PopDebugInfo => {},
+        }
+    }
+
     pub fn debug_info(&self) -> Option<Box<Span>> {
         match self {
             Bytecode::Const { debug_info, .. } |
@@ -452,18 +537,19 @@ impl Bytecode {
     }
 
     // Whether it's okay for the optimizer to remove this bytecode.
+    // The caller has to make sure that the `dst` is not used.
     pub fn is_observable(&self) -> bool {
         match self {
             Bytecode::Const { .. } |
             Bytecode::Move { .. } |
             Bytecode::Phi { .. } |
+            Bytecode::Update { .. } |
+            Bytecode::InitTuple { .. } |
+            Bytecode::InitList { .. } => true,
             Bytecode::Jump(_) |
             Bytecode::JumpIf { .. } |
             Bytecode::Label(_) |
             Bytecode::Return(_) |
-            Bytecode::Update { .. } |
-            Bytecode::InitTuple { .. } |
-            Bytecode::InitList { .. } |
             Bytecode::PushDebugInfo { .. } |
             Bytecode::PopDebugInfo => false,
             Bytecode::Call { effect, .. } |
@@ -472,6 +558,8 @@ impl Bytecode {
             // as of now, all the `let` statements are pure
             Bytecode::InitOrJump { .. } => false,
 
+            // `Panic`, `Print` and `EPrint` are `Fn`s, but they have side effects.
+            Bytecode::Intrinsic { intrinsic: Intrinsic::Panic | Intrinsic::Print | Intrinsic::EPrint, .. } => false,
             Bytecode::Intrinsic { intrinsic, .. } => matches!(intrinsic.effect(), FuncEffect::Fn | FuncEffect::NdetFn),
         }
     }
@@ -1,12 +1,19 @@
 use crate::{Bytecode, Executable, Label, Session, Value};
+use sodigy_endec::Endec;
 use sodigy_span::Span;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 impl Session<'_, '_> {
+    // It only links the items that are reachable from the assertions.
     pub fn link(&self) -> Executable {
+        let reachable = self.reachable_items();
         let mut concated_bytecodes = vec![];
         let mut label_map: HashMap<(Span, Label), usize> = HashMap::new();
         let mut func_pointer_map: HashMap<Span, usize> = HashMap::new();
+        let mut constants: Vec<Value> = vec![];
+
+        // key: encoded `Value`, value: index of `constants`
+        let mut constant_map: HashMap<Vec<u8>, usize> = HashMap::new();
 
         for (def_span, bytecodes) in self.asserts.iter().map(
             |assert| (assert.keyword_span.clone(), &assert.bytecodes)
@@ -18,6 +25,8 @@ impl Session<'_, '_> {
             self.funcs.iter().map(
                 |func| (func.name_span.clone(), &func.bytecodes)
             )
+        ).filter(
+            |(def_span, _)| reachable.contains(def_span)
         ) {
             let mut curr_label = (def_span.clone(), Label::Global(def_span.clone()));
             let mut last_index = 0;
@@ -81,10 +90,40 @@ impl Session<'_, '_> {
                 Bytecode::Const { value: Value::FuncPointer { def_span, program_counter }, .. } => {
                     *program_counter = Some(*func_pointer_map.get(def_span).unwrap());
                 },
+                Bytecode::Const {
+                    value: value @ (
+                        Value::Int(_) |
+                        Value::List(_) |
+                        Value::Compound(_) |
+                        Value::String { .. } |
+                        Value::Span(_)
+                    ),
+                    ..
+                } => {
+                    let encoded = value.encode();
+                    let index = match constant_map.get(&encoded) {
+                        Some(index) => *index,
+                        None => {
+                            let index = constants.len();
+                            constants.push(value.clone());
+                            constant_map.insert(encoded, index);
+                            index
+                        },
+                    };
+
+                    *value = Value::Pooled(index);
+                },
                 _ => {},
             }
         }
 
+        let memoized = self.funcs.iter().filter_map(
+            |func| match (&func.memoize, func_pointer_map.get(&func.name_span)) {
+                (Some(memoize), Some(entry_point)) => Some((*entry_point, memoize.clone())),
+                _ => None,
+            }
+        ).collect();
+
         Executable {
             asserts: self.asserts.iter().map(
                 |assert| (
@@ -93,6 +132,50 @@ impl Session<'_, '_> {
                 )
             ).collect(),
             bytecodes: concated_bytecodes,
+            constants,
+            memoized,
         }
     }
+
+    // Entry points are the assertions (there's no `main` yet). From there, it
+    // follows calls, tail calls, initializations of top-level `let`s and
+    // function pointers (which may be called by `CallDynamic`).
+    fn reachable_items(&self) -> HashSet<Span> {
+        let items: HashMap<Span, &[Bytecode]> = self.asserts.iter().map(
+            |assert| (assert.keyword_span.clone(), &assert.bytecodes[..])
+        ).chain(
+            self.lets.iter().map(
+                |r#let| (r#let.name_span.clone(), &r#let.bytecodes[..])
+            )
+        ).chain(
+            self.funcs.iter().map(
+                |func| (func.name_span.clone(), &func.bytecodes[..])
+            )
+        ).collect();
+        let mut stack: Vec<Span> = self.asserts.iter().map(|assert| assert.keyword_span.clone()).collect();
+        let mut reachable: HashSet<Span> = stack.iter().cloned().collect();
+
+        while let Some(item) = stack.pop() {
+            let Some(bytecodes) = items.get(&item) else { continue; };
+
+            for bytecode in bytecodes.iter() {
+                let def_span = match bytecode {
+                    Bytecode::Jump(Label::Global(def_span)) |
+                    Bytecode::Call { func: Label::Global(def_span), .. } |
+                    Bytecode::JumpIf { label: Label::Global(def_span), .. } |
+                    Bytecode::InitOrJump { func: Label::Global(def_span), .. } |
+                    Bytecode::Const { value: Value::FuncPointer { def_span, .. }, .. } => def_span,
+                    _ => {
+                        continue;
+                    },
+                };
+
+                if reachable.insert(def_span.clone()) {
+                    stack.push(def_span.clone());
+                }
+            }
+        }
+
+        reachable
+    }
 }
@@ -1,4 +1,20 @@
-use crate::{Bytecode, Memory, Offset, Value};
+use crate::{
+    Assert,
+    Bytecode,
+    Func,
+    Label,
+    Memory,
+    Offset,
+    SSA,
+    Session,
+    Value,
+    mark_in_place_updates,
+};
+use sodigy_error::FuncEffect;
+use sodigy_mir::GlobalContext;
+use sodigy_span::Span;
+use sodigy_string::intern_string;
+use std::collections::HashMap;
 use std::mem::size_of;
 
 #[test]
@@ -8,3 +24,106 @@ fn size_assertions() {
     assert!(size_of::<Offset>() <= 160, "{}", size_of::<Offset>());
     assert!(size_of::<Value>() <= 160, "{}", size_of::<Value>());
 }
+
+// assert -> f -> (tail call) g
+//           f -> (func pointer) h
+// `unused` and `unused2` are not reachable.
+#[test]
+fn link_reachable_items() {
+    let span = |name: &str| Span::Prelude(intern_string(name.as_bytes(), "").unwrap());
+    let func = |name: &str, bytecodes: Vec<Bytecode>| Func {
+        effect: FuncEffect::Fn,
+        name: intern_string(name.as_bytes(), "").unwrap(),
+        name_span: span(name),
+        params: 0,
+        bytecodes,
+        memoize: None,
+    };
+    let session = Session {
+        intermediate_dir: String::new(),
+        label_counter: 0,
+        ssa_counter: 0,
+        ssa_map: HashMap::new(),
+        funcs: vec![
+            func("f", vec![
+                Bytecode::Const {
+                    value: Value::FuncPointer { def_span: span("h"), program_counter: None },
+                    dst: Memory::SSA(SSA::from_u32(0)),
+                    debug_info: None,
+                },
+                Bytecode::Jump(Label::Global(span("g"))),
+            ]),
+            func("g", vec![Bytecode::Return(SSA::from_u32(0))]),
+            func("h", vec![Bytecode::Return(SSA::from_u32(0))]),
+            func("unused", vec![Bytecode::Jump(Label::Global(span("unused2")))]),
+            func("unused2", vec![Bytecode::Return(SSA::from_u32(0))]),
+        ],
+        asserts: vec![Assert {
+            keyword_span: span("assert"),
+            name: intern_string(b"assert", "").unwrap(),
+            bytecodes: vec![Bytecode::Call {
+                func: Label::Global(span("f")),
+                args: vec![],
+                dst: Some(Memory::SSA(SSA::from_u32(0))),
+                debug_info: None,
+                effect: Box::new(FuncEffect::Fn),
+            }],
+        }],
+        lets: vec![],
+        intrinsics: HashMap::new(),
+        errors: vec![],
+        warnings: vec![],
+        global_context: GlobalContext::new(),
+        debug_info: false,
+    };
+    let executable = session.link();
+    let linked = executable.bytecodes.iter().filter_map(
+        |bytecode| match bytecode {
+            Bytecode::Label(Label::Global(def_span)) => Some(def_span.clone()),
+            _ => None,
+        }
+    ).collect::<Vec<_>>();
+
+    assert_eq!(linked, vec![span("assert"), span("f"), span("g"), span("h")]);
+}
+
+// `_0` is a parameter, so it can't be updated in place.
+// `_1` is a new tuple and it's not used after the update, so it can be updated in place.
+// `_3` is a new tuple, but it's read after the update.
+#[test]
+fn in_place_updates() {
+    let ssa = |n: u32| SSA::from_u32(n);
+    let update = |src: u32, dst: u32| Bytecode::Update {
+        src: ssa(src),
+        size: 2,
+        index: 0,
+        value: ssa(10),
+        dst: Memory::SSA(ssa(dst)),
+        in_place: false,
+    };
+    let element = |ptr: u32, offset: u32| Memory::Heap { ptr: ssa(ptr), offset: Offset::Static(offset) };
+    let mut bytecodes = vec![
+        Bytecode::Const { value: Value::Scalar(0), dst: Memory::SSA(ssa(10)), debug_info: None },
+        update(0, 20),
+        Bytecode::InitTuple { elements: 2, dst: Memory::SSA(ssa(1)), debug_info: None },
+        Bytecode::Move { src: Memory::SSA(ssa(10)), dst: element(1, 0) },
+        Bytecode::Move { src: Memory::SSA(ssa(10)), dst: element(1, 1) },
+        Bytecode::Move { src: element(1, 1), dst: Memory::SSA(ssa(11)) },
+        Bytecode::Move { src: Memory::SSA(ssa(1)), dst: Memory::SSA(ssa(2)) },
+        update(2, 21),
+        update(21, 22),
+        Bytecode::InitTuple { elements: 2, dst: Memory::SSA(ssa(3)), debug_info: None },
+        update(3, 23),
+        Bytecode::Move { src: element(3, 1), dst: Memory::SSA(ssa(12)) },
+        Bytecode::Return(ssa(22)),
+    ];
+    mark_in_place_updates(&mut bytecodes);
+    let in_place = bytecodes.iter().filter_map(
+        |bytecode| match bytecode {
+            Bytecode::Update { src, in_place, .. } => Some((src.to_u32(), *in_place)),
+            _ => None,
+        }
+    ).collect::<Vec<_>>();
+
+    assert_eq!(in_place, vec![(0, false), (2, true), (21, true), (3, false)]);
+}
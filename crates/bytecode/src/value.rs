@@ -5,7 +5,6 @@ use sodigy_string::{InternedString, unintern_string};
 use sodigy_token::Constant;
 
 // This is how values are represented in Sodigy runtime.
-// TODO: intern values
 #[derive(Clone, Debug)]
 pub enum Value {
     Scalar(u32),
@@ -16,6 +15,13 @@ pub enum Value {
     List(Vec<Value>),
     Compound(Vec<Value>),
 
+    // `String` and `Bytes` constants. They're `[Char]` and `[Byte]`, but the
+    // runtime stores them in a compact representation (UTF-8 and bytes).
+    String {
+        binary: bool,
+        bytes: Vec<u8>,
+    },
+
     FuncPointer {
         def_span: Span,
         program_counter: Option<usize>,
@@ -24,6 +30,28 @@ pub enum Value {
     // It's only used for some debug information.
     // The runtime may implement a span-renderer, or completely ignore this.
     Span(Span),
+
+    // `Session::link()` moves the values that have to be allocated in the heap
+    // (`Int`, `List`, `Compound`, `String` and `Span`) to `Executable::constants`,
+    // and replaces them with an index of the pool. The runtime allocates the
+    // pool once, so that it doesn't have to allocate the value every time.
+    Pooled(usize),
+}
+
+// The runtime doesn't know the types of the values, so it needs this to read
+// a value back from the heap (e.g. when the compiler evaluates a top-level `let`).
+#[derive(Clone, Debug)]
+pub enum ValueShape {
+    // `Char`, `Byte`, `Scalar` and enums whose representation is `EnumRepr::Scalar`
+    Scalar,
+    Int,
+    List(Box<ValueShape>),
+
+    // tuples and structs
+    Compound(Vec<ValueShape>),
+
+    // Niche-optimized enums. It's either 0 or a value of the inner shape.
+    Nullable(Box<ValueShape>),
 }
 
 impl Session<'_, '_> {
@@ -37,21 +65,12 @@ impl Session<'_, '_> {
         }
     }
 
-    // TODO: we need some kinda intern mechanism here... again!
     // FIXME: so many unwraps!
     pub fn string_to_value(&self, s: InternedString, binary: bool) -> Value {
-        let b = unintern_string(s, &self.intermediate_dir).unwrap().unwrap();
-        let elems: Vec<Value> = if binary {
-            b.iter().map(
-                |b| Value::Scalar(*b as u32)
-            ).collect()
-        } else {
-            String::from_utf8(b).unwrap().chars().map(
-                |c| Value::Scalar(c as u32)
-            ).collect()
-        };
-
-        Value::List(elems)
+        Value::String {
+            binary,
+            bytes: unintern_string(s, &self.intermediate_dir).unwrap().unwrap(),
+        }
     }
 
     pub fn number_to_value(&self, n: InternedNumber) -> Value {
@@ -49,6 +49,14 @@ pub fn get_closest_string(
     candidates: &[String],
     input: &str,
 ) -> Option<String> {
+    get_close_strings(candidates, input).into_iter().next()
+}
+
+// It returns the candidates that are close enough to `input`, the closest one first.
+pub fn get_close_strings(
+    candidates: &[String],
+    input: &str,
+) -> Vec<String> {
     let b = input.as_bytes();
     let mut close_strings = vec![];
 
@@ -58,15 +66,11 @@ pub fn get_closest_string(
         // Different strings can have 0-distance, since it does a case-insensitive comparison
         if dist <= input.len().min(c.len()) / 3 {
             close_strings.push((c.to_string(), dist));
-
-            if dist == 0 {
-                break;
-            }
         }
     }
 
     close_strings.sort_by_key(|(_, dist)| *dist);
-    close_strings.get(0).map(|(s, _)| s.to_string())
+    close_strings.into_iter().map(|(s, _)| s).collect()
 }
 
 // VERY EXPENSIVE
@@ -4,6 +4,7 @@ mod file_size;
 mod parser;
 mod span;
 
+pub use dist::{get_close_strings, get_closest_string};
 pub use error::Error;
 pub use parser::{ArgCount, ArgFlag, ArgParser, ArgType, Flag, ParsedArgs};
 pub use span::underline_span;
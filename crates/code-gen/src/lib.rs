@@ -1,6 +1,12 @@
-use sodigy_bytecode::Session;
+use sodigy_bytecode::{Bytecode, Executable, Label, Session, Value};
 use sodigy_endec::Endec;
-use sodigy_error::{Error, Warning};
+use sodigy_error::{Error, ErrorKind, Lint, LintKind, Warning};
+use sodigy_interpreter::{ConstAssertResult, eval_const, eval_const_assert};
+use sodigy_span::Span;
+use std::collections::HashMap;
+
+// How many bytecodes can the compiler run to evaluate a top-level `let` or an assertion?
+const CONST_EVAL_STEPS: usize = 1 << 20;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Backend {
@@ -10,12 +16,29 @@ pub enum Backend {
     Bytecode,
 }
 
-pub fn lower(mut bytecode_session: Session, backend: Backend) -> (Vec<u8>, Vec<Error>, Vec<Warning>) {
+pub fn lower(
+    mut bytecode_session: Session,
+    backend: Backend,
+
+    // If it's set, it evaluates the assertions at compile time (`--check-const-asserts`).
+    check_const_asserts: bool,
+) -> (Vec<u8>, Vec<Error>, Vec<Warning>) {
     match backend {
         Backend::Bytecode => {
             let executable = bytecode_session.link();
+            let values = eval_const_lets(&bytecode_session, &executable);
+
+            let executable = if values.is_empty() {
+                executable
+            } else {
+                bytecode_session.embed_const_lets(&values);
+                bytecode_session.link()
+            };
+
+            if check_const_asserts {
+                check_asserts(&mut bytecode_session, &executable);
+            }
 
-            // It doesn't generate extra errors/warnings!
             (
                 executable.encode(),
                 bytecode_session.errors.drain(..).collect(),
@@ -25,3 +48,67 @@ pub fn lower(mut bytecode_session: Session, backend: Backend) -> (Vec<u8>, Vec<E
         _ => todo!(),
     }
 }
+
+// It runs the linked executable to evaluate top-level `let`s. If a `let` cannot be
+// evaluated at compile time (e.g. it panics or takes too long), it's not in the
+// result and it's lazy-initialized at runtime.
+fn eval_const_lets(bytecode_session: &Session, executable: &Executable) -> HashMap<Span, Value> {
+    let entry_points: HashMap<Span, usize> = executable.bytecodes.iter().enumerate().filter_map(
+        |(i, bytecode)| match bytecode {
+            Bytecode::Label(Label::Global(def_span)) => Some((def_span.clone(), i + 1)),
+            _ => None,
+        }
+    ).collect();
+    let mut values = HashMap::new();
+
+    for r#let in bytecode_session.lets.iter() {
+        let (Some(shape), Some(entry_point)) = (&r#let.shape, entry_points.get(&r#let.name_span)) else {
+            continue;
+        };
+
+        if let Some(value) = eval_const(
+            executable,
+            *entry_point,
+            shape,
+            CONST_EVAL_STEPS,
+            &bytecode_session.intermediate_dir,
+        ) {
+            values.insert(r#let.name_span.clone(), value);
+        }
+    }
+
+    values
+}
+
+// An assertion is a closed term, so the compiler can run it. If it fails, it's a compile
+// error. If the compiler can't tell (e.g. it's non-deterministic or takes too long), it's
+// checked at runtime, like the other assertions.
+fn check_asserts(bytecode_session: &mut Session, executable: &Executable) {
+    // `link` keeps the order of the assertions.
+    for (assert, (_, entry_point)) in bytecode_session.asserts.iter().zip(executable.asserts.iter()) {
+        match eval_const_assert(
+            executable,
+            *entry_point,
+            CONST_EVAL_STEPS,
+            &bytecode_session.intermediate_dir,
+        ) {
+            ConstAssertResult::Pass => {},
+            ConstAssertResult::Fail { note } => {
+                bytecode_session.errors.push(Error {
+                    kind: ErrorKind::ConstAssertionFailed { name: assert.name },
+                    spans: assert.keyword_span.simple_error(),
+                    note,
+                    suggestions: vec![],
+                });
+            },
+            ConstAssertResult::Skip(failure) => {
+                bytecode_session.warnings.push(Lint {
+                    kind: LintKind::CannotCheckConstAssertion(failure),
+                    spans: assert.keyword_span.simple_error(),
+                    note: None,
+                    suggestions: vec![],
+                });
+            },
+        }
+    }
+}
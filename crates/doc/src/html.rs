@@ -0,0 +1,261 @@
+use crate::{ErrorDoc, ItemDoc, ItemRef, Linker, ModuleDoc, ModuleLayout};
+use crate::text::{Block, parse_blocks, split_inline_code, split_words};
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 960px; margin: 0 auto; padding: 16px; line-height: 1.5; }
+pre, code { font-family: monospace; background: #f4f4f4; }
+pre { padding: 8px; overflow-x: auto; }
+pre.signature { font-weight: bold; }
+section.item { margin: 24px 0; }
+section.item section.item { margin-left: 24px; }
+a { color: #2a5db0; text-decoration: none; }
+a:hover { text-decoration: underline; }";
+
+/// It returns `(file name, content)` of each page: one page per module and an index page.
+pub fn render_html(modules: &[ModuleDoc]) -> Vec<(String, String)> {
+    let linker = Linker::new(modules);
+    let mut pages = vec![(String::from("index.html"), render_index(modules))];
+
+    for module in modules.iter() {
+        pages.push((format!("{}.html", module.path), render_module(module, &linker)));
+    }
+
+    pages
+}
+
+/// A single page that lists all the error kinds, with their extended explanations.
+pub fn render_error_index(errors: &[ErrorDoc]) -> String {
+    // The explanations don't link to any item.
+    let linker = Linker::new(&[]);
+    let mut body = vec![String::from("<h1>Errors</h1>"), String::from("<ul>")];
+
+    for error in errors.iter() {
+        body.push(format!(
+            "<li><a href=\"#{}\"><code>{}</code></a> {} ({})</li>",
+            error.code,
+            error.code,
+            escape(&error.name),
+            error.level,
+        ));
+    }
+
+    body.push(String::from("</ul>"));
+
+    for error in errors.iter() {
+        body.push(format!("<section class=\"item\" id=\"{}\">", error.code));
+        body.push(format!("<h2><code>{}</code> {}</h2>", error.code, escape(&error.name)));
+
+        match &error.explanation {
+            Some(explanation) => {
+                body.push(render_doc(explanation, "", &linker));
+            },
+            None => {
+                body.push(String::from("<p>There's no extended explanation for this error yet.</p>"));
+            },
+        }
+
+        body.push(String::from("</section>"));
+    }
+
+    page("Errors", &body.join("\n"))
+}
+
+fn render_index(modules: &[ModuleDoc]) -> String {
+    let mut body = vec![String::from("<h1>Modules</h1>"), String::from("<ul>")];
+
+    for module in modules.iter() {
+        let summary = match &module.doc {
+            Some(doc) => match parse_blocks(doc).first() {
+                Some(Block::Paragraph(p)) => format!(" &mdash; {}", escape(p)),
+                _ => String::new(),
+            },
+            None => String::new(),
+        };
+
+        body.push(format!("<li><a href=\"{}.html\"><code>{}</code></a>{summary}</li>", module.path, escape(&module.path)));
+    }
+
+    body.push(String::from("</ul>"));
+    page("Modules", &body.join("\n"))
+}
+
+fn render_module(module: &ModuleDoc, linker: &Linker) -> String {
+    let layout = ModuleLayout::new(module);
+    let mut body = vec![
+        String::from("<nav><a href=\"index.html\">index</a></nav>"),
+        format!("<h1>Module <code>{}</code></h1>", escape(&module.path)),
+    ];
+
+    if let Some(doc) = &module.doc {
+        body.push(render_doc(doc, &module.path, linker));
+    }
+
+    if !module.modules.is_empty() {
+        body.push(String::from("<h2>Modules</h2>"));
+        body.push(String::from("<ul>"));
+
+        for child in module.modules.iter() {
+            body.push(format!("<li><a href=\"{child}.html\"><code>{}</code></a></li>", escape(child)));
+        }
+
+        body.push(String::from("</ul>"));
+    }
+
+    for (kind, items) in layout.sections.iter() {
+        body.push(format!("<h2>{}</h2>", kind.section_title()));
+
+        for item in items.iter() {
+            body.push(render_item(item, &layout, &module.path, linker));
+        }
+    }
+
+    for (r#type, items) in layout.foreign_associated_items.iter() {
+        body.push(format!("<h2>Associated functions of {}</h2>", render_ref(r#type)));
+
+        for item in items.iter() {
+            body.push(render_item(item, &layout, &module.path, linker));
+        }
+    }
+
+    page(&module.path, &body.join("\n"))
+}
+
+fn render_item(item: &ItemDoc, layout: &ModuleLayout, module: &str, linker: &Linker) -> String {
+    let mut result = vec![
+        format!("<section class=\"item\" id=\"{}\">", escape(&item.anchor)),
+        format!("<pre class=\"signature\">{}</pre>", render_code(&item.signature, module, linker, Some(&item.anchor))),
+    ];
+
+    if let Some(doc) = &item.doc {
+        result.push(render_doc(doc, module, linker));
+    }
+
+    if !item.members.is_empty() {
+        result.push(String::from("<ul class=\"members\">"));
+
+        for member in item.members.iter() {
+            result.push(format!(
+                "<li><code>{}</code>{}</li>",
+                render_code(&member.signature, module, linker, None),
+                match &member.doc {
+                    Some(doc) => render_doc(doc, module, linker),
+                    None => String::new(),
+                },
+            ));
+        }
+
+        result.push(String::from("</ul>"));
+    }
+
+    if item.is_poly {
+        if item.impls.is_empty() {
+            result.push(String::from("<p>This is a <code>#[poly]</code> function without any implementations.</p>"));
+        }
+
+        else {
+            result.push(String::from("<p>Implementations of this <code>#[poly]</code> function:</p>"));
+            result.push(String::from("<ul>"));
+
+            for r#impl in item.impls.iter() {
+                result.push(format!("<li>{}</li>", render_ref(r#impl)));
+            }
+
+            result.push(String::from("</ul>"));
+        }
+    }
+
+    if let Some(poly) = &item.implements {
+        result.push(format!("<p>Implements <code>#[poly]</code> {}.</p>", render_ref(poly)));
+    }
+
+    if let Some(associated_items) = layout.associated_items.get(item.anchor.as_str()) {
+        result.push(String::from("<h3>Associated functions</h3>"));
+
+        for associated_item in associated_items.iter() {
+            result.push(render_item(associated_item, layout, module, linker));
+        }
+    }
+
+    result.push(String::from("</section>"));
+    result.join("\n")
+}
+
+fn render_ref(item: &ItemRef) -> String {
+    match (&item.module, &item.anchor) {
+        (Some(module), Some(anchor)) => format!("<a href=\"{module}.html#{anchor}\"><code>{}</code></a>", escape(&item.name)),
+        _ => format!("<code>{}</code>", escape(&item.name)),
+    }
+}
+
+fn render_doc(doc: &str, module: &str, linker: &Linker) -> String {
+    let mut result = vec![];
+
+    for block in parse_blocks(doc).iter() {
+        match block {
+            Block::Paragraph(p) => {
+                result.push(format!("<p>{}</p>", render_inline(p, module, linker)));
+            },
+            Block::List(items) => {
+                result.push(String::from("<ul>"));
+
+                for item in items.iter() {
+                    result.push(format!("<li>{}</li>", render_inline(item, module, linker)));
+                }
+
+                result.push(String::from("</ul>"));
+            },
+            Block::Code(code) => {
+                result.push(format!("<pre><code>{}</code></pre>", escape(code)));
+            },
+        }
+    }
+
+    result.join("\n")
+}
+
+fn render_inline(s: &str, module: &str, linker: &Linker) -> String {
+    split_inline_code(s).into_iter().map(
+        |(is_code, s)| if is_code {
+            match linker.find(s, module) {
+                Some((module, anchor)) => format!("<a href=\"{module}.html#{anchor}\"><code>{}</code></a>", escape(s)),
+                None => format!("<code>{}</code>", escape(s)),
+            }
+        } else {
+            escape(s)
+        }
+    ).collect()
+}
+
+// It links the names in a code, except the item itself.
+fn render_code(code: &str, current_module: &str, linker: &Linker, current_item: Option<&str>) -> String {
+    split_words(code).into_iter().map(
+        |(is_word, s)| match linker.find(s, current_module) {
+            Some((module, anchor)) if is_word && (module != current_module || Some(anchor) != current_item) => {
+                format!("<a href=\"{module}.html#{anchor}\">{}</a>", escape(s))
+            },
+            _ => escape(s),
+        }
+    ).collect()
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape(title),
+    )
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
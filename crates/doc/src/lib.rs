@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+mod html;
+mod markdown;
+mod text;
+
+#[cfg(test)]
+mod tests;
+
+pub use html::{render_error_index, render_html};
+pub use markdown::render_markdown;
+pub use text::code_blocks;
+
+// The doc generator doesn't know anything about the compiler. The driver
+// collects the information from the irs, and this crate renders it.
+#[derive(Clone, Debug)]
+pub struct ModuleDoc {
+    // `lib`, `lib.foo`, `std`, `std.option`, ...
+    pub path: String,
+
+    // `//!` comments
+    pub doc: Option<String>,
+
+    // paths of the child modules
+    pub modules: Vec<String>,
+
+    pub items: Vec<ItemDoc>,
+}
+
+// An entry of the error index (`sodigy explain --index`).
+#[derive(Clone, Debug)]
+pub struct ErrorDoc {
+    // `e-0455`
+    pub code: String,
+    pub name: String,
+
+    // `error`, `warning` or `lint`
+    pub level: String,
+    pub explanation: Option<String>,
+}
+
+// A fenced code block in a doc comment. `sodigy test --doc` runs them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodeBlock {
+    // ```` ```sodigy, compile_error ```` -> `["sodigy", "compile_error"]`
+    pub attributes: Vec<String>,
+    pub code: String,
+
+    // indexes of the opening and closing fences in the doc comment lines
+    // If the block is not closed, `last_line` is the last line of the doc comment.
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemDoc {
+    pub kind: ItemKind,
+    pub name: String,
+
+    // It's unique in a module. Use `anchor()` to create one.
+    pub anchor: String,
+
+    // `fn add(a: Int, b: Int) -> Int`
+    // If a type annotation is missing, the driver fills it with the inferred type.
+    pub signature: String,
+    pub doc: Option<String>,
+
+    // fields of a struct or variants of an enum
+    pub members: Vec<MemberDoc>,
+
+    // `#[associate(Foo)]`
+    // If `Foo` is in the same module, it's documented under `Foo`.
+    pub associated_with: Option<ItemRef>,
+
+    // `#[poly]`
+    pub is_poly: bool,
+    pub impls: Vec<ItemRef>,
+
+    // `#[impl(foo)]`
+    pub implements: Option<ItemRef>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ItemKind {
+    Alias,
+    Enum,
+    Func,
+    Let,
+    Struct,
+}
+
+impl ItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemKind::Alias => "type",
+            ItemKind::Enum => "enum",
+            ItemKind::Func => "fn",
+            ItemKind::Let => "let",
+            ItemKind::Struct => "struct",
+        }
+    }
+
+    fn section_title(&self) -> &'static str {
+        match self {
+            ItemKind::Alias => "Type Aliases",
+            ItemKind::Enum => "Enums",
+            ItemKind::Func => "Functions",
+            ItemKind::Let => "Values",
+            ItemKind::Struct => "Structs",
+        }
+    }
+}
+
+// The order of the sections in a module document.
+const SECTIONS: [ItemKind; 5] = [
+    ItemKind::Struct,
+    ItemKind::Enum,
+    ItemKind::Alias,
+    ItemKind::Func,
+    ItemKind::Let,
+];
+
+#[derive(Clone, Debug)]
+pub struct MemberDoc {
+    pub name: String,
+
+    // `x: Int`, `Some(T)`, `Circle { radius: Number }`
+    pub signature: String,
+    pub doc: Option<String>,
+}
+
+// A reference to another item. If the item is not documented (e.g. it's private),
+// `module` and `anchor` are `None` and the name is rendered without a link.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ItemRef {
+    pub name: String,
+    pub module: Option<String>,
+    pub anchor: Option<String>,
+}
+
+impl ItemRef {
+    pub fn unlinked(name: &str) -> ItemRef {
+        ItemRef {
+            name: name.to_string(),
+            module: None,
+            anchor: None,
+        }
+    }
+}
+
+/// An anchor of an item in its module's document. An associated function is
+/// prefixed with the name of the type, so that it doesn't collide with a function
+/// with the same name.
+pub fn anchor(kind: ItemKind, name: &str, associated_with: Option<&str>) -> String {
+    match associated_with {
+        Some(r#type) => format!("{type}.{}.{name}", kind.as_str()),
+        None => format!("{}.{name}", kind.as_str()),
+    }
+}
+
+// Signatures and doc comments link names of the documented items. If there are
+// multiple items with the same name, it prefers the one in the current module.
+// If it's still ambiguous, it doesn't link the name.
+pub(crate) struct Linker {
+    // name -> (module, anchor)
+    names: HashMap<String, Vec<(String, String)>>,
+}
+
+impl Linker {
+    pub fn new(modules: &[ModuleDoc]) -> Linker {
+        let mut names: HashMap<String, Vec<(String, String)>> = HashMap::new();
+
+        for module in modules.iter() {
+            for item in module.items.iter() {
+                // `foo.bar` is not a name of an associated function. It's `Foo.bar`.
+                if item.associated_with.is_some() {
+                    continue;
+                }
+
+                names.entry(item.name.to_string()).or_default().push((module.path.to_string(), item.anchor.to_string()));
+            }
+        }
+
+        Linker { names }
+    }
+
+    pub fn find(&self, name: &str, current_module: &str) -> Option<(&str, &str)> {
+        let candidates = self.names.get(name)?;
+
+        if let Some((module, anchor)) = candidates.iter().find(|(module, _)| module == current_module) {
+            return Some((module, anchor));
+        }
+
+        match candidates.as_slice() {
+            [(module, anchor)] => Some((module, anchor)),
+            _ => None,
+        }
+    }
+}
+
+// Items in a module are grouped by their kinds. Associated items of a type
+// in the same module are nested under the type.
+pub(crate) struct ModuleLayout<'m> {
+    pub sections: Vec<(ItemKind, Vec<&'m ItemDoc>)>,
+
+    // key: anchor of the type
+    pub associated_items: HashMap<&'m str, Vec<&'m ItemDoc>>,
+
+    // associated items of the types in the other modules, grouped by the names of the types
+    pub foreign_associated_items: Vec<(&'m ItemRef, Vec<&'m ItemDoc>)>,
+}
+
+impl<'m> ModuleLayout<'m> {
+    pub fn new(module: &'m ModuleDoc) -> ModuleLayout<'m> {
+        let mut sections = vec![];
+        let mut associated_items: HashMap<&str, Vec<&ItemDoc>> = HashMap::new();
+        let mut foreign_associated_items: Vec<(&ItemRef, Vec<&ItemDoc>)> = vec![];
+
+        for item in module.items.iter() {
+            let Some(r#type) = &item.associated_with else { continue };
+
+            match (&r#type.module, &r#type.anchor) {
+                (Some(type_module), Some(anchor)) if type_module == &module.path => {
+                    associated_items.entry(anchor.as_str()).or_default().push(item);
+                },
+                _ => match foreign_associated_items.iter_mut().find(|(type_, _)| type_.name == r#type.name) {
+                    Some((_, items)) => {
+                        items.push(item);
+                    },
+                    None => {
+                        foreign_associated_items.push((r#type, vec![item]));
+                    },
+                },
+            }
+        }
+
+        for kind in SECTIONS {
+            let items = module.items.iter().filter(
+                |item| item.kind == kind && item.associated_with.is_none()
+            ).collect::<Vec<_>>();
+
+            if !items.is_empty() {
+                sections.push((kind, items));
+            }
+        }
+
+        ModuleLayout {
+            sections,
+            associated_items,
+            foreign_associated_items,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{ItemDoc, ItemRef, Linker, ModuleDoc, ModuleLayout};
+use crate::text::{Block, parse_blocks, split_inline_code};
+
+/// It returns `(file name, content)` of each page: one page per module and an index page.
+///
+/// Markdown can't have links in a code block, so names in the signatures are not linked.
+pub fn render_markdown(modules: &[ModuleDoc]) -> Vec<(String, String)> {
+    let linker = Linker::new(modules);
+    let mut pages = vec![(String::from("index.md"), render_index(modules))];
+
+    for module in modules.iter() {
+        pages.push((format!("{}.md", module.path), render_module(module, &linker)));
+    }
+
+    pages
+}
+
+fn render_index(modules: &[ModuleDoc]) -> String {
+    let mut lines = vec![String::from("# Modules"), String::new()];
+
+    for module in modules.iter() {
+        let summary = match &module.doc {
+            Some(doc) => match parse_blocks(doc).first() {
+                Some(Block::Paragraph(p)) => format!(": {p}"),
+                _ => String::new(),
+            },
+            None => String::new(),
+        };
+
+        lines.push(format!("- [`{}`]({}.md){summary}", module.path, module.path));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn render_module(module: &ModuleDoc, linker: &Linker) -> String {
+    let layout = ModuleLayout::new(module);
+    let mut lines = vec![format!("# Module `{}`", module.path), String::new()];
+
+    if let Some(doc) = &module.doc {
+        lines.push(render_doc(doc, &module.path, linker));
+        lines.push(String::new());
+    }
+
+    if !module.modules.is_empty() {
+        lines.push(String::from("## Modules"));
+        lines.push(String::new());
+
+        for child in module.modules.iter() {
+            lines.push(format!("- [`{child}`]({child}.md)"));
+        }
+
+        lines.push(String::new());
+    }
+
+    for (kind, items) in layout.sections.iter() {
+        lines.push(format!("## {}", kind.section_title()));
+        lines.push(String::new());
+
+        for item in items.iter() {
+            render_item(item, &layout, &module.path, linker, 3, &mut lines);
+        }
+    }
+
+    for (r#type, items) in layout.foreign_associated_items.iter() {
+        lines.push(format!("## Associated functions of {}", render_ref(r#type)));
+        lines.push(String::new());
+
+        for item in items.iter() {
+            render_item(item, &layout, &module.path, linker, 3, &mut lines);
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn render_item(
+    item: &ItemDoc,
+    layout: &ModuleLayout,
+    module: &str,
+    linker: &Linker,
+    heading_level: usize,
+    lines: &mut Vec<String>,
+) {
+    // An explicit anchor, because the heading's own anchor is not predictable.
+    lines.push(format!("<a id=\"{}\"></a>", item.anchor));
+    lines.push(String::new());
+    lines.push(format!("{} {} `{}`", "#".repeat(heading_level), item.kind.as_str(), item.name));
+    lines.push(String::new());
+    lines.push(String::from("```sodigy"));
+    lines.push(item.signature.to_string());
+    lines.push(String::from("```"));
+    lines.push(String::new());
+
+    if let Some(doc) = &item.doc {
+        lines.push(render_doc(doc, module, linker));
+        lines.push(String::new());
+    }
+
+    for member in item.members.iter() {
+        lines.push(format!(
+            "- `{}`{}",
+            member.signature,
+            match &member.doc {
+                // A list item can't have multiple paragraphs without an indentation.
+                Some(doc) => format!(": {}", render_doc(doc, module, linker).replace('\n', "\n  ")),
+                None => String::new(),
+            },
+        ));
+    }
+
+    if !item.members.is_empty() {
+        lines.push(String::new());
+    }
+
+    if item.is_poly {
+        if item.impls.is_empty() {
+            lines.push(String::from("This is a `#[poly]` function without any implementations."));
+        }
+
+        else {
+            lines.push(String::from("Implementations of this `#[poly]` function:"));
+            lines.push(String::new());
+
+            for r#impl in item.impls.iter() {
+                lines.push(format!("- {}", render_ref(r#impl)));
+            }
+        }
+
+        lines.push(String::new());
+    }
+
+    if let Some(poly) = &item.implements {
+        lines.push(format!("Implements `#[poly]` {}.", render_ref(poly)));
+        lines.push(String::new());
+    }
+
+    if let Some(associated_items) = layout.associated_items.get(item.anchor.as_str()) {
+        lines.push(format!("{} Associated functions", "#".repeat(heading_level + 1)));
+        lines.push(String::new());
+
+        for associated_item in associated_items.iter() {
+            render_item(associated_item, layout, module, linker, heading_level + 2, lines);
+        }
+    }
+}
+
+fn render_ref(item: &ItemRef) -> String {
+    match (&item.module, &item.anchor) {
+        (Some(module), Some(anchor)) => format!("[`{}`]({module}.md#{anchor})", item.name),
+        _ => format!("`{}`", item.name),
+    }
+}
+
+fn render_doc(doc: &str, module: &str, linker: &Linker) -> String {
+    let mut result = vec![];
+
+    for block in parse_blocks(doc).iter() {
+        match block {
+            Block::Paragraph(p) => {
+                result.push(render_inline(p, module, linker));
+            },
+            Block::List(items) => {
+                result.push(items.iter().map(
+                    |item| format!("- {}", render_inline(item, module, linker))
+                ).collect::<Vec<_>>().join("\n"));
+            },
+            Block::Code(code) => {
+                result.push(format!("```sodigy\n{code}\n```"));
+            },
+        }
+    }
+
+    result.join("\n\n")
+}
+
+fn render_inline(s: &str, module: &str, linker: &Linker) -> String {
+    split_inline_code(s).into_iter().map(
+        |(is_code, s)| if is_code {
+            match linker.find(s, module) {
+                Some((module, anchor)) => format!("[`{s}`]({module}.md#{anchor})"),
+                None => format!("`{s}`"),
+            }
+        } else {
+            s.to_string()
+        }
+    ).collect()
+}
@@ -0,0 +1,207 @@
+use crate::{
+    CodeBlock,
+    ErrorDoc,
+    ItemDoc,
+    ItemKind,
+    ItemRef,
+    MemberDoc,
+    ModuleDoc,
+    anchor,
+    code_blocks,
+    render_error_index,
+    render_html,
+    render_markdown,
+};
+use crate::text::{Block, parse_blocks, split_inline_code, split_words};
+
+fn item(kind: ItemKind, name: &str, signature: &str, doc: Option<&str>) -> ItemDoc {
+    ItemDoc {
+        kind,
+        name: name.to_string(),
+        anchor: anchor(kind, name, None),
+        signature: signature.to_string(),
+        doc: doc.map(|doc| doc.to_string()),
+        members: vec![],
+        associated_with: None,
+        is_poly: false,
+        impls: vec![],
+        implements: None,
+    }
+}
+
+fn item_ref(module: &str, kind: ItemKind, name: &str) -> ItemRef {
+    ItemRef {
+        name: name.to_string(),
+        module: Some(module.to_string()),
+        anchor: Some(anchor(kind, name, None)),
+    }
+}
+
+fn sample_modules() -> Vec<ModuleDoc> {
+    let mut point = item(ItemKind::Struct, "Point", "struct Point", Some("A point in 2D."));
+    point.members = vec![
+        MemberDoc { name: String::from("x"), signature: String::from("x: Int"), doc: Some(String::from("x coordinate")) },
+        MemberDoc { name: String::from("y"), signature: String::from("y: Int"), doc: None },
+    ];
+
+    let mut norm = item(ItemKind::Func, "norm", "fn norm(self) -> Int", None);
+    norm.anchor = anchor(ItemKind::Func, "norm", Some("Point"));
+    norm.associated_with = Some(item_ref("lib", ItemKind::Struct, "Point"));
+
+    let mut greet = item(ItemKind::Func, "greet", "fn greet<T>(x: T) -> String", Some("Greets anything."));
+    greet.is_poly = true;
+    greet.impls = vec![item_ref("lib.shapes", ItemKind::Func, "greet_point"), ItemRef::unlinked("greet_secret")];
+
+    let mut greet_point = item(ItemKind::Func, "greet_point", "fn greet_point(p: Point) -> String", None);
+    greet_point.implements = Some(item_ref("lib", ItemKind::Func, "greet"));
+
+    let origin = item(ItemKind::Let, "origin", "let origin: Point", Some("See `Point` and `norm`.\n\n```\nlet p = origin;\n```"));
+
+    vec![
+        ModuleDoc {
+            path: String::from("lib"),
+            doc: Some(String::from(" The root module.\n\n More docs.")),
+            modules: vec![String::from("lib.shapes")],
+            items: vec![origin, point, norm, greet],
+        },
+        ModuleDoc {
+            path: String::from("lib.shapes"),
+            doc: None,
+            modules: vec![],
+            items: vec![greet_point],
+        },
+    ]
+}
+
+#[test]
+fn html() {
+    let pages = render_html(&sample_modules());
+    let names = pages.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["index.html", "lib.html", "lib.shapes.html"]);
+
+    let index = &pages[0].1;
+    assert!(index.contains("<a href=\"lib.html\"><code>lib</code></a> &mdash; The root module.</li>"));
+
+    let lib = &pages[1].1;
+
+    // Structs come before values, and the associated function is nested under the struct.
+    let point = lib.find("id=\"struct.Point\"").unwrap();
+    let norm = lib.find("id=\"Point.fn.norm\"").unwrap();
+    let origin = lib.find("id=\"let.origin\"").unwrap();
+    assert!(point < norm && norm < origin);
+    assert!(lib.contains("<h3>Associated functions</h3>"));
+    assert!(lib.contains("<pre class=\"signature\">struct Point</pre>"));
+
+    // cross-links in signatures and doc comments
+    assert!(lib.contains("<pre class=\"signature\">let origin: <a href=\"lib.html#struct.Point\">Point</a></pre>"));
+    assert!(lib.contains("See <a href=\"lib.html#struct.Point\"><code>Point</code></a> and <code>norm</code>."));
+    assert!(lib.contains("<pre><code>let p = origin;</code></pre>"));
+    assert!(lib.contains("<li><code>x: Int</code><p>x coordinate</p></li>"));
+
+    // `#[poly]` and `#[impl]`
+    assert!(lib.contains("<li><a href=\"lib.shapes.html#fn.greet_point\"><code>greet_point</code></a></li>"));
+    assert!(lib.contains("<li><code>greet_secret</code></li>"));
+    assert!(lib.contains("&lt;T&gt;"));
+
+    let shapes = &pages[2].1;
+    assert!(shapes.contains("Implements <code>#[poly]</code> <a href=\"lib.html#fn.greet\"><code>greet</code></a>."));
+    assert!(shapes.contains("greet_point(p: <a href=\"lib.html#struct.Point\">Point</a>)"));
+}
+
+#[test]
+fn markdown() {
+    let pages = render_markdown(&sample_modules());
+    let names = pages.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["index.md", "lib.md", "lib.shapes.md"]);
+
+    let lib = &pages[1].1;
+    assert!(lib.contains("# Module `lib`\n\nThe root module.\n\nMore docs.\n"));
+    assert!(lib.contains("- [`lib.shapes`](lib.shapes.md)"));
+    assert!(lib.contains("<a id=\"struct.Point\"></a>\n\n### struct `Point`\n\n```sodigy\nstruct Point\n```"));
+    assert!(lib.contains("- `x: Int`: x coordinate\n- `y: Int`\n"));
+    assert!(lib.contains("#### Associated functions\n\n<a id=\"Point.fn.norm\"></a>\n\n##### fn `norm`"));
+    assert!(lib.contains("See [`Point`](lib.md#struct.Point) and `norm`."));
+    assert!(lib.contains("- [`greet_point`](lib.shapes.md#fn.greet_point)\n- `greet_secret`"));
+}
+
+#[test]
+fn doc_text() {
+    assert_eq!(
+        parse_blocks(" It does\n something.\n\n - a\n - b\n   continued\n\n ```\n let x = 3;\n ```"),
+        vec![
+            Block::Paragraph(String::from("It does something.")),
+            Block::List(vec![String::from("a"), String::from("b continued")]),
+            Block::Code(String::from(" let x = 3;")),
+        ],
+    );
+    assert_eq!(split_inline_code("a `b` c `d"), vec![(false, "a "), (true, "b"), (false, " c `d")]);
+    assert_eq!(
+        split_words("Fn(Int) -> [T2]"),
+        vec![(true, "Fn"), (false, "("), (true, "Int"), (false, ") -> ["), (true, "T2"), (false, "]")],
+    );
+    assert_eq!(split_words("x + 3"), vec![(true, "x"), (false, " + "), (false, "3")]);
+}
+
+#[test]
+fn error_index() {
+    let page = render_error_index(&[
+        ErrorDoc {
+            code: String::from("e-0330"),
+            name: String::from("UndefinedName"),
+            level: String::from("error"),
+            explanation: Some(String::from("The name is not defined.\n\n```sodigy, compile_error\nlet x = y;\n```")),
+        },
+        ErrorDoc {
+            code: String::from("w-5000"),
+            name: String::from("UnusedNames"),
+            level: String::from("warning"),
+            explanation: None,
+        },
+    ]);
+
+    assert!(page.contains("<li><a href=\"#e-0330\"><code>e-0330</code></a> UndefinedName (error)</li>"));
+    assert!(page.contains("<section class=\"item\" id=\"w-5000\">"));
+    assert!(page.contains("<p>The name is not defined.</p>\n<pre><code>let x = y;</code></pre>"));
+    assert!(page.contains("There's no extended explanation for this error yet."));
+}
+
+#[test]
+fn doc_code_blocks() {
+    let lines = [
+        " Adds two numbers.",
+        "",
+        " ```sodigy",
+        " assert add(1, 2) == 3;",
+        " ```",
+        "",
+        " ```sodigy, compile_error",
+        " let x = add(1);",
+        " ```",
+        " ```",
+        " unterminated",
+    ];
+
+    assert_eq!(
+        code_blocks(&lines),
+        vec![
+            CodeBlock {
+                attributes: vec![String::from("sodigy")],
+                code: String::from("assert add(1, 2) == 3;"),
+                first_line: 2,
+                last_line: 4,
+            },
+            CodeBlock {
+                attributes: vec![String::from("sodigy"), String::from("compile_error")],
+                code: String::from("let x = add(1);"),
+                first_line: 6,
+                last_line: 8,
+            },
+            CodeBlock {
+                attributes: vec![],
+                code: String::from("unterminated"),
+                first_line: 9,
+                last_line: 10,
+            },
+        ],
+    );
+}
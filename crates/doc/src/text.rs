@@ -0,0 +1,182 @@
+use crate::CodeBlock;
+
+// A tiny subset of markdown that doc comments use: paragraphs, bullet lists,
+// fenced code blocks and inline code.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Block {
+    Paragraph(String),
+    List(Vec<String>),
+    Code(String),
+}
+
+pub(crate) fn parse_blocks(doc: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut list: Vec<String> = vec![];
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in doc.lines() {
+        if let Some(code_lines) = &mut code {
+            if line.trim_start().starts_with("```") {
+                blocks.push(Block::Code(code_lines.join("\n")));
+                code = None;
+            }
+
+            else {
+                code_lines.push(line);
+            }
+
+            continue;
+        }
+
+        let trimmed = line.trim();
+
+        let ends_paragraph = trimmed.starts_with("```") || trimmed.is_empty() || trimmed.starts_with("- ") || trimmed.starts_with("* ");
+
+        if ends_paragraph && !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+
+        if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+            list.push(trimmed[2..].trim().to_string());
+            continue;
+        }
+
+        // a continuation of a list item
+        if !list.is_empty() && !trimmed.is_empty() && line.starts_with(' ') && !trimmed.starts_with("```") {
+            let last = list.last_mut().unwrap();
+            last.push(' ');
+            last.push_str(trimmed);
+            continue;
+        }
+
+        if !list.is_empty() {
+            blocks.push(Block::List(std::mem::take(&mut list)));
+        }
+
+        if trimmed.starts_with("```") {
+            code = Some(vec![]);
+        }
+
+        else if !trimmed.is_empty() {
+            paragraph.push(trimmed);
+        }
+    }
+
+    if !paragraph.is_empty() {
+        blocks.push(Block::Paragraph(paragraph.join(" ")));
+    }
+
+    if !list.is_empty() {
+        blocks.push(Block::List(list));
+    }
+
+    // an unterminated code block
+    if let Some(code_lines) = code {
+        blocks.push(Block::Code(code_lines.join("\n")));
+    }
+
+    blocks
+}
+
+// Unlike `parse_blocks`, it keeps the info strings and the line numbers of the code blocks.
+// A doc comment line starts with a space (`/// foo`), and it's removed from the code.
+pub fn code_blocks(lines: &[&str]) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut curr: Option<CodeBlock> = None;
+    let mut code_lines: Vec<&str> = vec![];
+
+    for (i, line) in lines.iter().enumerate() {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match &mut curr {
+            Some(block) => {
+                if is_fence {
+                    block.code = code_lines.join("\n");
+                    block.last_line = i;
+                    blocks.push(curr.take().unwrap());
+                    code_lines.clear();
+                }
+
+                else {
+                    code_lines.push(line.strip_prefix(' ').unwrap_or(line));
+                }
+            },
+            None if is_fence => {
+                let info = &line.trim_start()[3..];
+
+                curr = Some(CodeBlock {
+                    attributes: info.split(',').map(
+                        |attribute| attribute.trim().to_string()
+                    ).filter(
+                        |attribute| !attribute.is_empty()
+                    ).collect(),
+                    code: String::new(),
+                    first_line: i,
+                    last_line: i,
+                });
+            },
+            None => {},
+        }
+    }
+
+    if let Some(mut block) = curr {
+        block.code = code_lines.join("\n");
+        block.last_line = lines.len() - 1;
+        blocks.push(block);
+    }
+
+    blocks
+}
+
+// It splits a line into texts and inline codes. `(true, s)` is an inline code.
+pub(crate) fn split_inline_code(s: &str) -> Vec<(bool, &str)> {
+    let mut result = vec![];
+    let mut rest = s;
+
+    while let Some(start) = rest.find('`') {
+        let Some(end) = rest[(start + 1)..].find('`') else { break };
+
+        if start > 0 {
+            result.push((false, &rest[..start]));
+        }
+
+        result.push((true, &rest[(start + 1)..(start + 1 + end)]));
+        rest = &rest[(start + 2 + end)..];
+    }
+
+    if !rest.is_empty() {
+        result.push((false, rest));
+    }
+
+    result
+}
+
+// It splits a code into identifiers and the others. `(true, s)` is an identifier.
+pub(crate) fn split_words(s: &str) -> Vec<(bool, &str)> {
+    let mut result = vec![];
+    let mut start = 0;
+    let mut is_word = false;
+    let word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    for (i, c) in s.char_indices() {
+        if word_char(c) != is_word {
+            if start < i {
+                result.push((is_word, &s[start..i]));
+            }
+
+            start = i;
+            is_word = word_char(c);
+        }
+    }
+
+    if start < s.len() {
+        result.push((is_word, &s[start..]));
+    }
+
+    // `3` is not an identifier.
+    result.into_iter().map(
+        |(is_word, word)| (is_word && !word.starts_with(|c: char| c.is_ascii_digit()), word)
+    ).collect()
+}
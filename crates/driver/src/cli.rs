@@ -23,8 +23,10 @@ pub enum CliCommand {
         validate_token_spans: ValidateTokenSpans,
         jobs: usize,
         color: ColorWhen,
+        message_format: MessageFormat,
         dump_post_mir_log: bool,
         dump_timings: bool,
+        check_const_asserts: bool,
     },
     Run {
         optimize_level: OptimizeLevel,
@@ -34,6 +36,7 @@ pub enum CliCommand {
         validate_token_spans: ValidateTokenSpans,
         jobs: usize,
         color: ColorWhen,
+        message_format: MessageFormat,
         dump_post_mir_log: bool,
         dump_timings: bool,
     },
@@ -45,8 +48,15 @@ pub enum CliCommand {
         validate_token_spans: ValidateTokenSpans,
         jobs: usize,
         color: ColorWhen,
+        message_format: MessageFormat,
         dump_post_mir_log: bool,
         dump_timings: bool,
+
+        // `--doc` runs the code blocks in the doc comments instead of the assertions.
+        doc: bool,
+
+        // `--std` runs the doc tests of std, too.
+        include_std: bool,
     },
     Clean,
     Help(String),
@@ -57,6 +67,34 @@ pub enum CliCommand {
     New {
         project_name: String,
     },
+    Lsp {
+        jobs: usize,
+    },
+    Doc {
+        output_dir: String,
+        format: DocFormat,
+        include_std: bool,
+        document_private_items: bool,
+        jobs: usize,
+    },
+    Explain {
+        // `455`, `e-0455` or `NonExhaustiveArms`
+        // If it's `None`, it lists all the error kinds.
+        error: Option<String>,
+
+        // `--index` writes the error index at `{output_dir}/errors.html`.
+        index: bool,
+        output_dir: String,
+    },
+    Fmt {
+        // If it's empty, it formats all the files in `src/`.
+        paths: Vec<String>,
+        check: bool,
+    },
+    Fix {
+        jobs: usize,
+        color: ColorWhen,
+    },
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
@@ -66,6 +104,21 @@ pub enum ColorWhen {
     Never,
 }
 
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MessageFormat {
+    // colored text for humans
+    Human,
+
+    // JSON Lines for tools: one object per error/warning
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DocFormat {
+    Html,
+    Markdown,
+}
+
 pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
     match args.get(1).map(|a| a.as_str()) {
         Some("build") => {
@@ -73,6 +126,7 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 .optional_arg_flag("--output", ArgType::String)
                 .optional_arg_flag("--backend", ArgType::enum_(&["c", "rust", "python", "bytecode"]))
                 .optional_arg_flag("--color", ArgType::enum_(&["auto", "always", "never"]))
+                .optional_arg_flag("--message-format", ArgType::enum_(&["human", "json"]))
                 .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
                 .optional_flag(&["--release"])
                 .optional_flag(&["--test"])
@@ -85,6 +139,7 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                     "--validate-std-token-spans",
                     "--validate-lib-token-spans",
                 ])
+                .optional_flag(&["--check-const-asserts"])
                 .alias("-O", "--release")
                 .short_flag(&["--output", "--jobs"])
                 .args(ArgType::String, ArgCount::None)
@@ -110,6 +165,12 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 None => ColorWhen::Auto,  // default
                 _ => unreachable!(),
             };
+            let message_format = match parsed_args.arg_flags.get("--message-format").map(|f| f.as_str()) {
+                Some("human") => MessageFormat::Human,
+                Some("json") => MessageFormat::Json,
+                None => MessageFormat::Human,  // default
+                _ => unreachable!(),
+            };
             let jobs = parsed_args.arg_flags.get("--jobs").map(
                 |n| n.parse::<usize>().unwrap()
             ).unwrap_or_else(
@@ -141,6 +202,8 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 _ => unreachable!(),
             };
 
+            let check_const_asserts = parsed_args.get_flag(6).is_some();
+
             let output_path = match output_path {
                 Some(output_path) => output_path,
                 None => String::from("out.sdgbc"),
@@ -157,8 +220,10 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 emit_irs,
                 jobs,
                 color,
+                message_format,
                 dump_post_mir_log,
                 dump_timings,
+                check_const_asserts,
             })
         },
         Some("clean") => {
@@ -203,6 +268,131 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 profile: Profile::Test,
             })
         },
+        Some("lsp") => {
+            let parsed_args = ArgParser::new()
+                .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
+                .short_flag(&["--jobs"])
+                .args(ArgType::String, ArgCount::None)
+                .parse(args, 2)?;
+
+            if parsed_args.show_help() {
+                return Ok(CliCommand::Help(String::from("lsp")));
+            }
+
+            let jobs = parsed_args.arg_flags.get("--jobs").map(
+                |n| n.parse::<usize>().unwrap()
+            ).unwrap_or_else(
+                || std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+            );
+
+            Ok(CliCommand::Lsp { jobs })
+        },
+        Some("doc") => {
+            let parsed_args = ArgParser::new()
+                .optional_arg_flag("--output", ArgType::String)
+                .optional_arg_flag("--format", ArgType::enum_(&["html", "markdown"]))
+                .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
+                .optional_flag(&["--std"])
+                .optional_flag(&["--document-private-items"])
+                .short_flag(&["--output", "--jobs"])
+                .args(ArgType::String, ArgCount::None)
+                .parse(args, 2)?;
+
+            if parsed_args.show_help() {
+                return Ok(CliCommand::Help(String::from("doc")));
+            }
+
+            let output_dir = parsed_args.arg_flags.get("--output").map(
+                |p| p.to_string()
+            ).unwrap_or_else(
+                || String::from("target/doc")
+            );
+            let format = match parsed_args.arg_flags.get("--format").map(|f| f.as_str()) {
+                Some("html") => DocFormat::Html,
+                Some("markdown") => DocFormat::Markdown,
+                None => DocFormat::Html,  // default
+                _ => unreachable!(),
+            };
+            let jobs = parsed_args.arg_flags.get("--jobs").map(
+                |n| n.parse::<usize>().unwrap()
+            ).unwrap_or_else(
+                || std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+            );
+
+            Ok(CliCommand::Doc {
+                output_dir,
+                format,
+                include_std: parsed_args.get_flag(0).is_some(),
+                document_private_items: parsed_args.get_flag(1).is_some(),
+                jobs,
+            })
+        },
+        Some("explain") => {
+            let parsed_args = ArgParser::new()
+                .optional_arg_flag("--output", ArgType::String)
+                .optional_flag(&["--index"])
+                .short_flag(&["--output"])
+                .args(ArgType::String, ArgCount::Leq(1))  // error index or name
+                .parse(args, 2)?;
+
+            if parsed_args.show_help() {
+                return Ok(CliCommand::Help(String::from("explain")));
+            }
+
+            let output_dir = parsed_args.arg_flags.get("--output").map(
+                |p| p.to_string()
+            ).unwrap_or_else(
+                || String::from("target/doc")
+            );
+
+            Ok(CliCommand::Explain {
+                error: parsed_args.get_args().first().map(|e| e.to_string()),
+                index: parsed_args.get_flag(0).is_some(),
+                output_dir,
+            })
+        },
+        Some("fix") => {
+            let parsed_args = ArgParser::new()
+                .optional_arg_flag("--color", ArgType::enum_(&["auto", "always", "never"]))
+                .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
+                .short_flag(&["--jobs"])
+                .args(ArgType::String, ArgCount::None)
+                .parse(args, 2)?;
+
+            if parsed_args.show_help() {
+                return Ok(CliCommand::Help(String::from("fix")));
+            }
+
+            let color = match parsed_args.arg_flags.get("--color").map(|f| f.as_str()) {
+                Some("auto") => ColorWhen::Auto,
+                Some("always") => ColorWhen::Always,
+                Some("never") => ColorWhen::Never,
+                None => ColorWhen::Auto,  // default
+                _ => unreachable!(),
+            };
+            let jobs = parsed_args.arg_flags.get("--jobs").map(
+                |n| n.parse::<usize>().unwrap()
+            ).unwrap_or_else(
+                || std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4)
+            );
+
+            Ok(CliCommand::Fix { jobs, color })
+        },
+        Some("fmt") => {
+            let parsed_args = ArgParser::new()
+                .optional_flag(&["--check"])
+                .args(ArgType::String, ArgCount::Any)  // paths
+                .parse(args, 2)?;
+
+            if parsed_args.show_help() {
+                return Ok(CliCommand::Help(String::from("fmt")));
+            }
+
+            Ok(CliCommand::Fmt {
+                paths: parsed_args.get_args(),
+                check: parsed_args.get_flag(0).is_some(),
+            })
+        },
         Some("new") => {
             let parsed_args = ArgParser::new()
                 .args(ArgType::String, ArgCount::Exact(1))  // project name
@@ -219,6 +409,7 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
         Some("run") => {
             let parsed_args = ArgParser::new()
                 .optional_arg_flag("--color", ArgType::enum_(&["auto", "always", "never"]))
+                .optional_arg_flag("--message-format", ArgType::enum_(&["human", "json"]))
                 .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
                 .optional_flag(&["--release"])
                 .optional_flag(&["--emit-irs"])
@@ -251,6 +442,12 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 None => ColorWhen::Auto,  // default
                 _ => unreachable!(),
             };
+            let message_format = match parsed_args.arg_flags.get("--message-format").map(|f| f.as_str()) {
+                Some("human") => MessageFormat::Human,
+                Some("json") => MessageFormat::Json,
+                None => MessageFormat::Human,  // default
+                _ => unreachable!(),
+            };
             let jobs = parsed_args.arg_flags.get("--jobs").map(
                 |n| n.parse::<usize>().unwrap()
             ).unwrap_or_else(
@@ -276,6 +473,7 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 emit_irs,
                 jobs,
                 color,
+                message_format,
                 dump_post_mir_log,
                 dump_timings,
             })
@@ -283,6 +481,7 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
         Some("test") => {
             let parsed_args = ArgParser::new()
                 .optional_arg_flag("--color", ArgType::enum_(&["auto", "always", "never"]))
+                .optional_arg_flag("--message-format", ArgType::enum_(&["human", "json"]))
                 .optional_arg_flag("--jobs", ArgType::integer_between(Some(1), Some(u32::MAX.into())))
                 .optional_flag(&["--release"])
                 .optional_flag(&["--emit-irs"])
@@ -294,6 +493,8 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                     "--validate-std-token-spans",
                     "--validate-lib-token-spans",
                 ])
+                .optional_flag(&["--doc"])
+                .optional_flag(&["--std"])
                 .alias("-O", "--release")
                 .short_flag(&["--jobs"])
                 .args(ArgType::String, ArgCount::None)
@@ -315,6 +516,12 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 None => ColorWhen::Auto,  // default
                 _ => unreachable!(),
             };
+            let message_format = match parsed_args.arg_flags.get("--message-format").map(|f| f.as_str()) {
+                Some("human") => MessageFormat::Human,
+                Some("json") => MessageFormat::Json,
+                None => MessageFormat::Human,  // default
+                _ => unreachable!(),
+            };
             let jobs = parsed_args.arg_flags.get("--jobs").map(
                 |n| n.parse::<usize>().unwrap()
             ).unwrap_or_else(
@@ -332,6 +539,9 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 _ => unreachable!(),
             };
 
+            let doc = parsed_args.get_flag(5).is_some();
+            let include_std = parsed_args.get_flag(6).is_some();
+
             Ok(CliCommand::Test {
                 optimize_level,
                 custom_error_levels: HashMap::new(),  // TODO: make it configurable
@@ -340,8 +550,11 @@ pub fn parse_args(args: &[String]) -> Result<CliCommand, CliError> {
                 emit_irs,
                 jobs,
                 color,
+                message_format,
                 dump_post_mir_log,
                 dump_timings,
+                doc,
+                include_std,
             })
         },
         Some(_) => todo!(),
@@ -5,9 +5,11 @@ use crate::{
 };
 use sodigy_code_gen::Backend;
 use sodigy_file::{FileOrStd, ModulePath};
+use sodigy_mir::Func;
 use sodigy_optimize::OptimizeLevel;
 use sodigy_span::Span;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 // Read `crates/driver/src/compile_stage.rs` for more information.
 #[derive(Clone, Debug)]
@@ -55,6 +57,7 @@ pub enum Command {
         intermediate_dir: String,
         backend: Backend,
         dump_bytecodes: bool,
+        check_const_asserts: bool,
         output_path: StoreIrAt,
     },
     LoadInterHirSession {
@@ -62,6 +65,9 @@ pub enum Command {
     },
     LoadMirGlobalContext {
         intermediate_dir: String,
+
+        // It's shared by every worker. Read the comments in `GlobalContext`.
+        funcs: Option<Arc<RwLock<HashMap<Span, Func>>>>,
     },
 }
 
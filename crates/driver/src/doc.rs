@@ -0,0 +1,468 @@
+// `sodigy doc` generates API documents of a project.
+//
+// It compiles the project, and reads the items from the ast (doc comments,
+// decorators and type annotations are there) and the inferred types, polys and
+// associated items from the inter-mir session.
+
+use crate::{CompileStage, Error, ModuleCompileState, check_project, get_cached_ir};
+use crate::cli::DocFormat;
+use sodigy_doc::{ItemDoc, ItemKind, ItemRef, MemberDoc, ModuleDoc, anchor, render_html, render_markdown};
+use sodigy_endec::Endec;
+use sodigy_error::DumpErrorOption;
+use sodigy_file::{File, FileOrStd, ModulePath};
+use sodigy_fs_api::{WriteMode, create_dir_all, exists, join, write_string};
+use sodigy_inter_mir as inter_mir;
+use sodigy_mir as mir;
+use sodigy_parse::{self as ast, Attribute, EnumVariantFields, Generic};
+use sodigy_span::Span;
+use sodigy_string::InternedString;
+use std::collections::HashMap;
+
+/// It generates the documents of the project in `src_dir` at `output_dir`.
+///
+/// Only `pub` items are documented unless `document_private_items` is set. std doesn't
+/// annotate visibility yet, so every item in std is documented.
+pub fn generate_docs(
+    src_dir: String,
+    ir_dir: String,
+    output_dir: &str,
+    format: DocFormat,
+    include_std: bool,
+    document_private_items: bool,
+    jobs: usize,
+) -> Result<(), Error> {
+    let mut modules: HashMap<ModulePath, ModuleCompileState> = HashMap::new();
+    let (errors, warnings) = check_project(src_dir, ir_dir.clone(), jobs, &mut modules);
+
+    // It needs the inferred types, which are available only if the project compiles.
+    if !errors.is_empty() {
+        eprintln!("{}", sodigy_error::dump_errors(
+            errors,
+            warnings,
+            &ir_dir,
+            DumpErrorOption::default(),
+            None,
+            true,  // show summary
+        ));
+        return Err(Error::CompileError);
+    }
+
+    let session = match get_cached_ir(&ir_dir, CompileStage::InterMir, None)? {
+        Some(bytes) => {
+            let mut session = inter_mir::Session::decode(&bytes)?;
+            session.intermediate_dir = ir_dir.to_string();
+            session
+        },
+        None => {
+            return Err(Error::IrCacheNotFound(CompileStage::InterMir));
+        },
+    };
+
+    let mut modules = modules.into_values().filter(
+        |module| include_std || matches!(module.file_path, FileOrStd::File(_))
+    ).collect::<Vec<_>>();
+    modules.sort_by_key(|module| doc_module_path(&module.module_path));
+
+    let mut collector = Collector::new(&session, document_private_items);
+
+    for module in modules.iter() {
+        collector.collect_module(module)?;
+    }
+
+    let module_docs = collector.finish();
+    let pages = match format {
+        DocFormat::Html => render_html(&module_docs),
+        DocFormat::Markdown => render_markdown(&module_docs),
+    };
+
+    if !exists(output_dir) {
+        create_dir_all(output_dir)?;
+    }
+
+    for (name, content) in pages.iter() {
+        write_string(&join(output_dir, name)?, content, WriteMode::CreateOrTruncate)?;
+    }
+
+    Ok(())
+}
+
+// `lib`, `lib.foo`, `std`, `std.option`
+pub(crate) fn doc_module_path(module_path: &ModulePath) -> String {
+    let module_path = module_path.to_string();
+
+    match module_path.strip_prefix("@std.lib") {
+        Some(rest) => format!("std{rest}"),
+        None => module_path,
+    }
+}
+
+// An item that might be documented. Whether it's documented or not is decided after
+// all the modules are collected, because an associated function is documented if its
+// type is documented.
+struct Candidate {
+    module: usize,
+    item: ItemDoc,
+    name_span: Span,
+    public: bool,
+
+    // def_span of the type, if it's an associated item
+    associated_type: Option<Span>,
+}
+
+struct Collector<'s> {
+    session: &'s inter_mir::Session,
+    intermediate_dir: String,
+    document_private_items: bool,
+
+    // of the current module
+    is_std: bool,
+
+    modules: Vec<ModuleDoc>,
+    candidates: Vec<Candidate>,
+
+    // name_span of an associated item -> def_span of the type
+    associated_types: HashMap<Span, Span>,
+}
+
+impl<'s> Collector<'s> {
+    fn new(session: &'s inter_mir::Session, document_private_items: bool) -> Self {
+        let mut associated_types = HashMap::new();
+
+        for (type_span, (funcs, lets)) in session.struct_shapes.iter().map(
+            |(span, shape)| (span, (&shape.associated_funcs, &shape.associated_lets))
+        ).chain(session.enum_shapes.iter().map(
+            |(span, shape)| (span, (&shape.associated_funcs, &shape.associated_lets))
+        )) {
+            for func in funcs.values() {
+                for name_span in func.name_spans.iter() {
+                    associated_types.insert(name_span.clone(), type_span.clone());
+                }
+            }
+
+            for name_span in lets.values() {
+                associated_types.insert(name_span.clone(), type_span.clone());
+            }
+        }
+
+        Collector {
+            session,
+            intermediate_dir: session.intermediate_dir.to_string(),
+            document_private_items,
+            is_std: false,
+            modules: vec![],
+            candidates: vec![],
+            associated_types,
+        }
+    }
+
+    fn collect_module(&mut self, module: &ModuleCompileState) -> Result<(), Error> {
+        let (file, is_std) = match &module.file_path {
+            FileOrStd::File(_) => match File::from_module_path(&module.module_path.to_string(), &self.intermediate_dir)? {
+                Some(file) => (file, false),
+                None => {
+                    return Ok(());
+                },
+            },
+            FileOrStd::Std(n) => (File::std(*n), true),
+        };
+        let Some(bytes) = file.read_bytes(&self.intermediate_dir)? else { return Ok(()) };
+        self.is_std = is_std;
+
+        // The project has been compiled, so there's no syntax error.
+        let lex_session = sodigy_lex::lex(file, bytes.clone(), self.intermediate_dir.to_string(), is_std, false);
+        let file_span = lex_session.file_span();
+        let parse_session = sodigy_parse::parse(lex_session, file_span);
+        let ast = &parse_session.ast;
+
+        let path = doc_module_path(&module.module_path);
+        let source = Source { bytes: &bytes };
+
+        self.modules.push(ModuleDoc {
+            path: path.to_string(),
+            doc: ast.attribute.as_ref().and_then(|attribute| self.render_doc_comment(attribute)),
+            modules: ast.modules.iter().map(
+                |module| format!("{path}.{}", module.name.unintern_or_default(&self.intermediate_dir))
+            ).collect(),
+            items: vec![],
+        });
+
+        for r#struct in ast.structs.iter() {
+            let members = r#struct.fields.as_ref().map(
+                |fields| fields.iter().map(|field| self.field_doc(field, &source)).collect()
+            ).unwrap_or_default();
+            let signature = format!(
+                "struct {}{}",
+                r#struct.name.unintern_or_default(&self.intermediate_dir),
+                self.render_generics(&r#struct.generics),
+            );
+            self.push_candidate(ItemKind::Struct, r#struct.name, &r#struct.name_span, &r#struct.attribute, signature, members);
+        }
+
+        for r#enum in ast.enums.iter() {
+            let members = r#enum.variants.as_ref().map(
+                |variants| variants.iter().map(
+                    |variant| {
+                        let name = variant.name.unintern_or_default(&self.intermediate_dir);
+                        let signature = match &variant.fields {
+                            EnumVariantFields::None => name.to_string(),
+                            EnumVariantFields::Tuple(types) => format!(
+                                "{name}({})",
+                                types.iter().map(|(r#type, _)| source.text(&r#type.error_span_wide())).collect::<Vec<_>>().join(", "),
+                            ),
+                            EnumVariantFields::Struct(fields) => format!(
+                                "{name} {{ {} }}",
+                                fields.iter().map(|field| self.field_doc(field, &source).signature).collect::<Vec<_>>().join(", "),
+                            ),
+                        };
+
+                        MemberDoc {
+                            name,
+                            signature,
+                            doc: self.render_doc_comment(&variant.attribute),
+                        }
+                    }
+                ).collect()
+            ).unwrap_or_default();
+            let signature = format!(
+                "enum {}{}",
+                r#enum.name.unintern_or_default(&self.intermediate_dir),
+                self.render_generics(&r#enum.generics),
+            );
+            self.push_candidate(ItemKind::Enum, r#enum.name, &r#enum.name_span, &r#enum.attribute, signature, members);
+        }
+
+        for alias in ast.aliases.iter() {
+            let signature = format!(
+                "type {}{} = {}",
+                alias.name.unintern_or_default(&self.intermediate_dir),
+                self.render_generics(&alias.generics),
+                source.text(&alias.r#type.error_span_wide()),
+            );
+            self.push_candidate(ItemKind::Alias, alias.name, &alias.name_span, &alias.attribute, signature, vec![]);
+        }
+
+        for func in ast.funcs.iter() {
+            let signature = self.func_signature(func, &source);
+            self.push_candidate(ItemKind::Func, func.name, &func.name_span, &func.attribute, signature, vec![]);
+        }
+
+        for r#let in ast.lets.iter() {
+            let signature = format!(
+                "let {}: {}",
+                r#let.name.unintern_or_default(&self.intermediate_dir),
+                self.render_type(r#let.type_annot.as_ref(), &r#let.name_span, &source),
+            );
+            self.push_candidate(ItemKind::Let, r#let.name, &r#let.name_span, &r#let.attribute, signature, vec![]);
+        }
+
+        Ok(())
+    }
+
+    fn push_candidate(
+        &mut self,
+        kind: ItemKind,
+        name: InternedString,
+        name_span: &Span,
+        attribute: &Attribute,
+        signature: String,
+        members: Vec<MemberDoc>,
+    ) {
+        let name = name.unintern_or_default(&self.intermediate_dir);
+        let associated_type = self.associated_types.get(name_span).cloned();
+        let anchor = anchor(kind, &name, associated_type.as_ref().map(|span| self.span_name(span)).as_deref());
+
+        self.candidates.push(Candidate {
+            module: self.modules.len() - 1,
+            item: ItemDoc {
+                kind,
+                name,
+                anchor,
+                signature,
+                doc: self.render_doc_comment(attribute),
+                members,
+                associated_with: None,
+                is_poly: self.session.polys.contains_key(name_span),
+                impls: vec![],
+                implements: None,
+            },
+            name_span: name_span.clone(),
+            public: attribute.visibility.is_some() || self.is_std || self.document_private_items,
+            associated_type,
+        });
+    }
+
+    fn finish(mut self) -> Vec<ModuleDoc> {
+        let mut refs: HashMap<Span, ItemRef> = HashMap::new();
+
+        // An associated item is documented if it's public or its type is documented.
+        for associated in [false, true] {
+            for candidate in self.candidates.iter() {
+                if candidate.associated_type.is_some() != associated {
+                    continue;
+                }
+
+                let documented = candidate.public || candidate.associated_type.as_ref().map(
+                    |r#type| refs.contains_key(r#type)
+                ).unwrap_or(false);
+
+                if documented {
+                    refs.insert(candidate.name_span.clone(), ItemRef {
+                        name: candidate.item.name.to_string(),
+                        module: Some(self.modules[candidate.module].path.to_string()),
+                        anchor: Some(candidate.item.anchor.to_string()),
+                    });
+                }
+            }
+        }
+
+        // impl -> poly
+        let mut implements = HashMap::new();
+
+        for (poly_span, poly) in self.session.polys.iter() {
+            for impl_span in poly.impls.iter() {
+                implements.insert(impl_span.clone(), poly_span.clone());
+            }
+        }
+
+        for mut candidate in std::mem::take(&mut self.candidates).into_iter() {
+            if !refs.contains_key(&candidate.name_span) {
+                continue;
+            }
+
+            candidate.item.associated_with = candidate.associated_type.as_ref().map(|span| self.item_ref(span, &refs));
+
+            if let Some(poly) = self.session.polys.get(&candidate.name_span) {
+                candidate.item.impls = poly.impls.iter().map(|span| self.item_ref(span, &refs)).collect();
+            }
+
+            // Associated functions implement compiler-generated polys, which are not worth documenting.
+            if let Some(poly_span) = implements.get(&candidate.name_span) &&
+                matches!(poly_span, Span::Range(_))
+            {
+                candidate.item.implements = Some(self.item_ref(poly_span, &refs));
+            }
+
+            self.modules[candidate.module].items.push(candidate.item);
+        }
+
+        self.modules
+    }
+
+    fn item_ref(&self, span: &Span, refs: &HashMap<Span, ItemRef>) -> ItemRef {
+        match refs.get(span) {
+            Some(item_ref) => item_ref.clone(),
+            None => ItemRef::unlinked(&self.span_name(span)),
+        }
+    }
+
+    // It finds the name of an item in the inter-mir session.
+    fn span_name(&self, span: &Span) -> String {
+        let name = if let Some(shape) = self.session.struct_shapes.get(span) {
+            Some(shape.name)
+        } else if let Some(shape) = self.session.enum_shapes.get(span) {
+            Some(shape.name)
+        } else {
+            span.id().and_then(|id| self.session.span_string_map.get(&id).copied())
+        };
+
+        match name {
+            Some(name) => name.unintern_or_default(&self.intermediate_dir),
+            None => String::from("_"),
+        }
+    }
+
+    fn func_signature(&self, func: &ast::Func, source: &Source) -> String {
+        let func_type = match self.session.types.get(&func.name_span) {
+            Some(mir::Type::Func { params, r#return, .. }) => Some((params, r#return)),
+            _ => None,
+        };
+        let params = func.params.iter().enumerate().map(
+            |(i, param)| {
+                let name = param.name.unintern_or_default(&self.intermediate_dir);
+                let r#type = match (&param.type_annot, func_type.and_then(|(params, _)| params.get(i))) {
+                    (Some(r#type), _) => Some(source.text(&r#type.error_span_wide())),
+
+                    // `self` is obvious.
+                    (None, _) if name == "self" => None,
+                    (None, Some(r#type)) => Some(self.session.render_type(r#type)),
+                    (None, None) => None,
+                };
+                let default_value = param.default_value.as_ref().map(
+                    |value| format!(" = {}", source.text(&value.error_span_wide()))
+                ).unwrap_or_default();
+
+                match r#type {
+                    Some(r#type) => format!("{name}: {type}{default_value}"),
+                    None => format!("{name}{default_value}"),
+                }
+            }
+        ).collect::<Vec<_>>();
+        let r#return = match (&func.type_annot, func_type) {
+            (Some(r#type), _) => format!(" -> {}", source.text(&r#type.error_span_wide())),
+            (None, Some((_, r#return))) => format!(" -> {}", self.session.render_type(r#return)),
+            (None, None) => String::new(),
+        };
+
+        format!(
+            "{}{} {}{}({}){return}",
+            if func.ndet_span.is_some() { "ndet " } else { "" },
+            source.text(&func.keyword_span),
+            func.name.unintern_or_default(&self.intermediate_dir),
+            self.render_generics(&func.generics),
+            params.join(", "),
+        )
+    }
+
+    fn field_doc(&self, field: &ast::StructField, source: &Source) -> MemberDoc {
+        let name = field.name.unintern_or_default(&self.intermediate_dir);
+
+        MemberDoc {
+            signature: format!("{name}: {}", self.render_type(field.type_annot.as_ref(), &field.name_span, source)),
+            name,
+            doc: self.render_doc_comment(&field.attribute),
+        }
+    }
+
+    // If there's no type annotation, it uses the inferred type.
+    fn render_type(&self, type_annot: Option<&ast::Type>, name_span: &Span, source: &Source) -> String {
+        match (type_annot, self.session.types.get(name_span)) {
+            (Some(r#type), _) => source.text(&r#type.error_span_wide()),
+            (None, Some(r#type)) => self.session.render_type(r#type),
+            (None, None) => String::from("_"),
+        }
+    }
+
+    fn render_generics(&self, generics: &[Generic]) -> String {
+        if generics.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "<{}>",
+                generics.iter().map(
+                    |generic| generic.name.unintern_or_default(&self.intermediate_dir)
+                ).collect::<Vec<_>>().join(", "),
+            )
+        }
+    }
+
+    fn render_doc_comment(&self, attribute: &Attribute) -> Option<String> {
+        attribute.doc_comment.as_ref().map(
+            |doc_comment| doc_comment.0.iter().map(
+                |line| line.content.unintern_or_default(&self.intermediate_dir)
+            ).collect::<Vec<_>>().join("\n")
+        )
+    }
+}
+
+struct Source<'b> {
+    bytes: &'b [u8],
+}
+
+impl Source<'_> {
+    // It collapses whitespaces, so that a multi-line type annotation fits in a line.
+    fn text(&self, span: &Span) -> String {
+        let Some((offset, length)) = span.get_offset_and_length() else { return String::from("_") };
+        let Some(bytes) = self.bytes.get(offset as usize..(offset + length) as usize) else { return String::from("_") };
+        String::from_utf8_lossy(bytes).split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
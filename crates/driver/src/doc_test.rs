@@ -0,0 +1,309 @@
+// `sodigy test --doc` runs the code blocks in the doc comments.
+//
+// Each code block is compiled as its own snippet project. If the block is in a module of
+// the project, the snippet project is a copy of the project where the code block is appended
+// to the module, so the block can use everything in the module. std modules can't be copied,
+// so a block in std is the snippet's `lib.sdg`, and the items of the module are imported with `use`.
+
+use crate::{
+    ColorWhen,
+    Error,
+    MessageFormat,
+    ModuleCompileState,
+    StoreIrAt,
+    check_project,
+    dump_error_option,
+    load_executable,
+};
+use crate::doc::doc_module_path;
+use sodigy_bytecode::{Bytecode, Label};
+use sodigy_doc::code_blocks;
+use sodigy_error::{DocTestOutcome, Error as SodigyError, ErrorKind};
+use sodigy_file::{File, FileOrStd, ModulePath};
+use sodigy_fs_api::{
+    WriteMode,
+    copy_dir,
+    create_dir_all,
+    exists,
+    join,
+    read_string,
+    remove_dir_all,
+    write_string,
+};
+use sodigy_parse::{Attribute, DocComment};
+use sodigy_span::Span;
+use std::collections::HashMap;
+
+/// It runs the code blocks in the doc comments of the project in `src_dir`.
+///
+/// ```` ```sodigy ```` has to compile and pass all the assertions, ```` ```sodigy, compile_error ````
+/// has to fail to compile, and ```` ```sodigy, run_error ```` has to compile and fail an assertion.
+/// The code blocks in the other languages are not tested.
+pub fn run_doc_tests(
+    src_dir: String,
+    ir_dir: String,
+    include_std: bool,
+    jobs: usize,
+    color: ColorWhen,
+    message_format: MessageFormat,
+) -> Result<(), Error> {
+    let mut modules: HashMap<ModulePath, ModuleCompileState> = HashMap::new();
+    let (errors, warnings) = check_project(src_dir.clone(), ir_dir.clone(), jobs, &mut modules);
+
+    // A snippet project is a copy of the project, so it doesn't make sense
+    // to run the doc tests if the project doesn't compile.
+    if !errors.is_empty() {
+        dump_errors(errors, warnings, &ir_dir, color, message_format);
+        return Err(Error::CompileError);
+    }
+
+    let mut modules = modules.into_values().filter(
+        |module| include_std || matches!(module.file_path, FileOrStd::File(_))
+    ).collect::<Vec<_>>();
+    modules.sort_by_key(|module| doc_module_path(&module.module_path));
+
+    let doc_tests_dir = join(&ir_dir, "doc-tests")?;
+
+    if exists(&doc_tests_dir) {
+        remove_dir_all(&doc_tests_dir)?;
+    }
+
+    let mut failures = vec![];
+    let mut count = 0;
+
+    for module in modules.iter() {
+        let (file, is_std) = match &module.file_path {
+            FileOrStd::File(_) => match File::from_module_path(&module.module_path.to_string(), &ir_dir)? {
+                Some(file) => (file, false),
+                None => {
+                    continue;
+                },
+            },
+            FileOrStd::Std(n) => (File::std(*n), true),
+        };
+        let Some(bytes) = file.read_bytes(&ir_dir)? else { continue };
+
+        // The project has been compiled, so there's no syntax error.
+        let lex_session = sodigy_lex::lex(file, bytes.clone(), ir_dir.to_string(), is_std, false);
+        let file_span = lex_session.file_span();
+        let parse_session = sodigy_parse::parse(lex_session, file_span);
+        let ast = &parse_session.ast;
+        let doc_comments = collect_doc_comments(ast);
+
+        // A code block in std can only see the items of its module that are imported.
+        let std_use = if is_std {
+            let names = ast.lets.iter().map(|r#let| r#let.name).chain(
+                ast.funcs.iter().map(|func| func.name)
+            ).chain(
+                ast.structs.iter().map(|r#struct| r#struct.name)
+            ).chain(
+                ast.enums.iter().map(|r#enum| r#enum.name)
+            ).chain(
+                ast.aliases.iter().map(|alias| alias.name)
+            ).map(
+                |name| name.unintern_or_default(&ir_dir)
+            ).collect::<Vec<_>>();
+
+            if names.is_empty() {
+                String::new()
+            } else {
+                format!("use {}.{{{}}};\n", doc_module_path(&module.module_path), names.join(", "))
+            }
+        } else {
+            String::new()
+        };
+
+        for doc_comment in doc_comments.iter() {
+            let lines = doc_comment.0.iter().map(
+                |line| line.content.unintern_or_default(&ir_dir)
+            ).collect::<Vec<_>>();
+            let lines = lines.iter().map(|line| line.as_str()).collect::<Vec<_>>();
+
+            for block in code_blocks(&lines) {
+                let Some(expected) = expected_outcome(&block.attributes) else { continue };
+                let span = doc_comment.0[block.first_line].marker_span.merge(&doc_comment.0[block.last_line].content_span);
+                let name = format!(
+                    "{}:{}",
+                    doc_module_path(&module.module_path),
+                    line_no(&bytes, &span),
+                );
+
+                let snippet_dir = join(&doc_tests_dir, &count.to_string())?;
+                let snippet_src_dir = join(&snippet_dir, "src")?;
+                let snippet_ir_dir = join(&snippet_dir, "target")?;
+                count += 1;
+                create_dir_all(&snippet_dir)?;
+
+                // The snippet project has the assertions of the project, but only the ones
+                // in the code block (at and after this offset of the module) are tested.
+                let snippet_offset;
+
+                if is_std {
+                    snippet_offset = 0;
+                    create_dir_all(&snippet_src_dir)?;
+                    write_string(
+                        &join(&snippet_src_dir, "lib.sdg")?,
+                        &format!("{std_use}{}\n", block.code),
+                        WriteMode::CreateOrTruncate,
+                    )?;
+                }
+
+                else {
+                    copy_dir(&src_dir, &snippet_src_dir)?;
+
+                    let FileOrStd::File(module_file) = module.module_path.get_file_path(&snippet_src_dir).map_err(
+                        |_| Error::MiscError
+                    )? else { unreachable!() };
+                    let source = read_string(&module_file)?;
+                    snippet_offset = source.len() as u32 + 1;
+                    write_string(
+                        &module_file,
+                        &format!("{source}\n{}\n", block.code),
+                        WriteMode::CreateOrTruncate,
+                    )?;
+                }
+
+                let snippet_module = if is_std { ModulePath::lib() } else { module.module_path.clone() };
+                let (got, snippet_errors) = run_snippet(&snippet_src_dir, &snippet_ir_dir, &snippet_module, snippet_offset, jobs)?;
+                println!("doc test `{name}`: {}", if got == expected { "success" } else { "fail" });
+
+                if got != expected {
+                    // If it's not supposed to fail to compile, the compile errors are helpful.
+                    if !snippet_errors.is_empty() {
+                        dump_errors(snippet_errors, vec![], &snippet_ir_dir, color, message_format);
+                    }
+
+                    failures.push(SodigyError {
+                        kind: ErrorKind::DocTestFailed { expected, got },
+                        spans: span.simple_error(),
+                        note: Some(format!("The snippet project is at `{snippet_src_dir}`.")),
+                        suggestions: vec![],
+                    });
+                }
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    }
+
+    else {
+        dump_errors(failures, vec![], &ir_dir, color, message_format);
+        Err(Error::RuntimeError)
+    }
+}
+
+// The doc comments of the module and its items.
+fn collect_doc_comments(ast: &sodigy_parse::Block) -> Vec<&DocComment> {
+    let mut attributes: Vec<&Attribute> = vec![];
+    attributes.extend(ast.attribute.iter());
+    attributes.extend(ast.lets.iter().map(|r#let| &r#let.attribute));
+    attributes.extend(ast.funcs.iter().map(|func| &func.attribute));
+    attributes.extend(ast.aliases.iter().map(|alias| &alias.attribute));
+    attributes.extend(ast.modules.iter().map(|module| &module.attribute));
+
+    for r#struct in ast.structs.iter() {
+        attributes.push(&r#struct.attribute);
+        attributes.extend(r#struct.fields.iter().flatten().map(|field| &field.attribute));
+    }
+
+    for r#enum in ast.enums.iter() {
+        attributes.push(&r#enum.attribute);
+        attributes.extend(r#enum.variants.iter().flatten().map(|variant| &variant.attribute));
+    }
+
+    let mut doc_comments = attributes.into_iter().filter_map(
+        |attribute| attribute.doc_comment.as_ref()
+    ).collect::<Vec<_>>();
+
+    // so that the doc tests run in the order they're written
+    doc_comments.sort_by_key(|doc_comment| doc_comment.0[0].marker_span.get_offset_and_length().map(|(offset, _)| offset));
+    doc_comments
+}
+
+// ```` ```sodigy, compile_error ```` -> `Some(DocTestOutcome::CompileError)`
+fn expected_outcome(attributes: &[String]) -> Option<DocTestOutcome> {
+    if attributes.first().map(|language| language.as_str()) != Some("sodigy") {
+        return None;
+    }
+
+    if attributes.iter().any(|attribute| attribute == "compile_error") {
+        Some(DocTestOutcome::CompileError)
+    }
+
+    else if attributes.iter().any(|attribute| attribute == "run_error") {
+        Some(DocTestOutcome::RuntimeError)
+    }
+
+    else {
+        Some(DocTestOutcome::Success)
+    }
+}
+
+fn run_snippet(
+    src_dir: &str,
+    ir_dir: &str,
+    snippet_module: &ModulePath,
+    snippet_offset: u32,
+    jobs: usize,
+) -> Result<(DocTestOutcome, Vec<SodigyError>), Error> {
+    let (errors, _) = check_project(src_dir.to_string(), ir_dir.to_string(), jobs, &mut HashMap::new());
+
+    if !errors.is_empty() {
+        return Ok((DocTestOutcome::CompileError, errors));
+    }
+
+    let exe = load_executable(StoreIrAt::IntermediateDir, ir_dir)?;
+    let snippet_file = File::from_module_path(&snippet_module.to_string(), ir_dir)?;
+
+    for (_, label) in exe.asserts.iter() {
+        // An assertion's entry point is right after the label of its keyword span.
+        let Some(Bytecode::Label(Label::Global(keyword_span))) = exe.bytecodes.get(label - 1) else { unreachable!() };
+        let in_snippet = keyword_span.file() == snippet_file && match keyword_span.get_offset_and_length() {
+            Some((offset, _)) => offset >= snippet_offset,
+            None => false,
+        };
+
+        if !in_snippet {
+            continue;
+        }
+
+        if sodigy_interpreter::interpret(&exe, *label, ir_dir).is_err() {
+            return Ok((DocTestOutcome::RuntimeError, vec![]));
+        }
+    }
+
+    Ok((DocTestOutcome::Success, vec![]))
+}
+
+fn dump_errors(
+    errors: Vec<SodigyError>,
+    warnings: Vec<sodigy_error::Warning>,
+    ir_dir: &str,
+    color: ColorWhen,
+    message_format: MessageFormat,
+) {
+    let e = match message_format {
+        MessageFormat::Human => sodigy_error::dump_errors(
+            errors,
+            warnings,
+            ir_dir,
+            dump_error_option(color),
+            None,
+            true,  // show summary
+        ),
+        MessageFormat::Json => sodigy_error::dump_errors_json(errors, warnings, ir_dir),
+    };
+
+    if !e.is_empty() {
+        eprintln!("{e}");
+    }
+}
+
+fn line_no(bytes: &[u8], span: &Span) -> usize {
+    match span.get_offset_and_length() {
+        Some((offset, _)) => bytes[..(offset as usize).min(bytes.len())].iter().filter(|b| **b == b'\n').count() + 1,
+        None => 0,
+    }
+}
@@ -18,6 +18,12 @@ pub enum Error {
     MpscError,
     IrCacheNotFound(CompileStage),
 
+    /// `sodigy fmt --check` found files that are not formatted.
+    NotFormatted,
+
+    /// `sodigy explain` got an error kind that doesn't exist.
+    UnknownErrorKind(String),
+
     /// Errors other than the above errors.
     MiscError,
 }
@@ -0,0 +1,70 @@
+use crate::Error;
+use sodigy_doc::{ErrorDoc, render_error_index};
+use sodigy_error::{ERROR_KIND_DOCS, ErrorKindDoc, ErrorLevel};
+use sodigy_fs_api::{WriteMode, create_dir_all, exists, join, write_string};
+
+/// `sodigy explain 455` or `sodigy explain NonExhaustiveArms` prints the extended explanation
+/// of the error kind. If `error` is `None`, it lists all the error kinds.
+///
+/// If `index` is set, it writes a browsable error index at `{output_dir}/errors.html`.
+pub fn explain(error: Option<&str>, index: bool, output_dir: &str) -> Result<(), Error> {
+    if index {
+        let errors = ERROR_KIND_DOCS.iter().map(
+            |doc| ErrorDoc {
+                code: doc.code(),
+                name: doc.name.to_string(),
+                level: render_level(doc.level).to_string(),
+                explanation: doc.explanation.map(|explanation| explanation.to_string()),
+            }
+        ).collect::<Vec<_>>();
+
+        if !exists(output_dir) {
+            create_dir_all(output_dir)?;
+        }
+
+        let path = join(output_dir, "errors.html")?;
+        write_string(&path, &render_error_index(&errors), WriteMode::CreateOrTruncate)?;
+        println!("The error index is at `{path}`.");
+    }
+
+    match error {
+        Some(error) => match ErrorKindDoc::find(error) {
+            Some(doc) => {
+                println!("{}", render_explanation(doc));
+                Ok(())
+            },
+            None => Err(Error::UnknownErrorKind(error.to_string())),
+        },
+        None if !index => {
+            for doc in ERROR_KIND_DOCS.iter() {
+                println!(
+                    "{} {}{}",
+                    doc.code(),
+                    doc.name,
+                    if doc.explanation.is_some() { "" } else { " (no explanation yet)" },
+                );
+            }
+
+            Ok(())
+        },
+        None => Ok(()),
+    }
+}
+
+pub fn render_explanation(doc: &ErrorKindDoc) -> String {
+    format!(
+        "{} ({}): {}\n\n{}",
+        render_level(doc.level),
+        doc.code(),
+        doc.name,
+        doc.explanation.unwrap_or("There's no extended explanation for this error yet."),
+    )
+}
+
+fn render_level(level: ErrorLevel) -> &'static str {
+    match level {
+        ErrorLevel::Error => "error",
+        ErrorLevel::Warning => "warning",
+        ErrorLevel::Lint => "lint",
+    }
+}
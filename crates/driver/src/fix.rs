@@ -0,0 +1,167 @@
+// `sodigy fix` applies the machine-applicable suggestions of the errors and the warnings.
+//
+// The compiler stops at the first stage that has an error, so fixing the errors might reveal
+// more errors (and more suggestions). So it compiles the project again after applying the
+// suggestions, until there's nothing left to fix.
+
+use crate::{
+    ColorWhen,
+    Error,
+    ModuleCompileState,
+    check_project,
+    dump_error_option,
+};
+use sodigy_error::{Applicability, Suggestion};
+use sodigy_file::{File, ModulePath};
+use sodigy_fs_api::{WriteMode, read_bytes, write_string};
+use sodigy_span::Span;
+use std::collections::HashMap;
+
+// Applying a suggestion might generate another suggestion, and so on.
+// It gives up after this many rounds.
+const MAX_ROUNDS: usize = 8;
+
+/// It applies all the machine-applicable suggestions to the source files in `src_dir`.
+///
+/// If there are errors that `sodigy fix` can't fix, it dumps them and returns `Error::CompileError`.
+pub fn fix_project(
+    src_dir: String,
+    ir_dir: String,
+    jobs: usize,
+    color: ColorWhen,
+) -> Result<(), Error> {
+    let mut total_fixes = 0;
+    let mut fixed_files = vec![];
+    let mut remaining = (vec![], vec![]);
+
+    for _ in 0..MAX_ROUNDS {
+        let mut modules: HashMap<ModulePath, ModuleCompileState> = HashMap::new();
+        let (errors, warnings) = check_project(src_dir.clone(), ir_dir.clone(), jobs, &mut modules);
+        let mut suggestions_by_file: HashMap<File, Vec<Suggestion>> = HashMap::new();
+
+        for suggestion in errors.iter().chain(warnings.iter()).flat_map(|error| error.suggestions.iter()) {
+            // The compiler-generated spans (e.g. derived or monomorphized ones) don't point to the source code.
+            if suggestion.applicability != Applicability::MachineApplicable || !matches!(suggestion.span, Span::Range(_)) {
+                continue;
+            }
+
+            if let Some(file) = suggestion.span.file() && !file.is_std() {
+                suggestions_by_file.entry(file).or_default().push(suggestion.clone());
+            }
+        }
+
+        remaining = (errors, warnings);
+        let mut fixes = 0;
+        let mut files = suggestions_by_file.into_iter().collect::<Vec<_>>();
+        files.sort_by_key(|(file, _)| *file);
+
+        for (file, suggestions) in files.into_iter() {
+            let Some((_, path)) = file.get_path(&ir_dir)? else { continue };
+            let Some(compiled) = file.read_bytes(&ir_dir)? else { continue };
+            let current = read_bytes(&path)?;
+
+            // The file has changed since the compilation, so the spans are not valid anymore.
+            if current != compiled {
+                continue;
+            }
+
+            let Ok(source) = String::from_utf8(current) else { continue };
+            let (fixed, count) = apply_suggestions(&source, &suggestions);
+
+            if count > 0 {
+                write_string(&path, &fixed, WriteMode::Atomic)?;
+                fixes += count;
+
+                if !fixed_files.contains(&path) {
+                    fixed_files.push(path);
+                }
+            }
+        }
+
+        if fixes == 0 {
+            break;
+        }
+
+        total_fixes += fixes;
+    }
+
+    for path in fixed_files.iter() {
+        println!("fixed: {path}");
+    }
+
+    println!(
+        "Applied {total_fixes} suggestion{} to {} file{}.",
+        if total_fixes == 1 { "" } else { "s" },
+        fixed_files.len(),
+        if fixed_files.len() == 1 { "" } else { "s" },
+    );
+
+    let (errors, warnings) = remaining;
+    let has_error = !errors.is_empty();
+
+    if has_error || !warnings.is_empty() {
+        eprintln!("{}", sodigy_error::dump_errors(
+            errors,
+            warnings,
+            &ir_dir,
+            dump_error_option(color),
+            None,
+            true,  // show summary
+        ));
+    }
+
+    if has_error {
+        Err(Error::CompileError)
+    }
+
+    else {
+        Ok(())
+    }
+}
+
+// It applies the suggestions to `source` and returns the result and the number of the applied suggestions.
+// If suggestions overlap, only the last one (in the source code) is applied. If a removal leaves
+// an empty line, the line is removed too.
+pub(crate) fn apply_suggestions(source: &str, suggestions: &[Suggestion]) -> (String, usize) {
+    let mut edits = suggestions.iter().filter_map(
+        |suggestion| suggestion.span.get_offset_and_length().map(
+            |(offset, length)| (offset as usize, (offset + length) as usize, suggestion.replacement.as_str())
+        )
+    ).filter(
+        |(start, end, _)| *end <= source.len() && source.is_char_boundary(*start) && source.is_char_boundary(*end)
+    ).collect::<Vec<_>>();
+
+    // There can be duplicate errors (see the comments in `compile`), hence duplicate suggestions.
+    edits.sort();
+    edits.dedup();
+
+    let mut result = source.to_string();
+    let mut count = 0;
+
+    // The edits are applied from the end of the file, so that the offsets of the other edits don't change.
+    let mut prev_start = usize::MAX;
+
+    for (start, end, replacement) in edits.into_iter().rev() {
+        if end > prev_start {
+            continue;
+        }
+
+        let (mut start, mut end) = (start, end);
+
+        if replacement.is_empty() {
+            let line_start = result[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let line_end = result[end..].find('\n').map(|i| end + i).unwrap_or(result.len());
+
+            if result[line_start..start].trim().is_empty() && result[end..line_end].trim().is_empty() {
+                start = line_start;
+                end = (line_end + 1).min(result.len());
+            }
+        }
+
+        result.replace_range(start..end, replacement);
+        prev_start = start;
+        count += 1;
+    }
+
+    (result, count)
+}
@@ -0,0 +1,96 @@
+use crate::{Error, init_ir_dir};
+use sodigy_error::DumpErrorOption;
+use sodigy_file::File;
+use sodigy_fmt::{Config, format};
+use sodigy_fs_api::{
+    WriteMode,
+    extension,
+    is_dir,
+    read_bytes,
+    read_dir,
+    write_string,
+};
+
+/// It formats the files in `paths`. If `paths` is empty, it formats all the `.sdg` files in `src_dir`.
+///
+/// If `check` is set, it doesn't modify the files. It just prints the files that are not
+/// formatted and returns `Error::NotFormatted` if there's any.
+pub fn format_files(
+    paths: &[String],
+    check: bool,
+    src_dir: &str,
+    intermediate_dir: &str,
+) -> Result<(), Error> {
+    let paths = if paths.is_empty() {
+        let mut paths = vec![];
+        collect_source_files(src_dir, &mut paths)?;
+        paths
+    } else {
+        paths.to_vec()
+    };
+
+    // The lexer needs the intermediate dir for the string interner.
+    init_ir_dir(intermediate_dir, true)?;
+    let config = Config::default();
+    let mut errors = vec![];
+    let mut not_formatted = vec![];
+
+    for path in paths.iter() {
+        let input = read_bytes(path)?;
+
+        // The file map is used to render the spans of the syntax errors.
+        let file = File::register(path, path, intermediate_dir)?;
+
+        match format(file, &input, intermediate_dir, &config) {
+            Ok(output) if output.as_bytes() == input => {},
+            Ok(output) => {
+                if check {
+                    not_formatted.push(path.to_string());
+                }
+
+                else {
+                    write_string(path, &output, WriteMode::Atomic)?;
+                }
+            },
+            Err(e) => {
+                errors.extend(e);
+            },
+        }
+    }
+
+    if !errors.is_empty() {
+        eprintln!("{}", sodigy_error::dump_errors(
+            errors,
+            vec![],
+            intermediate_dir,
+            DumpErrorOption::default(),
+            None,
+            true,  // show summary
+        ));
+        return Err(Error::CompileError);
+    }
+
+    if !not_formatted.is_empty() {
+        for path in not_formatted.iter() {
+            println!("not formatted: {path}");
+        }
+
+        return Err(Error::NotFormatted);
+    }
+
+    Ok(())
+}
+
+fn collect_source_files(dir: &str, paths: &mut Vec<String>) -> Result<(), Error> {
+    for path in read_dir(dir, true)? {
+        if is_dir(&path) {
+            collect_source_files(&path, paths)?;
+        }
+
+        else if extension(&path)?.as_deref() == Some("sdg") {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
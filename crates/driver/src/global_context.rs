@@ -1,6 +1,6 @@
 use sodigy_inter_hir as inter_hir;
 use sodigy_inter_mir as inter_mir;
-use sodigy_mir::{GlobalContext as MirGlobalContext, Type};
+use sodigy_mir::{Func, GlobalContext as MirGlobalContext, Type};
 use sodigy_span::Span;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -16,10 +16,15 @@ use std::sync::{Arc, RwLock};
 // 5. When loading the inter_mir_session, the workers also load the `types`.
 // 6. The `types` are shared. Any worker can update the `types` and the updates
 //    are propagated immediately.
+// 7. If optimization is enabled, the workers also get `funcs`, which is created by
+//    the main thread and shared by every worker. Each worker writes functions
+//    of its modules after post-mir, and the optimizer reads the functions after
+//    every module's post-mir is complete.
 pub struct GlobalContext {
     pub inter_hir_session: Option<inter_hir::Session>,
     pub inter_mir_session: Option<inter_mir::Session>,
     pub types: Option<Arc<RwLock<HashMap<Span, Type>>>>,
+    pub funcs: Option<Arc<RwLock<HashMap<Span, Func>>>>,
 }
 
 impl GlobalContext {
@@ -28,6 +33,7 @@ impl GlobalContext {
             inter_hir_session: None,
             inter_mir_session: None,
             types: None,
+            funcs: None,
         }
     }
 
@@ -42,6 +48,7 @@ impl GlobalContext {
             lang_items: Some(&self.inter_mir_session.as_ref().unwrap().lang_items),
             built_in_funcs: Some(&self.inter_mir_session.as_ref().unwrap().built_in_funcs),
             types: self.types.clone(),
+            funcs: self.funcs.clone(),
             generic_args: Some(&self.inter_mir_session.as_ref().unwrap().generic_args),
             span_string_map: Some(&self.inter_mir_session.as_ref().unwrap().span_string_map),
         }
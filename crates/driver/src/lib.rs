@@ -7,7 +7,7 @@ use sodigy_error::{
     ErrorLevel,
     Warning as SodigyWarning,
 };
-use sodigy_file::{FileOrStd, ModulePath};
+use sodigy_file::{File, FileOrStd, ModulePath};
 use sodigy_fs_api::{
     FileError,
     FileErrorKind,
@@ -23,35 +23,50 @@ use sodigy_fs_api::{
 pub use sodigy_optimize::OptimizeLevel;
 use sodigy_span::{Color, Span};
 use std::collections::HashMap;
-use std::sync::mpsc;
+use std::sync::{Arc, RwLock, mpsc};
 use std::time::Instant;
 
 mod cli;
 mod command;
 mod compile_stage;
+mod doc;
+mod doc_test;
 mod error;
+mod explain;
+mod fix;
+mod fmt;
 mod global_context;
 mod ir_store;
 mod log;
+mod lsp;
+mod suggest;
 mod worker;
 
 #[cfg(test)]
 mod tests;
 
-pub use cli::{CliCommand, ColorWhen};
+pub use cli::{CliCommand, ColorWhen, DocFormat, MessageFormat};
 pub use command::{Command, ValidateTokenSpans};
 pub use compile_stage::CompileStage;
 pub use error::Error;
 pub use ir_store::{EmitIrOption, StoreIrAt};
 
 use cli::parse_args;
+pub use doc::generate_docs;
+pub use doc_test::run_doc_tests;
+pub use explain::explain;
+pub use fix::fix_project;
+pub use fmt::format_files;
 use compile_stage::COMPILE_STAGES;
 use global_context::GlobalContext;
 use ir_store::{emit_irs_if_has_to, get_cached_ir};
+pub use lsp::run_language_server;
+use suggest::suggest_imports;
 use log::{
     TimingsEntry,
     dump_inter_hir_log,
     dump_inter_mir_log,
+    dump_mir_optimize_log,
     dump_post_mir_log,
     dump_timings,
     store_inter_hir_log,
@@ -107,6 +122,12 @@ pub fn main_() {
                     Error::IrCacheNotFound(s) => {
                         eprintln!("IrCacheNotFound({s:?})");
                     },
+                    Error::NotFormatted => {
+                        // The paths are already dumped!
+                    },
+                    Error::UnknownErrorKind(e) => {
+                        eprintln!("There's no error kind `{e}`. Run `sodigy explain` to see the list of the error kinds.");
+                    },
                     Error::MiscError => {
                         eprintln!("Unknown Error");
                     },
@@ -141,10 +162,18 @@ pub fn run_cli_command(command: CliCommand) -> Result<(), Error> {
             init_project(project_name)?;
             Ok(())
         },
+        CliCommand::Test { doc: true, include_std, jobs, color, message_format, .. } => run_doc_tests(
+            src_dir,
+            ir_dir,
+            *include_std,
+            *jobs,
+            *color,
+            *message_format,
+        ),
         cli_command @ (
-            CliCommand::Build { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, dump_post_mir_log, dump_timings, .. } |
-            CliCommand::Run { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, dump_post_mir_log, dump_timings } |
-            CliCommand::Test { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, dump_post_mir_log, dump_timings }
+            CliCommand::Build { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, message_format, dump_post_mir_log, dump_timings, .. } |
+            CliCommand::Run { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, message_format, dump_post_mir_log, dump_timings } |
+            CliCommand::Test { optimize_level, custom_error_levels, emit_irs, graceful_shutdown, validate_token_spans, jobs, color, message_format, dump_post_mir_log, dump_timings, .. }
         ) => {
             // maybe we need a finer control??
             let dump_bytecodes = *emit_irs;
@@ -154,21 +183,24 @@ pub fn run_cli_command(command: CliCommand) -> Result<(), Error> {
             let quiet = false;
             let verify_built_ins = false;
 
-            let (output_path, backend, interpret_with_profile) = match cli_command {
+            let (output_path, backend, interpret_with_profile, check_const_asserts) = match cli_command {
                 CliCommand::Run { .. } => (
                     StoreIrAt::IntermediateDir,
                     Backend::Bytecode,
                     Some(Profile::Script),
+                    false,
                 ),
                 CliCommand::Test { .. } => (
                     StoreIrAt::IntermediateDir,
                     Backend::Bytecode,
                     Some(Profile::Test),
+                    false,
                 ),
-                CliCommand::Build { output_path, backend, .. } => (
+                CliCommand::Build { output_path, backend, check_const_asserts, .. } => (
                     StoreIrAt::File(output_path.to_string()),
                     *backend,
                     None,
+                    *check_const_asserts,
                 ),
                 _ => todo!(),
             };
@@ -183,9 +215,11 @@ pub fn run_cli_command(command: CliCommand) -> Result<(), Error> {
                 *dump_post_mir_log,
                 *dump_timings,
                 dump_bytecodes,
+                check_const_asserts,
                 *graceful_shutdown,
                 *jobs,
                 *color,
+                *message_format,
                 incremental_compilation,
                 *validate_token_spans,
                 verify_built_ins,
@@ -198,6 +232,37 @@ pub fn run_cli_command(command: CliCommand) -> Result<(), Error> {
             *profile,
             &ir_dir,
         ),
+        CliCommand::Lsp { jobs } => lsp::run_language_server(
+            std::io::stdin().lock(),
+            std::io::stdout().lock(),
+            *jobs,
+        ),
+        CliCommand::Doc { output_dir, format, include_std, document_private_items, jobs } => generate_docs(
+            src_dir,
+            ir_dir,
+            output_dir,
+            *format,
+            *include_std,
+            *document_private_items,
+            *jobs,
+        ),
+        CliCommand::Explain { error, index, output_dir } => explain(
+            error.as_deref(),
+            *index,
+            output_dir,
+        ),
+        CliCommand::Fmt { paths, check } => format_files(
+            paths,
+            *check,
+            &src_dir,
+            &ir_dir,
+        ),
+        CliCommand::Fix { jobs, color } => fix_project(
+            src_dir,
+            ir_dir,
+            *jobs,
+            *color,
+        ),
         CliCommand::Clean => {
             if exists(&ir_dir) {
                 remove_dir_all(&ir_dir)?;
@@ -222,9 +287,11 @@ pub fn init_workers_and_compile(
     dump_post_mir_log: bool,
     dump_timings_flag: bool,
     dump_bytecodes: bool,
+    check_const_asserts: bool,
     graceful_shutdown: u32,  // in milliseconds
     jobs: usize,
     color: ColorWhen,
+    message_format: MessageFormat,
     incremental_compilation: bool,
     validate_token_spans: ValidateTokenSpans,
     verify_built_ins: bool,
@@ -235,6 +302,7 @@ pub fn init_workers_and_compile(
     let mut errors = vec![];
     let mut warnings = vec![];
     let mut timings_log = HashMap::new();
+    let mut modules = HashMap::new();
     let channels = init_workers_and_channels(jobs);
 
     let result = compile(
@@ -247,28 +315,20 @@ pub fn init_workers_and_compile(
         emit_irs,
         dump_post_mir_log,
         dump_bytecodes,
+        check_const_asserts,
         graceful_shutdown,
         incremental_compilation,
         validate_token_spans,
         verify_built_ins,
         &channels,
+        &mut modules,
         &mut errors,
         &mut warnings,
         &mut timings_log,
     );
 
     let elapsed_ms = Instant::now().duration_since(started_at).as_millis();
-    let dump_error_option = match color {
-        // TODO: `ColorWhen::Auto` is WIP
-        ColorWhen::Auto | ColorWhen::Always => DumpErrorOption::default(),
-        ColorWhen::Never => DumpErrorOption {
-            error_color: Color::None,
-            warning_color: Color::None,
-            auxiliary_color: Color::None,
-            info_color: Color::None,
-            ..DumpErrorOption::default()
-        },
-    };
+    suggest_imports(&mut errors, &modules, &ir_dir);
 
     apply_custom_error_levels(
         custom_error_levels,
@@ -277,16 +337,25 @@ pub fn init_workers_and_compile(
     );
 
     if !quiet {
-        let e = sodigy_error::dump_errors(
-            errors,
-            warnings,
-            &ir_dir,
-            dump_error_option,
-            Some(elapsed_ms as u64),
-            true,  // show summary
-        );
-
-        eprintln!("{e}");
+        let e = match message_format {
+            MessageFormat::Human => sodigy_error::dump_errors(
+                errors,
+                warnings,
+                &ir_dir,
+                dump_error_option(color),
+                Some(elapsed_ms as u64),
+                true,  // show summary
+            ),
+            MessageFormat::Json => sodigy_error::dump_errors_json(
+                errors,
+                warnings,
+                &ir_dir,
+            ),
+        };
+
+        if !e.is_empty() {
+            eprintln!("{e}");
+        }
     }
 
     let mut all_worker_ids = Vec::with_capacity(channels.len());
@@ -321,6 +390,55 @@ pub fn init_workers_and_compile(
     }
 }
 
+// It compiles the project (with the incremental cache) so that the tools (`sodigy lsp`,
+// `sodigy doc`) can read the cached irs. A compile error is not an error of the tools,
+// so the errors are returned instead of being dumped.
+fn check_project(
+    src_dir: String,
+    ir_dir: String,
+    jobs: usize,
+    modules: &mut HashMap<ModulePath, ModuleCompileState>,
+) -> (Vec<SodigyError>, Vec<SodigyWarning>) {
+    let mut errors = vec![];
+    let mut warnings = vec![];
+    let mut timings_log = HashMap::new();
+    let channels = init_workers_and_channels(jobs);
+
+    let _ = compile(
+        src_dir,
+        StoreIrAt::IntermediateDir,
+        Backend::Bytecode,
+        ir_dir.clone(),
+        OptimizeLevel::None,
+        &HashMap::new(),
+        false,  // emit irs
+        false,  // dump post-mir log
+        false,  // dump bytecodes
+        false,  // check const asserts
+        300,  // graceful shutdown
+        true,  // incremental compilation
+        ValidateTokenSpans::Never,
+        false,  // verify built-ins
+        &channels,
+        modules,
+        &mut errors,
+        &mut warnings,
+        &mut timings_log,
+    );
+
+    for channel in channels.iter() {
+        let _ = channel.send(MessageToWorker::Kill);
+    }
+
+    for channel in channels.into_iter() {
+        channel.join();
+    }
+
+    suggest_imports(&mut errors, modules, &ir_dir);
+    apply_custom_error_levels(&HashMap::new(), &mut errors, &mut warnings);
+    (errors, warnings)
+}
+
 // How it handles compile errors/warnings:
 // 1. When a worker finishes a stage, the worker sends all the errors/warnings to the master.
 //   - The worker doesn't discard the errors/warnings. Errors/warnings are never discarded.
@@ -339,18 +457,19 @@ fn compile(
     emit_irs: bool,
     dump_post_mir_log_flag: bool,
     dump_bytecodes: bool,
+    check_const_asserts: bool,
     graceful_shutdown: u32,  // in milliseconds
     incremental_compilation: bool,
     validate_token_spans: ValidateTokenSpans,
     verify_built_ins: bool,
     workers: &[Channel],
+    modules: &mut HashMap<ModulePath, ModuleCompileState>,
     errors: &mut Vec<SodigyError>,
     warnings: &mut Vec<SodigyWarning>,
     timings_log: &mut HashMap<WorkerId, Vec<TimingsEntry>>,
 ) -> Result<(), Error> {
     let mut shutdown_countdown: Option<Instant> = None;
     let mut round_robin = 0;
-    let mut modules: HashMap<ModulePath, ModuleCompileState> = HashMap::new();
     let emit_irs = if emit_irs {
         [
             CompileStage::Lex,
@@ -374,6 +493,13 @@ fn compile(
         vec![]
     };
 
+    // If optimization is enabled, the modules stop after post-mir and share their functions
+    // so that the optimizer can inline functions in other modules.
+    let shared_funcs = match optimize_level {
+        OptimizeLevel::None => None,
+        _ => Some(Arc::new(RwLock::new(HashMap::new()))),
+    };
+
     let lib_module_path = ModulePath::lib();
     let lib_file_path = match lib_module_path.get_file_path(&src_dir) {
         Ok(p) => p,
@@ -382,6 +508,7 @@ fn compile(
                 kind: e.into(),
                 spans: Span::Lib.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
             return Err(Error::CompileError);
         },
@@ -420,6 +547,7 @@ fn compile(
 
         let mut every_hir_complete = true;
         let mut every_mir_complete = true;
+        let mut every_post_mir_complete = true;
         let mut every_bytecode_complete = true;
 
         for module in modules.values_mut() {
@@ -451,6 +579,7 @@ fn compile(
                 )) {
                     every_hir_complete = false;
                     every_mir_complete = false;
+                    every_post_mir_complete = false;
                     every_bytecode_complete = false;
                     break;
                 }
@@ -468,6 +597,10 @@ fn compile(
                 every_mir_complete = false;
             }
 
+            if (module.compile_stage, module.running) != (CompileStage::PostMir, false) {
+                every_post_mir_complete = false;
+            }
+
             if (module.compile_stage, module.running) != (CompileStage::BytecodeOptimize, false) {
                 every_bytecode_complete = false;
             }
@@ -522,6 +655,33 @@ fn compile(
             }
         }
 
+        if every_post_mir_complete {
+            for module in modules.values_mut() {
+                module.running = true;
+
+                workers[round_robin % workers.len()].send(MessageToWorker::Run(
+                    Command::PerFileIr {
+                        input_file_path: module.file_path.clone(),
+                        input_module_path: module.module_path.clone(),
+                        optimize_level,
+                        intermediate_dir: ir_dir.clone(),
+                        find_modules: false,
+                        emit_ir_options: emit_irs.clone_and_push(
+                            EmitIrOption {
+                                stage: CompileStage::BytecodeOptimize,
+                                store: StoreIrAt::IntermediateDir,
+                                human_readable: false,
+                            },
+                        ),
+                        dump_post_mir_log: dump_post_mir_log_flag,
+                        stop_after: CompileStage::BytecodeOptimize,
+                        validate_token_spans,
+                    },
+                ))?;
+                round_robin += 1;
+            }
+        }
+
         if every_bytecode_complete {
             workers[round_robin % workers.len()].send(MessageToWorker::Run(
                 Command::CodeGen {
@@ -531,6 +691,7 @@ fn compile(
                     intermediate_dir: ir_dir.clone(),
                     backend,
                     dump_bytecodes,
+                    check_const_asserts,
                     output_path: output_path.clone(),
                 },
             ))?;
@@ -554,6 +715,7 @@ fn compile(
                                         kind: e.into(),
                                         spans: span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     });
 
                                     if shutdown_countdown.is_none() {
@@ -636,10 +798,20 @@ fn compile(
                             (CompileStage::InterMir, None) => {
                                 for worker in workers.iter() {
                                     worker.send(MessageToWorker::Run(
-                                        Command::LoadMirGlobalContext { intermediate_dir: ir_dir.clone() },
+                                        Command::LoadMirGlobalContext {
+                                            intermediate_dir: ir_dir.clone(),
+                                            funcs: shared_funcs.clone(),
+                                        },
                                     ))?;
                                 }
 
+                                // If optimization is enabled, it has to wait until every module's post-mir
+                                // is complete. Read the comments in `GlobalContext`.
+                                let stop_after = match optimize_level {
+                                    OptimizeLevel::None => CompileStage::BytecodeOptimize,
+                                    _ => CompileStage::PostMir,
+                                };
+
                                 for module in modules.values_mut() {
                                     module.compile_stage = CompileStage::InterMir;
                                     module.running = false;
@@ -653,13 +825,13 @@ fn compile(
                                             find_modules: false,
                                             emit_ir_options: emit_irs.clone_and_push(
                                                 EmitIrOption {
-                                                    stage: CompileStage::BytecodeOptimize,
+                                                    stage: stop_after,
                                                     store: StoreIrAt::IntermediateDir,
                                                     human_readable: false,
                                                 },
                                             ),
                                             dump_post_mir_log: dump_post_mir_log_flag,
-                                            stop_after: CompileStage::BytecodeOptimize,
+                                            stop_after,
                                             validate_token_spans,
                                         },
                                     ))?;
@@ -698,6 +870,9 @@ fn compile(
                     MessageToMain::PostMirLog(matches) => {
                         dump_post_mir_log(&matches, &ir_dir)?;
                     },
+                    MessageToMain::MirOptimizeLog(log) => {
+                        dump_mir_optimize_log(&log, &ir_dir)?;
+                    },
                     MessageToMain::Error(e) => {
                         return Err(e);
                     },
@@ -712,18 +887,7 @@ fn compile(
 }
 
 fn interpret(exe: StoreIrAt, profile: Profile, intermediate_dir: &str) -> Result<(), Error> {
-    let exe_bytes = match exe {
-        StoreIrAt::File(f) => read_bytes(&f)?,
-        StoreIrAt::IntermediateDir => get_cached_ir(
-            intermediate_dir,
-            CompileStage::CodeGen,
-            None,
-        )?.ok_or(Error::IrCacheNotFound(CompileStage::CodeGen))?,
-    };
-
-    // `emit_irs_if_has_to` will encode `Vec<u8>` twice...
-    let exe_bytes = Vec::<u8>::decode(&exe_bytes)?;
-    let exe = sodigy_bytecode::Executable::decode(&exe_bytes)?;
+    let exe = load_executable(exe, intermediate_dir)?;
 
     match profile {
         Profile::Test => {
@@ -748,6 +912,35 @@ fn interpret(exe: StoreIrAt, profile: Profile, intermediate_dir: &str) -> Result
     Ok(())
 }
 
+fn load_executable(exe: StoreIrAt, intermediate_dir: &str) -> Result<sodigy_bytecode::Executable, Error> {
+    let exe_bytes = match exe {
+        StoreIrAt::File(f) => read_bytes(&f)?,
+        StoreIrAt::IntermediateDir => get_cached_ir(
+            intermediate_dir,
+            CompileStage::CodeGen,
+            None,
+        )?.ok_or(Error::IrCacheNotFound(CompileStage::CodeGen))?,
+    };
+
+    // `emit_irs_if_has_to` will encode `Vec<u8>` twice...
+    let exe_bytes = Vec::<u8>::decode(&exe_bytes)?;
+    Ok(sodigy_bytecode::Executable::decode(&exe_bytes)?)
+}
+
+fn dump_error_option(color: ColorWhen) -> DumpErrorOption {
+    match color {
+        // TODO: `ColorWhen::Auto` is WIP
+        ColorWhen::Auto | ColorWhen::Always => DumpErrorOption::default(),
+        ColorWhen::Never => DumpErrorOption {
+            error_color: Color::None,
+            warning_color: Color::None,
+            auxiliary_color: Color::None,
+            info_color: Color::None,
+            ..DumpErrorOption::default()
+        },
+    }
+}
+
 pub fn init_project(name: &str) -> Result<(), FileError> {
     // TODO: make sure that `project_name` is a valid identifier
 
@@ -813,8 +1006,8 @@ fn init_ir_dir(
         }
     }
 
-    // TODO: reset file-map (not id, but only hashes)
-    // File::clear_cache(0 /* project id */, intermediate_dir)?;
+    // It resets only the content hashes, not the file ids.
+    File::clear_content_hashes(intermediate_dir)?;
     Ok(())
 }
 
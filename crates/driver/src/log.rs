@@ -20,11 +20,13 @@ use std::collections::HashMap;
 
 mod inter_hir;
 mod inter_mir;
+mod mir_optimize;
 mod post_mir;
 mod timing;
 
 pub use inter_hir::{dump_inter_hir_log, store_inter_hir_log};
 pub use inter_mir::dump_inter_mir_log;
+pub use mir_optimize::dump_mir_optimize_log;
 pub use post_mir::dump_post_mir_log;
 pub use timing::{TimingsEntry, dump_timings};
 
@@ -0,0 +1,107 @@
+use sodigy_fs_api::{
+    FileError,
+    WriteMode,
+    create_dir,
+    exists,
+    join4,
+    parent,
+    write_bytes,
+    write_string,
+};
+use sodigy_optimize::{AccumulatorLog, InlineLog, MirOptimizeLog};
+use sodigy_span::{
+    RenderSpanOption,
+    RenderSpanSession,
+    RenderableSpan,
+    render_spans,
+};
+
+// TODO: it should dump html files, like the others!!
+pub fn dump_mir_optimize_log(log: &MirOptimizeLog, intermediate_dir: &str) -> Result<(), FileError> {
+    let mut buffer = vec![];
+    let mut render_span_session = RenderSpanSession::new(intermediate_dir);
+    let render_span_option = RenderSpanOption {
+        max_width: 128,
+        max_height: 20,
+        context: 3,
+        render_source: true,
+        color: None,
+        group_delim: None,
+    };
+
+    for InlineLog { call_span, callee, callee_span, reason } in log.inlines.iter() {
+        let callee = callee.unintern_or_default(intermediate_dir);
+        let spans = vec![
+            RenderableSpan {
+                span: call_span.clone(),
+                auxiliary: false,
+                note: Some(format!("`{callee}` is inlined")),
+            },
+            RenderableSpan {
+                span: callee_span.clone(),
+                auxiliary: true,
+                note: Some(format!("`{callee}` is defined here")),
+            },
+        ];
+
+        buffer.push(String::from("------\n"));
+        buffer.push(format!("# Inline `{callee}`: {reason}\n"));
+        buffer.push(String::from("```\n"));
+        buffer.push(render_spans(
+            &spans,
+            &render_span_option,
+            &mut render_span_session,
+        ));
+        buffer.push(String::from("```\n"));
+    }
+
+    for AccumulatorLog { func, func_span, kind, call_spans } in log.accumulators.iter() {
+        let func = func.unintern_or_default(intermediate_dir);
+        let mut spans = vec![
+            RenderableSpan {
+                span: func_span.clone(),
+                auxiliary: true,
+                note: Some(format!("`{func}` is defined here")),
+            },
+        ];
+
+        for call_span in call_spans.iter() {
+            spans.push(RenderableSpan {
+                span: call_span.clone(),
+                auxiliary: false,
+                note: Some(String::from("It becomes a tail call.")),
+            });
+        }
+
+        buffer.push(String::from("------\n"));
+        buffer.push(format!("# Accumulator `{func}`: {kind} is rewritten to a tail call with an accumulator\n"));
+        buffer.push(String::from("```\n"));
+        buffer.push(render_spans(
+            &spans,
+            &render_span_option,
+            &mut render_span_session,
+        ));
+        buffer.push(String::from("```\n"));
+    }
+
+    let save_at = join4(
+        intermediate_dir,
+        "irs",
+        "miroptimize",
+        "log",
+    )?;
+
+    if !exists(&parent(&save_at)?) {
+        create_dir(&parent(&save_at)?)?;
+    }
+
+    if !exists(&save_at) {
+        write_bytes(&save_at, b"", WriteMode::AlwaysCreate)?;
+    }
+
+    write_string(
+        &save_at,
+        &buffer.join("\n"),
+        WriteMode::AlwaysAppend,
+    )
+}
@@ -0,0 +1,420 @@
+// `sodigy lsp` speaks the Language Server Protocol over stdin/stdout.
+//
+// It doesn't have its own analyzer. Whenever a file is opened or saved, it
+// compiles the entire project (up to bytecode, with the incremental cache) and
+// reads the cached irs to answer the requests. It means that it only sees the
+// files on disk, not the unsaved buffers in the editor.
+
+use crate::{Error, ModuleCompileState, check_project};
+use sodigy_error::{Applicability, Error as SodigyError, Warning as SodigyWarning};
+use sodigy_file::ModulePath;
+use sodigy_fs_api::{current_dir, into_abs_path, join};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, Write};
+
+mod index;
+pub(crate) mod json;
+
+use index::Index;
+use json::Json;
+
+// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#errorCodes
+const METHOD_NOT_FOUND: i64 = -32601;
+
+pub fn run_language_server<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    jobs: usize,
+) -> Result<(), Error> {
+    let mut server = Server {
+        root: current_dir()?,
+        jobs,
+        index: None,
+        published_uris: HashSet::new(),
+        code_actions: HashMap::new(),
+    };
+
+    while let Some(message) = read_message(&mut reader)? {
+        let Ok(message) = json::parse(&message) else { continue };
+        let method = message.get("method").and_then(|m| m.as_str()).unwrap_or("");
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Json::Null);
+
+        match (method, id) {
+            ("exit", _) => {
+                return Ok(());
+            },
+            ("initialize", Some(id)) => {
+                server.initialize(&params)?;
+                write_message(&mut writer, &response(id, server.capabilities()))?;
+            },
+            ("shutdown", Some(id)) => {
+                write_message(&mut writer, &response(id, Json::Null))?;
+            },
+            ("textDocument/definition", Some(id)) => {
+                let result = server.definition(&params).unwrap_or(Json::Null);
+                write_message(&mut writer, &response(id, result))?;
+            },
+            ("textDocument/hover", Some(id)) => {
+                let result = server.hover(&params).unwrap_or(Json::Null);
+                write_message(&mut writer, &response(id, result))?;
+            },
+            ("textDocument/documentSymbol", Some(id)) => {
+                let result = server.document_symbol(&params).unwrap_or(Json::Null);
+                write_message(&mut writer, &response(id, result))?;
+            },
+            ("textDocument/codeAction", Some(id)) => {
+                let result = server.code_action(&params).unwrap_or(Json::Null);
+                write_message(&mut writer, &response(id, result))?;
+            },
+            (_, Some(id)) => {
+                write_message(&mut writer, &Json::object(vec![
+                    ("jsonrpc", Json::string("2.0")),
+                    ("id", id),
+                    ("error", Json::object(vec![
+                        ("code", Json::Number(METHOD_NOT_FOUND as f64)),
+                        ("message", Json::string(&format!("unknown method: {method}"))),
+                    ])),
+                ]))?;
+            },
+            ("textDocument/didOpen" | "textDocument/didSave", None) => {
+                for notification in server.check()? {
+                    write_message(&mut writer, &notification)?;
+                }
+            },
+
+            // It ignores the other notifications (e.g. `initialized`, `textDocument/didChange`).
+            (_, None) => {},
+        }
+    }
+
+    Ok(())
+}
+
+struct Server {
+    // the directory that has `src/` and `target/`
+    root: String,
+    jobs: usize,
+
+    // It's `None` until the first compilation.
+    index: Option<Index>,
+
+    // If a file had diagnostics in the previous check but doesn't have
+    // any in this check, the server has to publish an empty list.
+    published_uris: HashSet<String>,
+
+    // The suggestions of the diagnostics in the previous check.
+    // uri -> [(first line of the diagnostic, last line of the diagnostic, code action)]
+    code_actions: HashMap<String, Vec<(usize, usize, Json)>>,
+}
+
+impl Server {
+    fn initialize(&mut self, params: &Json) -> Result<(), Error> {
+        if let Some(uri) = params.get("rootUri").and_then(|uri| uri.as_str()) {
+            self.root = uri_to_path(uri);
+        }
+
+        else if let Some(path) = params.get("rootPath").and_then(|path| path.as_str()) {
+            self.root = path.to_string();
+        }
+
+        self.root = into_abs_path(&self.root)?;
+        Ok(())
+    }
+
+    fn capabilities(&self) -> Json {
+        Json::object(vec![
+            ("capabilities", Json::object(vec![
+                ("textDocumentSync", Json::object(vec![
+                    ("openClose", Json::Bool(true)),
+                    ("change", Json::number(0)),  // none
+                    ("save", Json::object(vec![("includeText", Json::Bool(false))])),
+                ])),
+                ("definitionProvider", Json::Bool(true)),
+                ("hoverProvider", Json::Bool(true)),
+                ("documentSymbolProvider", Json::Bool(true)),
+                ("codeActionProvider", Json::Bool(true)),
+            ])),
+            ("serverInfo", Json::object(vec![
+                ("name", Json::string("sodigy")),
+                ("version", Json::string(env!("CARGO_PKG_VERSION"))),
+            ])),
+        ])
+    }
+
+    // It compiles the project and returns `textDocument/publishDiagnostics` notifications.
+    fn check(&mut self) -> Result<Vec<Json>, Error> {
+        let src_dir = join(&self.root, "src")?;
+        let ir_dir = join(&self.root, "target")?;
+        let mut modules: HashMap<ModulePath, ModuleCompileState> = HashMap::new();
+        let (errors, warnings) = check_project(src_dir, ir_dir.clone(), self.jobs, &mut modules);
+        let index = Index::build(&modules, &ir_dir);
+        let diagnostics = self.collect_diagnostics(&index, &errors, &warnings)?;
+        self.index = Some(index);
+
+        let mut notifications = vec![];
+        let mut uris = diagnostics.keys().cloned().collect::<HashSet<_>>();
+        uris.extend(self.published_uris.drain());
+        let mut uris = uris.into_iter().collect::<Vec<_>>();
+        uris.sort();
+
+        for uri in uris.into_iter() {
+            let diagnostics = diagnostics.get(&uri).cloned().unwrap_or(vec![]);
+
+            if !diagnostics.is_empty() {
+                self.published_uris.insert(uri.clone());
+            }
+
+            notifications.push(Json::object(vec![
+                ("jsonrpc", Json::string("2.0")),
+                ("method", Json::string("textDocument/publishDiagnostics")),
+                ("params", Json::object(vec![
+                    ("uri", Json::string(&uri)),
+                    ("diagnostics", Json::Array(diagnostics)),
+                ])),
+            ]));
+        }
+
+        Ok(notifications)
+    }
+
+    fn collect_diagnostics(
+        &mut self,
+        index: &Index,
+        errors: &[SodigyError],
+        warnings: &[SodigyWarning],
+    ) -> Result<HashMap<String, Vec<Json>>, Error> {
+        let mut result: HashMap<String, Vec<Json>> = HashMap::new();
+        self.code_actions.clear();
+        let lib_path = into_abs_path(&join(&self.root, "src/lib.sdg")?)?;
+
+        for (error, is_warning) in errors.iter().map(|e| (e, false)).chain(warnings.iter().map(|w| (w, true))) {
+            let primary_span = error.spans.iter().find(|span| !span.auxiliary).or(error.spans.first());
+            let (path, range) = match primary_span.and_then(|span| span.span.file().map(|file| (span, file))) {
+                Some((span, file)) => match index.find_file(file) {
+                    Some((path, lines)) => (path.to_string(), lines.span_to_range(&span.span)),
+
+                    // std
+                    None => { continue; },
+                },
+                None => (lib_path.clone(), zero_range()),
+            };
+            let mut related_information = vec![];
+
+            for span in error.spans.iter() {
+                if primary_span.is_some_and(|primary_span| std::ptr::eq(span, primary_span)) {
+                    continue;
+                }
+
+                let Some(file) = span.span.file() else { continue };
+                let Some((path, lines)) = index.find_file(file) else { continue };
+
+                related_information.push(Json::object(vec![
+                    ("location", Json::object(vec![
+                        ("uri", Json::string(&path_to_uri(path))),
+                        ("range", lines.span_to_range(&span.span)),
+                    ])),
+                    ("message", Json::string(span.note.as_deref().unwrap_or(""))),
+                ]));
+            }
+
+            let mut message = error.kind.render(&index.intermediate_dir);
+
+            if let Some(note) = &error.note {
+                message = format!("{message}\n\n{note}");
+            }
+
+            let (first_line, last_line) = (
+                range.get_path(&["start", "line"]).and_then(|line| line.as_usize()).unwrap_or(0),
+                range.get_path(&["end", "line"]).and_then(|line| line.as_usize()).unwrap_or(0),
+            );
+
+            for suggestion in error.suggestions.iter() {
+                let Some(file) = suggestion.span.file() else { continue };
+                let Some((suggestion_path, lines)) = index.find_file(file) else { continue };
+
+                self.code_actions.entry(path_to_uri(&path)).or_default().push((first_line, last_line, Json::object(vec![
+                    ("title", Json::string(&suggestion.message)),
+                    ("kind", Json::string("quickfix")),
+                    ("isPreferred", Json::Bool(suggestion.applicability == Applicability::MachineApplicable)),
+                    ("edit", Json::object(vec![
+                        ("changes", Json::object(vec![
+                            (path_to_uri(suggestion_path).as_str(), Json::Array(vec![Json::object(vec![
+                                ("range", lines.span_to_range(&suggestion.span)),
+                                ("newText", Json::string(&suggestion.replacement)),
+                            ])])),
+                        ])),
+                    ])),
+                ])));
+            }
+
+            result.entry(path_to_uri(&path)).or_default().push(Json::object(vec![
+                ("range", range),
+                ("severity", Json::number(if is_warning { 2 } else { 1 })),
+                ("code", Json::string(&format!("{}-{:04}", if is_warning { "w" } else { "e" }, error.kind.index()))),
+                ("source", Json::string("sodigy")),
+                ("message", Json::string(&message)),
+                ("relatedInformation", Json::Array(related_information)),
+            ]));
+        }
+
+        Ok(result)
+    }
+
+    fn definition(&self, params: &Json) -> Option<Json> {
+        let index = self.index.as_ref()?;
+        let (path, offset) = self.get_cursor(params)?;
+        let (_, def_span) = index.find_name(&path, offset)?;
+        let (def_path, lines) = index.find_file(def_span.file()?)?;
+
+        Some(Json::object(vec![
+            ("uri", Json::string(&path_to_uri(def_path))),
+            ("range", lines.span_to_range(&def_span)),
+        ]))
+    }
+
+    fn hover(&self, params: &Json) -> Option<Json> {
+        let index = self.index.as_ref()?;
+        let (path, offset) = self.get_cursor(params)?;
+        let (span, def_span) = index.find_name(&path, offset)?;
+        let r#type = index.render_type_of(&def_span)?;
+        let lines = &index.files.get(&path)?.lines;
+        let name = lines.text(&span)?;
+
+        Some(Json::object(vec![
+            ("contents", Json::object(vec![
+                ("kind", Json::string("markdown")),
+                ("value", Json::string(&format!("```sodigy\n{name}: {type}\n```"))),
+            ])),
+            ("range", lines.span_to_range(&span)),
+        ]))
+    }
+
+    fn document_symbol(&self, params: &Json) -> Option<Json> {
+        let index = self.index.as_ref()?;
+        let uri = params.get_path(&["textDocument", "uri"])?.as_str()?;
+        let file_index = index.files.get(&into_abs_path(&uri_to_path(uri)).ok()?)?;
+
+        Some(Json::Array(file_index.symbols.iter().map(
+            |symbol| symbol.to_json(&file_index.lines)
+        ).collect()))
+    }
+
+    // It returns the suggestions of the diagnostics that overlap with the requested range.
+    fn code_action(&self, params: &Json) -> Option<Json> {
+        let uri = params.get_path(&["textDocument", "uri"])?.as_str()?;
+        let start = params.get_path(&["range", "start", "line"])?.as_usize()?;
+        let end = params.get_path(&["range", "end", "line"])?.as_usize()?;
+        let path = into_abs_path(&uri_to_path(uri)).ok()?;
+        let code_actions = self.code_actions.get(&path_to_uri(&path))?;
+
+        Some(Json::Array(code_actions.iter().filter(
+            |(first_line, last_line, _)| *first_line <= end && start <= *last_line
+        ).map(
+            |(_, _, action)| action.clone()
+        ).collect()))
+    }
+
+    // (absolute path, byte offset)
+    fn get_cursor(&self, params: &Json) -> Option<(String, usize)> {
+        let uri = params.get_path(&["textDocument", "uri"])?.as_str()?;
+        let line = params.get_path(&["position", "line"])?.as_usize()?;
+        let character = params.get_path(&["position", "character"])?.as_usize()?;
+        let path = into_abs_path(&uri_to_path(uri)).ok()?;
+        let offset = self.index.as_ref()?.files.get(&path)?.lines.offset(line, character);
+        Some((path, offset))
+    }
+}
+
+fn response(id: Json, result: Json) -> Json {
+    Json::object(vec![
+        ("jsonrpc", Json::string("2.0")),
+        ("id", id),
+        ("result", result),
+    ])
+}
+
+fn zero_range() -> Json {
+    let zero = Json::object(vec![
+        ("line", Json::number(0)),
+        ("character", Json::number(0)),
+    ]);
+
+    Json::object(vec![
+        ("start", zero.clone()),
+        ("end", zero),
+    ])
+}
+
+// Each message is `Content-Length: <n>\r\n\r\n<n bytes of json>`.
+// It returns `None` if the input is closed.
+fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Vec<u8>>, Error> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line).map_err(|_| Error::MiscError)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim();
+
+        if line.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') && key.trim().eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let mut buffer = vec![0; content_length.unwrap()];
+    reader.read_exact(&mut buffer).map_err(|_| Error::MiscError)?;
+    Ok(Some(buffer))
+}
+
+fn write_message<W: Write>(writer: &mut W, message: &Json) -> Result<(), Error> {
+    let message = message.render();
+    write!(writer, "Content-Length: {}\r\n\r\n{message}", message.len()).map_err(|_| Error::MiscError)?;
+    writer.flush().map_err(|_| Error::MiscError)?;
+    Ok(())
+}
+
+fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let bytes = path.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && let Some(Ok(b)) = bytes.get((i + 1)..(i + 3)).map(|hex| u8::from_str_radix(&String::from_utf8_lossy(hex), 16)) {
+            result.push(b);
+            i += 3;
+        }
+
+        else {
+            result.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&result).to_string()
+}
+
+fn path_to_uri(path: &str) -> String {
+    let mut result = String::from("file://");
+
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => { result.push(b as char); },
+            _ => { result.push_str(&format!("%{b:02X}")); },
+        }
+    }
+
+    result
+}
@@ -0,0 +1,506 @@
+use super::json::Json;
+use crate::{CompileStage, ModuleCompileState, get_cached_ir};
+use sodigy_endec::Endec;
+use sodigy_file::{File, FileOrStd, ModulePath};
+use sodigy_fs_api::into_abs_path;
+use sodigy_hir::{self as hir, EnumVariantFields, FuncOrigin, LetOrigin};
+use sodigy_inter_mir as inter_mir;
+use sodigy_mir::{self as mir, Callable, MacroKind};
+use sodigy_span::Span;
+use std::collections::HashMap;
+
+// Everything the language server knows about the project after a compilation.
+// It's rebuilt from the cached irs whenever the project is compiled.
+//
+// - Document symbols come from the hir sessions, so they're available as long as the file can be parsed.
+// - Definitions come from the mir sessions, which are available if the project passes inter-hir.
+// - Hovers come from the inter-mir session (`types`), which is available if the project reaches inter-mir.
+pub struct Index {
+    pub intermediate_dir: String,
+
+    // key is an absolute path of the file
+    pub files: HashMap<String, FileIndex>,
+
+    pub inter_mir_session: Option<inter_mir::Session>,
+}
+
+pub struct FileIndex {
+    pub file: File,
+    pub lines: LineIndex,
+
+    // (span of an identifier, def_span of the identifier)
+    pub refs: Vec<(Span, Span)>,
+
+    // name_spans of the definitions in this file
+    pub defs: Vec<Span>,
+
+    pub symbols: Vec<Symbol>,
+}
+
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+
+    // from the keyword to the name
+    pub span: Span,
+    pub name_span: Span,
+    pub children: Vec<Symbol>,
+}
+
+// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#symbolKind
+#[derive(Clone, Copy, Debug)]
+pub enum SymbolKind {
+    Module = 2,
+    Field = 8,
+    Enum = 10,
+    Function = 12,
+    Variable = 13,
+    EnumMember = 22,
+    Struct = 23,
+    Event = 24,
+    TypeParameter = 26,
+}
+
+impl Index {
+    pub fn build(
+        modules: &HashMap<ModulePath, ModuleCompileState>,
+        intermediate_dir: &str,
+    ) -> Index {
+        let mut files = HashMap::new();
+
+        for module in modules.values() {
+            // There's nothing the user can do in std.
+            let FileOrStd::File(path) = &module.file_path else { continue };
+            let Ok(Some(file)) = File::from_module_path(&module.module_path.to_string(), intermediate_dir) else { continue };
+            let Ok(Some(bytes)) = file.read_bytes(intermediate_dir) else { continue };
+            let Ok(content_hash) = file.get_content_hash(intermediate_dir) else { continue };
+            let Ok(path) = into_abs_path(path) else { continue };
+
+            let mut file_index = FileIndex {
+                file,
+                lines: LineIndex::new(&bytes),
+                refs: vec![],
+                defs: vec![],
+                symbols: vec![],
+            };
+
+            if let Ok(Some(bytes)) = get_cached_ir(intermediate_dir, CompileStage::Hir, Some(content_hash)) &&
+                let Ok(hir_session) = hir::Session::decode(&bytes)
+            {
+                file_index.symbols = collect_symbols(&hir_session, intermediate_dir);
+            }
+
+            if let Ok(Some(bytes)) = get_cached_ir(intermediate_dir, CompileStage::Mir, Some(content_hash)) &&
+                let Ok(mir_session) = mir::Session::decode(&bytes)
+            {
+                collect_names(&mir_session, &mut file_index.refs, &mut file_index.defs);
+
+                // Each file has to remember only its own names.
+                file_index.refs.retain(|(span, _)| is_in_file(span, file));
+                file_index.defs.retain(|span| is_in_file(span, file));
+            }
+
+            files.insert(path, file_index);
+        }
+
+        let inter_mir_session = match get_cached_ir(intermediate_dir, CompileStage::InterMir, None) {
+            Ok(Some(bytes)) => match inter_mir::Session::decode(&bytes) {
+                Ok(mut session) => {
+                    session.intermediate_dir = intermediate_dir.to_string();
+                    Some(session)
+                },
+                Err(_) => None,
+            },
+            _ => None,
+        };
+
+        Index {
+            intermediate_dir: intermediate_dir.to_string(),
+            files,
+            inter_mir_session,
+        }
+    }
+
+    // It finds the name at `offset` and returns (span of the name, def_span of the name).
+    // If the cursor is on a definition, def_span is the name itself.
+    pub fn find_name(&self, path: &str, offset: usize) -> Option<(Span, Span)> {
+        let file_index = self.files.get(path)?;
+        let mut candidates = vec![];
+
+        for (span, def_span) in file_index.refs.iter() {
+            if contains(span, offset) {
+                candidates.push((span, def_span));
+            }
+        }
+
+        for def_span in file_index.defs.iter() {
+            if contains(def_span, offset) {
+                candidates.push((def_span, def_span));
+            }
+        }
+
+        // the innermost one
+        candidates.sort_by_key(|(span, _)| span.get_offset_and_length().map(|(_, length)| length).unwrap_or(u32::MAX));
+        candidates.first().map(|(span, def_span)| ((*span).clone(), (*def_span).clone()))
+    }
+
+    // (absolute path, LineIndex of the file)
+    pub fn find_file(&self, file: File) -> Option<(&str, &LineIndex)> {
+        self.files.iter().find(
+            |(_, file_index)| file_index.file == file
+        ).map(
+            |(path, file_index)| (path.as_str(), &file_index.lines)
+        )
+    }
+
+    pub fn render_type_of(&self, def_span: &Span) -> Option<String> {
+        let session = self.inter_mir_session.as_ref()?;
+        let r#type = session.types.get(def_span)?;
+        Some(session.render_type(r#type))
+    }
+}
+
+impl Symbol {
+    pub fn to_json(&self, lines: &LineIndex) -> Json {
+        Json::object(vec![
+            ("name", Json::string(&self.name)),
+            ("kind", Json::number(self.kind as usize)),
+            ("range", lines.span_to_range(&self.span)),
+            ("selectionRange", lines.span_to_range(&self.name_span)),
+            ("children", Json::Array(self.children.iter().map(|child| child.to_json(lines)).collect())),
+        ])
+    }
+}
+
+// Spans are in bytes, but LSP wants (line, character) where `character`
+// is in UTF-16 code units. So it remembers the source code of the file.
+pub struct LineIndex {
+    pub bytes: Vec<u8>,
+
+    // byte offsets of the first character of each line
+    pub line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(bytes: &[u8]) -> LineIndex {
+        let mut line_starts = vec![0];
+
+        for (i, b) in bytes.iter().enumerate() {
+            if *b == b'\n' {
+                line_starts.push(i + 1);
+            }
+        }
+
+        LineIndex {
+            bytes: bytes.to_vec(),
+            line_starts,
+        }
+    }
+
+    // (line, character), both 0-based
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.bytes.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(n) => n,
+            Err(n) => n - 1,
+        };
+        let line_start = self.line_starts[line];
+        let character = String::from_utf8_lossy(&self.bytes[line_start..offset]).encode_utf16().count();
+
+        (line, character)
+    }
+
+    pub fn offset(&self, line: usize, character: usize) -> usize {
+        let Some(line_start) = self.line_starts.get(line) else { return self.bytes.len() };
+        let line_end = self.line_starts.get(line + 1).map(|n| *n - 1).unwrap_or(self.bytes.len());
+        let mut utf16_count = 0;
+        let mut offset = *line_start;
+
+        for c in String::from_utf8_lossy(&self.bytes[*line_start..line_end]).chars() {
+            if utf16_count >= character {
+                break;
+            }
+
+            utf16_count += c.len_utf16();
+            offset += c.len_utf8();
+        }
+
+        offset.min(line_end)
+    }
+
+    pub fn span_to_range(&self, span: &Span) -> Json {
+        let (start, end) = match span.get_offset_and_length() {
+            Some((offset, length)) => (offset as usize, (offset + length) as usize),
+            None => (0, 0),
+        };
+
+        self.range(start, end)
+    }
+
+    pub fn range(&self, start: usize, end: usize) -> Json {
+        let (start_line, start_character) = self.position(start);
+        let (end_line, end_character) = self.position(end);
+
+        Json::object(vec![
+            ("start", Json::object(vec![
+                ("line", Json::number(start_line)),
+                ("character", Json::number(start_character)),
+            ])),
+            ("end", Json::object(vec![
+                ("line", Json::number(end_line)),
+                ("character", Json::number(end_character)),
+            ])),
+        ])
+    }
+
+    pub fn text(&self, span: &Span) -> Option<String> {
+        let (offset, length) = span.get_offset_and_length()?;
+        let bytes = self.bytes.get(offset as usize..(offset + length) as usize)?;
+        Some(String::from_utf8_lossy(bytes).to_string())
+    }
+}
+
+fn is_in_file(span: &Span, file: File) -> bool {
+    matches!(span, Span::Range(_)) && span.file() == Some(file)
+}
+
+// `end` is inclusive because the cursor can be right after the last character of a name.
+fn contains(span: &Span, offset: usize) -> bool {
+    match span.get_offset_and_length() {
+        Some((start, length)) => start as usize <= offset && offset <= (start + length) as usize,
+        None => false,
+    }
+}
+
+fn collect_symbols(session: &hir::Session, intermediate_dir: &str) -> Vec<Symbol> {
+    let mut symbols = vec![];
+
+    for r#let in session.lets.iter() {
+        if let LetOrigin::TopLevel = r#let.origin {
+            symbols.push(Symbol {
+                name: r#let.name.unintern_or_default(intermediate_dir),
+                kind: SymbolKind::Variable,
+                span: r#let.keyword_span.merge(&r#let.name_span),
+                name_span: r#let.name_span.clone(),
+                children: vec![],
+            });
+        }
+    }
+
+    for func in session.funcs.iter() {
+        if let FuncOrigin::TopLevel = func.origin {
+            symbols.push(Symbol {
+                name: func.name.unintern_or_default(intermediate_dir),
+                kind: SymbolKind::Function,
+                span: func.keyword_span.merge(&func.name_span),
+                name_span: func.name_span.clone(),
+                children: vec![],
+            });
+        }
+    }
+
+    for r#struct in session.structs.iter() {
+        symbols.push(Symbol {
+            name: r#struct.name.unintern_or_default(intermediate_dir),
+            kind: SymbolKind::Struct,
+            span: r#struct.keyword_span.merge(&r#struct.name_span),
+            name_span: r#struct.name_span.clone(),
+            children: r#struct.fields.iter().map(
+                |field| Symbol {
+                    name: field.name.unintern_or_default(intermediate_dir),
+                    kind: SymbolKind::Field,
+                    span: field.name_span.clone(),
+                    name_span: field.name_span.clone(),
+                    children: vec![],
+                }
+            ).collect(),
+        });
+    }
+
+    for r#enum in session.enums.iter() {
+        symbols.push(Symbol {
+            name: r#enum.name.unintern_or_default(intermediate_dir),
+            kind: SymbolKind::Enum,
+            span: r#enum.keyword_span.merge(&r#enum.name_span),
+            name_span: r#enum.name_span.clone(),
+            children: r#enum.variants.iter().map(
+                |variant| Symbol {
+                    name: variant.name.unintern_or_default(intermediate_dir),
+                    kind: SymbolKind::EnumMember,
+                    span: variant.name_span.clone(),
+                    name_span: variant.name_span.clone(),
+                    children: match &variant.fields {
+                        EnumVariantFields::Struct(fields) => fields.iter().map(
+                            |field| Symbol {
+                                name: field.name.unintern_or_default(intermediate_dir),
+                                kind: SymbolKind::Field,
+                                span: field.name_span.clone(),
+                                name_span: field.name_span.clone(),
+                                children: vec![],
+                            }
+                        ).collect(),
+                        EnumVariantFields::None | EnumVariantFields::Tuple(_) => vec![],
+                    },
+                }
+            ).collect(),
+        });
+    }
+
+    for alias in session.aliases.iter() {
+        symbols.push(Symbol {
+            name: alias.name.unintern_or_default(intermediate_dir),
+            kind: SymbolKind::TypeParameter,
+            span: alias.keyword_span.merge(&alias.name_span),
+            name_span: alias.name_span.clone(),
+            children: vec![],
+        });
+    }
+
+    for module in session.modules.iter() {
+        symbols.push(Symbol {
+            name: module.name.unintern_or_default(intermediate_dir),
+            kind: SymbolKind::Module,
+            span: module.keyword_span.merge(&module.name_span),
+            name_span: module.name_span.clone(),
+            children: vec![],
+        });
+    }
+
+    // Only named assertions are worth showing. They're tests.
+    for assert in session.asserts.iter() {
+        if let Some(name) = assert.name {
+            symbols.push(Symbol {
+                name: name.unintern_or_default(intermediate_dir),
+                kind: SymbolKind::Event,
+                span: assert.keyword_span.clone(),
+                name_span: assert.keyword_span.clone(),
+                children: vec![],
+            });
+        }
+    }
+
+    // The spans might be in a different file (e.g. items generated by the compiler).
+    symbols.retain(|symbol| matches!(symbol.name_span, Span::Range(_)));
+    symbols.sort_by_key(|symbol| symbol.name_span.clone());
+    symbols
+}
+
+fn collect_names(session: &mir::Session, refs: &mut Vec<(Span, Span)>, defs: &mut Vec<Span>) {
+    for r#let in session.lets.iter() {
+        defs.push(r#let.name_span.clone());
+        collect_names_in_expr(&r#let.value, refs, defs);
+    }
+
+    for func in session.funcs.iter() {
+        // Monomorphized functions are copies of the original functions.
+        if let FuncOrigin::Monomorphization = func.origin {
+            continue;
+        }
+
+        defs.push(func.name_span.clone());
+
+        for param in func.params.iter() {
+            defs.push(param.name_span.clone());
+        }
+
+        collect_names_in_expr(&func.value, refs, defs);
+    }
+
+    for r#struct in session.structs.iter() {
+        defs.push(r#struct.name_span.clone());
+
+        for field in r#struct.fields.iter() {
+            defs.push(field.name_span.clone());
+        }
+    }
+
+    for r#enum in session.enums.iter() {
+        defs.push(r#enum.name_span.clone());
+
+        for variant in r#enum.variants.iter() {
+            defs.push(variant.name_span.clone());
+        }
+    }
+
+    for assert in session.asserts.iter() {
+        collect_names_in_assert(assert, refs, defs);
+    }
+}
+
+fn collect_names_in_assert(assert: &mir::Assert, refs: &mut Vec<(Span, Span)>, defs: &mut Vec<Span>) {
+    collect_names_in_expr(&assert.value, refs, defs);
+
+    if let Some(note) = &assert.note {
+        collect_names_in_expr(note, refs, defs);
+    }
+}
+
+fn collect_names_in_expr(expr: &mir::Expr, refs: &mut Vec<(Span, Span)>, defs: &mut Vec<Span>) {
+    match expr {
+        mir::Expr::Ident { id, .. } => {
+            refs.push((id.span.clone(), id.def_span.clone()));
+        },
+        mir::Expr::Constant(_) => {},
+        mir::Expr::If(r#if) => {
+            collect_names_in_expr(&r#if.cond, refs, defs);
+            collect_names_in_expr(&r#if.true_value, refs, defs);
+            collect_names_in_expr(&r#if.false_value, refs, defs);
+        },
+        mir::Expr::Match(r#match) => {
+            collect_names_in_expr(&r#match.scrutinee, refs, defs);
+
+            for arm in r#match.arms.iter() {
+                if let Some(guard) = &arm.guard {
+                    collect_names_in_expr(guard, refs, defs);
+                }
+
+                collect_names_in_expr(&arm.value, refs, defs);
+            }
+        },
+        mir::Expr::Block(block) => {
+            for r#let in block.lets.iter() {
+                defs.push(r#let.name_span.clone());
+                collect_names_in_expr(&r#let.value, refs, defs);
+            }
+
+            for assert in block.asserts.iter() {
+                collect_names_in_assert(assert, refs, defs);
+            }
+
+            for r#do in block.dos.iter() {
+                collect_names_in_expr(&r#do.value, refs, defs);
+            }
+
+            collect_names_in_expr(&block.value, refs, defs);
+        },
+        mir::Expr::Field { lhs, .. } => {
+            collect_names_in_expr(lhs, refs, defs);
+        },
+        mir::Expr::FieldUpdate { lhs, rhs, .. } => {
+            collect_names_in_expr(lhs, refs, defs);
+            collect_names_in_expr(rhs, refs, defs);
+        },
+        mir::Expr::Call { func, args, .. } => {
+            match func {
+                Callable::Static { def_span, span } |
+                Callable::StructInit { def_span, span } |
+                Callable::EnumInit { variant_def_span: def_span, span, .. } => {
+                    refs.push((span.clone(), def_span.clone()));
+                },
+                Callable::Dynamic(func) => {
+                    collect_names_in_expr(func, refs, defs);
+                },
+                Callable::TupleInit { .. } | Callable::ListInit { .. } => {},
+            }
+
+            for arg in args.iter() {
+                collect_names_in_expr(arg, refs, defs);
+            }
+        },
+        mir::Expr::Macro { kind, .. } => {
+            if let MacroKind::TypeNameOfValue { value } = kind.as_ref() {
+                collect_names_in_expr(value, refs, defs);
+            }
+        },
+    }
+}
@@ -0,0 +1,286 @@
+// I know that you want to depend on serde_json... but please don't!
+// It's a tiny json parser/renderer that's just enough for the language server.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+
+    // The order of the keys is preserved, so that the rendered output is deterministic.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    // `json.get_path(&["params", "textDocument", "uri"])`
+    pub fn get_path(&self, keys: &[&str]) -> Option<&Json> {
+        let mut curr = self;
+
+        for key in keys.iter() {
+            curr = curr.get(key)?;
+        }
+
+        Some(curr)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+            _ => None,
+        }
+    }
+
+    pub fn object(entries: Vec<(&str, Json)>) -> Json {
+        Json::Object(entries.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+    }
+
+    pub fn string(s: &str) -> Json {
+        Json::String(s.to_string())
+    }
+
+    pub fn number(n: usize) -> Json {
+        Json::Number(n as f64)
+    }
+
+    pub fn render(&self) -> String {
+        let mut buffer = String::new();
+        self.render_worker(&mut buffer);
+        buffer
+    }
+
+    fn render_worker(&self, buffer: &mut String) {
+        match self {
+            Json::Null => { buffer.push_str("null"); },
+            Json::Bool(b) => { buffer.push_str(if *b { "true" } else { "false" }); },
+            Json::Number(n) => {
+                if n.fract() == 0.0 && n.abs() < 1e15 {
+                    buffer.push_str(&format!("{}", *n as i64));
+                } else {
+                    buffer.push_str(&format!("{n}"));
+                }
+            },
+            Json::String(s) => { render_string(s, buffer); },
+            Json::Array(elements) => {
+                buffer.push('[');
+
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        buffer.push(',');
+                    }
+
+                    element.render_worker(buffer);
+                }
+
+                buffer.push(']');
+            },
+            Json::Object(entries) => {
+                buffer.push('{');
+
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        buffer.push(',');
+                    }
+
+                    render_string(key, buffer);
+                    buffer.push(':');
+                    value.render_worker(buffer);
+                }
+
+                buffer.push('}');
+            },
+        }
+    }
+}
+
+fn render_string(s: &str, buffer: &mut String) {
+    buffer.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => { buffer.push_str("\\\""); },
+            '\\' => { buffer.push_str("\\\\"); },
+            '\n' => { buffer.push_str("\\n"); },
+            '\r' => { buffer.push_str("\\r"); },
+            '\t' => { buffer.push_str("\\t"); },
+            c if (c as u32) < 0x20 || c == '\x7f' => {
+                buffer.push_str(&format!("\\u{:04x}", c as u32));
+            },
+            c => { buffer.push(c); },
+        }
+    }
+
+    buffer.push('"');
+}
+
+pub fn parse(s: &[u8]) -> Result<Json, ()> {
+    let (json, cursor) = parse_value(s, skip_whitespaces(s, 0))?;
+
+    if skip_whitespaces(s, cursor) != s.len() {
+        return Err(());
+    }
+
+    Ok(json)
+}
+
+fn parse_value(s: &[u8], cursor: usize) -> Result<(Json, usize), ()> {
+    match s.get(cursor) {
+        Some(b'n') if s[cursor..].starts_with(b"null") => Ok((Json::Null, cursor + 4)),
+        Some(b't') if s[cursor..].starts_with(b"true") => Ok((Json::Bool(true), cursor + 4)),
+        Some(b'f') if s[cursor..].starts_with(b"false") => Ok((Json::Bool(false), cursor + 5)),
+        Some(b'"') => {
+            let (s, cursor) = parse_string(s, cursor)?;
+            Ok((Json::String(s), cursor))
+        },
+        Some(b'[') => {
+            let mut elements = vec![];
+            let mut cursor = skip_whitespaces(s, cursor + 1);
+
+            if let Some(b']') = s.get(cursor) {
+                return Ok((Json::Array(elements), cursor + 1));
+            }
+
+            loop {
+                let (element, new_cursor) = parse_value(s, cursor)?;
+                elements.push(element);
+                cursor = skip_whitespaces(s, new_cursor);
+
+                match s.get(cursor) {
+                    Some(b',') => {
+                        cursor = skip_whitespaces(s, cursor + 1);
+                    },
+                    Some(b']') => {
+                        return Ok((Json::Array(elements), cursor + 1));
+                    },
+                    _ => {
+                        return Err(());
+                    },
+                }
+            }
+        },
+        Some(b'{') => {
+            let mut entries = vec![];
+            let mut cursor = skip_whitespaces(s, cursor + 1);
+
+            if let Some(b'}') = s.get(cursor) {
+                return Ok((Json::Object(entries), cursor + 1));
+            }
+
+            loop {
+                let (key, new_cursor) = parse_string(s, cursor)?;
+                cursor = skip_whitespaces(s, new_cursor);
+
+                let Some(b':') = s.get(cursor) else { return Err(()) };
+                cursor = skip_whitespaces(s, cursor + 1);
+
+                let (value, new_cursor) = parse_value(s, cursor)?;
+                entries.push((key, value));
+                cursor = skip_whitespaces(s, new_cursor);
+
+                match s.get(cursor) {
+                    Some(b',') => {
+                        cursor = skip_whitespaces(s, cursor + 1);
+                    },
+                    Some(b'}') => {
+                        return Ok((Json::Object(entries), cursor + 1));
+                    },
+                    _ => {
+                        return Err(());
+                    },
+                }
+            }
+        },
+        Some(b'-' | b'0'..=b'9') => {
+            let mut end = cursor + 1;
+
+            while let Some(b'0'..=b'9' | b'.' | b'e' | b'E' | b'+' | b'-') = s.get(end) {
+                end += 1;
+            }
+
+            let n = String::from_utf8_lossy(&s[cursor..end]).parse::<f64>().map_err(|_| ())?;
+            Ok((Json::Number(n), end))
+        },
+        _ => Err(()),
+    }
+}
+
+fn parse_string(s: &[u8], cursor: usize) -> Result<(String, usize), ()> {
+    let Some(b'"') = s.get(cursor) else { return Err(()) };
+    let mut buffer = vec![];
+    let mut cursor = cursor + 1;
+
+    loop {
+        match s.get(cursor) {
+            Some(b'"') => {
+                let s = String::from_utf8(buffer).map_err(|_| ())?;
+                return Ok((s, cursor + 1));
+            },
+            Some(b'\\') => {
+                match s.get(cursor + 1) {
+                    Some(b'"') => { buffer.push(b'"'); },
+                    Some(b'\\') => { buffer.push(b'\\'); },
+                    Some(b'/') => { buffer.push(b'/'); },
+                    Some(b'b') => { buffer.push(8); },
+                    Some(b'f') => { buffer.push(12); },
+                    Some(b'n') => { buffer.push(b'\n'); },
+                    Some(b'r') => { buffer.push(b'\r'); },
+                    Some(b't') => { buffer.push(b'\t'); },
+                    Some(b'u') => {
+                        let mut code = parse_hex4(s, cursor + 2)?;
+                        cursor += 4;
+
+                        // surrogate pair
+                        if (0xd800..0xdc00).contains(&code) && s.get(cursor + 2) == Some(&b'\\') && s.get(cursor + 3) == Some(&b'u') {
+                            let low = parse_hex4(s, cursor + 4)?;
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+                            cursor += 6;
+                        }
+
+                        let c = char::from_u32(code).unwrap_or('\u{fffd}');
+                        buffer.extend(c.to_string().as_bytes());
+                    },
+                    _ => {
+                        return Err(());
+                    },
+                }
+
+                cursor += 2;
+            },
+            Some(c) => {
+                buffer.push(*c);
+                cursor += 1;
+            },
+            None => {
+                return Err(());
+            },
+        }
+    }
+}
+
+fn parse_hex4(s: &[u8], cursor: usize) -> Result<u32, ()> {
+    let hex = s.get(cursor..(cursor + 4)).ok_or(())?;
+    u32::from_str_radix(&String::from_utf8_lossy(hex), 16).map_err(|_| ())
+}
+
+fn skip_whitespaces(s: &[u8], mut cursor: usize) -> usize {
+    while let Some(b' ' | b'\n' | b'\r' | b'\t') = s.get(cursor) {
+        cursor += 1;
+    }
+
+    cursor
+}
@@ -0,0 +1,104 @@
+// A hir session only knows the names in its module, so it can't tell whether an undefined
+// name is defined in another module. Once the compilation is over, the driver looks for the
+// undefined names in std and in the other modules of the project, and suggests importing them.
+
+use crate::ModuleCompileState;
+use crate::doc::doc_module_path;
+use sodigy_error::{Error as SodigyError, ErrorKind, comma_list_strs};
+use sodigy_file::{File, FileOrStd, ModulePath, std_modules};
+use sodigy_string::InternedString;
+use std::collections::{HashMap, HashSet};
+
+pub(crate) fn suggest_imports(
+    errors: &mut [SodigyError],
+    modules: &HashMap<ModulePath, ModuleCompileState>,
+    ir_dir: &str,
+) {
+    let undefined_names = errors.iter().filter_map(
+        |error| match &error.kind {
+            ErrorKind::UndefinedName(name) => Some(*name),
+            _ => None,
+        }
+    ).collect::<HashSet<_>>();
+
+    if undefined_names.is_empty() {
+        return;
+    }
+
+    // The items in `lib` can't be imported with a path, so `lib` is not a candidate.
+    let mut project_modules = modules.values().filter(
+        |module| matches!(module.file_path, FileOrStd::File(_)) && !module.module_path.is_lib()
+    ).map(
+        |module| (module.module_path.clone(), module.file_path.clone())
+    ).collect::<Vec<_>>();
+    project_modules.sort_by_key(|(module_path, _)| module_path.to_string());
+
+    // name -> [(module path, file)]
+    let mut definitions: HashMap<InternedString, Vec<(String, File)>> = HashMap::new();
+
+    for (module_path, file_path) in std_modules().into_iter().chain(project_modules) {
+        let (file, is_std) = match &file_path {
+            FileOrStd::File(_) => match File::from_module_path(&module_path.to_string(), ir_dir) {
+                Ok(Some(file)) => (file, false),
+                _ => {
+                    continue;
+                },
+            },
+            FileOrStd::Std(n) => (File::std(*n), true),
+        };
+        let Ok(Some(bytes)) = file.read_bytes(ir_dir) else { continue };
+        let lex_session = sodigy_lex::lex(file, bytes, ir_dir.to_string(), is_std, false);
+
+        // The other modules might have syntax errors, and that's not what we're looking for.
+        if !lex_session.errors.is_empty() {
+            continue;
+        }
+
+        let file_span = lex_session.file_span();
+        let parse_session = sodigy_parse::parse(lex_session, file_span);
+
+        if !parse_session.errors.is_empty() {
+            continue;
+        }
+
+        let ast = &parse_session.ast;
+        let names = ast.lets.iter().map(|r#let| r#let.name).chain(
+            ast.funcs.iter().map(|func| func.name)
+        ).chain(
+            ast.structs.iter().map(|r#struct| r#struct.name)
+        ).chain(
+            ast.enums.iter().map(|r#enum| r#enum.name)
+        ).chain(
+            ast.aliases.iter().map(|alias| alias.name)
+        );
+
+        for name in names {
+            if undefined_names.contains(&name) {
+                definitions.entry(name).or_default().push((doc_module_path(&module_path), file));
+            }
+        }
+    }
+
+    for error in errors.iter_mut() {
+        let ErrorKind::UndefinedName(name) = &error.kind else { continue };
+        let Some(definitions) = definitions.get(name) else { continue };
+        let error_file = error.spans.first().and_then(|span| span.span.file());
+        let name = name.unintern_or_default(ir_dir);
+
+        // If it's defined in the same module, it's not an import issue (e.g. it's out of scope).
+        let mut imports = definitions.iter().filter(
+            |(_, file)| Some(*file) != error_file
+        ).map(
+            |(module_path, _)| format!("use {module_path}.{name};")
+        ).collect::<Vec<_>>();
+        imports.truncate(3);
+
+        if !imports.is_empty() {
+            error.add_note(format!(
+                "`{name}` is defined in {}. Try {}.",
+                if imports.len() == 1 { "another module" } else { "other modules" },
+                comma_list_strs(&imports, "`", "`", "or"),
+            ));
+        }
+    }
+}
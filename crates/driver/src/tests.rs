@@ -1,15 +1,33 @@
 use crate::{
     ColorWhen,
+    check_project,
+    DocFormat,
+    Error,
+    MessageFormat,
     Profile,
     StoreIrAt,
     ValidateTokenSpans,
+    fix_project,
+    format_files,
+    generate_docs,
     init_project,
     init_workers_and_compile,
+    load_executable,
+    run_doc_tests,
+    run_language_server,
 };
+use crate::fix::apply_suggestions;
+use crate::lsp::json::{self, Json};
+use sodigy_bytecode::Bytecode;
 use sodigy_code_gen::Backend;
+use sodigy_doc::code_blocks;
+use sodigy_error::{Applicability, ERROR_KIND_DOCS, ErrorKindDoc, Suggestion};
+use sodigy_file::File;
 use sodigy_optimize::OptimizeLevel;
-use sodigy_fs_api::{exists, remove_dir_all};
+use sodigy_span::Span;
+use sodigy_fs_api::{WriteMode, create_dir_all, exists, into_abs_path, join, read_string, remove_dir_all, write_string};
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
 
 #[test]
 fn verify_built_ins() {
@@ -29,9 +47,11 @@ fn verify_built_ins() {
         false,  // dump-post-mir-log
         false,   // dump-timings
         false,  // dump-bytecodes
+        false,  // check-const-asserts
         0,  // graceful-shutdown
         8,  // jobs
         ColorWhen::Never,
+        MessageFormat::Human,
         true,  // incremental-compilation
         ValidateTokenSpans::Never,
         true,  // verify-built-ins
@@ -41,3 +61,590 @@ fn verify_built_ins() {
 
     remove_dir_all("verify_built_ins").unwrap();
 }
+
+// A top-level `let` that exceeds the budget of the compile-time evaluation (or doesn't
+// have a shape) is not embedded, and it's still lazy-initialized at runtime.
+#[test]
+fn const_eval_fallback() {
+    if exists("const_eval_fallback") {
+        remove_dir_all("const_eval_fallback").unwrap();
+    }
+
+    init_project("const_eval_fallback").unwrap();
+    let lib = join("const_eval_fallback", "src/lib.sdg").unwrap();
+    let source = "fn range(n: Int) -> [Int] = if n == 0 { [] } else { n +> range(n - 1) };
+fn sum(ns: [Int]) -> Int = match ns {
+    [] => 0,
+    [$n] ++ $rest => n + sum(rest),
+};
+fn count(n: Int, acc: Int) -> Int = if n == 0 { acc } else { count(n - 1, acc + 1) };
+struct Node = { value: Int, children: [Node] };
+
+let small: Int = sum(range(10));
+
+// deeper than `MAX_CALL_DEPTH` (128)
+let deep: Int = sum(range(200));
+
+// longer than `CONST_EVAL_STEPS` (2^20)
+let long: Int = count(1 << 20, 0);
+
+// a self-referential type doesn't have a shape
+let tree: Node = Node { value: 1, children: [Node { value: 2, children: [] }] };
+
+assert small == 55;
+assert deep == 20100;
+assert long == 1048576;
+assert tree.children[0].value == 2;
+";
+    write_string(&lib, source, WriteMode::CreateOrTruncate).unwrap();
+
+    // It also runs the assertions.
+    init_workers_and_compile(
+        String::from("const_eval_fallback/src"),
+        StoreIrAt::IntermediateDir,
+        Backend::Bytecode,
+        String::from("const_eval_fallback/target/"),
+        OptimizeLevel::None,
+        &HashMap::new(),
+        false,  // emit-irs
+        false,  // dump-post-mir-log
+        false,  // dump-timings
+        false,  // dump-bytecodes
+        false,  // check-const-asserts
+        0,  // graceful-shutdown
+        8,  // jobs
+        ColorWhen::Never,
+        MessageFormat::Human,
+        false,  // incremental-compilation
+        ValidateTokenSpans::Never,
+        false,  // verify-built-ins
+        Some(Profile::Test),
+        true,  // quiet
+    ).unwrap();
+
+    // An embedded `let` doesn't have `InitOrJump`.
+    let executable = load_executable(StoreIrAt::IntermediateDir, "const_eval_fallback/target/").unwrap();
+    let lazy_lets = executable.bytecodes.iter().filter_map(
+        |bytecode| match bytecode {
+            Bytecode::InitOrJump { def_span, .. } => {
+                let (offset, length) = def_span.get_offset_and_length()?;
+                source.get(offset as usize..(offset + length) as usize)
+            },
+            _ => None,
+        }
+    ).collect::<Vec<_>>();
+
+    assert!(!lazy_lets.contains(&"small"));
+    assert!(lazy_lets.contains(&"deep"));
+    assert!(lazy_lets.contains(&"long"));
+    assert!(lazy_lets.contains(&"tree"));
+
+    remove_dir_all("const_eval_fallback").unwrap();
+}
+
+// `--release` rewrites list-building recursions with an accumulator. Without the rewrite,
+// the recursion is too deep for the interpreter.
+#[test]
+fn accumulator_rewrite() {
+    if exists("accumulator_rewrite") {
+        remove_dir_all("accumulator_rewrite").unwrap();
+    }
+
+    init_project("accumulator_rewrite").unwrap();
+    write_string(
+        &join("accumulator_rewrite", "src/lib.sdg").unwrap(),
+        "fn range(n: Int) -> [Int] = if n == 0 { [] } else { n +> range(n - 1) };
+fn rev(ns: [Int]) -> [Int] = match ns {
+    [] => [],
+    [$n] ++ $rest => rev(rest) <+ n,
+};
+
+// It cannot be rewritten, because it both prepends and appends.
+fn zigzag(ns: [Int]) -> [Int] = match ns {
+    [] => [],
+    [$n] ++ $rest => if n % 2 == 0 { n +> zigzag(rest) } else { zigzag(rest) <+ n },
+};
+
+assert range(100000).len() == 100000;
+assert rev(range(100000))[0] == 1;
+assert zigzag([1, 2, 3, 4]) == [2, 4, 3, 1];
+",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+
+    // It also runs the assertions.
+    init_workers_and_compile(
+        String::from("accumulator_rewrite/src"),
+        StoreIrAt::IntermediateDir,
+        Backend::Bytecode,
+        String::from("accumulator_rewrite/target/"),
+        OptimizeLevel::Mild,
+        &HashMap::new(),
+        true,  // emit-irs
+        false,  // dump-post-mir-log
+        false,  // dump-timings
+        false,  // dump-bytecodes
+        false,  // check-const-asserts
+        0,  // graceful-shutdown
+        8,  // jobs
+        ColorWhen::Never,
+        MessageFormat::Human,
+        false,  // incremental-compilation
+        ValidateTokenSpans::Never,
+        false,  // verify-built-ins
+        Some(Profile::Test),
+        true,  // quiet
+    ).unwrap();
+
+    let log = read_string("accumulator_rewrite/target/irs/miroptimize/log").unwrap();
+    assert!(log.contains("# Accumulator `range`"));
+    assert!(log.contains("# Accumulator `rev`"));
+    assert!(!log.contains("# Accumulator `zigzag`"));
+
+    remove_dir_all("accumulator_rewrite").unwrap();
+}
+
+// It runs the language server with a scripted client.
+#[test]
+fn language_server() {
+    if exists("language_server") {
+        remove_dir_all("language_server").unwrap();
+    }
+
+    init_project("language_server").unwrap();
+    let root = into_abs_path("language_server").unwrap();
+    let lib = join(&root, "src/lib.sdg").unwrap();
+    let lib_uri = format!("file://{lib}");
+    let text_document = format!("{{\"textDocument\":{{\"uri\":\"{lib_uri}\"}}}}");
+
+    let (client_reader, mut server_writer) = std::io::pipe().unwrap();
+    let (server_reader, mut client_writer) = std::io::pipe().unwrap();
+    let mut client_reader = BufReader::new(client_reader);
+    let server = std::thread::spawn(move || run_language_server(BufReader::new(server_reader), &mut server_writer, 2));
+
+    lsp_request(&mut client_writer, Some(1), "initialize", &format!("{{\"rootUri\":\"file://{root}\"}}"));
+    let response = lsp_response(&mut client_reader);
+    assert!(response.get_path(&["result", "capabilities", "hoverProvider"]).is_some());
+    lsp_request(&mut client_writer, None, "initialized", "{}");
+
+    // `z` is not defined, and `b` is not used
+    write_string(&lib, "fn add(a: Int, b: Int) -> Int = a + z;\n", WriteMode::CreateOrTruncate).unwrap();
+    lsp_request(&mut client_writer, None, "textDocument/didOpen", &text_document);
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get("method").unwrap().as_str(), Some("textDocument/publishDiagnostics"));
+    assert_eq!(response.get_path(&["params", "uri"]).unwrap().as_str(), Some(lib_uri.as_str()));
+    let Some(Json::Array(diagnostics)) = response.get_path(&["params", "diagnostics"]) else { panic!() };
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].get("severity").unwrap().as_usize(), Some(1));
+    assert_eq!(diagnostics[0].get("code").unwrap().as_str(), Some("e-0330"));
+    assert_eq!(diagnostics[0].get_path(&["range", "start", "line"]).unwrap().as_usize(), Some(0));
+    assert_eq!(diagnostics[0].get_path(&["range", "start", "character"]).unwrap().as_usize(), Some(36));
+    assert_eq!(diagnostics[1].get("severity").unwrap().as_usize(), Some(2));
+    assert_eq!(diagnostics[1].get_path(&["range", "start", "character"]).unwrap().as_usize(), Some(15));
+
+    // The suggestion of the unused-name warning is a code action.
+    lsp_request(&mut client_writer, Some(7), "textDocument/codeAction", &format!("{{\"textDocument\":{{\"uri\":\"{lib_uri}\"}},\"range\":{{\"start\":{{\"line\":0,\"character\":15}},\"end\":{{\"line\":0,\"character\":16}}}}}}"));
+    let response = lsp_response(&mut client_reader);
+    let Some(Json::Array(actions)) = response.get("result") else { panic!() };
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].get("title").unwrap().as_str(), Some("Rename `b` to `_`."));
+    let Some(Json::Array(edits)) = actions[0].get_path(&["edit", "changes", &lib_uri]) else { panic!() };
+    assert_eq!(edits[0].get("newText").unwrap().as_str(), Some("_"));
+    assert_eq!(edits[0].get_path(&["range", "start", "character"]).unwrap().as_usize(), Some(15));
+
+    // The diagnostics have to be cleared.
+    write_string(
+        &lib,
+        "fn add(a: Int, b: Int) -> Int = a + b;\n\nstruct Point = { x: Int, y: Int };\n\nlet three = add(1, 2);\n",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    lsp_request(&mut client_writer, None, "textDocument/didSave", &text_document);
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get_path(&["params", "uri"]).unwrap().as_str(), Some(lib_uri.as_str()));
+    assert_eq!(response.get_path(&["params", "diagnostics"]), Some(&Json::Array(vec![])));
+
+    // `add` in `add(1, 2)`
+    lsp_request(&mut client_writer, Some(2), "textDocument/definition", &format!("{{\"textDocument\":{{\"uri\":\"{lib_uri}\"}},\"position\":{{\"line\":4,\"character\":13}}}}"));
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get_path(&["result", "uri"]).unwrap().as_str(), Some(lib_uri.as_str()));
+    assert_eq!(response.get_path(&["result", "range", "start", "line"]).unwrap().as_usize(), Some(0));
+    assert_eq!(response.get_path(&["result", "range", "start", "character"]).unwrap().as_usize(), Some(3));
+
+    // `three`
+    lsp_request(&mut client_writer, Some(3), "textDocument/hover", &format!("{{\"textDocument\":{{\"uri\":\"{lib_uri}\"}},\"position\":{{\"line\":4,\"character\":5}}}}"));
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get_path(&["result", "contents", "value"]).unwrap().as_str(), Some("```sodigy\nthree: Int\n```"));
+
+    lsp_request(&mut client_writer, Some(4), "textDocument/documentSymbol", &text_document);
+    let response = lsp_response(&mut client_reader);
+    let Some(Json::Array(symbols)) = response.get("result") else { panic!() };
+    let symbols = symbols.iter().map(|symbol| symbol.get("name").unwrap().as_str().unwrap()).collect::<Vec<_>>();
+    assert_eq!(symbols, vec!["add", "Point", "three"]);
+
+    lsp_request(&mut client_writer, Some(5), "textDocument/formatting", "{}");
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get_path(&["error", "code"]), Some(&Json::Number(-32601.0)));
+
+    lsp_request(&mut client_writer, Some(6), "shutdown", "null");
+    let response = lsp_response(&mut client_reader);
+    assert_eq!(response.get("id").unwrap().as_usize(), Some(6));
+    assert_eq!(response.get("result"), Some(&Json::Null));
+
+    lsp_request(&mut client_writer, None, "exit", "null");
+    server.join().unwrap().unwrap();
+
+    remove_dir_all("language_server").unwrap();
+}
+
+#[test]
+fn format_source_files() {
+    if exists("format_source_files") {
+        remove_dir_all("format_source_files").unwrap();
+    }
+
+    init_project("format_source_files").unwrap();
+    let src_dir = join("format_source_files", "src").unwrap();
+    let ir_dir = join("format_source_files", "target").unwrap();
+    let lib = join(&src_dir, "lib.sdg").unwrap();
+    write_string(&lib, "fn add(a:Int,b:Int)->Int=a+b;\n", WriteMode::CreateOrTruncate).unwrap();
+
+    // `--check` doesn't modify the file.
+    assert!(matches!(format_files(&[], true, &src_dir, &ir_dir), Err(Error::NotFormatted)));
+    assert_eq!(read_string(&lib).unwrap(), "fn add(a:Int,b:Int)->Int=a+b;\n");
+
+    format_files(&[], false, &src_dir, &ir_dir).unwrap();
+    assert_eq!(read_string(&lib).unwrap(), "fn add(a: Int, b: Int) -> Int = a + b;\n");
+    format_files(&[], true, &src_dir, &ir_dir).unwrap();
+
+    // It doesn't format a file with a syntax error.
+    write_string(&lib, "fn add(a:Int,b:Int)->Int=a+;\n", WriteMode::CreateOrTruncate).unwrap();
+    assert!(matches!(format_files(&[lib.to_string()], false, &src_dir, &ir_dir), Err(Error::CompileError)));
+    assert_eq!(read_string(&lib).unwrap(), "fn add(a:Int,b:Int)->Int=a+;\n");
+
+    remove_dir_all("format_source_files").unwrap();
+}
+
+#[test]
+fn generate_documents() {
+    if exists("generate_documents") {
+        remove_dir_all("generate_documents").unwrap();
+    }
+
+    init_project("generate_documents").unwrap();
+    let src_dir = join("generate_documents", "src").unwrap();
+    let ir_dir = join("generate_documents", "target").unwrap();
+    let doc_dir = join("generate_documents", "doc").unwrap();
+    write_string(
+        &join(&src_dir, "lib.sdg").unwrap(),
+        "//! Shapes and greetings.
+
+mod shapes;
+
+use shapes.Point;
+
+/// Greets anything.
+#[poly]
+pub fn greet<T>(x: T) -> String;
+
+/// The origin of `Point`.
+pub let origin = Point { x: 0, y: 0 };
+
+fn private_helper() -> Int = 0;
+
+#[impl(greet)]
+pub fn greet_point(p: Point) -> String = \"Hello, point!\";
+
+fn main() = greet(origin);
+",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    write_string(
+        &join(&src_dir, "shapes.sdg").unwrap(),
+        "/// A point in 2D.
+pub struct Point = {
+    /// x coordinate
+    x: Int,
+    y: Int,
+};
+
+/// Manhattan distance from the origin.
+#[associate(Point)]
+fn norm(self) -> Int = self.x + self.y;
+",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+
+    generate_docs(src_dir.to_string(), ir_dir.to_string(), &doc_dir, DocFormat::Html, false, false, 2).unwrap();
+    let lib = read_string(&join(&doc_dir, "lib.html").unwrap()).unwrap();
+    let shapes = read_string(&join(&doc_dir, "lib.shapes.html").unwrap()).unwrap();
+
+    // inferred type of an unannotated `let`
+    assert!(lib.contains(r#"let origin: <a href="lib.shapes.html#struct.Point">Point</a>"#));
+    assert!(lib.contains("<p>Shapes and greetings.</p>"));
+    assert!(lib.contains(r#"<li><a href="lib.html#fn.greet_point"><code>greet_point</code></a></li>"#));
+    assert!(lib.contains(r#"Implements <code>#[poly]</code> <a href="lib.html#fn.greet"><code>greet</code></a>"#));
+    assert!(!lib.contains("private_helper"));
+    assert!(shapes.contains(r#"id="Point.fn.norm""#));
+    assert!(shapes.contains("<li><code>x: Int</code><p>x coordinate</p></li>"));
+
+    generate_docs(src_dir.to_string(), ir_dir.to_string(), &doc_dir, DocFormat::Markdown, false, true, 2).unwrap();
+    let lib = read_string(&join(&doc_dir, "lib.md").unwrap()).unwrap();
+    assert!(lib.contains("private_helper"));
+    assert!(lib.contains("let origin: Point"));
+
+    remove_dir_all("generate_documents").unwrap();
+}
+
+#[test]
+fn doc_tests() {
+    if exists("doc_tests") {
+        remove_dir_all("doc_tests").unwrap();
+    }
+
+    init_project("doc_tests").unwrap();
+    let src_dir = join("doc_tests", "src").unwrap();
+    let ir_dir = join("doc_tests", "target").unwrap();
+    let lib = join(&src_dir, "lib.sdg").unwrap();
+    write_string(
+        &lib,
+        "mod math;
+",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+
+    // `helper` is private, but the code blocks can use it.
+    let math = "fn helper(x: Int) -> Int = x + 1;
+
+/// It adds 1.
+///
+/// ```sodigy
+/// assert add1(1) == 2;
+/// assert helper(1) == 2;
+/// ```
+///
+/// ```sodigy, compile_error
+/// let x: String = add1(1);
+/// ```
+///
+/// ```sodigy, run_error
+/// assert add1(1) == 3;
+/// ```
+///
+/// ```text
+/// It's not tested.
+/// ```
+pub fn add1(x: Int) -> Int = helper(x);
+";
+    write_string(&join(&src_dir, "math.sdg").unwrap(), math, WriteMode::CreateOrTruncate).unwrap();
+    run_doc_tests(src_dir.to_string(), ir_dir.to_string(), false, 2, ColorWhen::Never, MessageFormat::Human).unwrap();
+
+    write_string(
+        &join(&src_dir, "math.sdg").unwrap(),
+        &math.replace("sodigy, run_error", "sodigy"),
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    assert!(matches!(
+        run_doc_tests(src_dir.to_string(), ir_dir.to_string(), false, 2, ColorWhen::Never, MessageFormat::Human),
+        Err(Error::RuntimeError),
+    ));
+
+    // Only the assertions in the code block are tested, not the ones in the project.
+    write_string(
+        &join(&src_dir, "math.sdg").unwrap(),
+        &format!("{math}\nfn triple(x: Int) -> Int = x * 3;\nassert triple(1) == 4;\n"),
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    run_doc_tests(src_dir.to_string(), ir_dir.to_string(), false, 2, ColorWhen::Never, MessageFormat::Human).unwrap();
+
+    write_string(
+        &join(&src_dir, "math.sdg").unwrap(),
+        &format!("{}\nfn triple(x: Int) -> Int = x * 3;\nassert triple(1) == 4;\n", math.replace("add1(1) == 3", "add1(1) == 2")),
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    assert!(matches!(
+        run_doc_tests(src_dir.to_string(), ir_dir.to_string(), false, 2, ColorWhen::Never, MessageFormat::Human),
+        Err(Error::RuntimeError),
+    ));
+
+    remove_dir_all("doc_tests").unwrap();
+}
+
+#[test]
+fn apply_fixes() {
+    let file = File(0);
+    let source = "#[memoize]\n#[memoize]\nfn f(x: Int) -> Int = { let y = 3; x };\n";
+    let suggestion = |offset, length, replacement: &str| Suggestion::replace(
+        Span::range(file, offset, length),
+        replacement.to_string(),
+        Applicability::MachineApplicable,
+        String::new(),
+    );
+
+    // The removal leaves an empty line, so the line is removed too.
+    assert_eq!(
+        apply_suggestions(source, &[suggestion(11, 10, ""), suggestion(50, 1, "_")]),
+        (String::from("#[memoize]\nfn f(x: Int) -> Int = { let _ = 3; x };\n"), 2),
+    );
+
+    // duplicate suggestions
+    assert_eq!(
+        apply_suggestions(source, &[suggestion(50, 1, "_"), suggestion(50, 1, "_")]),
+        (String::from("#[memoize]\n#[memoize]\nfn f(x: Int) -> Int = { let _ = 3; x };\n"), 1),
+    );
+
+    // overlapping suggestions: only the last one is applied
+    assert_eq!(
+        apply_suggestions(source, &[suggestion(22, 4, "proc"), suggestion(25, 1, "g")]),
+        (String::from("#[memoize]\n#[memoize]\nfn g(x: Int) -> Int = { let y = 3; x };\n"), 1),
+    );
+
+    // insertion
+    assert_eq!(
+        apply_suggestions(source, &[suggestion(22, 0, "ndet ")]),
+        (String::from("#[memoize]\n#[memoize]\nndet fn f(x: Int) -> Int = { let y = 3; x };\n"), 1),
+    );
+}
+
+#[test]
+fn fix_source_files() {
+    if exists("fix_source_files") {
+        remove_dir_all("fix_source_files").unwrap();
+    }
+
+    init_project("fix_source_files").unwrap();
+    let src_dir = join("fix_source_files", "src").unwrap();
+    let ir_dir = join("fix_source_files", "target").unwrap();
+    let lib = join(&src_dir, "lib.sdg").unwrap();
+
+    // 1. The redundant decorator is an error, so the compiler stops before it finds the impure call.
+    // 2. `sodigy fix` removes the decorator and compiles the project again, and fixes the impure call.
+    write_string(
+        &lib,
+        "#[memoize]
+#[memoize]
+fn double(x: Int) -> Int = {
+    let unused = 3;
+    x * 2
+};
+
+proc hello() = std.io.print(\"hello\");
+fn greet() = hello();
+",
+        WriteMode::CreateOrTruncate,
+    ).unwrap();
+    fix_project(src_dir.to_string(), ir_dir.to_string(), 2, ColorWhen::Never).unwrap();
+    assert_eq!(
+        read_string(&lib).unwrap(),
+        "#[memoize]
+fn double(x: Int) -> Int = {
+    let _ = 3;
+    x * 2
+};
+
+proc hello() = std.io.print(\"hello\");
+proc greet() = hello();
+",
+    );
+
+    // `sodigy fix` doesn't apply the suggestions that are not machine-applicable.
+    let code = "fn first(x: Option<Int>) -> Int = match x {
+    Some(y) => 1,
+    None => 0,
+};
+";
+    write_string(&lib, code, WriteMode::CreateOrTruncate).unwrap();
+    assert!(matches!(
+        fix_project(src_dir.to_string(), ir_dir.to_string(), 2, ColorWhen::Never),
+        Err(Error::CompileError),
+    ));
+    assert_eq!(read_string(&lib).unwrap(), code);
+
+    remove_dir_all("fix_source_files").unwrap();
+}
+
+// Every example in the extended explanations of the error kinds must do what it says:
+// ```` ```sodigy, compile_error ```` and ```` ```sodigy, warning ```` examples produce the
+// error kind, and ```` ```sodigy ```` examples compile without the error kind.
+#[test]
+fn error_explanations() {
+    let root = "error_explanations";
+
+    if exists(root) {
+        remove_dir_all(root).unwrap();
+    }
+
+    create_dir_all(root).unwrap();
+
+    for doc in ERROR_KIND_DOCS.iter() {
+        let Some(explanation) = doc.explanation else { continue };
+        let blocks = code_blocks(&explanation.lines().map(|line| format!(" {line}")).collect::<Vec<_>>().iter().map(|line| line.as_str()).collect::<Vec<_>>());
+        let mut has_failing_example = false;
+        let mut has_fixed_example = false;
+
+        for (i, block) in blocks.iter().enumerate() {
+            assert_eq!(block.attributes.first().map(|a| a.as_str()), Some("sodigy"), "{}: {block:?}", doc.name);
+            let expect_error = block.attributes.iter().any(|a| a == "compile_error");
+            let expect_warning = block.attributes.iter().any(|a| a == "warning");
+            let project = join(root, &format!("{}-{i}", doc.code())).unwrap();
+            init_project(&project).unwrap();
+            let src_dir = join(&project, "src").unwrap();
+            let ir_dir = join(&project, "target").unwrap();
+            write_string(&join(&src_dir, "lib.sdg").unwrap(), &block.code, WriteMode::CreateOrTruncate).unwrap();
+
+            let (errors, warnings) = check_project(src_dir, ir_dir.to_string(), 2, &mut HashMap::new());
+
+            // std has warnings, too.
+            let lib = File::from_module_path("lib", &ir_dir).unwrap();
+            let warnings = warnings.into_iter().filter(
+                |w| w.spans.iter().any(|span| span.span.file() == lib)
+            ).collect::<Vec<_>>();
+            let has_error = errors.iter().any(|e| e.kind.index() == doc.index);
+            let has_warning = warnings.iter().any(|w| w.kind.index() == doc.index);
+            let got = errors.iter().map(|e| e.kind.name()).chain(warnings.iter().map(|w| w.kind.name())).collect::<Vec<_>>();
+
+            if expect_error {
+                assert!(has_error, "{}: the example doesn't produce the error, but {got:?}\n{}", doc.name, block.code);
+                has_failing_example = true;
+            }
+
+            else if expect_warning {
+                assert!(errors.is_empty() && has_warning, "{}: the example doesn't produce the warning, but {got:?}\n{}", doc.name, block.code);
+                has_failing_example = true;
+            }
+
+            else {
+                assert!(errors.is_empty() && !has_warning, "{}: the fixed example doesn't compile cleanly: {got:?}\n{}", doc.name, block.code);
+                has_fixed_example = true;
+            }
+        }
+
+        assert!(has_failing_example && has_fixed_example, "{}: an explanation needs a failing example and a fixed example", doc.name);
+    }
+
+    remove_dir_all(root).unwrap();
+
+    let doc = ErrorKindDoc::find("455").unwrap();
+    assert_eq!(doc.name, "NonExhaustiveArms");
+    assert_eq!(ErrorKindDoc::find("e-0455").unwrap().name, "NonExhaustiveArms");
+    assert_eq!(ErrorKindDoc::find("NonExhaustiveArms").unwrap().index, 455);
+    assert_eq!(ErrorKindDoc::find("w-5000").unwrap().code(), "w-5000");
+    assert!(ErrorKindDoc::find("NoSuchError").is_none());
+}
+
+fn lsp_request<W: Write>(writer: &mut W, id: Option<usize>, method: &str, params: &str) {
+    let id = id.map(|id| format!("\"id\":{id},")).unwrap_or_default();
+    let message = format!("{{\"jsonrpc\":\"2.0\",{id}\"method\":\"{method}\",\"params\":{params}}}");
+    write!(writer, "Content-Length: {}\r\n\r\n{message}", message.len()).unwrap();
+    writer.flush().unwrap();
+}
+
+fn lsp_response<R: BufRead>(reader: &mut R) -> Json {
+    let mut header = String::new();
+    reader.read_line(&mut header).unwrap();
+    let length = header.trim().strip_prefix("Content-Length: ").unwrap().parse::<usize>().unwrap();
+    reader.read_line(&mut header).unwrap();
+
+    let mut buffer = vec![0; length];
+    reader.read_exact(&mut buffer).unwrap();
+    json::parse(&buffer).unwrap()
+}
@@ -17,6 +17,7 @@ use sodigy_file::{File, FileOrStd, ModulePath};
 use sodigy_fs_api::{WriteMode, join3, write_bytes, write_string};
 use sodigy_hir as hir;
 use sodigy_mir::{self as mir, GlobalContext as MirGlobalContext};
+use sodigy_optimize::{MirOptimizeLog, OptimizeLevel};
 use sodigy_post_mir::MatchDump;
 use sodigy_span::Span;
 use std::sync::{Arc, RwLock, mpsc};
@@ -54,6 +55,7 @@ pub enum MessageToMain {
         entries: Vec<TimingsEntry>,
     },
     PostMirLog(Vec<MatchDump>),
+    MirOptimizeLog(MirOptimizeLog),
     Error(Error),
 }
 
@@ -223,6 +225,30 @@ impl Worker {
                 };
                 let content_hash = file.get_content_hash(&intermediate_dir)?;
 
+                // If optimization is enabled, the modules stop after post-mir and wait for
+                // the other modules so that the optimizer can see functions in every module.
+                // Then they continue from the cached post-mir session.
+                if optimize_level != OptimizeLevel::None && stop_after >= CompileStage::MirOptimize && let Some(post_mir_session_bytes) = get_cached_ir(
+                    &intermediate_dir,
+                    CompileStage::PostMir,
+                    Some(content_hash),
+                )? {
+                    let mut mir_session = mir::Session::decode(&post_mir_session_bytes)?;
+                    mir_session.intermediate_dir = intermediate_dir.clone();
+                    mir_session.global_context = global_context.mir_global_context();
+
+                    return self.optimize_and_lower_mir(
+                        mir_session,
+                        input_module_path,
+                        optimize_level,
+                        &intermediate_dir,
+                        &emit_ir_options,
+                        stop_after,
+                        content_hash,
+                        tx_to_main,
+                    );
+                }
+
                 let mut mir_session = if stop_after >= CompileStage::Mir && let Some(mir_session_bytes) = get_cached_ir(
                     &intermediate_dir,
                     CompileStage::Mir,
@@ -417,6 +443,15 @@ impl Worker {
                 )?;
 
                 if !mir_session.errors.is_empty() || stop_after <= CompileStage::PostMir {
+                    // The optimizer reads this map after every module's post-mir is complete.
+                    if let Some(funcs) = &global_context.funcs {
+                        let mut funcs = funcs.write().expect("global context poisoned");
+
+                        for func in mir_session.funcs.iter() {
+                            funcs.insert(func.name_span.clone(), func.clone());
+                        }
+                    }
+
                     tx_to_main.send(MessageToMain::StageComplete {
                         module_path: Some(input_module_path),
                         compile_stage: CompileStage::PostMir,
@@ -427,71 +462,16 @@ impl Worker {
                     return compile_error_if_not_empty(&mir_session.errors);
                 }
 
-                self.stage_start(CompileStage::MirOptimize, None, Some(input_module_path.to_string()));
-                let optimized_mir_session = sodigy_optimize::optimize_mir(mir_session, optimize_level);
-                self.stage_end(!optimized_mir_session.errors.is_empty());
-
-                emit_irs_if_has_to(
-                    &optimized_mir_session,
-                    &emit_ir_options,
-                    CompileStage::MirOptimize,
-                    Some(content_hash),
+                self.optimize_and_lower_mir(
+                    mir_session,
+                    input_module_path,
+                    optimize_level,
                     &intermediate_dir,
-                )?;
-
-                if !optimized_mir_session.errors.is_empty() || stop_after <= CompileStage::MirOptimize {
-                    tx_to_main.send(MessageToMain::StageComplete {
-                        module_path: Some(input_module_path),
-                        compile_stage: CompileStage::MirOptimize,
-                        errors: optimized_mir_session.errors.clone(),
-                        warnings: optimized_mir_session.warnings.clone(),
-                    })?;
-
-                    return compile_error_if_not_empty(&optimized_mir_session.errors);
-                }
-
-                self.stage_start(CompileStage::Bytecode, None, Some(input_module_path.to_string()));
-                let bytecode_session = sodigy_bytecode::lower(optimized_mir_session);
-                self.stage_end(!bytecode_session.errors.is_empty());
-
-                emit_irs_if_has_to(
-                    &bytecode_session,
                     &emit_ir_options,
-                    CompileStage::Bytecode,
-                    Some(content_hash),
-                    &intermediate_dir,
+                    stop_after,
+                    content_hash,
+                    tx_to_main,
                 )?;
-
-                if !bytecode_session.errors.is_empty() || stop_after <= CompileStage::Bytecode {
-                    tx_to_main.send(MessageToMain::StageComplete {
-                        module_path: Some(input_module_path),
-                        compile_stage: CompileStage::Bytecode,
-                        errors: bytecode_session.errors.clone(),
-                        warnings: bytecode_session.warnings.clone(),
-                    })?;
-
-                    return compile_error_if_not_empty(&bytecode_session.errors);
-                }
-
-                self.stage_start(CompileStage::BytecodeOptimize, None, Some(input_module_path.to_string()));
-                let optimized_bytecode_session = sodigy_optimize::optimize_bytecode(bytecode_session, optimize_level);
-                self.stage_end(!optimized_bytecode_session.errors.is_empty());
-
-                emit_irs_if_has_to(
-                    &optimized_bytecode_session,
-                    &emit_ir_options,
-                    CompileStage::BytecodeOptimize,
-                    Some(content_hash),
-                    &intermediate_dir,
-                )?;
-
-                // bytecode optimizer doesn't emit any warning/error, and this must be the last stage!
-                tx_to_main.send(MessageToMain::StageComplete {
-                    module_path: Some(input_module_path),
-                    compile_stage: CompileStage::BytecodeOptimize,
-                    errors: optimized_bytecode_session.errors.clone(),
-                    warnings: optimized_bytecode_session.warnings.clone(),
-                })?;
             },
             Command::InterHir {
                 modules,
@@ -692,6 +672,7 @@ impl Worker {
                 intermediate_dir,
                 backend,
                 dump_bytecodes,
+                check_const_asserts,
                 output_path,
             } => {
                 self.stage_start(CompileStage::CodeGen, Some("load-bytecode-modules"), None);
@@ -734,29 +715,32 @@ impl Worker {
                 }
 
                 self.stage_start(CompileStage::CodeGen, Some("code-gen"), None);
-                let (code, errors, warnings) = sodigy_code_gen::lower(bytecode_session, backend);
-                self.stage_end(!errors.is_empty());
-
-                match output_path {
-                    StoreIrAt::File(f) => {
-                        write_bytes(&f, &code.encode(), WriteMode::CreateOrTruncate)?;
-                    },
-                    StoreIrAt::IntermediateDir => {
-                        emit_irs_if_has_to(
-                            &code,
-                            &[EmitIrOption {
-                                stage: CompileStage::CodeGen,
-                                store: StoreIrAt::IntermediateDir,
-                                human_readable: false,
-                            }],
-                            CompileStage::CodeGen,
-                            None,
-                            &intermediate_dir,
-                        )?;
-                    },
+                let (code, errors, warnings) = sodigy_code_gen::lower(bytecode_session, backend, check_const_asserts);
+                let has_error = !errors.is_empty();
+                self.stage_end(has_error);
+
+                // e.g. an assertion fails at compile time (`--check-const-asserts`)
+                if !has_error {
+                    match output_path {
+                        StoreIrAt::File(f) => {
+                            write_bytes(&f, &code.encode(), WriteMode::CreateOrTruncate)?;
+                        },
+                        StoreIrAt::IntermediateDir => {
+                            emit_irs_if_has_to(
+                                &code,
+                                &[EmitIrOption {
+                                    stage: CompileStage::CodeGen,
+                                    store: StoreIrAt::IntermediateDir,
+                                    human_readable: false,
+                                }],
+                                CompileStage::CodeGen,
+                                None,
+                                &intermediate_dir,
+                            )?;
+                        },
+                    }
                 }
 
-                let has_error = !errors.is_empty();
                 tx_to_main.send(MessageToMain::StageComplete {
                     module_path: None,
                     compile_stage: CompileStage::CodeGen,
@@ -777,7 +761,7 @@ impl Worker {
                 let inter_hir_session = sodigy_inter_hir::Session::decode(&inter_hir_session_bytes)?;
                 global_context.inter_hir_session = Some(inter_hir_session);
             },
-            Command::LoadMirGlobalContext { intermediate_dir } => {
+            Command::LoadMirGlobalContext { intermediate_dir, funcs } => {
                 let inter_mir_session_bytes = get_cached_ir(
                     &intermediate_dir,
                     CompileStage::InterMir,
@@ -785,12 +769,101 @@ impl Worker {
                 )?.ok_or(Error::IrCacheNotFound(CompileStage::InterMir))?;
                 let inter_mir_session = sodigy_inter_mir::Session::decode(&inter_mir_session_bytes)?;
                 global_context.types = Some(Arc::new(RwLock::new(inter_mir_session.types.clone())));
+                global_context.funcs = funcs;
                 global_context.inter_mir_session = Some(inter_mir_session);
             },
         }
 
         Ok(())
     }
+
+    // MirOptimize -> Bytecode -> BytecodeOptimize
+    fn optimize_and_lower_mir(
+        &mut self,
+        mir_session: mir::Session,
+        input_module_path: ModulePath,
+        optimize_level: OptimizeLevel,
+        intermediate_dir: &str,
+        emit_ir_options: &[EmitIrOption],
+        stop_after: CompileStage,
+        content_hash: u128,
+        tx_to_main: mpsc::Sender<MessageToMain>,
+    ) -> Result<(), Error> {
+        self.stage_start(CompileStage::MirOptimize, None, Some(input_module_path.to_string()));
+        let dump_optimize_log = emit_ir_options.iter().any(
+            |option| option.stage == CompileStage::MirOptimize && option.human_readable
+        );
+        let (optimized_mir_session, optimize_log) = sodigy_optimize::optimize_mir(mir_session, optimize_level, dump_optimize_log);
+        self.stage_end(!optimized_mir_session.errors.is_empty());
+
+        if let Some(optimize_log) = optimize_log {
+            tx_to_main.send(MessageToMain::MirOptimizeLog(optimize_log))?;
+        }
+
+        emit_irs_if_has_to(
+            &optimized_mir_session,
+            emit_ir_options,
+            CompileStage::MirOptimize,
+            Some(content_hash),
+            intermediate_dir,
+        )?;
+
+        if !optimized_mir_session.errors.is_empty() || stop_after <= CompileStage::MirOptimize {
+            tx_to_main.send(MessageToMain::StageComplete {
+                module_path: Some(input_module_path),
+                compile_stage: CompileStage::MirOptimize,
+                errors: optimized_mir_session.errors.clone(),
+                warnings: optimized_mir_session.warnings.clone(),
+            })?;
+
+            return compile_error_if_not_empty(&optimized_mir_session.errors);
+        }
+
+        self.stage_start(CompileStage::Bytecode, None, Some(input_module_path.to_string()));
+        let bytecode_session = sodigy_bytecode::lower(optimized_mir_session);
+        self.stage_end(!bytecode_session.errors.is_empty());
+
+        emit_irs_if_has_to(
+            &bytecode_session,
+            emit_ir_options,
+            CompileStage::Bytecode,
+            Some(content_hash),
+            intermediate_dir,
+        )?;
+
+        if !bytecode_session.errors.is_empty() || stop_after <= CompileStage::Bytecode {
+            tx_to_main.send(MessageToMain::StageComplete {
+                module_path: Some(input_module_path),
+                compile_stage: CompileStage::Bytecode,
+                errors: bytecode_session.errors.clone(),
+                warnings: bytecode_session.warnings.clone(),
+            })?;
+
+            return compile_error_if_not_empty(&bytecode_session.errors);
+        }
+
+        self.stage_start(CompileStage::BytecodeOptimize, None, Some(input_module_path.to_string()));
+        let optimized_bytecode_session = sodigy_optimize::optimize_bytecode(bytecode_session, optimize_level);
+        self.stage_end(!optimized_bytecode_session.errors.is_empty());
+
+        emit_irs_if_has_to(
+            &optimized_bytecode_session,
+            emit_ir_options,
+            CompileStage::BytecodeOptimize,
+            Some(content_hash),
+            intermediate_dir,
+        )?;
+
+        // bytecode optimizer doesn't emit any warning/error, and this must be the last stage!
+        tx_to_main.send(MessageToMain::StageComplete {
+            module_path: Some(input_module_path),
+            compile_stage: CompileStage::BytecodeOptimize,
+            errors: optimized_bytecode_session.errors.clone(),
+            warnings: optimized_bytecode_session.warnings.clone(),
+        })?;
+
+        Ok(())
+    }
 }
 
 fn compile_error_if_not_empty<E>(errors: &[E]) -> Result<(), Error> {
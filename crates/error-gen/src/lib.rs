@@ -24,6 +24,9 @@ struct ErrorKind {
     pub fields_parsed: EnumFields,
     pub index: u16,
     pub level: String,
+
+    // doc comments of the definition
+    pub explanation: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -147,10 +150,28 @@ pub fn error_kinds(tokens: TokenStream) -> TokenStream {
     let mut indexes = HashMap::new();
     let mut prev_index = 0;
 
+    // `/// foo` is `#[doc = " foo"]`.
+    let mut doc_lines: Vec<String> = vec![];
+
     for token in tokens {
         match state {
             ParseState::ExpectingDef => {
                 match &token {
+                    TokenTree::Punct(p) if p.as_char() == '#' => {
+                        continue;
+                    },
+                    TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket => match parse_doc_attribute(g.stream()) {
+                        Some(line) => {
+                            doc_lines.push(line);
+                            continue;
+                        },
+                        None => {
+                            return error_message(
+                                g.span(),
+                                String::from("Only doc comments are allowed here."),
+                            );
+                        },
+                    },
                     TokenTree::Group(g) if g.delimiter() == Delimiter::Parenthesis => {
                         let stream = g.stream();
 
@@ -163,7 +184,16 @@ pub fn error_kinds(tokens: TokenStream) -> TokenStream {
 
                         else {
                             match parse_definition(g.stream()) {
-                                Ok(def) => {
+                                Ok(mut def) => {
+                                    if !doc_lines.is_empty() {
+                                        def.explanation = Some(
+                                            doc_lines.iter().map(
+                                                |line| line.strip_prefix(' ').unwrap_or(line)
+                                            ).collect::<Vec<_>>().join("\n")
+                                        );
+                                        doc_lines.clear();
+                                    }
+
                                     if let Some(another_def) = indexes.insert(def.index, def.clone()) {
                                         return error_message(
                                             g.span(),
@@ -379,15 +409,65 @@ fn parse_definition(tokens: TokenStream) -> Result<ErrorKind, TokenStream> {
         fields_parsed: fields_parsed.unwrap(),
         index: index.unwrap(),
         level: level.unwrap(),
+        explanation: None,
     })
 }
 
+// `doc = " foo"` -> `Some(" foo")`
+fn parse_doc_attribute(tokens: TokenStream) -> Option<String> {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+    match &tokens[..] {
+        [
+            TokenTree::Ident(id),
+            TokenTree::Punct(p),
+            TokenTree::Literal(lit),
+        ] if id.to_string() == "doc" && p.as_char() == '=' => unquote(&lit.to_string()),
+        _ => None,
+    }
+}
+
+// It parses a string literal: `"a\"b"` or `r#"a"b"#`.
+fn unquote(s: &str) -> Option<String> {
+    if let Some(raw) = s.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..(raw.len() - hashes)];
+        return Some(raw.strip_prefix('"')?.strip_suffix('"')?.to_string());
+    }
+
+    let s = s.strip_prefix('"')?.strip_suffix('"')?;
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => { result.push('\n'); },
+            't' => { result.push('\t'); },
+            'r' => { result.push('\r'); },
+            '0' => { result.push('\0'); },
+            'u' => {
+                let hex = chars.by_ref().skip(1).take_while(|c| *c != '}').collect::<String>();
+                result.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            },
+            c => { result.push(c); },
+        }
+    }
+
+    Some(result)
+}
+
 fn render(definitions: Vec<ErrorKind>) -> TokenStream {
     let mut result = TokenStream::new();
     result.extend(render_enum_definition(&definitions));
     result.extend(render_enum_methods(&definitions));
     result.extend(render_error_level(&definitions));
     result.extend(render_error_kind_endec(&definitions));
+    result.extend(render_error_kind_docs(&definitions));
 
     // debug
     {
@@ -455,39 +535,7 @@ fn render_enum_definition(definitions: &[ErrorKind]) -> TokenStream {
 fn render_enum_methods(definitions: &[ErrorKind]) -> TokenStream {
     let index_match_arms = definitions.iter().map(
         |def| {
-            let mut arm = vec![
-                TokenTree::Ident(Ident::new("ErrorKind", Span::call_site())),
-                TokenTree::Punct(Punct::new(':', Spacing::Joint)),
-                TokenTree::Punct(Punct::new(':', Spacing::Alone)),
-                TokenTree::Ident(Ident::new(&def.name, Span::call_site())),
-            ];
-
-            match &def.fields_parsed {
-                EnumFields::None => {},
-                EnumFields::Tuple(t) => {
-                    let mut fields = vec![];
-
-                    for _ in t.iter() {
-                        fields.push(TokenTree::Ident(Ident::new("_", Span::call_site())));
-                        fields.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
-                    }
-
-                    arm.push(TokenTree::Group(Group::new(
-                        Delimiter::Parenthesis,
-                        fields.into_iter().collect(),
-                    )));
-                },
-                EnumFields::Struct(_) => {
-                    arm.push(TokenTree::Group(Group::new(
-                        Delimiter::Brace,
-                        vec![
-                            TokenTree::Punct(Punct::new('.', Spacing::Joint)),
-                            TokenTree::Punct(Punct::new('.', Spacing::Alone)),
-                        ].into_iter().collect(),
-                    )));
-                },
-            }
-
+            let mut arm = render_wildcard_pattern(def);
             arm.extend(vec![
                 TokenTree::Punct(Punct::new('=', Spacing::Joint)),
                 TokenTree::Punct(Punct::new('>', Spacing::Alone)),
@@ -497,6 +545,18 @@ fn render_enum_methods(definitions: &[ErrorKind]) -> TokenStream {
             arm
         }
     ).collect::<Vec<_>>().concat();
+    let name_match_arms = definitions.iter().map(
+        |def| {
+            let mut arm = render_wildcard_pattern(def);
+            arm.extend(vec![
+                TokenTree::Punct(Punct::new('=', Spacing::Joint)),
+                TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+                TokenTree::Literal(Literal::string(&def.name)),
+                TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+            ]);
+            arm
+        }
+    ).collect::<Vec<_>>().concat();
 
     vec![
         TokenTree::Ident(Ident::new("impl", Span::call_site())),
@@ -517,10 +577,66 @@ fn render_enum_methods(definitions: &[ErrorKind]) -> TokenStream {
                 TokenTree::Ident(Ident::new("self", Span::call_site())),
                 TokenTree::Group(Group::new(Delimiter::Brace, index_match_arms.into_iter().collect())),
             ].into_iter().collect())),
+            TokenTree::Ident(Ident::new("pub", Span::call_site())),
+            TokenTree::Ident(Ident::new("fn", Span::call_site())),
+            TokenTree::Ident(Ident::new("name", Span::call_site())),
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, vec![
+                TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+                TokenTree::Ident(Ident::new("self", Span::call_site())),
+            ].into_iter().collect())),
+            TokenTree::Punct(Punct::new('-', Spacing::Joint)),
+            TokenTree::Punct(Punct::new('>', Spacing::Alone)),
+            TokenTree::Punct(Punct::new('&', Spacing::Joint)),
+            TokenTree::Punct(Punct::new('\'', Spacing::Joint)),
+            TokenTree::Ident(Ident::new("static", Span::call_site())),
+            TokenTree::Ident(Ident::new("str", Span::call_site())),
+            TokenTree::Group(Group::new(Delimiter::Brace, vec![
+                TokenTree::Ident(Ident::new("match", Span::call_site())),
+                TokenTree::Ident(Ident::new("self", Span::call_site())),
+                TokenTree::Group(Group::new(Delimiter::Brace, name_match_arms.into_iter().collect())),
+            ].into_iter().collect())),
         ].into_iter().collect())),
     ].into_iter().collect()
 }
 
+// `ErrorKind::Foo`, `ErrorKind::Foo(_, _,)` or `ErrorKind::Foo { .. }`
+fn render_wildcard_pattern(def: &ErrorKind) -> Vec<TokenTree> {
+    let mut pattern = vec![
+        TokenTree::Ident(Ident::new("ErrorKind", Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new(&def.name, Span::call_site())),
+    ];
+
+    match &def.fields_parsed {
+        EnumFields::None => {},
+        EnumFields::Tuple(t) => {
+            let mut fields = vec![];
+
+            for _ in t.iter() {
+                fields.push(TokenTree::Ident(Ident::new("_", Span::call_site())));
+                fields.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+            }
+
+            pattern.push(TokenTree::Group(Group::new(
+                Delimiter::Parenthesis,
+                fields.into_iter().collect(),
+            )));
+        },
+        EnumFields::Struct(_) => {
+            pattern.push(TokenTree::Group(Group::new(
+                Delimiter::Brace,
+                vec![
+                    TokenTree::Punct(Punct::new('.', Spacing::Joint)),
+                    TokenTree::Punct(Punct::new('.', Spacing::Alone)),
+                ].into_iter().collect(),
+            )));
+        },
+    }
+
+    pattern
+}
+
 fn render_error_level(definitions: &[ErrorKind]) -> TokenStream {
     let arms = definitions.iter().map(
         |def| {
@@ -1007,6 +1123,67 @@ fn render_decode_impl_body(definitions: &[ErrorKind]) -> TokenStream {
     ].into_iter().collect()
 }
 
+// `pub const ERROR_KIND_DOCS: &[ErrorKindDoc] = &[ErrorKindDoc { name: "Foo", index: 0u16, level: ErrorLevel::Error, explanation: None }, ...];`
+fn render_error_kind_docs(definitions: &[ErrorKind]) -> TokenStream {
+    let docs = definitions.iter().map(
+        |def| {
+            let explanation = match &def.explanation {
+                Some(explanation) => vec![
+                    TokenTree::Ident(Ident::new("Some", Span::call_site())),
+                    TokenTree::Group(Group::new(
+                        Delimiter::Parenthesis,
+                        [TokenTree::Literal(Literal::string(explanation))].into_iter().collect(),
+                    )),
+                ],
+                None => vec![TokenTree::Ident(Ident::new("None", Span::call_site()))],
+            };
+
+            vec![
+                TokenTree::Ident(Ident::new("ErrorKindDoc", Span::call_site())),
+                TokenTree::Group(Group::new(Delimiter::Brace, [
+                    vec![
+                        TokenTree::Ident(Ident::new("name", Span::call_site())),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                        TokenTree::Literal(Literal::string(&def.name)),
+                        TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("index", Span::call_site())),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                        TokenTree::Literal(Literal::u16_suffixed(def.index)),
+                        TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("level", Span::call_site())),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("ErrorLevel", Span::call_site())),
+                        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new(&def.level, Span::call_site())),
+                        TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+                        TokenTree::Ident(Ident::new("explanation", Span::call_site())),
+                        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+                    ],
+                    explanation,
+                ].concat().into_iter().collect())),
+                TokenTree::Punct(Punct::new(',', Spacing::Alone)),
+            ]
+        }
+    ).collect::<Vec<_>>().concat();
+
+    vec![
+        TokenTree::Ident(Ident::new("pub", Span::call_site())),
+        TokenTree::Ident(Ident::new("const", Span::call_site())),
+        TokenTree::Ident(Ident::new("ERROR_KIND_DOCS", Span::call_site())),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Bracket,
+            [TokenTree::Ident(Ident::new("ErrorKindDoc", Span::call_site()))].into_iter().collect(),
+        )),
+        TokenTree::Punct(Punct::new('=', Spacing::Alone)),
+        TokenTree::Punct(Punct::new('&', Spacing::Alone)),
+        TokenTree::Group(Group::new(Delimiter::Bracket, docs.into_iter().collect())),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ].into_iter().collect()
+}
+
 fn turbo_fish(ts: &[TokenTree]) -> Vec<TokenTree> {
     let mut got_angle = false;
     let mut result = vec![];
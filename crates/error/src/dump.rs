@@ -1,4 +1,4 @@
-use crate::{Error, ErrorLevel};
+use crate::{Applicability, Error, ErrorLevel};
 use sodigy_span::{
     Color,
     ColorOption,
@@ -54,6 +54,11 @@ pub fn dump_errors(
     let mut truncated_errors = None;
     let total_warnings = warnings.len();
     let mut truncated_warnings = None;
+    let machine_applicable_suggestions = errors.iter().chain(warnings.iter()).map(
+        |e| e.suggestions.iter().filter(
+            |suggestion| suggestion.applicability == Applicability::MachineApplicable
+        ).count()
+    ).sum::<usize>();
 
     if let Some(n) = option.max_dump && errors.len() > n {
         truncated_errors = Some(errors.len() - n);
@@ -87,6 +92,9 @@ pub fn dump_errors(
         } else {
             String::new()
         };
+        let help = error.suggestions.iter().map(
+            |suggestion| format!("\nhelp: {}", suggestion.message)
+        ).collect::<Vec<_>>().concat();
         let rendered_span = format!("\n{}", render_spans(
             &error.spans,
             &RenderSpanOption {
@@ -105,7 +113,7 @@ pub fn dump_errors(
         ));
 
         stderr.push(format!(
-            "{colored_title}: {}{note}{rendered_span}",
+            "{colored_title}: {}{note}{help}{rendered_span}",
             error.kind.render(intermediate_dir),
         ));
     }
@@ -130,8 +138,16 @@ pub fn dump_errors(
             String::new()
         };
 
+        let fix_note = match machine_applicable_suggestions {
+            0 => String::new(),
+            n => format!(
+                "\nNote: Run `sodigy fix` to apply {n} suggestion{} automatically.",
+                if n == 1 { "" } else { "s" },
+            ),
+        };
+
         stderr.push(format!(
-            "Finished: {total_errors} error{} and {total_warnings} warning{}{}{error_truncation_note}{warning_truncation_note}\n",
+            "Finished: {total_errors} error{} and {total_warnings} warning{}{}{error_truncation_note}{warning_truncation_note}{fix_note}\n",
             if total_errors == 1 { "" } else { "s" },
             if total_warnings == 1 { "" } else { "s" },
             match elapsed_ms {
@@ -147,3 +163,108 @@ pub fn dump_errors(
 
     stderr.join(&option.delim)
 }
+
+/// It dumps errors and warnings in JSON Lines format: one json object per line.
+/// It's for the tools that consume the compiler output (CI bots, editor plugins, ...).
+///
+/// Unlike `dump_errors`, it doesn't truncate anything and doesn't dump a summary.
+/// Lines and columns are 1-based, and columns count bytes, not chars.
+/// `end` offsets are exclusive.
+#[must_use]
+pub fn dump_errors_json(
+    mut errors: Vec<Error>,
+    mut warnings: Vec<Error>,
+    intermediate_dir: &str,
+) -> String {
+    // I know that you want to depend on serde_json... but please don't!
+    errors.sort_by_key(|e| e.spans.first().map(|s| s.span.clone()).unwrap_or(Span::None));
+    warnings.sort_by_key(|w| w.spans.first().map(|s| s.span.clone()).unwrap_or(Span::None));
+
+    let mut lines = vec![];
+    let mut session = RenderSpanSession::new(intermediate_dir);
+
+    for (error, level) in warnings.iter().map(|w| (w, "warning")).chain(errors.iter().map(|e| (e, "error"))) {
+        let mut spans = Vec::with_capacity(error.spans.len());
+
+        for span in error.spans.iter() {
+            let file = session.get_path(&span.span);
+            let location = json_location(&span.span, &mut session);
+
+            spans.push(format!(
+                "{{\"file\":{},{location},\"auxiliary\":{},\"note\":{}}}",
+                json_string_or_null(file.as_deref()),
+                span.auxiliary,
+                json_string_or_null(span.note.as_deref()),
+            ));
+        }
+
+        let suggestions = error.suggestions.iter().map(
+            |suggestion| format!(
+                "{{\"file\":{},{},\"replacement\":{},\"applicability\":\"{}\",\"message\":{}}}",
+                json_string_or_null(session.get_path(&suggestion.span).as_deref()),
+                json_location(&suggestion.span, &mut session),
+                json_string(&suggestion.replacement),
+                suggestion.applicability.render(),
+                json_string(&suggestion.message),
+            )
+        ).collect::<Vec<_>>();
+
+        lines.push(format!(
+            "{{\"level\":\"{level}\",\"kind\":\"{}\",\"index\":{},\"message\":{},\"note\":{},\"spans\":[{}],\"suggestions\":[{}]}}",
+            error.kind.name(),
+            error.kind.index(),
+            json_string(&error.kind.render(intermediate_dir)),
+            json_string_or_null(error.note.as_deref()),
+            spans.join(","),
+            suggestions.join(","),
+        ));
+    }
+
+    lines.join("\n")
+}
+
+// `"start":..,"end":..,"line_start":..,"column_start":..,"line_end":..,"column_end":..`
+fn json_location(span: &Span, session: &mut RenderSpanSession) -> String {
+    match (span.file(), span.get_offset_and_length()) {
+        (Some(file), Some((offset, length))) => {
+            let (start, end) = (offset as usize, (offset + length) as usize);
+
+            match (session.get_line_col(file, start), session.get_line_col(file, end)) {
+                (Some((line_start, column_start)), Some((line_end, column_end))) => format!(
+                    "\"start\":{start},\"end\":{end},\"line_start\":{line_start},\"column_start\":{column_start},\"line_end\":{line_end},\"column_end\":{column_end}",
+                ),
+                _ => format!("\"start\":{start},\"end\":{end},\"line_start\":null,\"column_start\":null,\"line_end\":null,\"column_end\":null"),
+            }
+        },
+        _ => String::from("\"start\":null,\"end\":null,\"line_start\":null,\"column_start\":null,\"line_end\":null,\"column_end\":null"),
+    }
+}
+
+fn json_string_or_null(s: Option<&str>) -> String {
+    match s {
+        Some(s) => json_string(s),
+        None => String::from("null"),
+    }
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => { result.push_str("\\\""); },
+            '\\' => { result.push_str("\\\\"); },
+            '\n' => { result.push_str("\\n"); },
+            '\r' => { result.push_str("\\r"); },
+            '\t' => { result.push_str("\\t"); },
+            c if (c as u32) < 0x20 || c == '\x7f' => {
+                result.push_str(&format!("\\u{:04x}", c as u32));
+            },
+            c => { result.push(c); },
+        }
+    }
+
+    result.push('"');
+    result
+}
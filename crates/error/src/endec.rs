@@ -1,4 +1,7 @@
 use crate::{
+    Applicability,
+    ConstEvalFailure,
+    DocTestOutcome,
     EnumFieldKind,
     Error,
     ErrorKind,
@@ -8,6 +11,7 @@ use crate::{
     NameCollisionKind,
     NotXBut,
     ParamIndex,
+    Suggestion,
     TypeVarInfo,
 };
 use sodigy_endec::{DecodeError, Endec};
@@ -20,13 +24,58 @@ impl Endec for Error {
         self.kind.encode_impl(buffer);
         self.spans.encode_impl(buffer);
         self.note.encode_impl(buffer);
+        self.suggestions.encode_impl(buffer);
     }
 
     fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
         let (kind, cursor) = ErrorKind::decode_impl(buffer, cursor)?;
         let (spans, cursor) = Vec::<RenderableSpan>::decode_impl(buffer, cursor)?;
         let (note, cursor) = Option::<String>::decode_impl(buffer, cursor)?;
-        Ok((Error { kind, spans, note }, cursor))
+        let (suggestions, cursor) = Vec::<Suggestion>::decode_impl(buffer, cursor)?;
+        Ok((Error { kind, spans, note, suggestions }, cursor))
+    }
+}
+
+impl Endec for Suggestion {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        self.span.encode_impl(buffer);
+        self.replacement.encode_impl(buffer);
+        self.applicability.encode_impl(buffer);
+        self.message.encode_impl(buffer);
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        let (span, cursor) = Span::decode_impl(buffer, cursor)?;
+        let (replacement, cursor) = String::decode_impl(buffer, cursor)?;
+        let (applicability, cursor) = Applicability::decode_impl(buffer, cursor)?;
+        let (message, cursor) = String::decode_impl(buffer, cursor)?;
+        Ok((Suggestion { span, replacement, applicability, message }, cursor))
+    }
+}
+
+impl Endec for Applicability {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        match self {
+            Applicability::MachineApplicable => {
+                buffer.push(0);
+            },
+            Applicability::MaybeIncorrect => {
+                buffer.push(1);
+            },
+            Applicability::HasPlaceholders => {
+                buffer.push(2);
+            },
+        }
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        match buffer.get(cursor) {
+            Some(0) => Ok((Applicability::MachineApplicable, cursor + 1)),
+            Some(1) => Ok((Applicability::MaybeIncorrect, cursor + 1)),
+            Some(2) => Ok((Applicability::HasPlaceholders, cursor + 1)),
+            Some(n @ 3..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            None => Err(DecodeError::UnexpectedEof),
+        }
     }
 }
 
@@ -304,6 +353,66 @@ impl Endec for ParamIndex {
     }
 }
 
+impl Endec for ConstEvalFailure {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        match self {
+            ConstEvalFailure::StepLimit => {
+                buffer.push(0);
+            },
+            ConstEvalFailure::DepthLimit => {
+                buffer.push(1);
+            },
+            ConstEvalFailure::NonDeterministic => {
+                buffer.push(2);
+            },
+            ConstEvalFailure::SideEffect => {
+                buffer.push(3);
+            },
+            ConstEvalFailure::Panic => {
+                buffer.push(4);
+            },
+        }
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        match buffer.get(cursor) {
+            Some(0) => Ok((ConstEvalFailure::StepLimit, cursor + 1)),
+            Some(1) => Ok((ConstEvalFailure::DepthLimit, cursor + 1)),
+            Some(2) => Ok((ConstEvalFailure::NonDeterministic, cursor + 1)),
+            Some(3) => Ok((ConstEvalFailure::SideEffect, cursor + 1)),
+            Some(4) => Ok((ConstEvalFailure::Panic, cursor + 1)),
+            Some(n @ 5..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            None => Err(DecodeError::UnexpectedEof),
+        }
+    }
+}
+
+impl Endec for DocTestOutcome {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        match self {
+            DocTestOutcome::Success => {
+                buffer.push(0);
+            },
+            DocTestOutcome::CompileError => {
+                buffer.push(1);
+            },
+            DocTestOutcome::RuntimeError => {
+                buffer.push(2);
+            },
+        }
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        match buffer.get(cursor) {
+            Some(0) => Ok((DocTestOutcome::Success, cursor + 1)),
+            Some(1) => Ok((DocTestOutcome::CompileError, cursor + 1)),
+            Some(2) => Ok((DocTestOutcome::RuntimeError, cursor + 1)),
+            Some(n @ 3..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            None => Err(DecodeError::UnexpectedEof),
+        }
+    }
+}
+
 impl Endec for EnumFieldKind {
     fn encode_impl(&self, buffer: &mut Vec<u8>) {
         match self {
@@ -18,9 +18,15 @@ mod render;
 //    - That's why there are gaps in the indexes: so that I can insert new error kinds.
 //
 // You can see the result of the macro expansion in `src/proc_macro.rs`.
-// `ErrorKind` implements 1 method: `fn index(&self) -> u16;`.
+// `ErrorKind` implements 2 methods: `fn index(&self) -> u16;` and `fn name(&self) -> &'static str;`.
 // `ErrorKind` also implements `Endec`.
 // `ErrorLevel` implements 1 method: `fn from_error_kind(k: &ErrorKind) -> Self;`.
+// It also generates `ERROR_KIND_DOCS: &[ErrorKindDoc]`, which `sodigy explain` uses.
+//
+// An error kind can have an extended explanation in its doc comment. The explanation should
+// have a failing example (```` ```sodigy, compile_error ```` or ```` ```sodigy, warning ````)
+// and a fixed example (```` ```sodigy ````). The driver's test compiles the examples and checks
+// that the failing example produces the error and the fixed example doesn't.
 error_kinds!(
     // error variant,                                              index,    Error | Warning
     (InvalidNumberLiteral,                                             0,    Error),
@@ -28,6 +34,17 @@ error_kinds!(
     (EmptyIdent,                                                      10,    Error),
     (InvalidCharacterInIdent(char),                                   15,    Error),
     (WrongNumberOfQuotesInRawStringLiteral,                           20,    Error),
+    /// A string literal is opened, but never closed.
+    ///
+    /// ```sodigy, compile_error
+    /// let s = "Hello, world!;
+    /// ```
+    ///
+    /// Close the literal with the same number of quotes that opened it.
+    ///
+    /// ```sodigy
+    /// let s = "Hello, world!";
+    /// ```
     (UnterminatedStringLiteral,                                       25,    Error),
     (NotAllowedCharInFormattedString(u8),                             30,    Error),
     (UnmatchedBraceInFormattedString,                                 35,    Error),
@@ -38,6 +55,16 @@ error_kinds!(
     (UnterminatedCharLiteral,                                         60,    Error),
     (InvalidByteLiteral,                                              65,    Error),
     (InvalidEscape,                                                   70,    Error),
+    /// A character literal must have exactly one character. If you want an empty text, use a string literal.
+    ///
+    /// ```sodigy, compile_error
+    /// let c = '';
+    /// ```
+    ///
+    /// ```sodigy
+    /// let c = ' ';
+    /// let s = "";
+    /// ```
     (EmptyCharLiteral,                                                75,    Error),
     (UnterminatedBlockComment,                                        80,    Error),
     (InvalidUtf8,                                                     85,    Error),
@@ -89,13 +116,13 @@ error_kinds!(
     (EmptyMatchStatement,                                            230,    Error),
     (RedundantDecorator(InternedString),                             235,    Error),
 
-    // TODO: suggest similar names
     // TODO: tell what it's trying to decorate
     (InvalidDecorator(InternedString),                               240,    Error),
 
     (MissingDecoratorArg { expected: usize, got: usize },       245,    Error),
     (UnexpectedDecoratorArg { expected: usize, got: usize },    250,    Error),
     (WrongNumberOfLangItemGenerics { lang_items: usize, generic_params: usize },    255,    Error),
+    (InvalidCacheSize,                                               256,    Error),
     (CannotEvaluateConst,                                            260,    Error),
 
     // syntax errors in patterns
@@ -110,18 +137,71 @@ error_kinds!(
     (PatternDestructureWithoutNameBindings,                          301,    Error),
 
     // TODO: more context!
+    /// The same name is defined multiple times in the same scope. Sodigy doesn't have overloading.
+    ///
+    /// ```sodigy, compile_error
+    /// fn add(a: Int, b: Int) -> Int = a + b;
+    /// fn add(a: Int) -> Int = a + 1;
+    /// ```
+    ///
+    /// Give each definition a different name.
+    ///
+    /// ```sodigy
+    /// fn add(a: Int, b: Int) -> Int = a + b;
+    /// fn add1(a: Int) -> Int = a + 1;
+    /// ```
     (NameCollision { name: InternedString, kind: NameCollisionKind },   305,    Error),
 
+    /// The values of `let` statements depend on each other, so none of them can be evaluated.
+    ///
+    /// ```sodigy, compile_error
+    /// let a: Int = b + 1;
+    /// let b: Int = a + 1;
+    /// ```
+    ///
+    /// One of them has to be evaluated without the others.
+    ///
+    /// ```sodigy
+    /// let a: Int = 1;
+    /// let b: Int = a + 1;
+    /// ```
     (CyclicLet { names: Vec<InternedString> },                       310,    Error),
     (CyclicAlias { names: Vec<InternedString> },                     315,    Error),
+    /// `$` is the value of the previous stage of a pipeline, so it can only be used inside a pipeline.
+    ///
+    /// ```sodigy, compile_error
+    /// let x: Int = $ + 1;
+    /// ```
+    ///
+    /// ```sodigy
+    /// let x: Int = 1 |> $ + 1;
+    /// ```
     (DollarOutsidePipeline,                                          320,    Error),
+    /// A stage of a pipeline doesn't use the result of the previous stage (`$`), so the previous stage is meaningless.
+    ///
+    /// ```sodigy, compile_error
+    /// let x: Int = 100 |> 200;
+    /// ```
+    ///
+    /// ```sodigy
+    /// let x: Int = 100 |> $ + 200;
+    /// ```
     (DisconnectedPipeline,                                           325,    Error),
     (GenericFuncWithoutTypeAnnot,                                    326,    Error),
     (GenericStructWithoutTypeAnnot,                                  327,    Error),
     (GenericEnumVariantWithoutTypeAnnot,                             328,    Error),
 
     // TODO: more context!
-    // TODO: suggest similar names
+    /// The name is not defined in this scope. Check the spelling, or import the name with `use`.
+    ///
+    /// ```sodigy, compile_error
+    /// let x: Int = y + 1;
+    /// ```
+    ///
+    /// ```sodigy
+    /// let y: Int = 1;
+    /// let x: Int = y + 1;
+    /// ```
     (UndefinedName(InternedString),                                  330,    Error),
     (UndefinedMacro(InternedString),                                 331,    Error),
 
@@ -134,7 +214,6 @@ error_kinds!(
     (MissingKeywordArg(InternedString),                         366,    Error),
 
     // TODO: more context!
-    // TODO: suggest similar names
     (InvalidKeywordArg(InternedString),                              370,    Error),
 
     (MissingFunctionParameter { expected: usize, got: usize },       375,    Error),
@@ -160,9 +239,33 @@ error_kinds!(
     // Type errors are generated by `inter-mir` crate, and the crate uses its own data types to
     // represent types. But this crate cannot depend on `inter-mir`, so those types are converted
     // to string.
+    /// The type of a value is different from the type that is expected there.
+    ///
+    /// ```sodigy, compile_error
+    /// let x: Int = "hello";
+    /// ```
+    ///
+    /// Fix the value or the type annotation.
+    ///
+    /// ```sodigy
+    /// let x: String = "hello";
+    /// ```
     (UnexpectedType { expected: String, got: String },                   415,    Error),
 
     // TODO: more context
+    /// A function is called with a wrong number of arguments. Sodigy doesn't have currying.
+    ///
+    /// ```sodigy, compile_error
+    /// fn add(a: Int, b: Int) -> Int = a + b;
+    /// let x: Int = add(1);
+    /// ```
+    ///
+    /// Give all the arguments, or give the parameter a default value.
+    ///
+    /// ```sodigy
+    /// fn add(a: Int, b: Int = 1) -> Int = a + b;
+    /// let x: Int = add(1);
+    /// ```
     (WrongNumberOfArgs { expected: usize, got: usize },                  416,    Error),
 
     (WrongNumberOfGenericArgs { expected: usize, got: usize },           417,    Error),
@@ -173,16 +276,70 @@ error_kinds!(
     (CannotInferGenericType { id: Option<String> },                      430,    Error),
     (PartiallyInferedGenericType { id: Option<String>, r#type: String }, 435,    Error),
 
-    // TODO: suggest similar names
+    /// The type doesn't have a field (or an associated item) with that name.
+    ///
+    /// ```sodigy, compile_error
+    /// struct Point = { x: Int, y: Int };
+    /// let p: Point = Point { x: 0, y: 0 };
+    /// let z: Int = p.z;
+    /// ```
+    ///
+    /// ```sodigy
+    /// struct Point = { x: Int, y: Int };
+    /// let p: Point = Point { x: 0, y: 0 };
+    /// let x: Int = p.x;
+    /// ```
     (UnknownField { r#type: String, field: InternedString },               436,    Error),
 
     (CannotUpdateAssociatedFunc { r#type: String, name: InternedString },  439,  Error),
     (CannotApplyInfixOp { op: InfixOp, arg_types: Vec<String> },           440,  Error),
     (CannotSpecializePolyGeneric { num_candidates: usize },                445,  Error),
+    /// A pure function calls a function with an effect, like `random_int`. The effect of a function has to be written in its definition.
+    ///
+    /// ```sodigy, compile_error
+    /// use std.random.random_int;
+    ///
+    /// fn roll(n: Int) -> Int = random_int() % n;
+    /// ```
+    ///
+    /// ```sodigy
+    /// use std.random.random_int;
+    ///
+    /// ndet fn roll(n: Int) -> Int = random_int() % n;
+    /// ```
     (ImpureCallInPureContext { context: FuncEffect },                      450,  Error),
 
-    // TODO: tell what's missing
+    /// The arms of a `match` expression don't cover all the possible values. If no arm matches, the program cannot continue, so the compiler rejects it.
+    /// The error note lists the values that no arm covers, e.g. `..0` and `2..` in the example below.
+    ///
+    /// ```sodigy, compile_error
+    /// fn sign(n: Int) -> Int = match n {
+    ///     0 => 0,
+    ///     1 => 1,
+    /// };
+    /// ```
+    ///
+    /// Add arms for the missing values. A wildcard (`_`) matches anything.
+    ///
+    /// ```sodigy
+    /// fn sign(n: Int) -> Int = match n {
+    ///     0 => 0,
+    ///     1 => 1,
+    ///     _ => 2,
+    /// };
+    /// ```
     (NonExhaustiveArms,                                                  455,    Error),
+    /// The pattern of a `let` statement may not match the value. A `let` statement doesn't have a fallback, so its pattern must always match.
+    ///
+    /// ```sodigy, compile_error
+    /// let ($x, 0) = (1, 2);
+    /// ```
+    ///
+    /// Use a pattern that always matches, or use `match`.
+    ///
+    /// ```sodigy
+    /// let ($x, _) = (1, 2);
+    /// ```
     (RefutableLetPattern,                                                456,    Error),
 
     (MultipleModuleFiles { module: ModulePath, found_files: Vec<String> },    460,    Error),
@@ -198,12 +355,47 @@ error_kinds!(
     (CannotImplPoly { poly_type: String, impl_type: String, param_index: ParamIndex }, 500, Error),
     (MultiplePolyCandidates(usize),                                  505,    Error),
 
+    // `sodigy build --check-const-asserts` evaluates assertions at compile time.
+    (ConstAssertionFailed { name: InternedString },                  510,    Error),
+
+    // `sodigy test --doc` runs the code blocks in the doc comments.
+    (DocTestFailed { expected: DocTestOutcome, got: DocTestOutcome }, 515,    Error),
+
     // Warnings from here
+    /// A name is defined but never used. It's usually a typo or a leftover.
+    ///
+    /// ```sodigy, warning
+    /// fn first(a: Int, b: Int) -> Int = 0;
+    /// ```
+    ///
+    /// Use the name, or remove it.
+    ///
+    /// ```sodigy
+    /// fn first(a: Int, b: Int) -> Int = a + b;
+    /// ```
     (UnusedNames { names: Vec<InternedString>, kind: NameKind },    5000,  Warning),
     (UseUnusedName { name: InternedString },                        5001,  Warning),
+    /// An arm of a `match` expression can never be reached, because the arms above it already match all the values it matches.
+    ///
+    /// ```sodigy, warning
+    /// fn is_zero(n: Int) -> Int = match n {
+    ///     _ => 0,
+    ///     0 => 1,
+    /// };
+    /// ```
+    ///
+    /// The arms are checked from top to bottom, so put the more specific arms first.
+    ///
+    /// ```sodigy
+    /// fn is_zero(n: Int) -> Int = match n {
+    ///     0 => 1,
+    ///     _ => 0,
+    /// };
+    /// ```
     (UnreachableMatchArm,                                           5005,  Warning),
     (UnreachableOrPattern,                                          5006,  Warning),
     (NoImpureCallInImpureContext { context: FuncEffect },           5010,  Warning),
+    (CannotMemoize { param: InternedString },                       5015,  Warning),
 
     // Lints from here
     (FuncWithoutTypeAnnot,                                          8000,  Lint),
@@ -211,6 +403,7 @@ error_kinds!(
     (StructWithoutTypeAnnot,                                        8010,  Lint),
     (EnumVariantWithoutTypeAnnot,                                   8011,  Lint),
     (SelfParamNotNamedSelf,                                         8015,  Lint),
+    (CannotCheckConstAssertion(ConstEvalFailure),                   8020,  Lint),
 
     // These are very special kinds of errors.
     // These are bugs in the compiler, not in the user's Sodigy code.
@@ -219,6 +412,34 @@ error_kinds!(
     (InternalCompilerError { id: u32 },                             9999,    Error),
 );
 
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorKindDoc {
+    pub name: &'static str,
+    pub index: u16,
+    pub level: ErrorLevel,
+    pub explanation: Option<&'static str>,
+}
+
+impl ErrorKindDoc {
+    /// `455`, `0455`, `e-0455` or `NonExhaustiveArms`
+    pub fn find(query: &str) -> Option<&'static ErrorKindDoc> {
+        let index = query.strip_prefix("e-").or_else(|| query.strip_prefix("w-")).unwrap_or(query);
+
+        match index.parse::<u16>() {
+            Ok(index) => ERROR_KIND_DOCS.iter().find(|doc| doc.index == index),
+            Err(_) => ERROR_KIND_DOCS.iter().find(|doc| doc.name == query),
+        }
+    }
+
+    /// `e-0455`, as it's shown in the error messages
+    pub fn code(&self) -> String {
+        match self.level {
+            ErrorLevel::Error => format!("e-{:04}", self.index),
+            ErrorLevel::Warning | ErrorLevel::Lint => format!("w-{:04}", self.index),
+        }
+    }
+}
+
 impl From<GetFilePathError> for ErrorKind {
     fn from(e: GetFilePathError) -> ErrorKind {
         if e.is_std && e.found_files.is_empty() {
@@ -296,6 +517,56 @@ impl From<&NameKind> for NotXBut {
     }
 }
 
+// Why the compiler gave up evaluating something at compile time.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConstEvalFailure {
+    // It ran too many bytecodes.
+    StepLimit,
+
+    // It went too deep in the call stack.
+    DepthLimit,
+
+    // It called a non-deterministic function (e.g. `random_int`).
+    NonDeterministic,
+
+    // It called a function that has side effects (e.g. `print`).
+    SideEffect,
+
+    // It panicked.
+    Panic,
+}
+
+impl ConstEvalFailure {
+    pub fn render(&self) -> &'static str {
+        match self {
+            ConstEvalFailure::StepLimit => "it takes too long",
+            ConstEvalFailure::DepthLimit => "it recurses too deep",
+            ConstEvalFailure::NonDeterministic => "it calls a non-deterministic function",
+            ConstEvalFailure::SideEffect => "it calls a function with side effects",
+            ConstEvalFailure::Panic => "it panics before the assertion is checked",
+        }
+    }
+}
+
+// The expected outcome of a code block in a doc comment is written in its info string:
+// ```` ```sodigy ````, ```` ```sodigy, compile_error ```` or ```` ```sodigy, run_error ````.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DocTestOutcome {
+    Success,
+    CompileError,
+    RuntimeError,
+}
+
+impl DocTestOutcome {
+    pub fn render(&self) -> &'static str {
+        match self {
+            DocTestOutcome::Success => "success",
+            DocTestOutcome::CompileError => "a compile error",
+            DocTestOutcome::RuntimeError => "a runtime error",
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum EnumFieldKind {
     None,
@@ -77,6 +77,24 @@ impl ErrorKind {
                     "and",
                 ),
             ),
+            ErrorKind::ConstAssertionFailed { name } => format!(
+                "Assertion `{}` fails at compile time.",
+                name.unintern_or_default(intermediate_dir),
+            ),
+            ErrorKind::DocTestFailed { expected, got } => format!(
+                "This doc test is expected to result in {}, but it results in {}.",
+                expected.render(),
+                got.render(),
+            ),
+            ErrorKind::CannotCheckConstAssertion(failure) => format!(
+                "Cannot check this assertion at compile time because {}.",
+                failure.render(),
+            ),
+            ErrorKind::InvalidCacheSize => String::from("The size of a memoization cache must be a positive integer."),
+            ErrorKind::CannotMemoize { param } => format!(
+                "Cannot memoize this function because its parameter `{}` cannot be compared by value.",
+                param.unintern_or_default(intermediate_dir),
+            ),
             ErrorKind::UnexpectedType { expected, got } => format!("Expected type `{expected}`, got type `{got}`."),
             ErrorKind::UnusedNames { names, kind } => {
                 let names = names.iter().map(
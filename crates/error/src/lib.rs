@@ -1,3 +1,4 @@
+use sodigy_cli::get_close_strings;
 use sodigy_span::{RenderableSpan, Span};
 use sodigy_string::InternedString;
 use std::collections::HashSet;
@@ -6,15 +7,17 @@ mod dump;
 mod endec;
 mod kind;
 mod lint;
+mod suggestion;
 mod token;
 mod warning;
 
 #[cfg(test)]
 mod tests;
 
-pub use dump::{DumpErrorOption, dump_errors};
-pub use kind::{EnumFieldKind, ErrorKind, NameCollisionKind, NotXBut};
+pub use dump::{DumpErrorOption, dump_errors, dump_errors_json};
+pub use kind::{ConstEvalFailure, DocTestOutcome, ERROR_KIND_DOCS, EnumFieldKind, ErrorKind, ErrorKindDoc, NameCollisionKind, NotXBut};
 pub use lint::{Lint, LintKind};
+pub use suggestion::{Applicability, Suggestion};
 pub use token::ErrorToken;
 pub use warning::{Warning, WarningKind};
 
@@ -27,6 +30,9 @@ pub struct Error {
     pub spans: Vec<RenderableSpan>,
 
     pub note: Option<String>,
+
+    // Fixes that `sodigy fix` (or an editor) can apply.
+    pub suggestions: Vec<Suggestion>,
 }
 
 impl Error {
@@ -35,6 +41,7 @@ impl Error {
             kind: ErrorKind::Todo { id, message: message.to_string() },
             spans: span.simple_error(),
             note: None,
+            suggestions: vec![],
         }
     }
 
@@ -43,8 +50,17 @@ impl Error {
             kind: ErrorKind::InternalCompilerError { id },
             spans: span.simple_error(),
             note: None,
+            suggestions: vec![],
         }
     }
+
+    // If the error already has a note, the new note is appended to it.
+    pub fn add_note(&mut self, note: String) {
+        self.note = match self.note.take() {
+            Some(prev) => Some(format!("{prev} {note}")),
+            None => Some(note),
+        };
+    }
 }
 
 /// By default,
@@ -98,6 +114,30 @@ pub fn comma_list_strs(
     }
 }
 
+// `did_you_mean("lenght", ["len", "length", "list"])` -> `Some("Did you mean `length`?")`
+pub fn did_you_mean(name: &str, candidates: &[String]) -> Option<String> {
+    let mut candidates = candidates.iter().filter(
+        |candidate| candidate.as_str() != name
+    ).map(
+        |candidate| candidate.to_string()
+    ).collect::<Vec<_>>();
+
+    // The candidates are usually collected from `HashMap`s, but the suggestions have to be deterministic.
+    candidates.sort();
+    candidates.dedup();
+
+    let mut close_names = get_close_strings(&candidates, name);
+    close_names.truncate(3);
+
+    if close_names.is_empty() {
+        None
+    }
+
+    else {
+        Some(format!("Did you mean {}?", comma_list_strs(&close_names, "`", "`", "or")))
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum ItemKind {
     Alias,
@@ -26,10 +26,11 @@
     MissingDecoratorArg { expected: usize, got: usize },
     UnexpectedDecoratorArg { expected: usize, got: usize },
     WrongNumberOfLangItemGenerics
-    { lang_items: usize, generic_params: usize }, CannotEvaluateConst,
-    InvalidRangePattern, InvalidConcatPattern, CannotBindName(InternedString),
-    CannotApplyInfixOpToMultipleBindings, CannotApplyInfixOpToBinding,
-    CannotAnnotateType, RedundantNameBinding(InternedString, InternedString),
+    { lang_items: usize, generic_params: usize }, InvalidCacheSize,
+    CannotEvaluateConst, InvalidRangePattern, InvalidConcatPattern,
+    CannotBindName(InternedString), CannotApplyInfixOpToMultipleBindings,
+    CannotApplyInfixOpToBinding, CannotAnnotateType,
+    RedundantNameBinding(InternedString, InternedString),
     UnsupportedInfixOpInPattern(InfixOp),
     PatternDestructureWithoutNameBindings, NameCollision
     { name: InternedString, kind: NameCollisionKind }, CyclicLet
@@ -81,13 +82,16 @@
     PolyImplDifferentNumberOfParams
     { poly_params: usize, impl_params: usize }, CannotImplPoly
     { poly_type: String, impl_type: String, param_index: ParamIndex },
-    MultiplePolyCandidates(usize), UnusedNames
+    MultiplePolyCandidates(usize), ConstAssertionFailed
+    { name: InternedString }, DocTestFailed
+    { expected: DocTestOutcome, got: DocTestOutcome }, UnusedNames
     { names: Vec<InternedString>, kind: NameKind }, UseUnusedName
     { name: InternedString }, UnreachableMatchArm, UnreachableOrPattern,
-    NoImpureCallInImpureContext { context: FuncEffect }, FuncWithoutTypeAnnot,
-    LetWithoutTypeAnnot, StructWithoutTypeAnnot, EnumVariantWithoutTypeAnnot,
-    SelfParamNotNamedSelf, Todo { id: u32, message: String },
-    InternalCompilerError { id: u32 },
+    NoImpureCallInImpureContext { context: FuncEffect }, CannotMemoize
+    { param: InternedString }, FuncWithoutTypeAnnot, LetWithoutTypeAnnot,
+    StructWithoutTypeAnnot, EnumVariantWithoutTypeAnnot,
+    SelfParamNotNamedSelf, CannotCheckConstAssertion(ConstEvalFailure), Todo
+    { id: u32, message: String }, InternalCompilerError { id: u32 },
 } impl ErrorKind {
     pub fn index(& self) -> u16
     {
@@ -141,12 +145,12 @@
             MissingDecoratorArg { .. } => 245u16, ErrorKind ::
             UnexpectedDecoratorArg { .. } => 250u16, ErrorKind ::
             WrongNumberOfLangItemGenerics { .. } => 255u16, ErrorKind ::
-            CannotEvaluateConst => 260u16, ErrorKind :: InvalidRangePattern =>
-            265u16, ErrorKind :: InvalidConcatPattern => 270u16, ErrorKind ::
-            CannotBindName(_,) => 275u16, ErrorKind ::
-            CannotApplyInfixOpToMultipleBindings => 280u16, ErrorKind ::
-            CannotApplyInfixOpToBinding => 285u16, ErrorKind ::
-            CannotAnnotateType => 290u16, ErrorKind ::
+            InvalidCacheSize => 256u16, ErrorKind :: CannotEvaluateConst =>
+            260u16, ErrorKind :: InvalidRangePattern => 265u16, ErrorKind ::
+            InvalidConcatPattern => 270u16, ErrorKind :: CannotBindName(_,) =>
+            275u16, ErrorKind :: CannotApplyInfixOpToMultipleBindings =>
+            280u16, ErrorKind :: CannotApplyInfixOpToBinding => 285u16,
+            ErrorKind :: CannotAnnotateType => 290u16, ErrorKind ::
             RedundantNameBinding(_, _,) => 295u16, ErrorKind ::
             UnsupportedInfixOpInPattern(_,) => 300u16, ErrorKind ::
             PatternDestructureWithoutNameBindings => 301u16, ErrorKind ::
@@ -198,16 +202,191 @@
             ErrorKind :: CannotInferPolyGenericImpl { .. } => 490u16,
             ErrorKind :: PolyImplDifferentNumberOfParams { .. } => 495u16,
             ErrorKind :: CannotImplPoly { .. } => 500u16, ErrorKind ::
-            MultiplePolyCandidates(_,) => 505u16, ErrorKind :: UnusedNames
-            { .. } => 5000u16, ErrorKind :: UseUnusedName { .. } => 5001u16,
-            ErrorKind :: UnreachableMatchArm => 5005u16, ErrorKind ::
-            UnreachableOrPattern => 5006u16, ErrorKind ::
-            NoImpureCallInImpureContext { .. } => 5010u16, ErrorKind ::
-            FuncWithoutTypeAnnot => 8000u16, ErrorKind :: LetWithoutTypeAnnot
-            => 8005u16, ErrorKind :: StructWithoutTypeAnnot => 8010u16,
-            ErrorKind :: EnumVariantWithoutTypeAnnot => 8011u16, ErrorKind ::
-            SelfParamNotNamedSelf => 8015u16, ErrorKind :: Todo { .. } =>
-            9998u16, ErrorKind :: InternalCompilerError { .. } => 9999u16,
+            MultiplePolyCandidates(_,) => 505u16, ErrorKind ::
+            ConstAssertionFailed { .. } => 510u16, ErrorKind :: DocTestFailed
+            { .. } => 515u16, ErrorKind :: UnusedNames { .. } => 5000u16,
+            ErrorKind :: UseUnusedName { .. } => 5001u16, ErrorKind ::
+            UnreachableMatchArm => 5005u16, ErrorKind :: UnreachableOrPattern
+            => 5006u16, ErrorKind :: NoImpureCallInImpureContext { .. } =>
+            5010u16, ErrorKind :: CannotMemoize { .. } => 5015u16, ErrorKind
+            :: FuncWithoutTypeAnnot => 8000u16, ErrorKind ::
+            LetWithoutTypeAnnot => 8005u16, ErrorKind ::
+            StructWithoutTypeAnnot => 8010u16, ErrorKind ::
+            EnumVariantWithoutTypeAnnot => 8011u16, ErrorKind ::
+            SelfParamNotNamedSelf => 8015u16, ErrorKind ::
+            CannotCheckConstAssertion(_,) => 8020u16, ErrorKind :: Todo { .. }
+            => 9998u16, ErrorKind :: InternalCompilerError { .. } => 9999u16,
+        }
+    } pub fn name(& self) -> &'static str
+    {
+        match self
+        {
+            ErrorKind :: InvalidNumberLiteral => "InvalidNumberLiteral",
+            ErrorKind :: InvalidStringLiteralPrefix(_,) =>
+            "InvalidStringLiteralPrefix", ErrorKind :: EmptyIdent =>
+            "EmptyIdent", ErrorKind :: InvalidCharacterInIdent(_,) =>
+            "InvalidCharacterInIdent", ErrorKind ::
+            WrongNumberOfQuotesInRawStringLiteral =>
+            "WrongNumberOfQuotesInRawStringLiteral", ErrorKind ::
+            UnterminatedStringLiteral => "UnterminatedStringLiteral",
+            ErrorKind :: NotAllowedCharInFormattedString(_,) =>
+            "NotAllowedCharInFormattedString", ErrorKind ::
+            UnmatchedBraceInFormattedString =>
+            "UnmatchedBraceInFormattedString", ErrorKind ::
+            EmptyBraceInFormattedString => "EmptyBraceInFormattedString",
+            ErrorKind :: DotDotDot => "DotDotDot", ErrorKind ::
+            InvalidCharLiteral => "InvalidCharLiteral", ErrorKind ::
+            InvalidCharLiteralPrefix(_,) => "InvalidCharLiteralPrefix",
+            ErrorKind :: UnterminatedCharLiteral => "UnterminatedCharLiteral",
+            ErrorKind :: InvalidByteLiteral => "InvalidByteLiteral", ErrorKind
+            :: InvalidEscape => "InvalidEscape", ErrorKind :: EmptyCharLiteral
+            => "EmptyCharLiteral", ErrorKind :: UnterminatedBlockComment =>
+            "UnterminatedBlockComment", ErrorKind :: InvalidUtf8 =>
+            "InvalidUtf8", ErrorKind :: InvalidUnicodeCharacter =>
+            "InvalidUnicodeCharacter", ErrorKind :: InvalidUnicodeEscape =>
+            "InvalidUnicodeEscape", ErrorKind :: UnmatchedGroup { .. } =>
+            "UnmatchedGroup", ErrorKind :: TooManyQuotes => "TooManyQuotes",
+            ErrorKind :: UnclosedDelimiter(_,) => "UnclosedDelimiter",
+            ErrorKind :: UnexpectedByte(_,) => "UnexpectedByte", ErrorKind ::
+            UnexpectedToken { .. } => "UnexpectedToken", ErrorKind ::
+            WildcardNotAllowed => "WildcardNotAllowed", ErrorKind ::
+            UnexpectedEof { .. } => "UnexpectedEof", ErrorKind ::
+            UnexpectedEog { .. } => "UnexpectedEog", ErrorKind ::
+            MissingDocComment => "MissingDocComment", ErrorKind ::
+            DocCommentNotAllowed => "DocCommentNotAllowed", ErrorKind ::
+            DanglingDocComment => "DanglingDocComment", ErrorKind ::
+            ModuleDocCommentNotAtTop => "ModuleDocCommentNotAtTop", ErrorKind
+            :: MissingDecorator(_,) => "MissingDecorator", ErrorKind ::
+            DecoratorNotAllowed => "DecoratorNotAllowed", ErrorKind ::
+            DanglingDecorator => "DanglingDecorator", ErrorKind ::
+            UnexpectedDecorator(_,) => "UnexpectedDecorator", ErrorKind ::
+            ModuleDecoratorNotAtTop => "ModuleDecoratorNotAtTop", ErrorKind ::
+            MissingVisibility => "MissingVisibility", ErrorKind ::
+            CannotBePublic => "CannotBePublic", ErrorKind ::
+            DanglingVisibility => "DanglingVisibility", ErrorKind ::
+            FunctionWithoutBody => "FunctionWithoutBody", ErrorKind ::
+            StructWithoutBody => "StructWithoutBody", ErrorKind ::
+            EnumWithoutBody => "EnumWithoutBody", ErrorKind ::
+            BlockWithoutValue => "BlockWithoutValue", ErrorKind :: TopLevelDo
+            => "TopLevelDo", ErrorKind :: StructWithoutField =>
+            "StructWithoutField", ErrorKind :: EmptyCurlyBraceBlock =>
+            "EmptyCurlyBraceBlock", ErrorKind :: AmbiguousCurlyBraces =>
+            "AmbiguousCurlyBraces", ErrorKind :: AmbiguousAngleBrackets =>
+            "AmbiguousAngleBrackets", ErrorKind ::
+            PositionalArgAfterKeywordArg => "PositionalArgAfterKeywordArg",
+            ErrorKind :: NonDefaultValueAfterDefaultValue =>
+            "NonDefaultValueAfterDefaultValue", ErrorKind ::
+            CannotDeclareInlineModule => "CannotDeclareInlineModule",
+            ErrorKind :: InclusiveRangeWithNoEnd => "InclusiveRangeWithNoEnd",
+            ErrorKind :: MultipleRestPatterns => "MultipleRestPatterns",
+            ErrorKind :: DifferentNameBindingsInOrPattern =>
+            "DifferentNameBindingsInOrPattern", ErrorKind :: InvalidFnType =>
+            "InvalidFnType", ErrorKind :: EmptyMatchStatement =>
+            "EmptyMatchStatement", ErrorKind :: RedundantDecorator(_,) =>
+            "RedundantDecorator", ErrorKind :: InvalidDecorator(_,) =>
+            "InvalidDecorator", ErrorKind :: MissingDecoratorArg { .. } =>
+            "MissingDecoratorArg", ErrorKind :: UnexpectedDecoratorArg { .. }
+            => "UnexpectedDecoratorArg", ErrorKind ::
+            WrongNumberOfLangItemGenerics { .. } =>
+            "WrongNumberOfLangItemGenerics", ErrorKind :: InvalidCacheSize =>
+            "InvalidCacheSize", ErrorKind :: CannotEvaluateConst =>
+            "CannotEvaluateConst", ErrorKind :: InvalidRangePattern =>
+            "InvalidRangePattern", ErrorKind :: InvalidConcatPattern =>
+            "InvalidConcatPattern", ErrorKind :: CannotBindName(_,) =>
+            "CannotBindName", ErrorKind ::
+            CannotApplyInfixOpToMultipleBindings =>
+            "CannotApplyInfixOpToMultipleBindings", ErrorKind ::
+            CannotApplyInfixOpToBinding => "CannotApplyInfixOpToBinding",
+            ErrorKind :: CannotAnnotateType => "CannotAnnotateType", ErrorKind
+            :: RedundantNameBinding(_, _,) => "RedundantNameBinding",
+            ErrorKind :: UnsupportedInfixOpInPattern(_,) =>
+            "UnsupportedInfixOpInPattern", ErrorKind ::
+            PatternDestructureWithoutNameBindings =>
+            "PatternDestructureWithoutNameBindings", ErrorKind ::
+            NameCollision { .. } => "NameCollision", ErrorKind :: CyclicLet
+            { .. } => "CyclicLet", ErrorKind :: CyclicAlias { .. } =>
+            "CyclicAlias", ErrorKind :: DollarOutsidePipeline =>
+            "DollarOutsidePipeline", ErrorKind :: DisconnectedPipeline =>
+            "DisconnectedPipeline", ErrorKind :: GenericFuncWithoutTypeAnnot
+            => "GenericFuncWithoutTypeAnnot", ErrorKind ::
+            GenericStructWithoutTypeAnnot => "GenericStructWithoutTypeAnnot",
+            ErrorKind :: GenericEnumVariantWithoutTypeAnnot =>
+            "GenericEnumVariantWithoutTypeAnnot", ErrorKind ::
+            UndefinedName(_,) => "UndefinedName", ErrorKind ::
+            UndefinedMacro(_,) => "UndefinedMacro", ErrorKind ::
+            EnumVariantInTypeAnnot => "EnumVariantInTypeAnnot", ErrorKind ::
+            KeywordArgRepeated(_,) => "KeywordArgRepeated", ErrorKind ::
+            KeywordArgNotAllowed => "KeywordArgNotAllowed", ErrorKind ::
+            AliasResolveRecursionLimitReached =>
+            "AliasResolveRecursionLimitReached", ErrorKind ::
+            MissingTypeParameter { .. } => "MissingTypeParameter", ErrorKind
+            :: UnexpectedTypeParameter { .. } => "UnexpectedTypeParameter",
+            ErrorKind :: MissingKeywordArg(_,) => "MissingKeywordArg",
+            ErrorKind :: InvalidKeywordArg(_,) => "InvalidKeywordArg",
+            ErrorKind :: MissingFunctionParameter { .. } =>
+            "MissingFunctionParameter", ErrorKind ::
+            UnexpectedFunctionParameter { .. } =>
+            "UnexpectedFunctionParameter", ErrorKind ::
+            StructFieldRepeated(_,) => "StructFieldRepeated", ErrorKind ::
+            MissingStructFields { .. } => "MissingStructFields", ErrorKind ::
+            InvalidStructFields { .. } => "InvalidStructFields", ErrorKind ::
+            MismatchedEnumFieldKind { .. } => "MismatchedEnumFieldKind",
+            ErrorKind :: CannotAssociateItem => "CannotAssociateItem",
+            ErrorKind :: TooGeneralToAssociateItem =>
+            "TooGeneralToAssociateItem", ErrorKind :: NotType { .. } =>
+            "NotType", ErrorKind :: NotCallable { .. } => "NotCallable",
+            ErrorKind :: NotStruct { .. } => "NotStruct", ErrorKind :: NotExpr
+            { .. } => "NotExpr", ErrorKind :: NotPolyGeneric { .. } =>
+            "NotPolyGeneric", ErrorKind :: CannotAliasLocalValue(_,) =>
+            "CannotAliasLocalValue", ErrorKind :: UnexpectedType { .. } =>
+            "UnexpectedType", ErrorKind :: WrongNumberOfArgs { .. } =>
+            "WrongNumberOfArgs", ErrorKind :: WrongNumberOfGenericArgs { .. }
+            => "WrongNumberOfGenericArgs", ErrorKind :: UnnecessaryGenericArgs
+            => "UnnecessaryGenericArgs", ErrorKind :: MissingGenericArgs =>
+            "MissingGenericArgs", ErrorKind :: CannotInferType { .. } =>
+            "CannotInferType", ErrorKind :: PartiallyInferedType { .. } =>
+            "PartiallyInferedType", ErrorKind :: CannotInferGenericType { .. }
+            => "CannotInferGenericType", ErrorKind ::
+            PartiallyInferedGenericType { .. } =>
+            "PartiallyInferedGenericType", ErrorKind :: UnknownField { .. } =>
+            "UnknownField", ErrorKind :: CannotUpdateAssociatedFunc { .. } =>
+            "CannotUpdateAssociatedFunc", ErrorKind :: CannotApplyInfixOp
+            { .. } => "CannotApplyInfixOp", ErrorKind ::
+            CannotSpecializePolyGeneric { .. } =>
+            "CannotSpecializePolyGeneric", ErrorKind ::
+            ImpureCallInPureContext { .. } => "ImpureCallInPureContext",
+            ErrorKind :: NonExhaustiveArms => "NonExhaustiveArms", ErrorKind
+            :: RefutableLetPattern => "RefutableLetPattern", ErrorKind ::
+            MultipleModuleFiles { .. } => "MultipleModuleFiles", ErrorKind ::
+            ModuleFileNotFound { .. } => "ModuleFileNotFound", ErrorKind ::
+            LibFileNotFound => "LibFileNotFound", ErrorKind ::
+            SelfParamWithTypeAnnot => "SelfParamWithTypeAnnot", ErrorKind ::
+            AssociatedFuncWithoutSelfParam =>
+            "AssociatedFuncWithoutSelfParam", ErrorKind ::
+            CannotInferPolyGenericParam { .. } =>
+            "CannotInferPolyGenericParam", ErrorKind ::
+            CannotInferPolyGenericImpl { .. } => "CannotInferPolyGenericImpl",
+            ErrorKind :: PolyImplDifferentNumberOfParams { .. } =>
+            "PolyImplDifferentNumberOfParams", ErrorKind :: CannotImplPoly
+            { .. } => "CannotImplPoly", ErrorKind ::
+            MultiplePolyCandidates(_,) => "MultiplePolyCandidates", ErrorKind
+            :: ConstAssertionFailed { .. } => "ConstAssertionFailed",
+            ErrorKind :: DocTestFailed { .. } => "DocTestFailed", ErrorKind ::
+            UnusedNames { .. } => "UnusedNames", ErrorKind :: UseUnusedName
+            { .. } => "UseUnusedName", ErrorKind :: UnreachableMatchArm =>
+            "UnreachableMatchArm", ErrorKind :: UnreachableOrPattern =>
+            "UnreachableOrPattern", ErrorKind :: NoImpureCallInImpureContext
+            { .. } => "NoImpureCallInImpureContext", ErrorKind ::
+            CannotMemoize { .. } => "CannotMemoize", ErrorKind ::
+            FuncWithoutTypeAnnot => "FuncWithoutTypeAnnot", ErrorKind ::
+            LetWithoutTypeAnnot => "LetWithoutTypeAnnot", ErrorKind ::
+            StructWithoutTypeAnnot => "StructWithoutTypeAnnot", ErrorKind ::
+            EnumVariantWithoutTypeAnnot => "EnumVariantWithoutTypeAnnot",
+            ErrorKind :: SelfParamNotNamedSelf => "SelfParamNotNamedSelf",
+            ErrorKind :: CannotCheckConstAssertion(_,) =>
+            "CannotCheckConstAssertion", ErrorKind :: Todo { .. } => "Todo",
+            ErrorKind :: InternalCompilerError { .. } =>
+            "InternalCompilerError",
         }
     }
 } impl ErrorLevel {
@@ -276,9 +455,10 @@
             ErrorKind :: MissingDecoratorArg { .. } => ErrorLevel :: Error,
             ErrorKind :: UnexpectedDecoratorArg { .. } => ErrorLevel :: Error,
             ErrorKind :: WrongNumberOfLangItemGenerics { .. } => ErrorLevel ::
-            Error, ErrorKind :: CannotEvaluateConst => ErrorLevel :: Error,
-            ErrorKind :: InvalidRangePattern => ErrorLevel :: Error, ErrorKind
-            :: InvalidConcatPattern => ErrorLevel :: Error, ErrorKind ::
+            Error, ErrorKind :: InvalidCacheSize => ErrorLevel :: Error,
+            ErrorKind :: CannotEvaluateConst => ErrorLevel :: Error, ErrorKind
+            :: InvalidRangePattern => ErrorLevel :: Error, ErrorKind ::
+            InvalidConcatPattern => ErrorLevel :: Error, ErrorKind ::
             CannotBindName(_,) => ErrorLevel :: Error, ErrorKind ::
             CannotApplyInfixOpToMultipleBindings => ErrorLevel :: Error,
             ErrorKind :: CannotApplyInfixOpToBinding => ErrorLevel :: Error,
@@ -344,16 +524,20 @@
             Error, ErrorKind :: PolyImplDifferentNumberOfParams { .. } =>
             ErrorLevel :: Error, ErrorKind :: CannotImplPoly { .. } =>
             ErrorLevel :: Error, ErrorKind :: MultiplePolyCandidates(_,) =>
+            ErrorLevel :: Error, ErrorKind :: ConstAssertionFailed { .. } =>
+            ErrorLevel :: Error, ErrorKind :: DocTestFailed { .. } =>
             ErrorLevel :: Error, ErrorKind :: UnusedNames { .. } => ErrorLevel
             :: Warning, ErrorKind :: UseUnusedName { .. } => ErrorLevel ::
             Warning, ErrorKind :: UnreachableMatchArm => ErrorLevel ::
             Warning, ErrorKind :: UnreachableOrPattern => ErrorLevel ::
             Warning, ErrorKind :: NoImpureCallInImpureContext { .. } =>
+            ErrorLevel :: Warning, ErrorKind :: CannotMemoize { .. } =>
             ErrorLevel :: Warning, ErrorKind :: FuncWithoutTypeAnnot =>
             ErrorLevel :: Lint, ErrorKind :: LetWithoutTypeAnnot => ErrorLevel
             :: Lint, ErrorKind :: StructWithoutTypeAnnot => ErrorLevel ::
             Lint, ErrorKind :: EnumVariantWithoutTypeAnnot => ErrorLevel ::
             Lint, ErrorKind :: SelfParamNotNamedSelf => ErrorLevel :: Lint,
+            ErrorKind :: CannotCheckConstAssertion(_,) => ErrorLevel :: Lint,
             ErrorKind :: Todo { .. } => ErrorLevel :: Error, ErrorKind ::
             InternalCompilerError { .. } => ErrorLevel :: Error,
         }
@@ -487,12 +671,13 @@
                 buffer.push(0u8); buffer.push(255u8);
                 r#lang_items.encode_impl(buffer);
                 r#generic_params.encode_impl(buffer);
-            }, ErrorKind :: CannotEvaluateConst =>
-            { buffer.push(1u8); buffer.push(4u8); }, ErrorKind ::
-            InvalidRangePattern => { buffer.push(1u8); buffer.push(9u8); },
-            ErrorKind :: InvalidConcatPattern =>
-            { buffer.push(1u8); buffer.push(14u8); }, ErrorKind ::
-            CannotBindName(t0,) =>
+            }, ErrorKind :: InvalidCacheSize =>
+            { buffer.push(1u8); buffer.push(0u8); }, ErrorKind ::
+            CannotEvaluateConst => { buffer.push(1u8); buffer.push(4u8); },
+            ErrorKind :: InvalidRangePattern =>
+            { buffer.push(1u8); buffer.push(9u8); }, ErrorKind ::
+            InvalidConcatPattern => { buffer.push(1u8); buffer.push(14u8); },
+            ErrorKind :: CannotBindName(t0,) =>
             { buffer.push(1u8); buffer.push(19u8); t0.encode_impl(buffer); },
             ErrorKind :: CannotApplyInfixOpToMultipleBindings =>
             { buffer.push(1u8); buffer.push(24u8); }, ErrorKind ::
@@ -704,7 +889,15 @@
                 r#param_index.encode_impl(buffer);
             }, ErrorKind :: MultiplePolyCandidates(t0,) =>
             { buffer.push(1u8); buffer.push(249u8); t0.encode_impl(buffer); },
-            ErrorKind :: UnusedNames { r#names, r#kind, } =>
+            ErrorKind :: ConstAssertionFailed { r#name, } =>
+            {
+                buffer.push(1u8); buffer.push(254u8);
+                r#name.encode_impl(buffer);
+            }, ErrorKind :: DocTestFailed { r#expected, r#got, } =>
+            {
+                buffer.push(2u8); buffer.push(3u8);
+                r#expected.encode_impl(buffer); r#got.encode_impl(buffer);
+            }, ErrorKind :: UnusedNames { r#names, r#kind, } =>
             {
                 buffer.push(19u8); buffer.push(136u8);
                 r#names.encode_impl(buffer); r#kind.encode_impl(buffer);
@@ -720,6 +913,10 @@
             {
                 buffer.push(19u8); buffer.push(146u8);
                 r#context.encode_impl(buffer);
+            }, ErrorKind :: CannotMemoize { r#param, } =>
+            {
+                buffer.push(19u8); buffer.push(151u8);
+                r#param.encode_impl(buffer);
             }, ErrorKind :: FuncWithoutTypeAnnot =>
             { buffer.push(31u8); buffer.push(64u8); }, ErrorKind ::
             LetWithoutTypeAnnot => { buffer.push(31u8); buffer.push(69u8); },
@@ -728,8 +925,10 @@
             EnumVariantWithoutTypeAnnot =>
             { buffer.push(31u8); buffer.push(75u8); }, ErrorKind ::
             SelfParamNotNamedSelf =>
-            { buffer.push(31u8); buffer.push(79u8); }, ErrorKind :: Todo
-            { r#id, r#message, } =>
+            { buffer.push(31u8); buffer.push(79u8); }, ErrorKind ::
+            CannotCheckConstAssertion(t0,) =>
+            { buffer.push(31u8); buffer.push(84u8); t0.encode_impl(buffer); },
+            ErrorKind :: Todo { r#id, r#message, } =>
             {
                 buffer.push(39u8); buffer.push(14u8);
                 r#id.encode_impl(buffer); r#message.encode_impl(buffer);
@@ -878,9 +1077,10 @@
                 = usize :: decode_impl(buffer, cursor) ? ;
                 Ok((ErrorKind :: WrongNumberOfLangItemGenerics
                 { r#lang_items, r#generic_params, }, cursor))
-            }, 260u16 => Ok((ErrorKind :: CannotEvaluateConst, cursor)),
-            265u16 => Ok((ErrorKind :: InvalidRangePattern, cursor)), 270u16
-            => Ok((ErrorKind :: InvalidConcatPattern, cursor)), 275u16 =>
+            }, 256u16 => Ok((ErrorKind :: InvalidCacheSize, cursor)), 260u16
+            => Ok((ErrorKind :: CannotEvaluateConst, cursor)), 265u16 =>
+            Ok((ErrorKind :: InvalidRangePattern, cursor)), 270u16 =>
+            Ok((ErrorKind :: InvalidConcatPattern, cursor)), 275u16 =>
             {
                 let (t0, cursor) = InternedString ::
                 decode_impl(buffer, cursor) ? ;
@@ -1176,6 +1376,18 @@
             {
                 let (t0, cursor) = usize :: decode_impl(buffer, cursor) ? ;
                 Ok((ErrorKind :: MultiplePolyCandidates(t0,), cursor))
+            }, 510u16 =>
+            {
+                let (r#name, cursor) = InternedString ::
+                decode_impl(buffer, cursor) ? ;
+                Ok((ErrorKind :: ConstAssertionFailed { r#name, }, cursor))
+            }, 515u16 =>
+            {
+                let (r#expected, cursor) = DocTestOutcome ::
+                decode_impl(buffer, cursor) ? ; let (r#got, cursor) =
+                DocTestOutcome :: decode_impl(buffer, cursor) ? ;
+                Ok((ErrorKind :: DocTestFailed { r#expected, r#got, },
+                cursor))
             }, 5000u16 =>
             {
                 let (r#names, cursor) = Vec :: < InternedString >::
@@ -1195,11 +1407,21 @@
                 decode_impl(buffer, cursor) ? ;
                 Ok((ErrorKind :: NoImpureCallInImpureContext { r#context, },
                 cursor))
+            }, 5015u16 =>
+            {
+                let (r#param, cursor) = InternedString ::
+                decode_impl(buffer, cursor) ? ;
+                Ok((ErrorKind :: CannotMemoize { r#param, }, cursor))
             }, 8000u16 => Ok((ErrorKind :: FuncWithoutTypeAnnot, cursor)),
             8005u16 => Ok((ErrorKind :: LetWithoutTypeAnnot, cursor)), 8010u16
             => Ok((ErrorKind :: StructWithoutTypeAnnot, cursor)), 8011u16 =>
             Ok((ErrorKind :: EnumVariantWithoutTypeAnnot, cursor)), 8015u16 =>
-            Ok((ErrorKind :: SelfParamNotNamedSelf, cursor)), 9998u16 =>
+            Ok((ErrorKind :: SelfParamNotNamedSelf, cursor)), 8020u16 =>
+            {
+                let (t0, cursor) = ConstEvalFailure ::
+                decode_impl(buffer, cursor) ? ;
+                Ok((ErrorKind :: CannotCheckConstAssertion(t0,), cursor))
+            }, 9998u16 =>
             {
                 let (r#id, cursor) = u32 :: decode_impl(buffer, cursor) ? ;
                 let (r#message, cursor) = String ::
@@ -1213,4 +1435,607 @@
             Err(DecodeError :: InvalidLargeEnumVariant(variant as u32)),
         }
     }
-} 
\ No newline at end of file
+} pub const ERROR_KIND_DOCS : & [ErrorKindDoc] = & [ErrorKindDoc
+{
+    name : "InvalidNumberLiteral", index : 0u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidStringLiteralPrefix", index : 5u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "EmptyIdent", index : 10u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidCharacterInIdent", index : 15u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "WrongNumberOfQuotesInRawStringLiteral", index : 20u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnterminatedStringLiteral", index : 25u16, level : ErrorLevel ::
+    Error, explanation :
+    Some("A string literal is opened, but never closed.\n\n```sodigy, compile_error\nlet s = \"Hello, world!;\n```\n\nClose the literal with the same number of quotes that opened it.\n\n```sodigy\nlet s = \"Hello, world!\";\n```")
+}, ErrorKindDoc
+{
+    name : "NotAllowedCharInFormattedString", index : 30u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnmatchedBraceInFormattedString", index : 35u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "EmptyBraceInFormattedString", index : 40u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "DotDotDot", index : 45u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidCharLiteral", index : 50u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidCharLiteralPrefix", index : 55u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnterminatedCharLiteral", index : 60u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidByteLiteral", index : 65u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidEscape", index : 70u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "EmptyCharLiteral", index : 75u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("A character literal must have exactly one character. If you want an empty text, use a string literal.\n\n```sodigy, compile_error\nlet c = '';\n```\n\n```sodigy\nlet c = ' ';\nlet s = \"\";\n```")
+}, ErrorKindDoc
+{
+    name : "UnterminatedBlockComment", index : 80u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidUtf8", index : 85u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidUnicodeCharacter", index : 90u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidUnicodeEscape", index : 95u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnmatchedGroup", index : 100u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "TooManyQuotes", index : 105u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnclosedDelimiter", index : 110u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedByte", index : 114u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedToken", index : 115u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "WildcardNotAllowed", index : 116u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedEof", index : 120u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedEog", index : 125u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingDocComment", index : 130u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "DocCommentNotAllowed", index : 135u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "DanglingDocComment", index : 136u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "ModuleDocCommentNotAtTop", index : 140u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingDecorator", index : 145u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "DecoratorNotAllowed", index : 150u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "DanglingDecorator", index : 151u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedDecorator", index : 155u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "ModuleDecoratorNotAtTop", index : 160u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingVisibility", index : 165u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotBePublic", index : 170u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "DanglingVisibility", index : 171u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "FunctionWithoutBody", index : 175u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "StructWithoutBody", index : 176u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "EnumWithoutBody", index : 177u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "BlockWithoutValue", index : 180u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "TopLevelDo", index : 181u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "StructWithoutField", index : 185u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "EmptyCurlyBraceBlock", index : 190u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "AmbiguousCurlyBraces", index : 191u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "AmbiguousAngleBrackets", index : 192u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "PositionalArgAfterKeywordArg", index : 195u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "NonDefaultValueAfterDefaultValue", index : 200u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotDeclareInlineModule", index : 205u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InclusiveRangeWithNoEnd", index : 210u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MultipleRestPatterns", index : 215u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "DifferentNameBindingsInOrPattern", index : 220u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidFnType", index : 225u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "EmptyMatchStatement", index : 230u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "RedundantDecorator", index : 235u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidDecorator", index : 240u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingDecoratorArg", index : 245u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedDecoratorArg", index : 250u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "WrongNumberOfLangItemGenerics", index : 255u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidCacheSize", index : 256u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotEvaluateConst", index : 260u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidRangePattern", index : 265u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidConcatPattern", index : 270u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotBindName", index : 275u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotApplyInfixOpToMultipleBindings", index : 280u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotApplyInfixOpToBinding", index : 285u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotAnnotateType", index : 290u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "RedundantNameBinding", index : 295u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnsupportedInfixOpInPattern", index : 300u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "PatternDestructureWithoutNameBindings", index : 301u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "NameCollision", index : 305u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The same name is defined multiple times in the same scope. Sodigy doesn't have overloading.\n\n```sodigy, compile_error\nfn add(a: Int, b: Int) -> Int = a + b;\nfn add(a: Int) -> Int = a + 1;\n```\n\nGive each definition a different name.\n\n```sodigy\nfn add(a: Int, b: Int) -> Int = a + b;\nfn add1(a: Int) -> Int = a + 1;\n```")
+}, ErrorKindDoc
+{
+    name : "CyclicLet", index : 310u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The values of `let` statements depend on each other, so none of them can be evaluated.\n\n```sodigy, compile_error\nlet a: Int = b + 1;\nlet b: Int = a + 1;\n```\n\nOne of them has to be evaluated without the others.\n\n```sodigy\nlet a: Int = 1;\nlet b: Int = a + 1;\n```")
+}, ErrorKindDoc
+{
+    name : "CyclicAlias", index : 315u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "DollarOutsidePipeline", index : 320u16, level : ErrorLevel ::
+    Error, explanation :
+    Some("`$` is the value of the previous stage of a pipeline, so it can only be used inside a pipeline.\n\n```sodigy, compile_error\nlet x: Int = $ + 1;\n```\n\n```sodigy\nlet x: Int = 1 |> $ + 1;\n```")
+}, ErrorKindDoc
+{
+    name : "DisconnectedPipeline", index : 325u16, level : ErrorLevel ::
+    Error, explanation :
+    Some("A stage of a pipeline doesn't use the result of the previous stage (`$`), so the previous stage is meaningless.\n\n```sodigy, compile_error\nlet x: Int = 100 |> 200;\n```\n\n```sodigy\nlet x: Int = 100 |> $ + 200;\n```")
+}, ErrorKindDoc
+{
+    name : "GenericFuncWithoutTypeAnnot", index : 326u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "GenericStructWithoutTypeAnnot", index : 327u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "GenericEnumVariantWithoutTypeAnnot", index : 328u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UndefinedName", index : 330u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The name is not defined in this scope. Check the spelling, or import the name with `use`.\n\n```sodigy, compile_error\nlet x: Int = y + 1;\n```\n\n```sodigy\nlet y: Int = 1;\nlet x: Int = y + 1;\n```")
+}, ErrorKindDoc
+{
+    name : "UndefinedMacro", index : 331u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "EnumVariantInTypeAnnot", index : 335u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "KeywordArgRepeated", index : 340u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "KeywordArgNotAllowed", index : 345u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "AliasResolveRecursionLimitReached", index : 350u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingTypeParameter", index : 355u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedTypeParameter", index : 360u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingKeywordArg", index : 366u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidKeywordArg", index : 370u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingFunctionParameter", index : 375u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedFunctionParameter", index : 380u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "StructFieldRepeated", index : 385u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingStructFields", index : 390u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "InvalidStructFields", index : 395u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MismatchedEnumFieldKind", index : 396u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotAssociateItem", index : 398u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "TooGeneralToAssociateItem", index : 399u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "NotType", index : 400u16, level : ErrorLevel :: Error, explanation
+    : None
+}, ErrorKindDoc
+{
+    name : "NotCallable", index : 404u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "NotStruct", index : 405u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "NotExpr", index : 406u16, level : ErrorLevel :: Error, explanation
+    : None
+}, ErrorKindDoc
+{
+    name : "NotPolyGeneric", index : 410u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotAliasLocalValue", index : 411u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnexpectedType", index : 415u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The type of a value is different from the type that is expected there.\n\n```sodigy, compile_error\nlet x: Int = \"hello\";\n```\n\nFix the value or the type annotation.\n\n```sodigy\nlet x: String = \"hello\";\n```")
+}, ErrorKindDoc
+{
+    name : "WrongNumberOfArgs", index : 416u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("A function is called with a wrong number of arguments. Sodigy doesn't have currying.\n\n```sodigy, compile_error\nfn add(a: Int, b: Int) -> Int = a + b;\nlet x: Int = add(1);\n```\n\nGive all the arguments, or give the parameter a default value.\n\n```sodigy\nfn add(a: Int, b: Int = 1) -> Int = a + b;\nlet x: Int = add(1);\n```")
+}, ErrorKindDoc
+{
+    name : "WrongNumberOfGenericArgs", index : 417u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnnecessaryGenericArgs", index : 418u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "MissingGenericArgs", index : 419u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotInferType", index : 420u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "PartiallyInferedType", index : 425u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotInferGenericType", index : 430u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "PartiallyInferedGenericType", index : 435u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "UnknownField", index : 436u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The type doesn't have a field (or an associated item) with that name.\n\n```sodigy, compile_error\nstruct Point = { x: Int, y: Int };\nlet p: Point = Point { x: 0, y: 0 };\nlet z: Int = p.z;\n```\n\n```sodigy\nstruct Point = { x: Int, y: Int };\nlet p: Point = Point { x: 0, y: 0 };\nlet x: Int = p.x;\n```")
+}, ErrorKindDoc
+{
+    name : "CannotUpdateAssociatedFunc", index : 439u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotApplyInfixOp", index : 440u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotSpecializePolyGeneric", index : 445u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "ImpureCallInPureContext", index : 450u16, level : ErrorLevel ::
+    Error, explanation :
+    Some("A pure function calls a function with an effect, like `random_int`. The effect of a function has to be written in its definition.\n\n```sodigy, compile_error\nuse std.random.random_int;\n\nfn roll(n: Int) -> Int = random_int() % n;\n```\n\n```sodigy\nuse std.random.random_int;\n\nndet fn roll(n: Int) -> Int = random_int() % n;\n```")
+}, ErrorKindDoc
+{
+    name : "NonExhaustiveArms", index : 455u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The arms of a `match` expression don't cover all the possible values. If no arm matches, the program cannot continue, so the compiler rejects it.\nThe error note lists the values that no arm covers, e.g. `..0` and `2..` in the example below.\n\n```sodigy, compile_error\nfn sign(n: Int) -> Int = match n {\n    0 => 0,\n    1 => 1,\n};\n```\n\nAdd arms for the missing values. A wildcard (`_`) matches anything.\n\n```sodigy\nfn sign(n: Int) -> Int = match n {\n    0 => 0,\n    1 => 1,\n    _ => 2,\n};\n```")
+}, ErrorKindDoc
+{
+    name : "RefutableLetPattern", index : 456u16, level : ErrorLevel :: Error,
+    explanation :
+    Some("The pattern of a `let` statement may not match the value. A `let` statement doesn't have a fallback, so its pattern must always match.\n\n```sodigy, compile_error\nlet ($x, 0) = (1, 2);\n```\n\nUse a pattern that always matches, or use `match`.\n\n```sodigy\nlet ($x, _) = (1, 2);\n```")
+}, ErrorKindDoc
+{
+    name : "MultipleModuleFiles", index : 460u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "ModuleFileNotFound", index : 465u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "LibFileNotFound", index : 470u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "SelfParamWithTypeAnnot", index : 475u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "AssociatedFuncWithoutSelfParam", index : 480u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotInferPolyGenericParam", index : 485u16, level : ErrorLevel
+    :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotInferPolyGenericImpl", index : 490u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "PolyImplDifferentNumberOfParams", index : 495u16, level :
+    ErrorLevel :: Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotImplPoly", index : 500u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "MultiplePolyCandidates", index : 505u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "ConstAssertionFailed", index : 510u16, level : ErrorLevel ::
+    Error, explanation : None
+}, ErrorKindDoc
+{
+    name : "DocTestFailed", index : 515u16, level : ErrorLevel :: Error,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnusedNames", index : 5000u16, level : ErrorLevel :: Warning,
+    explanation :
+    Some("A name is defined but never used. It's usually a typo or a leftover.\n\n```sodigy, warning\nfn first(a: Int, b: Int) -> Int = 0;\n```\n\nUse the name, or remove it.\n\n```sodigy\nfn first(a: Int, b: Int) -> Int = a + b;\n```")
+}, ErrorKindDoc
+{
+    name : "UseUnusedName", index : 5001u16, level : ErrorLevel :: Warning,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "UnreachableMatchArm", index : 5005u16, level : ErrorLevel ::
+    Warning, explanation :
+    Some("An arm of a `match` expression can never be reached, because the arms above it already match all the values it matches.\n\n```sodigy, warning\nfn is_zero(n: Int) -> Int = match n {\n    _ => 0,\n    0 => 1,\n};\n```\n\nThe arms are checked from top to bottom, so put the more specific arms first.\n\n```sodigy\nfn is_zero(n: Int) -> Int = match n {\n    0 => 1,\n    _ => 0,\n};\n```")
+}, ErrorKindDoc
+{
+    name : "UnreachableOrPattern", index : 5006u16, level : ErrorLevel ::
+    Warning, explanation : None
+}, ErrorKindDoc
+{
+    name : "NoImpureCallInImpureContext", index : 5010u16, level : ErrorLevel
+    :: Warning, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotMemoize", index : 5015u16, level : ErrorLevel :: Warning,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "FuncWithoutTypeAnnot", index : 8000u16, level : ErrorLevel ::
+    Lint, explanation : None
+}, ErrorKindDoc
+{
+    name : "LetWithoutTypeAnnot", index : 8005u16, level : ErrorLevel :: Lint,
+    explanation : None
+}, ErrorKindDoc
+{
+    name : "StructWithoutTypeAnnot", index : 8010u16, level : ErrorLevel ::
+    Lint, explanation : None
+}, ErrorKindDoc
+{
+    name : "EnumVariantWithoutTypeAnnot", index : 8011u16, level : ErrorLevel
+    :: Lint, explanation : None
+}, ErrorKindDoc
+{
+    name : "SelfParamNotNamedSelf", index : 8015u16, level : ErrorLevel ::
+    Lint, explanation : None
+}, ErrorKindDoc
+{
+    name : "CannotCheckConstAssertion", index : 8020u16, level : ErrorLevel ::
+    Lint, explanation : None
+}, ErrorKindDoc
+{
+    name : "Todo", index : 9998u16, level : ErrorLevel :: Error, explanation :
+    None
+}, ErrorKindDoc
+{
+    name : "InternalCompilerError", index : 9999u16, level : ErrorLevel ::
+    Error, explanation : None
+},] ; 
\ No newline at end of file
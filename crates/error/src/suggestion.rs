@@ -0,0 +1,66 @@
+use sodigy_span::Span;
+
+// A mechanical fix of an error (or a warning): replace the code at `span` with `replacement`.
+// An empty `replacement` removes the code, and a 0-length `span` inserts `replacement` there.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Suggestion {
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: Applicability,
+
+    // e.g. "Remove the unused name `x`."
+    pub message: String,
+}
+
+impl Suggestion {
+    pub fn replace(span: Span, replacement: String, applicability: Applicability, message: String) -> Self {
+        Suggestion { span, replacement, applicability, message }
+    }
+
+    pub fn remove(span: Span, applicability: Applicability, message: String) -> Self {
+        Suggestion { span, replacement: String::new(), applicability, message }
+    }
+
+    // It inserts `s` right before `span`.
+    pub fn insert_before(span: &Span, s: String, applicability: Applicability, message: String) -> Self {
+        Suggestion {
+            span: span.shrink_to_start(),
+            replacement: s,
+            applicability,
+            message,
+        }
+    }
+
+    // It inserts `s` right after `span`.
+    pub fn insert_after(span: &Span, s: String, applicability: Applicability, message: String) -> Self {
+        Suggestion {
+            span: span.shrink_to_end(),
+            replacement: s,
+            applicability,
+            message,
+        }
+    }
+}
+
+// It follows rustc's applicability.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Applicability {
+    // `sodigy fix` applies the suggestion without asking the user.
+    MachineApplicable,
+
+    // The suggestion is likely to be what the user wants, but the user has to check it.
+    MaybeIncorrect,
+
+    // The replacement has placeholders (e.g. `std.panic("TODO")`), which the user has to fill in.
+    HasPlaceholders,
+}
+
+impl Applicability {
+    pub fn render(&self) -> &'static str {
+        match self {
+            Applicability::MachineApplicable => "machine-applicable",
+            Applicability::MaybeIncorrect => "maybe-incorrect",
+            Applicability::HasPlaceholders => "has-placeholders",
+        }
+    }
+}
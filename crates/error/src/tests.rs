@@ -1,4 +1,6 @@
-use crate::{Error, FuncEffect};
+use crate::{Applicability, Error, ErrorKind, FuncEffect, Suggestion, did_you_mean, dump_errors_json};
+use crate::dump::json_string;
+use sodigy_span::Span;
 use std::mem::size_of;
 
 #[test]
@@ -6,3 +8,57 @@ fn size_assertions() {
     assert!(size_of::<Error>() <= 256, "{}", size_of::<Error>());
     assert!(size_of::<FuncEffect>() <= 16, "{}", size_of::<FuncEffect>());
 }
+
+#[test]
+fn json_string_escape() {
+    assert_eq!(json_string("abc"), "\"abc\"");
+    assert_eq!(json_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    assert_eq!(json_string("a\nb\tc"), "\"a\\nb\\tc\"");
+    assert_eq!(json_string("\x1b[31m"), "\"\\u001b[31m\"");
+    assert_eq!(json_string("가"), "\"가\"");
+}
+
+#[test]
+fn dump_errors_json_without_file() {
+    let error = Error {
+        kind: ErrorKind::InternalCompilerError { id: 0 },
+        spans: Span::None.simple_error_with_note("here"),
+        note: Some(String::from("a \"note\"")),
+        suggestions: vec![Suggestion::remove(Span::None, Applicability::MachineApplicable, String::from("Remove it."))],
+    };
+    let warning = Error {
+        kind: ErrorKind::InternalCompilerError { id: 1 },
+        spans: vec![],
+        note: None,
+        suggestions: vec![],
+    };
+    let dumped = dump_errors_json(vec![error.clone()], vec![warning], "target");
+    let lines = dumped.lines().collect::<Vec<_>>();
+
+    // warnings come before errors
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("{\"level\":\"warning\",\"kind\":\"InternalCompilerError\","));
+    assert!(lines[0].ends_with("\"note\":null,\"spans\":[],\"suggestions\":[]}"));
+    assert!(lines[1].starts_with(&format!("{{\"level\":\"error\",\"kind\":\"InternalCompilerError\",\"index\":{},", error.kind.index())));
+    assert!(lines[1].contains("\"note\":\"a \\\"note\\\"\""));
+    assert!(lines[1].contains("\"spans\":[{\"file\":null,\"start\":null,\"end\":null,\"line_start\":null,\"column_start\":null,\"line_end\":null,\"column_end\":null,\"auxiliary\":false,\"note\":\"here\"}],"));
+    assert!(lines[1].ends_with("\"suggestions\":[{\"file\":null,\"start\":null,\"end\":null,\"line_start\":null,\"column_start\":null,\"line_end\":null,\"column_end\":null,\"replacement\":\"\",\"applicability\":\"machine-applicable\",\"message\":\"Remove it.\"}]}"));
+}
+
+#[test]
+fn did_you_mean_suggestions() {
+    let candidates = ["len", "length", "list", "lenght"].iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    assert_eq!(did_you_mean("lenght", &candidates), Some(String::from("Did you mean `length`?")));
+    assert_eq!(did_you_mean("Length", &candidates), Some(String::from("Did you mean `length` or `lenght`?")));
+    assert_eq!(did_you_mean("x", &candidates), None);
+    assert_eq!(did_you_mean("lenght", &[]), None);
+}
+
+#[test]
+fn add_note() {
+    let mut error = Error::todo(0, "", Span::None);
+    error.add_note(String::from("Did you mean `x`?"));
+    error.add_note(String::from("Try `use foo.y;`."));
+    assert_eq!(error.note, Some(String::from("Did you mean `x`? Try `use foo.y;`.")));
+}
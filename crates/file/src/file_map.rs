@@ -6,6 +6,11 @@ use sodigy_fs_api::{FileError, FileErrorKind};
 // A file map is a list of `file_id: u32`, `content_hash: u128`, `module_path: String`, `file_path: String`.
 // The name of the file map file is the last 8 bits of file_id, which is same as the last 8 bits of `hash(module_path)`.
 
+// `intern_string` never returns a hash that starts with 0x7f, so it can be used as a marker.
+// It's the content hash of files that are registered in the previous compilations, but
+// not in the current compilation.
+pub const STALE_CONTENT_HASH: u128 = 0x7f << 120;
+
 pub fn length_file_map(file_map: &[u8], file_map_path: &str) -> Result<usize, FileError> {
     let mut cursor = 0;
     let mut length = 0;
@@ -145,3 +150,43 @@ pub fn search_file_map_by_id(file_map: &[u8], file_id: u32, file_map_path: &str)
         }
     }
 }
+
+// If `file_id` is `None`, it updates the content_hash of every file in the file map.
+pub fn set_content_hash(file_map: &mut [u8], file_id: Option<u32>, content_hash: u128, file_map_path: &str) -> Result<(), FileError> {
+    let mut cursor = 0;
+
+    loop {
+        if file_map.len() == cursor {
+            return Ok(());
+        }
+
+        if cursor + 24 >= file_map.len() {
+            return Err(FileError {
+                kind: FileErrorKind::CannotDecodeFile,
+                given_path: Some(file_map_path.to_string()),
+            });
+        }
+
+        let curr_file_id = u32::from_le_bytes((&file_map[cursor..(cursor + 4)]).try_into().unwrap());
+        cursor += 4;
+
+        if file_id.is_none() || file_id == Some(curr_file_id) {
+            file_map[cursor..(cursor + 16)].copy_from_slice(&content_hash.to_le_bytes());
+        }
+
+        cursor += 16;
+
+        // module_path, file_path
+        for _ in 0..2 {
+            if cursor + 4 > file_map.len() {
+                return Err(FileError {
+                    kind: FileErrorKind::CannotDecodeFile,
+                    given_path: Some(file_map_path.to_string()),
+                });
+            }
+
+            let str_len = u32::from_le_bytes((&file_map[cursor..(cursor + 4)]).try_into().unwrap());
+            cursor += 4 + str_len as usize;
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::std_file::{STD_FILES, STD_FILE_INDEXES};
-use sodigy_fs_api::{FileError, join3, read_bytes};
+use sodigy_fs_api::{FileError, WriteMode, exists, join3, read_bytes, write_bytes};
 use sodigy_string::{InternedString, hash, intern_string, unintern_string};
 use std::fmt;
 use std::fs::File as StdFile;
@@ -13,11 +13,13 @@ mod std_file;
 
 pub use error::GetFilePathError;
 pub use module_path::ModulePath;
-pub use std_file::std_root;
+pub use std_file::{std_modules, std_root};
 
 use file_map::{
+    STALE_CONTENT_HASH,
     length_file_map,
     push_file_map,
+    set_content_hash,
     search_file_map_by_id,
     search_file_map_by_module_path,
 };
@@ -50,6 +52,10 @@ impl File {
         File(0x8000_0000 | id)
     }
 
+    pub fn is_std(&self) -> bool {
+        self.0 >= 0x8000_0000
+    }
+
     pub fn register(
         // `read_bytes(file_path)` should work
         file_path: &str,
@@ -90,7 +96,22 @@ impl File {
             match search_file_map_by_module_path(&file_map, module_path, &file_map_path)? {
                 // If it's already registered, it returns the previous one without updating its content_hash.
                 // That means you cannot update a file while a compilation is going on.
+                Some((file_id, content_hash)) if content_hash != STALE_CONTENT_HASH => {
+                    return Ok(File(file_id));
+                },
+
+                // It's registered by a previous compilation. It keeps the file_id (the cached irs
+                // have spans with the file_id) but reads the file again.
                 Some((file_id, _)) => {
+                    let content = read_bytes(file_path)?;
+                    let content_hash = intern_string(&content, intermediate_dir)?;
+                    set_content_hash(&mut file_map, Some(file_id), content_hash.0, &file_map_path)?;
+                    file_map_fd.set_len(0).map_err(|e| FileError::from_std(e, &file_map_path))?;
+                    file_map_fd.write_all(&file_map).map_err(
+                        |e| FileError::from_std(e, &file_map_path)
+                    )?;
+                    file_map_fd.unlock().map_err(|e| FileError::from_std(e, &file_map_path))?;
+
                     return Ok(File(file_id));
                 },
                 None => ((length_file_map(&file_map, &file_map_path)? as u32) << 8) | module_path_hash,
@@ -108,6 +129,28 @@ impl File {
         Ok(File(file_id))
     }
 
+    // It has to be called before a compilation starts. Otherwise, the compiler would
+    // see the contents of the files at the previous compilation.
+    pub fn clear_content_hashes(intermediate_dir: &str) -> Result<(), FileError> {
+        for module_path_hash in 0..256 {
+            let file_map_path = join3(
+                intermediate_dir,
+                "file_map",
+                &format!("{module_path_hash:02x}"),
+            )?;
+
+            if !exists(&file_map_path) {
+                continue;
+            }
+
+            let mut file_map = read_bytes(&file_map_path)?;
+            set_content_hash(&mut file_map, None, STALE_CONTENT_HASH, &file_map_path)?;
+            write_bytes(&file_map_path, &file_map, WriteMode::CreateOrTruncate)?;
+        }
+
+        Ok(())
+    }
+
     pub fn from_module_path(module_path: &str, intermediate_dir: &str) -> Result<Option<File>, FileError> {
         let module_path_hash = (hash(module_path.as_bytes()) & 0xff) as u32;
         let file_map_path = join3(
@@ -101,3 +101,9 @@ pub fn std_root() -> (ModulePath, FileOrStd) {
     let s = &STD_FILES[*STD_ROOT_INDEX.deref()];
     (s.module_path.clone(), s.file_path.clone())
 }
+
+pub fn std_modules() -> Vec<(ModulePath, FileOrStd)> {
+    STD_FILES.iter().map(
+        |s| (s.module_path.clone(), s.file_path.clone())
+    ).collect()
+}
@@ -0,0 +1,104 @@
+use sodigy_error::Error;
+use sodigy_file::File;
+use sodigy_lex::lex;
+use sodigy_parse::parse;
+use sodigy_span::Span;
+use sodigy_token::{Token, TokenKind};
+
+mod node;
+mod printer;
+
+#[cfg(test)]
+mod tests;
+
+use node::{build_nodes, offsets};
+use printer::Printer;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+    pub max_width: usize,
+    pub indent: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_width: 100,
+            indent: 4,
+        }
+    }
+}
+
+/// It formats a Sodigy source file. If the file has a syntax error, it returns the error
+/// and doesn't format the file.
+///
+/// The formatter only changes whitespaces. It makes sure that by lexing the formatted
+/// code again and comparing the tokens and the comments with the original ones.
+pub fn format(
+    file: File,
+    input: &[u8],
+    intermediate_dir: &str,
+    config: &Config,
+) -> Result<String, Vec<Error>> {
+    let lex_session = lex(file, input.to_vec(), intermediate_dir.to_string(), false, false);
+
+    if !lex_session.errors.is_empty() {
+        return Err(lex_session.errors);
+    }
+
+    let tokens = lex_session.tokens.clone();
+    let comments = lex_session.comments.iter().map(offsets).collect::<Vec<_>>();
+    let file_span = lex_session.file_span();
+    let parse_session = parse(lex_session, file_span.clone());
+
+    if !parse_session.errors.is_empty() {
+        return Err(parse_session.errors);
+    }
+
+    let (nodes, trailing_comments) = build_nodes(&tokens, &comments, input, 0, input.len());
+    let mut printer = Printer::new(config, false);
+    printer.print_file(&nodes, &trailing_comments);
+    let output = printer.buffer;
+
+    // It's a bug of the formatter if the formatted code has different tokens.
+    let new_lex_session = lex(file, output.as_bytes().to_vec(), intermediate_dir.to_string(), false, false);
+
+    if !new_lex_session.errors.is_empty() ||
+        flatten_tokens(&tokens, input) != flatten_tokens(&new_lex_session.tokens, output.as_bytes()) ||
+        render_comments(&comments, input) != render_comments(&new_lex_session.comments.iter().map(offsets).collect::<Vec<_>>(), output.as_bytes())
+    {
+        return Err(vec![Error::ice(78614, file_span)]);
+    }
+
+    Ok(output)
+}
+
+fn flatten_tokens(tokens: &[Token], source: &[u8]) -> Vec<String> {
+    let mut result = vec![];
+
+    for token in tokens.iter() {
+        match &token.kind {
+            TokenKind::Group { delim, tokens } => {
+                result.push(delim.open().to_string());
+                result.extend(flatten_tokens(tokens, source));
+                result.push(delim.close().to_string());
+            },
+            _ => {
+                result.push(render_span(&token.span, source));
+            },
+        }
+    }
+
+    result
+}
+
+fn render_comments(comments: &[(usize, usize)], source: &[u8]) -> Vec<String> {
+    comments.iter().map(
+        |(start, end)| String::from_utf8_lossy(&source[*start..*end]).to_string()
+    ).collect()
+}
+
+fn render_span(span: &Span, source: &[u8]) -> String {
+    let (start, end) = offsets(span);
+    String::from_utf8_lossy(&source[start..end]).to_string()
+}
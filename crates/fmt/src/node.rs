@@ -0,0 +1,150 @@
+use sodigy_span::Span;
+use sodigy_token::{Delim, Token, TokenKind};
+
+// The formatter doesn't look at the AST. It re-arranges the tokens (and the
+// comments between them), and prints each token exactly as it's written in the
+// source code. So the only thing that can change is the whitespace.
+pub(crate) struct Node {
+    pub kind: NodeKind,
+
+    // comments between the previous node (or the opening delimiter) and this node
+    pub comments: Vec<Comment>,
+
+    // Whether there's a whitespace (or a comment) between the previous node and this node in the source code.
+    pub whitespace_before: bool,
+
+    // The line breaks between the last comment (or the previous node) and this node in the source code.
+    pub newline_before: bool,
+    pub blank_line_before: bool,
+}
+
+pub(crate) enum NodeKind {
+    Leaf {
+        kind: TokenKind,
+        text: String,
+    },
+    Group {
+        delim: Delim,
+        children: Vec<Node>,
+
+        // comments between the last child and the closing delimiter
+        trailing_comments: Vec<Comment>,
+
+        // Whether the group is multi-line in the source code.
+        multiline: bool,
+    },
+}
+
+pub(crate) struct Comment {
+    pub text: String,
+
+    // `//` comment
+    pub line: bool,
+
+    pub newline_before: bool,
+    pub blank_line_before: bool,
+}
+
+impl Node {
+    // The first character of the node. It's used to check if 2 adjacent nodes can be merged into 1 token.
+    pub fn first_char(&self) -> char {
+        match &self.kind {
+            NodeKind::Leaf { text, .. } => text.chars().next().unwrap_or(' '),
+            NodeKind::Group { delim, .. } => delim.open().chars().next().unwrap(),
+        }
+    }
+
+    pub fn last_char(&self) -> char {
+        match &self.kind {
+            NodeKind::Leaf { text, .. } => text.chars().last().unwrap_or(' '),
+            NodeKind::Group { delim, .. } => delim.close().chars().last().unwrap(),
+        }
+    }
+}
+
+// It returns the nodes and the comments after the last node.
+pub(crate) fn build_nodes(
+    tokens: &[Token],
+    comments: &[(usize, usize)],
+    source: &[u8],
+    start: usize,
+    end: usize,
+) -> (Vec<Node>, Vec<Comment>) {
+    let mut nodes = Vec::with_capacity(tokens.len());
+    let mut cursor = start;
+
+    for token in tokens.iter() {
+        let (token_start, token_end) = offsets(&token.span);
+        let (comments_before, gap_start) = collect_comments(comments, source, cursor, token_start);
+        let gap = &source[gap_start..token_start];
+        let kind = match &token.kind {
+            TokenKind::Group { delim, tokens } => {
+                let inner_start = token_start + delim.open().len();
+                let inner_end = token_end - delim.close().len();
+                let (children, trailing_comments) = build_nodes(tokens, comments, source, inner_start, inner_end);
+
+                NodeKind::Group {
+                    delim: *delim,
+                    children,
+                    trailing_comments,
+                    multiline: source[inner_start..inner_end].contains(&b'\n'),
+                }
+            },
+            kind => NodeKind::Leaf {
+                kind: kind.clone(),
+                text: String::from_utf8_lossy(&source[token_start..token_end]).to_string(),
+            },
+        };
+
+        nodes.push(Node {
+            kind,
+            comments: comments_before,
+            whitespace_before: cursor < token_start,
+            newline_before: count_newlines(gap) > 0,
+            blank_line_before: count_newlines(gap) > 1,
+        });
+        cursor = token_end;
+    }
+
+    let (trailing_comments, _) = collect_comments(comments, source, cursor, end);
+    (nodes, trailing_comments)
+}
+
+// It returns the comments in `start..end` and where the last comment ends.
+fn collect_comments(
+    comments: &[(usize, usize)],
+    source: &[u8],
+    start: usize,
+    end: usize,
+) -> (Vec<Comment>, usize) {
+    let mut result = vec![];
+    let mut cursor = start;
+
+    for (comment_start, comment_end) in comments.iter() {
+        if *comment_start < start || *comment_end > end {
+            continue;
+        }
+
+        let gap = &source[cursor..*comment_start];
+        result.push(Comment {
+            text: String::from_utf8_lossy(&source[*comment_start..*comment_end]).to_string(),
+            line: source[*comment_start..].starts_with(b"//"),
+            newline_before: count_newlines(gap) > 0,
+            blank_line_before: count_newlines(gap) > 1,
+        });
+        cursor = *comment_end;
+    }
+
+    (result, cursor)
+}
+
+fn count_newlines(s: &[u8]) -> usize {
+    s.iter().filter(|b| **b == b'\n').count()
+}
+
+pub(crate) fn offsets(span: &Span) -> (usize, usize) {
+    match span.get_offset_and_length() {
+        Some((offset, length)) => (offset as usize, (offset + length) as usize),
+        None => (0, 0),
+    }
+}
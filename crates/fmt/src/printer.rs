@@ -0,0 +1,435 @@
+use crate::Config;
+use crate::node::{Comment, Node, NodeKind};
+use sodigy_token::{Delim, Keyword, Punct, TokenKind};
+
+// What to print before the next token.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum Pending {
+    Nothing,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+pub(crate) struct Printer<'c> {
+    config: &'c Config,
+    pub buffer: String,
+    indent: usize,
+    pending: Pending,
+
+    // In flat mode, it prints everything in a single line.
+    // If it has to break a line, it sets `failed`.
+    flat: bool,
+    pub failed: bool,
+}
+
+impl<'c> Printer<'c> {
+    pub fn new(config: &'c Config, flat: bool) -> Self {
+        Printer {
+            config,
+            buffer: String::new(),
+            indent: 0,
+            pending: Pending::Nothing,
+            flat,
+            failed: false,
+        }
+    }
+
+    pub fn print_file(&mut self, nodes: &[Node], trailing_comments: &[Comment]) {
+        self.print_sequence(nodes, trailing_comments, None);
+
+        if !self.buffer.is_empty() {
+            self.buffer.push('\n');
+        }
+    }
+
+    fn space(&mut self) {
+        self.pending = self.pending.max(Pending::Space);
+    }
+
+    fn newline(&mut self, blank_line: bool) {
+        if self.flat {
+            self.failed = true;
+        }
+
+        self.pending = self.pending.max(if blank_line { Pending::BlankLine } else { Pending::Newline });
+    }
+
+    fn write(&mut self, s: &str) {
+        // It never starts a file with whitespaces.
+        if !self.buffer.is_empty() {
+            match self.pending {
+                Pending::Nothing => {},
+                Pending::Space => {
+                    self.buffer.push(' ');
+                },
+                Pending::Newline | Pending::BlankLine => {
+                    self.buffer.push('\n');
+
+                    if self.pending == Pending::BlankLine {
+                        self.buffer.push('\n');
+                    }
+
+                    self.buffer.push_str(&" ".repeat(self.indent));
+                },
+            }
+        }
+
+        if self.flat && s.contains('\n') {
+            self.failed = true;
+        }
+
+        self.pending = Pending::Nothing;
+        self.buffer.push_str(s);
+    }
+
+    // The column where the next token will be printed at.
+    fn next_column(&self) -> usize {
+        match self.pending {
+            Pending::Nothing => self.column(),
+            Pending::Space => self.column() + 1,
+            Pending::Newline | Pending::BlankLine => self.indent,
+        }
+    }
+
+    fn column(&self) -> usize {
+        match self.buffer.rfind('\n') {
+            Some(i) => self.buffer[(i + 1)..].chars().count(),
+            None => self.buffer.chars().count(),
+        }
+    }
+
+    fn print_comments(&mut self, comments: &[Comment], first_in_group: bool) {
+        for (i, comment) in comments.iter().enumerate() {
+            if comment.newline_before {
+                self.newline(comment.blank_line_before && !(first_in_group && i == 0));
+            }
+
+            // `let x = 3;  // a trailing comment`
+            else if comment.line && !self.buffer.is_empty() && self.pending <= Pending::Space {
+                self.pending = Pending::Nothing;
+                self.write(&format!("  {}", comment.text));
+                self.newline(false);
+                continue;
+            }
+
+            else {
+                self.space();
+            }
+
+            self.write(&comment.text);
+
+            if comment.line {
+                self.newline(false);
+            }
+        }
+    }
+
+    // Top-level items, statements in a block, fields of a struct, arms of a match, elements of a list, ...
+    // If it's not flat, each statement is printed in a separate line.
+    fn print_sequence(&mut self, nodes: &[Node], trailing_comments: &[Comment], delim: Option<Delim>) {
+        let statements = split_statements(nodes);
+
+        for (i, statement) in statements.iter().enumerate() {
+            let first = &statement[0];
+            self.print_comments(&first.comments, delim.is_some() && i == 0);
+
+            if self.flat {
+                if i > 0 || delim == Some(Delim::Brace) {
+                    self.space();
+                }
+            }
+
+            // `#[poly] fn foo() = ...;` can remain in a single line.
+            else if i > 0 && is_decorator(statements[i - 1].last().unwrap()) && !first.newline_before && first.comments.is_empty() {
+                self.space();
+            }
+
+            else {
+                self.newline(first.blank_line_before && (i > 0 || !first.comments.is_empty()));
+            }
+
+            self.print_statement(statement);
+        }
+
+        self.print_comments(trailing_comments, delim.is_some() && statements.is_empty());
+    }
+
+    fn print_statement(&mut self, nodes: &[Node]) {
+        let base_indent = self.indent;
+        let break_pipelines = !self.flat && self.should_break_pipelines(nodes);
+
+        for (i, node) in nodes.iter().enumerate() {
+            if i > 0 {
+                // The comment breaks the statement, so the rest of the statement is indented.
+                if node.comments.iter().any(|comment| comment.newline_before) {
+                    self.indent = base_indent + self.config.indent;
+                }
+
+                self.print_comments(&node.comments, false);
+
+                // It keeps the line breaks of method chains.
+                if (break_pipelines && is_punct(node, Punct::Pipeline)) ||
+                    (!self.flat && is_punct(node, Punct::Dot) && node.newline_before)
+                {
+                    self.indent = base_indent + self.config.indent;
+                    self.newline(false);
+                }
+
+                // It keeps the line breaks before `else`.
+                else if !self.flat && is_keyword(node, Keyword::Else) && node.newline_before {
+                    self.newline(node.blank_line_before);
+                }
+
+                else if needs_space(&nodes[i - 1], node) {
+                    self.space();
+                }
+            }
+
+            self.print_node(node);
+        }
+
+        self.indent = base_indent;
+    }
+
+    fn print_node(&mut self, node: &Node) {
+        match &node.kind {
+            NodeKind::Leaf { kind, text } => {
+                self.write(text);
+
+                if let TokenKind::DocComment { .. } = kind {
+                    self.newline(false);
+                }
+            },
+            NodeKind::Group { delim, children, trailing_comments, multiline } => {
+                self.print_group(*delim, children, trailing_comments, *multiline);
+            },
+        }
+    }
+
+    fn print_group(&mut self, delim: Delim, children: &[Node], trailing_comments: &[Comment], multiline: bool) {
+        let (open, close) = delim.markers();
+
+        if children.is_empty() && trailing_comments.is_empty() {
+            self.write(open);
+            self.write(close);
+            return;
+        }
+
+        // A multi-line block remains multi-line, and so does a multi-line list with a trailing comma.
+        let forced_multiline = multiline && (
+            delim == Delim::Brace ||
+            children.last().map(|child| is_punct(child, Punct::Comma)).unwrap_or(false)
+        );
+
+        if self.flat {
+            if forced_multiline {
+                self.failed = true;
+            }
+
+            self.write(open);
+            self.print_sequence(children, trailing_comments, Some(delim));
+
+            if delim == Delim::Brace {
+                self.space();
+            }
+
+            self.write(close);
+            return;
+        }
+
+        if !forced_multiline {
+            let mut flat = Printer::new(self.config, true);
+            flat.print_group(delim, children, trailing_comments, multiline);
+
+            if !flat.failed && self.next_column() + flat.buffer.chars().count() <= self.config.max_width {
+                self.write(&flat.buffer);
+                return;
+            }
+
+            // `foo(Person { ... })` hugs the multi-line block instead of breaking the parenthesis.
+            if delim != Delim::Brace &&
+                trailing_comments.is_empty() &&
+                split_statements(children).len() == 1 &&
+                children.iter().all(|child| child.comments.is_empty()) &&
+                let Some(Node { kind: NodeKind::Group { delim: Delim::Brace, multiline: true, .. }, .. }) = children.last()
+            {
+                self.write(open);
+                self.print_statement(children);
+                self.write(close);
+                return;
+            }
+        }
+
+        self.write(open);
+        let indent = self.indent;
+        self.indent += self.config.indent;
+        self.print_sequence(children, trailing_comments, Some(delim));
+        self.indent = indent;
+        self.newline(false);
+        self.write(close);
+    }
+
+    // `a |> f($) |> g($)` is broken into multiple lines if it's too long,
+    // or if it's already broken into multiple lines.
+    fn should_break_pipelines(&self, nodes: &[Node]) -> bool {
+        let pipelines = nodes.iter().skip(1).filter(|node| is_punct(node, Punct::Pipeline)).collect::<Vec<_>>();
+
+        if pipelines.is_empty() {
+            return false;
+        }
+
+        if pipelines.iter().any(|node| node.newline_before) {
+            return true;
+        }
+
+        let mut flat = Printer::new(self.config, true);
+        flat.print_statement(nodes);
+        !flat.failed && self.next_column() + flat.buffer.chars().count() > self.config.max_width
+    }
+}
+
+// A statement ends with `;` or `,`. Doc comments and decorators are separate statements
+// because they are printed in separate lines.
+fn split_statements(nodes: &[Node]) -> Vec<&[Node]> {
+    let mut result = vec![];
+    let mut start = 0;
+
+    // `Result<Int, String>` must not be splitted. It doesn't know whether `<` is a
+    // less-than operator or a generic, so it guesses from the whitespaces.
+    let mut angle_depth = 0;
+
+    for (i, node) in nodes.iter().enumerate() {
+        let end_of_statement = match &node.kind {
+            // `foo<T>`, `foo.<T>`
+            NodeKind::Leaf { kind: TokenKind::Punct(Punct::Lt), .. } => {
+                if i > 0 && !node.whitespace_before && matches!(
+                    nodes[i - 1].kind,
+                    NodeKind::Leaf { kind: TokenKind::Ident(_) | TokenKind::Punct(Punct::Dot), .. },
+                ) {
+                    angle_depth += 1;
+                }
+
+                false
+            },
+            NodeKind::Leaf { kind: TokenKind::Punct(Punct::Gt), .. } => {
+                angle_depth = angle_depth.max(1) - 1;
+                false
+            },
+            // `Option<Option<Int>>`
+            NodeKind::Leaf { kind: TokenKind::Punct(Punct::Shr), .. } => {
+                angle_depth = angle_depth.max(2) - 2;
+                false
+            },
+            NodeKind::Leaf { kind: TokenKind::Punct(Punct::Semicolon), .. } => true,
+            NodeKind::Leaf { kind: TokenKind::Punct(Punct::Comma), .. } => angle_depth == 0,
+            NodeKind::Leaf { kind: TokenKind::DocComment { .. }, .. } => true,
+            NodeKind::Group { delim: Delim::Decorator | Delim::ModuleDecorator, .. } => true,
+            _ => false,
+        };
+
+        if end_of_statement {
+            result.push(&nodes[start..(i + 1)]);
+            start = i + 1;
+            angle_depth = 0;
+        }
+    }
+
+    if start < nodes.len() {
+        result.push(&nodes[start..]);
+    }
+
+    result
+}
+
+fn needs_space(prev: &Node, next: &Node) -> bool {
+    let space = prefers_space(prev, next);
+
+    // 2 tokens that are adjacent in the source code can remain adjacent.
+    // Otherwise, they might be lexed as a single token (e.g. `<` and `=`).
+    space || (next.whitespace_before && can_be_merged(prev.last_char(), next.first_char()))
+}
+
+fn prefers_space(prev: &Node, next: &Node) -> bool {
+    match (punct(prev), punct(next)) {
+        (_, Some(Punct::Comma | Punct::Semicolon | Punct::Colon)) => {
+            return false;
+        },
+        (Some(Punct::Comma | Punct::Semicolon | Punct::Colon), _) => {
+            return true;
+        },
+        (Some(Punct::Dot), _) | (_, Some(Punct::Dot)) => {
+            return false;
+        },
+        _ => {},
+    }
+
+    // calls and indexes: `foo(x)`, `foo[0]`, `Fn(Int) -> Int`
+    if let NodeKind::Group { delim: Delim::Parenthesis | Delim::Bracket, .. } = &next.kind {
+        match &prev.kind {
+            NodeKind::Leaf { kind: TokenKind::Ident(_) | TokenKind::String { .. } | TokenKind::FormattedString { .. }, .. } |
+            NodeKind::Group { delim: Delim::Parenthesis | Delim::Bracket, .. } => {
+                return false;
+            },
+            NodeKind::Leaf { kind: TokenKind::Keyword(_), .. } => {
+                return true;
+            },
+            _ => {},
+        }
+    }
+
+    if is_binary_operator(prev) || is_binary_operator(next) {
+        return true;
+    }
+
+    // It doesn't know whether `-`, `<`, `>>`, `|`, `..`, ... are unary, binary or part of a
+    // generic/pattern, so it keeps the original whitespace.
+    if punct(prev).is_some() || punct(next).is_some() {
+        return next.whitespace_before;
+    }
+
+    true
+}
+
+fn punct(node: &Node) -> Option<Punct> {
+    match &node.kind {
+        NodeKind::Leaf { kind: TokenKind::Punct(p), .. } => Some(*p),
+        _ => None,
+    }
+}
+
+fn is_punct(node: &Node, p: Punct) -> bool {
+    punct(node) == Some(p)
+}
+
+fn is_keyword(node: &Node, k: Keyword) -> bool {
+    matches!(&node.kind, NodeKind::Leaf { kind: TokenKind::Keyword(keyword), .. } if *keyword == k)
+}
+
+fn is_decorator(node: &Node) -> bool {
+    matches!(&node.kind, NodeKind::Group { delim: Delim::Decorator | Delim::ModuleDecorator, .. })
+}
+
+fn is_binary_operator(node: &Node) -> bool {
+    matches!(
+        punct(node),
+        Some(
+            Punct::Assign | Punct::Eq | Punct::Neq | Punct::Leq | Punct::Geq |
+            Punct::AndAnd | Punct::OrOr | Punct::Arrow | Punct::ReturnType | Punct::Pipeline |
+            Punct::Concat | Punct::Append | Punct::Prepend |
+            Punct::Add | Punct::Mul | Punct::Div | Punct::Rem |
+            Punct::Xor | Punct::And
+        ),
+    )
+}
+
+// It's conservative: it might say `true` for 2 characters that can never be merged.
+fn can_be_merged(prev: char, next: char) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || !c.is_ascii();
+    let is_punct = |c: char| "!#$%&*+,-./:;<=>?@\\^|`".contains(c);
+
+    (is_word(prev) && (is_word(next) || next == '"' || next == '\'' || next == '#')) ||
+    (prev.is_ascii_digit() && next == '.') ||
+    (prev == '.' && next.is_ascii_digit()) ||
+    (is_punct(prev) && is_punct(next))
+}
@@ -0,0 +1,123 @@
+use crate::{Config, format};
+use sodigy_file::File;
+use sodigy_fs_api::{create_dir_all, exists, extension, read_bytes, read_dir};
+use sodigy_lex::lex;
+use sodigy_parse::parse;
+
+// It's under `target/` so that running the tests doesn't leave untracked files behind.
+const INTERMEDIATE_DIR: &str = "target/fmt-test";
+
+fn init_intermediate_dir() {
+    for dir in ["target/fmt-test/str", "target/fmt-test/num"] {
+        if !exists(dir) {
+            create_dir_all(dir).unwrap();
+        }
+    }
+}
+
+fn fmt(s: &str) -> String {
+    init_intermediate_dir();
+    format(File(0), s.as_bytes(), INTERMEDIATE_DIR, &Config::default()).unwrap()
+}
+
+#[test]
+fn whitespaces() {
+    assert_eq!(fmt("fn add(a:Int,b :Int)->Int=a+b;"), "fn add(a: Int, b: Int) -> Int = a + b;\n");
+    assert_eq!(fmt("let   x=foo .bar( 1 ,2 )[ 0 ];\n\n\n\nlet y = -x;"), "let x = foo.bar(1, 2)[0];\n\nlet y = -x;\n");
+    assert_eq!(fmt("let x = 0..10;"), "let x = 0..10;\n");
+    assert_eq!(fmt("let x:Result<Int,String> =Ok(3);"), "let x: Result<Int, String> = Ok(3);\n");
+}
+
+#[test]
+fn string_literals() {
+    assert_eq!(fmt("let x=f\"a{w}b\";"), "let x = f\"a{w}b\";\n");
+    assert_eq!(fmt("let x=[f\"{w}\",fr\"{ w }\",f\"\",b\"\",r\"\"];"), "let x = [f\"{w}\", fr\"{ w }\", f\"\", b\"\", r\"\"];\n");
+}
+
+#[test]
+fn comments() {
+    let s = "// a comment\nlet x = 3;  // trailing comment\n\n/* block */ let y = {\n    // inside a block\n    x\n};\n";
+    assert_eq!(fmt(s), "// a comment\nlet x = 3;  // trailing comment\n\n/* block */\nlet y = {\n    // inside a block\n    x\n};\n");
+}
+
+#[test]
+fn line_breaks() {
+    // A multi-line block remains multi-line, and a single-line one remains single-line.
+    assert_eq!(fmt("let x = {\nlet y = 3; y };"), "let x = {\n    let y = 3;\n    y\n};\n");
+    assert_eq!(fmt("struct P = { x: Int, y: Int };"), "struct P = { x: Int, y: Int };\n");
+    assert_eq!(
+        fmt("fn f(n: Int) -> Int = match n { 0 => 1,\n_ => 2 };"),
+        "fn f(n: Int) -> Int = match n {\n    0 => 1,\n    _ => 2\n};\n",
+    );
+
+    // decorators and doc comments have their own lines
+    assert_eq!(fmt("/// doc\n#[memoize]\nfn f(n: Int) -> Int = n;"), "/// doc\n#[memoize]\nfn f(n: Int) -> Int = n;\n");
+    assert_eq!(fmt("/// doc\n#[memoize]   fn f(n: Int) -> Int = n;"), "/// doc\n#[memoize] fn f(n: Int) -> Int = n;\n");
+
+    // method chains, `else` and a block in a call
+    assert_eq!(fmt("let x = xs\n.map(f)\n    .len();"), "let x = xs\n    .map(f)\n    .len();\n");
+    assert_eq!(fmt("let x = if c { 1 }\n\nelse { 2 };"), "let x = if c { 1 }\n\nelse { 2 };\n");
+    assert_eq!(fmt("let p = f(P {\n  x: 1,\n});"), "let p = f(P {\n    x: 1,\n});\n");
+
+    // long argument lists
+    let long_args = "let x = foo(aaaaaaaaaaaaaaaaaaaa, bbbbbbbbbbbbbbbbbbbb, cccccccccccccccccccc, dddddddddddddddddddd, eeeeeeeeeeeeeeeeeeee);";
+    assert_eq!(
+        fmt(long_args),
+        "let x = foo(\n    aaaaaaaaaaaaaaaaaaaa,\n    bbbbbbbbbbbbbbbbbbbb,\n    cccccccccccccccccccc,\n    dddddddddddddddddddd,\n    eeeeeeeeeeeeeeeeeeee\n);\n",
+    );
+
+    // long pipelines
+    let long_pipeline = "fn f(n: Int) = n |> aaaaaaaaaaaaaaaaaaaa($) |> bbbbbbbbbbbbbbbbbbbb($) |> cccccccccccccccccccc($) |> dddddddddddddddddddd($);";
+    assert_eq!(
+        fmt(long_pipeline),
+        "fn f(n: Int) = n\n    |> aaaaaaaaaaaaaaaaaaaa($)\n    |> bbbbbbbbbbbbbbbbbbbb($)\n    |> cccccccccccccccccccc($)\n    |> dddddddddddddddddddd($);\n",
+    );
+    assert_eq!(fmt("fn f(n: Int) = n\n|> $ + 1 |> $ + 2;"), "fn f(n: Int) = n\n    |> $ + 1\n    |> $ + 2;\n");
+}
+
+#[test]
+fn syntax_error() {
+    init_intermediate_dir();
+    assert!(format(File(0), b"let x = ;", INTERMEDIATE_DIR, &Config::default()).is_err());
+    assert!(format(File(0), b"let x = (3;", INTERMEDIATE_DIR, &Config::default()).is_err());
+}
+
+// Every test file that lexes and parses must be formatted without changing its tokens
+// (`format` checks that), and formatting the formatted code again must not change anything.
+#[test]
+fn round_trip_test_files() {
+    init_intermediate_dir();
+    let mut formatted = 0;
+
+    for path in read_dir("../../tests/compile-and-run", true).unwrap() {
+        if extension(&path).unwrap().as_deref() != Some("sdg") {
+            continue;
+        }
+
+        let input = read_bytes(&path).unwrap();
+
+        // Some tests are for syntax errors, and some of them even panic the parser.
+        let lex_session = lex(File(0), input.clone(), INTERMEDIATE_DIR.to_string(), false, false);
+
+        if !lex_session.errors.is_empty() {
+            continue;
+        }
+
+        let file_span = lex_session.file_span();
+        let Ok(parse_session) = std::panic::catch_unwind(|| parse(lex_session, file_span)) else { continue };
+
+        if !parse_session.errors.is_empty() {
+            continue;
+        }
+
+        let output = match format(File(0), &input, INTERMEDIATE_DIR, &Config::default()) {
+            Ok(output) => output,
+            Err(e) => panic!("{path}: {e:?}"),
+        };
+        let output2 = format(File(0), output.as_bytes(), INTERMEDIATE_DIR, &Config::default()).unwrap();
+        assert_eq!(output, output2, "{path}");
+        formatted += 1;
+    }
+
+    assert!(formatted > 100);
+}
@@ -120,6 +120,7 @@ impl Alias {
                     kind: ErrorKind::AliasResolveRecursionLimitReached,
                     spans: error_spans,
                     note: None,
+                    suggestions: vec![],
                 });
                 return Err(());
             }
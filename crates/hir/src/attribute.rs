@@ -16,7 +16,7 @@ use crate::{
     Type,
     Use,
 };
-use sodigy_error::{Error, ErrorKind, ErrorToken, ItemKind, comma_list_strs};
+use sodigy_error::{Applicability, Error, ErrorKind, ErrorToken, ItemKind, Suggestion, comma_list_strs, did_you_mean};
 use sodigy_name_analysis::{IdentWithOrigin, NameOrigin};
 use sodigy_parse::{self as ast, DocComment};
 use sodigy_span::{RenderableSpan, Span};
@@ -111,6 +111,7 @@ impl Session {
                             },
                         ],
                         note: None,
+                        suggestions: vec![],
                     });
                     return Err(());
                 }
@@ -139,6 +140,7 @@ impl Session {
                         },
                     ],
                     note: None,
+                    suggestions: vec![],
                 });
                 return Err(());
             }
@@ -183,6 +185,7 @@ impl Attribute {
                     kind: ErrorKind::MissingDocComment,
                     spans: item_keyword_span.simple_error(),
                     note: rule.doc_comment_error_note.clone(),
+                    suggestions: vec![],
                 });
                 None
             },
@@ -203,6 +206,7 @@ impl Attribute {
                         },
                     ],
                     note: rule.doc_comment_error_note.clone(),
+                    suggestions: vec![],
                 });
                 None
             },
@@ -215,6 +219,7 @@ impl Attribute {
                     kind: ErrorKind::MissingVisibility,
                     spans: item_keyword_span.simple_error(),
                     note: rule.visibility_error_note.clone(),
+                    suggestions: vec![],
                 });
                 Visibility::private()
             },
@@ -235,6 +240,7 @@ impl Attribute {
                         },
                     ],
                     note: rule.visibility_error_note.clone(),
+                    suggestions: vec![],
                 });
 
                 match Visibility::from_ast(ast_visibility, session) {
@@ -258,7 +264,7 @@ impl Attribute {
         let mut decorators = HashMap::with_capacity(ast_attribute.decorators.len());
 
         // for error messages
-        let mut spans_by_name: HashMap<InternedString, Vec<Span>> = HashMap::new();
+        let mut decorators_by_name: HashMap<InternedString, Vec<&ast::Decorator>> = HashMap::new();
 
         for ast_decorator in ast_attribute.decorators.iter() {
             match rule.decorators.get(&ast_decorator.name) {
@@ -269,6 +275,7 @@ impl Attribute {
                             kind: ErrorKind::UnexpectedDecorator(ast_decorator.name),
                             spans: ast_decorator.name_span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -282,6 +289,7 @@ impl Attribute {
                                 },
                                 spans: ast_decorator.name_span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             });
                         },
                         (Requirement::Never, Some(ast_args)) => {
@@ -304,6 +312,7 @@ impl Attribute {
                                     },
                                 ],
                                 note: None,
+                                suggestions: vec![],
                             });
                         },
                         (_, Some(ast_args)) => {
@@ -326,6 +335,7 @@ impl Attribute {
                                                     kind: ErrorKind::InvalidKeywordArg(*keyword),
                                                     spans: span.simple_error(),
                                                     note: requirement_error_note.clone(),
+                                                    suggestions: vec![],
                                                 });
                                             }
 
@@ -354,10 +364,19 @@ impl Attribute {
                                         },
                                         None => {
                                             has_error = true;
+                                            let keyword_args = rule.keyword_args.iter().filter(
+                                                |(_, keyword_rule)| keyword_rule.requirement != Requirement::Never
+                                            ).map(
+                                                |(keyword, _)| keyword.unintern_or_default(&session.intermediate_dir)
+                                            ).collect::<Vec<_>>();
                                             session.errors.push(Error {
                                                 kind: ErrorKind::InvalidKeywordArg(*keyword),
                                                 spans: span.simple_error(),
-                                                note: None,
+                                                note: did_you_mean(
+                                                    &keyword.unintern_or_default(&session.intermediate_dir),
+                                                    &keyword_args,
+                                                ),
+                                                suggestions: vec![],
                                             });
                                         },
                                     },
@@ -380,6 +399,7 @@ impl Attribute {
                                             }
                                         ).collect(),
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                 }
                             }
@@ -391,6 +411,7 @@ impl Attribute {
                                             kind: ErrorKind::MissingKeywordArg(*keyword),
                                             spans: ast_decorator.name_span.simple_error(),
                                             note: requirement_error_note.clone(),
+                                            suggestions: vec![],
                                         });
                                     }
                                 }
@@ -489,6 +510,7 @@ impl Attribute {
                                         kind: error_kind,
                                         spans: error_span,
                                         note: rule.arg_count_error_note.clone(),
+                                        suggestions: vec![],
                                     });
                                 },
                             }
@@ -508,37 +530,73 @@ impl Attribute {
                 },
                 None => {
                     has_error = true;
+
+                    // If the decorator is known but not allowed here, `decorator_error_notes` tells why.
+                    // Otherwise, it's likely to be a typo.
+                    let note = match rule.decorator_error_notes.get(&ast_decorator.name) {
+                        Some(note) => Some(note.to_string()),
+                        None => {
+                            let decorators = rule.decorators.iter().filter(
+                                |(_, decorator_rule)| decorator_rule.requirement != Requirement::Never
+                            ).map(
+                                |(name, _)| name.unintern_or_default(&session.intermediate_dir)
+                            ).collect::<Vec<_>>();
+
+                            did_you_mean(
+                                &ast_decorator.name.unintern_or_default(&session.intermediate_dir),
+                                &decorators,
+                            )
+                        },
+                    };
+
                     session.errors.push(Error {
                         kind: ErrorKind::InvalidDecorator(ast_decorator.name),
                         spans: ast_decorator.name_span.simple_error(),
-                        note: rule.decorator_error_notes.get(&ast_decorator.name).map(|n| n.to_string()),
+                        note,
+                        suggestions: vec![],
                     });
                 },
             }
 
-            match spans_by_name.entry(ast_decorator.name) {
+            match decorators_by_name.entry(ast_decorator.name) {
                 Entry::Occupied(mut e) => {
-                    e.get_mut().push(ast_decorator.name_span.clone());
+                    e.get_mut().push(ast_decorator);
                 },
                 Entry::Vacant(e) => {
-                    e.insert(vec![ast_decorator.name_span.clone()]);
+                    e.insert(vec![ast_decorator]);
                 },
             }
         }
 
-        for (name, spans) in spans_by_name.iter() {
-            if spans.len() > 1 {
+        for (name, decorators) in decorators_by_name.iter() {
+            if decorators.len() > 1 {
                 has_error = true;
+
+                // If none of them has arguments, they're all the same and it doesn't matter which one
+                // survives. Otherwise, the user has to choose which arguments to keep.
+                let applicability = if decorators.iter().all(|decorator| decorator.args.is_none()) {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+
                 session.errors.push(Error {
                     kind: ErrorKind::RedundantDecorator(*name),
-                    spans: spans.iter().map(
-                        |span| RenderableSpan {
-                            span: span.clone(),
+                    spans: decorators.iter().map(
+                        |decorator| RenderableSpan {
+                            span: decorator.name_span.clone(),
                             auxiliary: false,
                             note: None,
                         }
                     ).collect(),
                     note: None,
+                    suggestions: decorators[1..].iter().map(
+                        |decorator| Suggestion::remove(
+                            decorator.group_span.clone(),
+                            applicability,
+                            String::from("Remove the redundant decorator."),
+                        )
+                    ).collect(),
                 });
             }
         }
@@ -884,6 +942,7 @@ fn check_arg_type(arg: &DecoratorArg, arg_type: ArgType, error_note: &Option<Str
                 },
                 spans: arg.error_span_wide().simple_error(),
                 note: error_note.clone(),
+                suggestions: vec![],
             });
             Err(())
         },
@@ -896,6 +955,7 @@ fn check_arg_type(arg: &DecoratorArg, arg_type: ArgType, error_note: &Option<Str
                 },
                 spans: arg.error_span_wide().simple_error(),
                 note: error_note.clone(),
+                suggestions: vec![],
             });
             Err(())
         },
@@ -914,6 +974,7 @@ fn check_arg_type(arg: &DecoratorArg, arg_type: ArgType, error_note: &Option<Str
                 },
                 spans: arg.error_span_wide().simple_error(),
                 note: error_note.clone(),
+                suggestions: vec![],
             });
             Err(())
         },
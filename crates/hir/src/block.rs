@@ -294,6 +294,7 @@ impl Block {
                     }
                 ).collect(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
@@ -320,6 +321,7 @@ impl Block {
                     }
                 ).collect(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
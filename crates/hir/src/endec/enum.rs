@@ -153,8 +153,12 @@ impl Endec for EnumRepr {
             EnumRepr::Compound => {
                 buffer.push(1);
             },
-            EnumRepr::Niche => {
+            EnumRepr::Niche { null_variant } => {
                 buffer.push(2);
+                null_variant.encode_impl(buffer);
+            },
+            EnumRepr::Unboxed => {
+                buffer.push(3);
             },
         }
     }
@@ -163,8 +167,12 @@ impl Endec for EnumRepr {
         match buffer.get(cursor) {
             Some(0) => Ok((EnumRepr::Scalar, cursor + 1)),
             Some(1) => Ok((EnumRepr::Compound, cursor + 1)),
-            Some(2) => Ok((EnumRepr::Niche, cursor + 1)),
-            Some(n @ 3..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            Some(2) => {
+                let (null_variant, cursor) = usize::decode_impl(buffer, cursor + 1)?;
+                Ok((EnumRepr::Niche { null_variant }, cursor))
+            },
+            Some(3) => Ok((EnumRepr::Unboxed, cursor + 1)),
+            Some(n @ 4..) => Err(DecodeError::InvalidEnumVariant(*n)),
             None => Err(DecodeError::UnexpectedEof),
         }
     }
@@ -6,6 +6,7 @@ use crate::{
     FuncOrigin,
     FuncParam,
     FuncShape,
+    Memoize,
     Type,
     Visibility,
 };
@@ -32,6 +33,7 @@ impl Endec for Func {
         self.value.encode_impl(buffer);
         self.origin.encode_impl(buffer);
         self.built_in.encode_impl(buffer);
+        self.memoize.encode_impl(buffer);
         self.foreign_names.encode_impl(buffer);
         self.captured_names.encode_impl(buffer);
         self.use_counts.encode_impl(buffer);
@@ -51,6 +53,7 @@ impl Endec for Func {
         let (value, cursor) = Expr::decode_impl(buffer, cursor)?;
         let (origin, cursor) = FuncOrigin::decode_impl(buffer, cursor)?;
         let (built_in, cursor) = bool::decode_impl(buffer, cursor)?;
+        let (memoize, cursor) = Option::<Memoize>::decode_impl(buffer, cursor)?;
         let (foreign_names, cursor) = HashMap::<InternedString, (NameOrigin, Span)>::decode_impl(buffer, cursor)?;
         let (captured_names, cursor) = Option::<CapturedNames>::decode_impl(buffer, cursor)?;
         let (use_counts, cursor) = HashMap::<InternedString, UseCount>::decode_impl(buffer, cursor)?;
@@ -70,6 +73,7 @@ impl Endec for Func {
                 value,
                 origin,
                 built_in,
+                memoize,
                 foreign_names,
                 captured_names,
                 use_counts,
@@ -108,6 +112,19 @@ impl Endec for FuncParam {
     }
 }
 
+impl Endec for Memoize {
+    fn encode_impl(&self, buffer: &mut Vec<u8>) {
+        self.decorator_span.encode_impl(buffer);
+        self.max.encode_impl(buffer);
+    }
+
+    fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
+        let (decorator_span, cursor) = Span::decode_impl(buffer, cursor)?;
+        let (max, cursor) = Option::<usize>::decode_impl(buffer, cursor)?;
+        Ok((Memoize { decorator_span, max }, cursor))
+    }
+}
+
 impl Endec for FuncOrigin {
     fn encode_impl(&self, buffer: &mut Vec<u8>) {
         match self {
@@ -70,8 +70,15 @@ pub enum EnumRepr {
     // the first element is the variant index, and the remaining elements are for the enum payload
     Compound,
 
-    // I'll implement niche optimization someday...
-    Niche,
+    // An enum with 2 variants, where one variant has no payload and the other has exactly
+    // 1 payload which is never 0 at runtime (e.g. `Option<[Int]>`).
+    // The variant without payload is 0 (null), and the other variant is the payload itself.
+    // It's decided when the enum is monomorphized, because it depends on the type of the payload.
+    Niche { null_variant: usize },
+
+    // An enum with only 1 variant, which has exactly 1 payload.
+    // The enum value is the payload itself.
+    Unboxed,
 }
 
 impl Enum {
@@ -140,6 +147,7 @@ impl Enum {
                 kind: ErrorKind::EnumWithoutBody,
                 spans: ast_enum.name_span.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
             has_error = true;
         }
@@ -157,7 +165,13 @@ impl Enum {
                 keyword_span: ast_enum.keyword_span.clone(),
                 name: ast_enum.name,
                 name_span: ast_enum.name_span.clone(),
-                representation: if has_payload { EnumRepr::Compound } else { EnumRepr::Scalar },
+                representation: if !has_payload {
+                    EnumRepr::Scalar
+                } else if let [EnumVariant { fields: EnumVariantFields::Tuple(fields), .. }] = &variants[..] && fields.len() == 1 {
+                    EnumRepr::Unboxed
+                } else {
+                    EnumRepr::Compound
+                },
                 generics: ast_enum.generics.clone(),
                 generic_group_span: ast_enum.generic_group_span.clone(),
                 variants,
@@ -273,12 +287,14 @@ impl EnumVariant {
                             kind: ErrorKind::GenericEnumVariantWithoutTypeAnnot,
                             spans: error_spans,
                             note: Some(String::from("A variant of a generic enum needs type annotations because the compiler cannot infer the type otherwise.")),
+                            suggestions: vec![],
                         });
                     } else {
                         session.warnings.push(Lint {
                             kind: LintKind::EnumVariantWithoutTypeAnnot,
                             spans: error_spans,
                             note: None,
+                            suggestions: vec![],
                         });
                     }
                 }
@@ -343,12 +359,14 @@ impl EnumVariant {
                             kind: ErrorKind::GenericEnumVariantWithoutTypeAnnot,
                             spans: error_spans,
                             note: Some(format!("A variant of a generic enum needs type annotations because the compiler cannot infer the type otherwise.\n{help_message}")),
+                            suggestions: vec![],
                         });
                     } else {
                         session.warnings.push(Lint {
                             kind: LintKind::EnumVariantWithoutTypeAnnot,
                             spans: error_spans,
                             note: Some(help_message),
+                            suggestions: vec![],
                         });
                     }
                 }
@@ -412,9 +430,24 @@ impl EnumShape {
     // For example, variant-index of `Option.Some(_)` is always 1, regardless of niche optimization.
     // If `EnumRepr::Scalar`, the runtime representation of the enum is the same as variant-index.
     // If `EnumRepr::Compound`, the first element of the compound value is the same as variant-index.
+    // If `EnumRepr::Niche`, the variant-index is `null_variant` if the value is 0, and the other one otherwise.
+    // If `EnumRepr::Unboxed`, the variant-index is always 0.
     pub fn get_variant_index(&self, variant: SpanId) -> Option<u32> {
         self.variant_index.get(&variant).map(|i| *i as u32)
     }
+
+    // If this enum can be niche-optimized, it returns `(null_variant, payload_variant)`.
+    // It doesn't check the type of the payload.
+    pub fn niche_variants(&self) -> Option<(usize, usize)> {
+        match &self.variants[..] {
+            [a, b] => match (&a.fields, &b.fields) {
+                (EnumVariantFields::None, EnumVariantFields::Tuple(fields)) if fields.len() == 1 => Some((0, 1)),
+                (EnumVariantFields::Tuple(fields), EnumVariantFields::None) if fields.len() == 1 => Some((1, 0)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl From<&EnumVariantFields> for EnumFieldKind {
@@ -59,6 +59,7 @@ pub fn eval_const(expr: &Expr, session: &mut Session) -> Result<Expr, ()> {
                 kind: ErrorKind::CannotEvaluateConst,
                 spans: expr.error_span_wide().simple_error(),
                 note: None,
+                suggestions: vec![],
             });
             Err(())
         },
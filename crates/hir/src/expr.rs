@@ -428,6 +428,7 @@ impl Expr {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         });
                         has_error = true;
                     }
@@ -478,6 +479,7 @@ impl Expr {
                     kind: ErrorKind::DollarOutsidePipeline,
                     spans: span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 });
                 Err(())
             },
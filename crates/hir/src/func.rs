@@ -6,8 +6,10 @@ use crate::{
     Attribute,
     AttributeRule,
     CapturedNames,
+    DecoratorArg,
     DecoratorRule,
     Expr,
+    KeywordArgRule,
     Let,
     LetOrigin,
     Poly,
@@ -16,6 +18,7 @@ use crate::{
     Type,
     TypeAssertion,
     Visibility,
+    eval_const,
     get_decorator_error_notes,
 };
 use sodigy_error::{
@@ -35,9 +38,11 @@ use sodigy_name_analysis::{
     NameOrigin,
     UseCount,
 };
+use sodigy_number::unintern_number;
 use sodigy_parse::{self as ast, Generic};
 use sodigy_span::{RenderableSpan, Span, SpanDeriveKind};
 use sodigy_string::{InternedString, intern_string};
+use sodigy_token::Constant;
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -59,6 +64,9 @@ pub struct Func {
     pub origin: FuncOrigin,
     pub built_in: bool,
 
+    // `#[memoize]`
+    pub memoize: Option<Memoize>,
+
     // `Func::from_ast` first collects `.foreign_names`.
     // `.foreign_names` are consumed by `Block::from_ast` to check whether this function
     // is a closure or not. After that, this field is empty and related information is
@@ -85,6 +93,17 @@ pub struct FuncParam {
     pub unused_name: Option<Span>,
 }
 
+// The runtime caches the results of the function. The key of the cache is the
+// arguments, which are compared by value, not by reference.
+#[derive(Clone, Debug)]
+pub struct Memoize {
+    pub decorator_span: Span,
+
+    // `#[memoize(max = 10000)]`
+    // If the cache is full, the oldest entry is evicted.
+    pub max: Option<usize>,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum FuncOrigin {
     TopLevel,
@@ -213,6 +232,7 @@ impl Func {
                             kind: LintKind::SelfParamNotNamedSelf,
                             spans: ast_param.name_span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -235,6 +255,7 @@ impl Func {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         });
                         has_error = true;
                     }
@@ -244,12 +265,58 @@ impl Func {
                         kind: ErrorKind::AssociatedFuncWithoutSelfParam,
                         spans: ast_func.name_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                     has_error = true;
                 },
             }
         }
 
+        let mut memoize = None;
+
+        if let Some(decorator) = attribute.get_decorator(b"memoize", &session.intermediate_dir) {
+            let effect = FuncEffect::from_ndet_and_proc(ast_func.is_ndet, ast_func.is_proc);
+
+            // `fn` is pure, so it's always safe to memoize it. Others are not.
+            if effect != FuncEffect::Fn {
+                session.errors.push(Error {
+                    kind: ErrorKind::UnexpectedDecorator(decorator.name),
+                    spans: vec![
+                        RenderableSpan {
+                            span: decorator.name_span.clone(),
+                            auxiliary: false,
+                            note: None,
+                        },
+                        RenderableSpan {
+                            span: ast_func.ndet_span.clone().unwrap_or_else(|| ast_func.keyword_span.clone()),
+                            auxiliary: true,
+                            note: Some(format!("This function is `{}`.", effect.keyword())),
+                        },
+                    ],
+                    note: Some(String::from("Only pure functions (`fn`) can be memoized.")),
+                    suggestions: vec![],
+                });
+                has_error = true;
+            }
+
+            let max = match decorator.keyword_args.get(&intern_string(b"max", &session.intermediate_dir).unwrap()) {
+                Some(DecoratorArg::Expr(max)) => match eval_cache_size(max, session) {
+                    Ok(max) => Some(max),
+                    Err(()) => {
+                        has_error = true;
+                        None
+                    },
+                },
+                Some(DecoratorArg::Type(_)) => unreachable!(),
+                None => None,
+            };
+
+            memoize = Some(Memoize {
+                decorator_span: decorator.name_span.clone(),
+                max,
+            });
+        }
+
         if let Err(()) = session.collect_lang_items(
             &attribute,
             ast_func.name_span.clone(),
@@ -357,6 +424,7 @@ impl Func {
                     kind: LintKind::FuncWithoutTypeAnnot,
                     spans: error_spans,
                     note: Some(help_message),
+                    suggestions: vec![],
                 });
             } else {
                 has_error = true;
@@ -364,6 +432,7 @@ impl Func {
                     kind: LintKind::GenericFuncWithoutTypeAnnot,
                     spans: error_spans,
                     note: Some(format!("A generic function needs type annotations because the compiler cannot infer the types otherwise.\n{help_message}")),
+                    suggestions: vec![],
                 });
             }
         }
@@ -388,6 +457,7 @@ impl Func {
                         kind: ErrorKind::FunctionWithoutBody,
                         spans: ast_func.name_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                     None
                 }
@@ -446,6 +516,7 @@ impl Func {
                 value: value.unwrap(),
                 origin,
                 built_in,
+                memoize,
                 foreign_names,
 
                 // `Block::from_ast` will fill this field.
@@ -506,6 +577,27 @@ impl Func {
                         arg_type_error_note: Some(String::from("The argument must be a type that you want to associate the function with.")),
                         ..DecoratorRule::default()
                     },
+                ), (
+                    intern_string(b"memoize", intermediate_dir).unwrap(),
+                    DecoratorRule {
+                        name: intern_string(b"memoize", intermediate_dir).unwrap(),
+                        requirement: Requirement::Maybe,
+                        arg_requirement: Requirement::Maybe,
+                        arg_count: ArgCount::Zero,
+                        arg_count_error_note: Some(String::from("Use a keyword argument to set the size of the cache: `#[memoize(max = 10000)]`.")),
+                        keyword_args: vec![
+                            (
+                                intern_string(b"max", intermediate_dir).unwrap(),
+                                KeywordArgRule {
+                                    requirement: Requirement::Maybe,
+                                    requirement_error_note: None,
+                                    arg_type: ArgType::Expr,
+                                    arg_type_error_note: None,
+                                },
+                            ),
+                        ].into_iter().collect(),
+                        ..DecoratorRule::default()
+                    },
                 ),
             ].into_iter().collect(),
             decorator_error_notes: get_decorator_error_notes(ItemKind::Func, intermediate_dir),
@@ -648,3 +740,26 @@ impl FuncParam {
         attribute_rule
     }
 }
+
+fn eval_cache_size(max: &Expr, session: &mut Session) -> Result<usize, ()> {
+    let n = match eval_const(max, session)? {
+        Expr::Constant(Constant::Number { n, .. }) if n.is_integer() => match unintern_number(n, &session.intermediate_dir) {
+            Ok(n) if !n.numer.is_neg => u64::try_from(&n.numer).ok(),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    match n {
+        Some(n @ 1..) => Ok(n as usize),
+        _ => {
+            session.errors.push(Error {
+                kind: ErrorKind::InvalidCacheSize,
+                spans: max.error_span_wide().simple_error(),
+                note: None,
+                suggestions: vec![],
+            });
+            Err(())
+        },
+    }
+}
@@ -88,7 +88,7 @@ pub use r#do::Do;
 pub use r#enum::{Enum, EnumRepr, EnumShape, EnumVariant, EnumVariantFields};
 pub use eval::eval_const;
 pub use expr::{Expr, ExprOrString};
-pub use func::{CallArg, Func, FuncOrigin, FuncParam, FuncShape};
+pub use func::{CallArg, Func, FuncOrigin, FuncParam, FuncShape, Memoize};
 pub use r#if::If;
 pub use item_shape::{ItemShape, ItemShapeMut};
 pub use r#let::{Let, LetOrigin, TrivialLet};
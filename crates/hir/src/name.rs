@@ -87,4 +87,24 @@ impl Session {
             _ => None,
         }
     }
+
+    // Every name that `find_origin_and_count_usage` can find.
+    // It's used to suggest similar names when a name is not found.
+    pub fn names_in_scope(&self) -> Vec<InternedString> {
+        let mut result = vec![];
+
+        for namespace in self.name_stack.iter() {
+            match namespace {
+                Namespace::FuncParam { names, .. } |
+                Namespace::GenericParam { names, .. } |
+                Namespace::Block { names } |
+                Namespace::Pattern { names } => {
+                    result.extend(names.keys().copied());
+                },
+                Namespace::ForeignNameCollector { .. } => {},
+            }
+        }
+
+        result
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{Dotfish, Session, Type};
-use sodigy_error::{Error, ErrorKind};
+use sodigy_error::{Error, ErrorKind, did_you_mean};
 use sodigy_name_analysis::IdentWithOrigin;
 use sodigy_parse::{self as ast, Field};
 use sodigy_span::Span;
@@ -36,10 +36,17 @@ impl Path {
                 def_span,
             },
             None => {
+                let names_in_scope = session.names_in_scope().iter().map(
+                    |name| name.unintern_or_default(&session.intermediate_dir)
+                ).collect::<Vec<_>>();
                 session.errors.push(Error {
                     kind: ErrorKind::UndefinedName(ast_path.id),
                     spans: ast_path.id_span.simple_error(),
-                    note: None,
+                    note: did_you_mean(
+                        &ast_path.id.unintern_or_default(&session.intermediate_dir),
+                        &names_in_scope,
+                    ),
+                    suggestions: vec![],
                 });
                 return Err(());
             },
@@ -1,5 +1,5 @@
 use crate::{Expr, Path, Session, eval_const};
-use sodigy_error::Error;
+use sodigy_error::{Applicability, Error, ErrorKind, Suggestion};
 use sodigy_name_analysis::{IdentWithOrigin, NameKind, NameOrigin};
 use sodigy_parse::{self as ast, RestPattern};
 use sodigy_span::{Span, SpanDeriveKind};
@@ -147,7 +147,23 @@ impl PatternKind {
             // If `x` is an enum variant, `Some(x)` is lowered to `Some(x)`.
             // But the problem is that we don't know whether `x` is an expression or not
             // until inter-hir is complete. So we do the lowering later.
-            ast::PatternKind::Path(p) => Ok(PatternKind::Path(Path::from_ast(p, session)?)),
+            ast::PatternKind::Path(p) => match Path::from_ast(p, session) {
+                Ok(path) => Ok(PatternKind::Path(path)),
+                Err(()) => {
+                    // `Some(x)` where `x` is undefined: the user might have wanted to bind a name.
+                    if p.fields.is_empty() && let Some(error) = session.errors.last_mut() && error.kind == ErrorKind::UndefinedName(p.id) {
+                        let name = p.id.unintern_or_default(&session.intermediate_dir);
+                        error.suggestions.push(Suggestion::replace(
+                            p.id_span.clone(),
+                            format!("${name}"),
+                            Applicability::MaybeIncorrect,
+                            format!("If you want to bind a name, use `${name}`."),
+                        ));
+                    }
+
+                    Err(())
+                },
+            },
             ast::PatternKind::Constant(Constant::String { binary, s, span }) => {
                 // FIXME: Too many unwraps...
                 let s = unintern_string(*s, &session.intermediate_dir).unwrap().unwrap();
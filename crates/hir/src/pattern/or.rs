@@ -208,6 +208,7 @@ pub fn unreachable_or_pattern(reachable: &Pattern, unreachable: &Pattern) -> War
             },
         ],
         note: None,
+        suggestions: vec![],
     }
 }
 
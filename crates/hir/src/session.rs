@@ -19,7 +19,7 @@ use crate::{
     Use,
     prelude::prelude_namespace,
 };
-use sodigy_error::{Error, Warning, WarningKind};
+use sodigy_error::{Applicability, Error, Suggestion, Warning, WarningKind};
 use sodigy_name_analysis::{Counter, NameKind, Namespace, UseCount};
 use sodigy_parse::Session as ParseSession;
 use sodigy_session::SodigySession;
@@ -225,6 +225,7 @@ impl Session {
                         },
                     ],
                     note: None,
+                    suggestions: vec![],
                 });
             }
         }
@@ -239,6 +240,28 @@ impl Session {
                 None
             };
             names.sort_by_key(|(_, span)| span.clone());
+
+            // `_` is never warned, so renaming an unused name to `_` silences the warning.
+            // A local `let` is not visible to anyone else, so that's always safe. A func param
+            // might be used as a keyword argument, and a name binding might be followed by `@`.
+            let applicability = match kind {
+                _ if debug_only => None,
+                NameKind::Let { is_top_level: false } => Some(Applicability::MachineApplicable),
+                NameKind::FuncParam | NameKind::PatternNameBind => Some(Applicability::MaybeIncorrect),
+                _ => None,
+            };
+            let suggestions = match applicability {
+                Some(applicability) => names.iter().map(
+                    |(name, span)| Suggestion::replace(
+                        span.clone(),
+                        String::from("_"),
+                        applicability,
+                        format!("Rename `{}` to `_`.", name.unintern_or_default(&self.intermediate_dir)),
+                    )
+                ).collect(),
+                None => vec![],
+            };
+
             self.warnings.push(Warning {
                 kind: WarningKind::UnusedNames {
                     names: names.iter().map(
@@ -254,6 +277,7 @@ impl Session {
                     }
                 ).collect(),
                 note,
+                suggestions,
             });
         }
     }
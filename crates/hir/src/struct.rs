@@ -146,6 +146,7 @@ impl Struct {
                 kind: ErrorKind::StructWithoutBody,
                 spans: ast_struct.name_span.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
             has_error = true;
         }
@@ -177,6 +178,7 @@ impl Struct {
                     kind: LintKind::StructWithoutTypeAnnot,
                     spans: error_spans,
                     note: Some(help_message),
+                    suggestions: vec![],
                 });
             } else {
                 has_error = true;
@@ -184,6 +186,7 @@ impl Struct {
                     kind: ErrorKind::GenericStructWithoutTypeAnnot,
                     spans: error_spans,
                     note: Some(format!("A generic struct needs type annotations because the compiler cannot infer the types otherwise.\n{help_message}")),
+                    suggestions: vec![],
                 });
             }
         }
@@ -94,6 +94,7 @@ impl Use {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             });
 
             has_error = true;
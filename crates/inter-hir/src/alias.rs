@@ -121,6 +121,7 @@ impl Session {
                         }
                     ).collect(),
                     note: Some(String::from("It seems like these names are aliases of each other.")),
+                    suggestions: vec![],
                 });
                 return Err(());
             }
@@ -326,6 +327,7 @@ impl Session {
                         },
                     ],
                     note: None,
+                    suggestions: vec![],
                 });
                 Err(())
             },
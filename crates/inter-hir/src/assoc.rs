@@ -32,6 +32,7 @@ impl Session {
                                 kind: ErrorKind::TooGeneralToAssociateItem,
                                 spans: associated_item.type_span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }),
 
                             // already filtered out by `check_type_annot_path`
@@ -47,11 +48,13 @@ impl Session {
                     kind: ErrorKind::CannotAssociateItem,
                     spans: associated_item.type_span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }),
                 Type::Wildcard(_) => Err(Error {
                     kind: ErrorKind::TooGeneralToAssociateItem,
                     spans: associated_item.type_span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }),
             }
         }
@@ -239,6 +242,7 @@ impl Session {
                                     value: Expr::dummy(),
                                     origin: FuncOrigin::AssociatedFunc,
                                     built_in: false,
+                                    memoize: None,
                                     foreign_names: HashMap::new(),
                                     captured_names: None,
                                     use_counts: HashMap::new(),
@@ -547,6 +547,7 @@ pub(crate) fn not_x_but_y(
             },
         ],
         note: None,
+        suggestions: vec![],
     }
 }
 
@@ -1,5 +1,5 @@
 use crate::{LogId, Session, write_log};
-use sodigy_error::{Error, ErrorKind};
+use sodigy_error::{Error, ErrorKind, did_you_mean};
 use sodigy_hir::{
     Dotfish,
     Path,
@@ -265,10 +265,21 @@ impl Session {
                                 ),
                                 _ => unreachable!(),
                             };
+                            let item_names = items.keys().map(
+                                |item| item.unintern_or_default(&self.intermediate_dir)
+                            ).collect::<Vec<_>>();
+                            let error_message = match did_you_mean(
+                                &field_name.unintern_or_default(&self.intermediate_dir),
+                                &item_names,
+                            ) {
+                                Some(suggestion) => format!("{error_message} {suggestion}"),
+                                None => error_message,
+                            };
                             self.errors.push(Error {
                                 kind: ErrorKind::UndefinedName(field_name),
                                 spans: field_span.simple_error(),
                                 note: Some(error_message),
+                                suggestions: vec![],
                             });
                             Err(())
                         },
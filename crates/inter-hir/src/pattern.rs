@@ -185,6 +185,7 @@ impl Session {
                                         },
                                         spans: p.id.span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                     return Err(());
                                 },
@@ -350,6 +351,7 @@ impl Session {
                                 kind: ErrorKind::MismatchedEnumFieldKind { expected, got },
                                 spans: path.id.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             });
                             Err(())
                         },
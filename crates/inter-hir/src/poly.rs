@@ -60,6 +60,7 @@ impl Session {
                                     )
                                 }
                             ),
+                            suggestions: vec![],
                         });
                         has_error = true;
                     },
@@ -75,6 +76,7 @@ impl Session {
                             },
                         ],
                         note: Some(String::from("Only a function can be a poly generic.")),
+                        suggestions: vec![],
                     });
                     has_error = true;
                 },
@@ -1,17 +1,20 @@
 use crate::{Session, Type};
 use sodigy_error::{
+    Applicability,
     Error,
     ErrorKind,
     FuncEffect,
     ParamIndex,
+    Suggestion,
     TypeVarInfo,
     Warning,
     WarningKind,
     comma_list_strs,
+    did_you_mean,
     to_ordinal,
 };
 use sodigy_hir::{FuncOrigin, LetOrigin};
-use sodigy_mir::{render_type, span_to_string};
+use sodigy_mir::{get_def_span_from_id, render_type, span_to_string};
 use sodigy_parse::Field;
 use sodigy_span::{RenderableSpan, Span};
 use sodigy_string::InternedString;
@@ -421,6 +424,7 @@ impl Session {
                     },
                     spans,
                     note: context.note(&self.intermediate_dir).map(|s| s.to_string()),
+                    suggestions: vec![],
                 }
             },
             TypeError::WrongNumberOfArgs {
@@ -474,6 +478,7 @@ impl Session {
                     },
                     spans,
                     note: None,
+                    suggestions: vec![],
                 }
             },
             TypeError::WrongNumberOfGenericArgs {
@@ -502,6 +507,7 @@ impl Session {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::UnnecessaryGenericArgs {
                 def_span,
@@ -529,7 +535,7 @@ impl Session {
                     });
                 }
 
-                Error { kind: ErrorKind::UnnecessaryGenericArgs, spans, note: None }
+                Error { kind: ErrorKind::UnnecessaryGenericArgs, spans, note: None, suggestions: vec![] }
             },
             TypeError::MissingGenericArgs {
                 def_span,
@@ -559,12 +565,13 @@ impl Session {
                     });
                 }
 
-                Error { kind: ErrorKind::MissingGenericArgs, spans, note: None }
+                Error { kind: ErrorKind::MissingGenericArgs, spans, note: None, suggestions: vec![] }
             },
             TypeError::CannotInferType { info, span, is_return } => Error {
                 kind: ErrorKind::CannotInferType { info, is_return },
                 spans: span.simple_error(),
                 note: None,
+                suggestions: vec![],
             },
             TypeError::PartiallyInferedType {
                 info,
@@ -575,6 +582,7 @@ impl Session {
                 kind: ErrorKind::PartiallyInferedType { info, r#type: self.render_type(&r#type), is_return },
                 spans: span.simple_error(),
                 note: None,
+                suggestions: vec![],
             },
             TypeError::CannotInferGenericType { ref call, ref generic, ref func_def } |
             TypeError::PartiallyInferedGenericType { ref call, ref generic, ref func_def, .. } => {
@@ -612,6 +620,7 @@ impl Session {
                         kind: ErrorKind::CannotInferGenericType { id: generic_id },
                         spans,
                         note: None,
+                        suggestions: vec![],
                     },
                     TypeError::PartiallyInferedGenericType { r#type, .. } => Error {
                         kind: ErrorKind::PartiallyInferedGenericType {
@@ -620,18 +629,32 @@ impl Session {
                         },
                         spans,
                         note: None,
+                        suggestions: vec![],
                     },
                     _ => unreachable!(),
                 }
             },
             TypeError::UnknownField { r#type, field } => match field {
-                Field::Name { name, name_span, .. } => Error {
-                    kind: ErrorKind::UnknownField {
-                        r#type: self.render_type(&r#type),
-                        field: name,
-                    },
-                    spans: name_span.simple_error(),
-                    note: None,
+                Field::Name { name, name_span, .. } => {
+                    let associations = match &r#type {
+                        Type::Data { constructor_def_span, args, .. } => match self.get_item_shape(&get_def_span_from_id(*constructor_def_span, args)) {
+                            Some(item_shape) => item_shape.existing_associations().iter().map(
+                                |association| association.name.unintern_or_default(&self.intermediate_dir)
+                            ).collect(),
+                            None => vec![],
+                        },
+                        _ => vec![],
+                    };
+
+                    Error {
+                        kind: ErrorKind::UnknownField {
+                            r#type: self.render_type(&r#type),
+                            field: name,
+                        },
+                        spans: name_span.simple_error(),
+                        note: did_you_mean(&name.unintern_or_default(&self.intermediate_dir), &associations),
+                        suggestions: vec![],
+                    }
                 },
                 _ => todo!(),
             },
@@ -643,6 +666,7 @@ impl Session {
                     note: Some(String::from("This is an associated function, not a field.")),
                 }],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::NotCallable { r#type, func_span } => Error {
                 kind: ErrorKind::NotCallable {
@@ -654,6 +678,7 @@ impl Session {
                     note: None,
                 }],
                 note: None,
+                suggestions: vec![],
             },
             // TODO: based on the poly's def_span, I want it to throw
             //       `CannotApplyInfixOp` or so.
@@ -686,6 +711,7 @@ impl Session {
                     ).collect(),
                 ].concat(),
                 note: None,
+                suggestions: vec![],
             },
             TypeError::UnexpectedEffect {
                 expected_type,
@@ -755,6 +781,7 @@ impl Session {
                     },
                     spans,
                     note,
+                    suggestions: vec![],
                 }
             },
             TypeError::CannotInferPolyGenericParam { poly_span, param_index } => Error {
@@ -765,6 +792,7 @@ impl Session {
                     note: Some(String::from("This function needs a type annotation.")),
                 }],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::CannotInferPolyGenericImpl { poly_span, impl_span, param_index } => Error {
                 kind: ErrorKind::CannotInferPolyGenericImpl { param_index },
@@ -781,6 +809,7 @@ impl Session {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::PolyImplDifferentNumberOfParams { poly_params, poly_span, impl_params, impl_span } => Error {
                 kind: ErrorKind::PolyImplDifferentNumberOfParams { poly_params, impl_params },
@@ -797,6 +826,7 @@ impl Session {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::CannotImplPoly {
                 poly_type,
@@ -823,6 +853,7 @@ impl Session {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::MultiplePolyCandidates { call, poly_def, candidates } => {
                 let mut spans = vec![
@@ -850,6 +881,7 @@ impl Session {
                     kind: ErrorKind::MultiplePolyCandidates(candidates.len()),
                     spans,
                     note: None,
+                    suggestions: vec![],
                 }
             },
             TypeError::MissingStructFields { span, struct_name, enum_name, missing_fields } => Error {
@@ -864,6 +896,7 @@ impl Session {
                     note: None,
                 }],
                 note: None,
+                suggestions: vec![],
             },
             TypeError::ImpureCallInPureContext { call_spans, keyword_span, context, context_effect } => {
                 let mut spans = vec![];
@@ -925,10 +958,45 @@ impl Session {
                     }
                 }
 
+                // A function can be fixed by adding the missing keywords, but the other contexts can't.
+                let suggestions = match context {
+                    ExprContext::TopLevelFunc | ExprContext::InlineFunc => {
+                        let has_ndet = matches!(context_effect, FuncEffect::NdetFn | FuncEffect::NdetProc);
+                        let needs_ndet = has_ndet || call_spans.keys().any(
+                            |effect| matches!(effect, FuncEffect::NdetFn | FuncEffect::NdetProc | FuncEffect::Callable)
+                        );
+                        let needs_proc = matches!(context_effect, FuncEffect::Proc | FuncEffect::NdetProc) || call_spans.keys().any(
+                            |effect| matches!(effect, FuncEffect::Proc | FuncEffect::NdetProc | FuncEffect::Callable)
+                        );
+
+                        // `ndet` is before the keyword, so we only have to replace the keyword.
+                        let replacement = format!(
+                            "{}{}",
+                            if needs_ndet && !has_ndet { "ndet " } else { "" },
+                            if needs_proc { "proc" } else { "fn" },
+                        );
+
+                        vec![Suggestion::replace(
+                            keyword_span.clone(),
+                            replacement.clone(),
+
+                            // We're not sure about the effect of a callable.
+                            if call_spans.contains_key(&FuncEffect::Callable) {
+                                Applicability::MaybeIncorrect
+                            } else {
+                                Applicability::MachineApplicable
+                            },
+                            format!("Make it `{}`.", if has_ndet { format!("ndet {replacement}") } else { replacement }),
+                        )]
+                    },
+                    _ => vec![],
+                };
+
                 Error {
                     kind: ErrorKind::ImpureCallInPureContext { context: context_effect },
                     spans,
                     note: error_note,
+                    suggestions,
                 }
             },
 
@@ -940,6 +1008,7 @@ impl Session {
                     note: Some(String::from("This keyword makes this function effectful.")),
                 }],
                 note: None,
+                suggestions: vec![],
             },
 
             TypeError::TryToSolveGenericParam {
@@ -973,6 +1042,7 @@ impl Session {
                     kind: ErrorKind::InternalCompilerError { id: 90962 },
                     spans,
                     note: Some(format!("(This is for debugging the compiler itself, not your program)\nThe compiler tried to solve type with `Type::GenericParam {{ .. }}`. All the `Type::GenericParam {{ .. }}`s must be lowered to `Type::GenericArg {{ .. }}` beforehand.\nlhs: {lhs}\nrhs: {rhs}")),
+                    suggestions: vec![],
                 }
             },
         }
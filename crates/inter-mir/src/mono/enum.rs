@@ -1,7 +1,8 @@
 use super::Monomorphization;
 use crate::Session;
-use sodigy_hir::{self as hir, EnumShape};
-use sodigy_mir::{Enum, EnumVariant, EnumVariantFields, StructField};
+use sodigy_hir::{self as hir, EnumRepr, EnumShape};
+use sodigy_mir::{Enum, EnumVariant, EnumVariantFields, StructField, Type};
+use sodigy_span::Span;
 use std::collections::HashSet;
 
 impl Session {
@@ -69,15 +70,22 @@ impl Session {
             }
         ).collect();
 
+        // `monomorphize_enum` has already registered the types of the new variants.
+        let representation = match (enum_shape.representation, enum_shape.niche_variants()) {
+            (EnumRepr::Compound, Some((null_variant, payload_variant))) => match self.types.get(&new_variants[payload_variant].name_span) {
+                Some(Type::Func { params, .. }) if self.is_never_null(&params[0]) => EnumRepr::Niche { null_variant },
+                _ => EnumRepr::Compound,
+            },
+            (representation, _) => representation,
+        };
+
         EnumShape {
             name: enum_shape.name,
             variant_index: new_variants.iter().enumerate().map(
                 |(index, variant)| (variant.name_span.id().unwrap(), index)
             ).collect(),
             variants: new_variants,
-
-            // TODO: niche optimization
-            representation: enum_shape.representation,
+            representation,
 
             generics: vec![],
             generic_group_span: None,
@@ -85,4 +93,35 @@ impl Session {
             associated_lets: enum_shape.associated_lets.clone(),
         }
     }
+
+    // If a value of this type is always a pointer to the heap, it's never 0 at runtime,
+    // and 0 can be used as a niche.
+    // It's conservative: it returns false if it's not sure.
+    fn is_never_null(&self, r#type: &Type) -> bool {
+        let Type::Data { constructor_def_span, args, .. } = r#type else { return false; };
+
+        // A small integer has its most significant bit set, and a big integer is a pointer.
+        if *constructor_def_span == self.get_lang_item_span_id("type.Int") || *constructor_def_span == self.get_lang_item_span_id("type.List") {
+            true
+        }
+
+        // TODO: does an empty tuple allocate?
+        else if *constructor_def_span == self.get_lang_item_span_id("type.Tuple") {
+            args.as_ref().map(|args| !args.is_empty()).unwrap_or(false)
+        }
+
+        else if let Some(struct_shape) = self.struct_shapes.get(&Span::Range(*constructor_def_span)) {
+            !struct_shape.fields.is_empty()
+        }
+
+        // A monomorphized copy of the enum might be niche-optimized (e.g. `Option<Option<Int>>`),
+        // so it has to be a `Compound` that can never be niche-optimized.
+        else if let Some(enum_shape) = self.enum_shapes.get(&Span::Range(*constructor_def_span)) {
+            enum_shape.representation == EnumRepr::Compound && enum_shape.niche_variants().is_none()
+        }
+
+        else {
+            false
+        }
+    }
 }
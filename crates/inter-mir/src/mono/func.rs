@@ -79,6 +79,7 @@ impl Session {
             value: new_value,
             built_in: func.built_in,
             origin: FuncOrigin::Monomorphization,
+            memoize: func.memoize.clone(),
             wildcard_spans: vec![],
         };
 
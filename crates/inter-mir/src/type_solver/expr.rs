@@ -703,6 +703,7 @@ impl Session {
                                     },
                                     spans: arg_group_span.or(span).simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 });
                                 has_error = true;
                                 return (None, has_error);
@@ -0,0 +1,161 @@
+use crate::{Heap, Stack, call};
+use sodigy_bytecode::{Bytecode, DebugInfoKind, Executable, Value, ValueShape};
+use sodigy_error::{ConstEvalFailure, FuncEffect};
+use sodigy_mir::Intrinsic;
+use sodigy_span::RenderSpanSession;
+
+// The worker threads have small stacks, and `call` recurses for every non-tail call.
+const MAX_CALL_DEPTH: usize = 128;
+
+// The compiler evaluates top-level `let`s and assertions with the interpreter. The
+// evaluation stops (returns `Err`) if
+//   1. it runs more than `steps` bytecodes.
+//   2. it goes deeper than `MAX_CALL_DEPTH`.
+//   3. it calls something that's not a pure function: a `Proc`, a non-deterministic
+//      function, or an intrinsic that's observable (`panic`, `exit`, ...).
+// Then a `let` is lazy-initialized at runtime, like before. So if a `let` panics,
+// the program still panics at runtime, not at compile time.
+//
+// `print` and `eprint` don't stop the evaluation because `panic` prints a message
+// before it panics. They don't print anything at compile time, but a `let` that
+// prints something is not embedded, so that it still prints at runtime.
+pub struct ConstEval {
+    steps: usize,
+    depth: usize,
+    pub printed: bool,
+
+    // Why it stopped, and the depth of the call stack at that time.
+    stop: Option<(Stop, usize)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Stop {
+    // An assertion calls `exit` when it's done.
+    Exit,
+    Failure(ConstEvalFailure),
+}
+
+impl ConstEval {
+    fn new(steps: usize) -> ConstEval {
+        ConstEval { steps, depth: 0, printed: false, stop: None }
+    }
+
+    pub fn enter(&mut self) -> Result<(), ()> {
+        self.depth += 1;
+
+        if self.depth > MAX_CALL_DEPTH {
+            self.stop(Stop::Failure(ConstEvalFailure::DepthLimit))
+        }
+
+        else {
+            Ok(())
+        }
+    }
+
+    pub fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    pub fn step(&mut self, bytecode: &Bytecode) -> Result<(), ()> {
+        if self.steps == 0 {
+            return self.stop(Stop::Failure(ConstEvalFailure::StepLimit));
+        }
+
+        self.steps -= 1;
+
+        match bytecode {
+            Bytecode::Call { effect, .. } |
+            Bytecode::CallDynamic { effect, .. } => match &**effect {
+                FuncEffect::Fn => Ok(()),
+                FuncEffect::NdetFn | FuncEffect::NdetProc => self.stop(Stop::Failure(ConstEvalFailure::NonDeterministic)),
+                _ => self.stop(Stop::Failure(ConstEvalFailure::SideEffect)),
+            },
+            Bytecode::Intrinsic { intrinsic, .. } => match intrinsic {
+                Intrinsic::Exit => self.stop(Stop::Exit),
+                Intrinsic::Panic => self.stop(Stop::Failure(ConstEvalFailure::Panic)),
+                Intrinsic::Print | Intrinsic::EPrint => {
+                    self.printed = true;
+                    Ok(())
+                },
+                _ => match intrinsic.effect() {
+                    FuncEffect::Fn => Ok(()),
+                    FuncEffect::NdetFn | FuncEffect::NdetProc => self.stop(Stop::Failure(ConstEvalFailure::NonDeterministic)),
+                    _ => self.stop(Stop::Failure(ConstEvalFailure::SideEffect)),
+                },
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn stop(&mut self, stop: Stop) -> Result<(), ()> {
+        self.stop = Some((stop, self.depth));
+        Err(())
+    }
+}
+
+/// It evaluates a top-level `let` whose entry point is `label`, and reads the
+/// result back as a `Value` of the given shape. It returns `None` if the value
+/// can't be evaluated at compile time (read the comments in `ConstEval`).
+pub fn eval_const(
+    executable: &Executable,
+    label: usize,
+    shape: &ValueShape,
+    steps: usize,
+    intermediate_dir: &str,
+) -> Option<Value> {
+    let mut heap = Heap::new();
+    heap.alloc_constants(&executable.constants);
+    let mut render_span_session = RenderSpanSession::new(intermediate_dir);
+    let mut const_eval = Some(ConstEval::new(steps));
+    let result = call(Stack::new(), &mut heap, executable, label, &mut const_eval, &mut render_span_session).ok()?;
+
+    if const_eval.unwrap().printed {
+        return None;
+    }
+
+    Some(heap.read_value(result, shape))
+}
+
+#[derive(Clone, Debug)]
+pub enum ConstAssertResult {
+    Pass,
+
+    // `note` is the evaluated `#[note(..)]` of the assertion, if there's one.
+    Fail { note: Option<String> },
+
+    // It can't tell whether the assertion fails or not.
+    Skip(ConstEvalFailure),
+}
+
+/// It evaluates an assertion whose entry point is `label`.
+///
+/// An assertion panics when it fails, and the panic is in the assertion's own
+/// bytecodes (depth 1). If a function that the assertion calls panics, that's
+/// not a failure of the assertion, so it's skipped.
+pub fn eval_const_assert(
+    executable: &Executable,
+    label: usize,
+    steps: usize,
+    intermediate_dir: &str,
+) -> ConstAssertResult {
+    let mut heap = Heap::new();
+    heap.alloc_constants(&executable.constants);
+    let mut render_span_session = RenderSpanSession::new(intermediate_dir);
+    let mut const_eval = Some(ConstEval::new(steps));
+    let result = call(Stack::new(), &mut heap, executable, label, &mut const_eval, &mut render_span_session);
+
+    match (result, const_eval.unwrap().stop) {
+        (Ok(_), _) | (Err(()), Some((Stop::Exit, _))) => ConstAssertResult::Pass,
+        (Err(()), Some((Stop::Failure(ConstEvalFailure::Panic), 1))) => {
+            let note = heap.debug_info.iter().rev().find(
+                |(kind, _)| matches!(kind, DebugInfoKind::AssertionNote)
+            ).map(
+                |(_, note)| heap.inspect_string(*note as usize)
+            );
+
+            ConstAssertResult::Fail { note }
+        },
+        (Err(()), Some((Stop::Failure(failure), _))) => ConstAssertResult::Skip(failure),
+        (Err(()), None) => unreachable!(),
+    }
+}
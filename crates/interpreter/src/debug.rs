@@ -1,4 +1,4 @@
-use crate::{Heap, Stack};
+use crate::{Heap, LIST_BUFFER_HEADER, ListKind, Stack, small_int};
 use sodigy_bytecode::{Bytecode, SSA};
 use sodigy_number::bi_to_string;
 use sodigy_span::{RenderableSpan, RenderSpanOption, RenderSpanSession, render_spans};
@@ -96,13 +96,16 @@ pub fn debug(
 }
 
 fn debug_stack(value: u32, stack: &Stack, heap: &Heap) -> String {
-    let int = match try_inspect_int(&heap.data, value as usize) {
-        Some((is_neg, ns)) => bi_to_string(is_neg, ns),
-        None => String::from("????"),
+    let int = match small_int(value) {
+        Some(n) => n.to_string(),
+        None => match try_inspect_int(&heap.data, value as usize) {
+            Some((is_neg, ns)) => bi_to_string(is_neg, ns),
+            None => String::from("????"),
+        },
     };
     let string = match try_inspect_list(&heap.data, value as usize) {
         Some(s) => {
-            let (ss, truncated) = if s.len() > 12 { (&s[..12], true) } else { (s, false) };
+            let (ss, truncated) = if s.len() > 12 { (&s[..12], true) } else { (&s[..], false) };
             format!(
                 "{:?}{}",
                 ss.iter().map(
@@ -117,7 +120,7 @@ fn debug_stack(value: u32, stack: &Stack, heap: &Heap) -> String {
         },
         None => String::from("????"),
     };
-    let ref_count = if value > 0 {
+    let ref_count = if value > 0 && (value as usize) <= heap.data.len() {
         heap.data[value as usize - 1].to_string()
     } else {
         String::from("????")
@@ -144,20 +147,45 @@ fn try_inspect_int(heap: &[u32], ptr: usize) -> Option<(bool, &[u32])> {
     }
 }
 
-fn try_inspect_list(heap: &[u32], ptr: usize) -> Option<&[u32]> {
-    if ptr + 2 >= heap.len() {
+fn try_inspect_list(heap: &[u32], ptr: usize) -> Option<Vec<u32>> {
+    if ptr + 3 >= heap.len() {
         return None;
     }
 
-    let slice_ptr = heap[ptr] as usize;
+    let buffer_ptr = heap[ptr] as usize;
     let start = heap[ptr + 1] as usize;
     let length = heap[ptr + 2] as usize;
+    let size = heap[ptr + 3] as usize;
 
-    if slice_ptr + start + length + 1 >= heap.len() {
-        None
+    if buffer_ptr + LIST_BUFFER_HEADER >= heap.len() {
+        return None;
     }
 
-    else {
-        Some(&heap[(slice_ptr + start + 1)..(slice_ptr + start + length + 1)])
+    let elements = buffer_ptr + LIST_BUFFER_HEADER;
+
+    match ListKind::from_u32(heap[buffer_ptr + 2])? {
+        ListKind::Words => {
+            if elements + start + length >= heap.len() {
+                None
+            }
+
+            else {
+                Some(heap[(elements + start)..(elements + start + length)].to_vec())
+            }
+        },
+        kind @ (ListKind::Bytes | ListKind::Utf8) => {
+            if elements + (start + size).div_ceil(4) >= heap.len() {
+                return None;
+            }
+
+            let bytes = (start..(start + size)).map(
+                |i| (heap[elements + i / 4] >> ((i % 4) * 8)) as u8
+            ).collect::<Vec<_>>();
+
+            match kind {
+                ListKind::Bytes => Some(bytes.iter().map(|b| *b as u32).collect()),
+                _ => Some(String::from_utf8_lossy(&bytes).chars().map(|ch| ch as u32).collect()),
+            }
+        },
     }
 }
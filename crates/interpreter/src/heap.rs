@@ -1,10 +1,19 @@
-use sodigy_bytecode::{DebugInfoKind, DropType, Value};
+use crate::MemoCache;
+use sodigy_bytecode::{DebugInfoKind, DropType, Value, ValueShape};
+use sodigy_number::BigInt;
 use sodigy_span::{Span, SpanId};
 use std::collections::HashMap;
 
 #[cfg(feature="debug-heap")]
 mod debug;
 
+mod int;
+mod list;
+mod number;
+
+pub use int::{small_int, try_into_small_int};
+pub use list::{LIST_BUFFER_HEADER, ListKind};
+
 #[cfg(feature="debug-heap")]
 use debug::HeapDebugInfo;
 
@@ -18,6 +27,10 @@ use debug::HeapDebugInfo;
 //
 // pointer points to `d00`, not `hhh`.
 
+// Ref count of the blocks that must not be freed (e.g. constants).
+// It's big enough that `dec_rc` can never make it 0.
+const PINNED_REF_COUNT: u32 = 0x4000_0000;
+
 // You can change this constants to fine-tune performance.
 // But the heap implementation assumes something and you have to follow this conditions:
 // 1. SMALL_BLOCK_SIZE is at least 4.
@@ -36,6 +49,19 @@ pub struct Heap {
     // Global values are static: once initialized, it's alive until the end of the program.
     pub global_values: HashMap<Span, u32>,
 
+    // Caches of the memoized functions. Like global values, a cached value is
+    // alive until the end of the program.
+    // key: entry point of the function
+    pub memo_caches: HashMap<usize, MemoCache>,
+
+    // `Executable::constants` are allocated before the program starts (`alloc_constants`),
+    // and `Value::Pooled(i)` is `constants[i]`. Their ref counts are pinned, so they're
+    // never freed, and lists in the pool are read-only.
+    pub constants: Vec<u32>,
+
+    // If it's set, `alloc` pins the ref count of the new block.
+    pinning: bool,
+
     // Blocks in freelist_small are at least as big as SMALL_BLOCK_SIZE (can be bigger).
     // Each `usize` value is a pointer, where `self.data[pointer]` is a header of a block.
     pub freelist_small: Vec<usize>,
@@ -51,6 +77,9 @@ impl Heap {
         Heap {
             debug_info: vec![],
             global_values: HashMap::new(),
+            memo_caches: HashMap::new(),
+            constants: vec![],
+            pinning: false,
             data: vec![],
             freelist_small: vec![],
             freelist_medium: vec![],
@@ -81,87 +110,24 @@ impl Heap {
         }
     }
 
-    pub fn alloc_int_from_i32(&mut self, n: i32) -> u32 {
-        let ptr = self.alloc(2) as u32;
-        let metadata = if n < 0 { 0x8000_0001 } else { 1 };
-        self.data[ptr as usize] = metadata;
-        self.data[ptr as usize + 1] = n.abs() as u32;
-        ptr
-    }
-
-    pub fn alloc_int_from_u32(&mut self, n: u32) -> u32 {
-        let ptr = self.alloc(2) as u32;
-        self.data[ptr as usize] = 1;
-        self.data[ptr as usize + 1] = n;
-        ptr
-    }
-
     pub fn alloc_value(&mut self, value: &Value) -> u32 {
         match value {
             Value::Scalar(v) => *v,
 
-            // heap:   hhh  rrr  d00  d01  d02 ...
-            //                   ^
-            //                   *-- pointer points to this
-            //
-            // `hhh` and `rrr` are memory allocator's metadata.
-            // `d00` is the integer's metadata.
-            //
-            // The most significant bit of `d00` is whether the integer is negative or not.
-            // If it's negative the most significant bit is 1.
-            // The other bits is the length of the integer (how many scalar values).
-            // `d01` and the remaining are the actual data.
-            // `d01` is the least significant part of the integer.
-            // So, the integer's value is `d01 + d02 * 4294967296 + d03 * 18446744073709551616 + ...`.
-            //
-            // If the integer is -5, it'd be represented like this:
-            //
-            // heap: [hhh, rrr, 0x8000_0001, 0x5]
-            //                  ^
-            //                  *-- the most significant bit is 1 because it's negative.
-            //                  |
-            //                  *-- pointer points to this
-            Value::Int(n) => {
-                let ptr = self.alloc(n.nums.len() + 1) as u32;
-                let mut metadata = n.nums.len() as u32;
-
-                if n.is_neg {
-                    metadata |= 0x8000_0000;
-                }
-
-                self.data[ptr as usize] = metadata;
+            // Read the comments in `heap/int.rs`.
+            Value::Int(n) => self.alloc_int(n.is_neg, &n.nums),
 
-                for (i, n) in n.nums.iter().enumerate() {
-                    self.data[ptr as usize + 1 + i] = *n;
-                }
-
-                ptr
-            },
-
-            // heap:   hhh  rrr  d00  d01  d02
-            //                   ^
-            //                   *-- pointer points here
-            //
-            // `hhh` and `rrr` are memory allocator's metadata.
-            // `d00` is a pointer to the actual data
-            // `d01` is a scalar value. It's the start index of the slice.
-            // `d02` is a scalar value. It's the length of the slice.
+            // Read the comments in `heap/list.rs`.
             Value::List(vs) => {
-                // TODO: don't alloc if `vs` is empty
-                let data_ptr = self.alloc(vs.len() + 1);
-                self.data[data_ptr] = vs.len() as u32;
+                let slice_ptr = self.alloc_list(vs.len());
 
                 for (i, v) in vs.iter().enumerate() {
                     let v_p = self.alloc_value(v);
-                    self.data[data_ptr + i + 1] = v_p;
+                    let index = self.list_element_index(slice_ptr as usize, i);
+                    self.data[index] = v_p;
                 }
 
-                let slice_ptr = self.alloc(3);
-                self.data[slice_ptr] = data_ptr as u32;
-                self.data[slice_ptr + 1] = 0;
-                self.data[slice_ptr + 2] = vs.len() as u32;
-
-                slice_ptr as u32
+                slice_ptr
             },
             Value::Compound(vs) => {
                 // TODO: don't alloc if `vs` is empty
@@ -175,6 +141,12 @@ impl Heap {
                 ptr
             },
             Value::FuncPointer { program_counter, .. } => program_counter.unwrap() as u32,
+
+            Value::String { binary, bytes } => {
+                let kind = if *binary { ListKind::Bytes } else { ListKind::Utf8 };
+                self.alloc_packed_list(kind, bytes)
+            },
+            Value::Pooled(i) => self.constants[*i],
             Value::Span(span) => match span.id() {
                 Some(SpanId(id)) => {
                     let ptr = self.alloc(4);
@@ -191,6 +163,52 @@ impl Heap {
         }
     }
 
+    // It's the opposite of `alloc_value`. The runtime doesn't know the types of
+    // the values, so the caller has to tell the shape of the value.
+    pub fn read_value(&self, value: u32, shape: &ValueShape) -> Value {
+        match shape {
+            ValueShape::Scalar => Value::Scalar(value),
+            ValueShape::Int => {
+                let (is_neg, nums) = self.inspect_int(value);
+                Value::Int(BigInt { is_neg, nums: nums.to_vec() })
+            },
+            ValueShape::List(element) => match self.list_kind(value as usize) {
+                ListKind::Words => Value::List(
+                    self.inspect_list(value as usize).into_iter().map(
+                        |element_value| self.read_value(element_value, element)
+                    ).collect(),
+                ),
+                ListKind::Bytes => Value::String {
+                    binary: true,
+                    bytes: self.inspect_list(value as usize).into_iter().map(|b| b as u8).collect(),
+                },
+                ListKind::Utf8 => Value::String {
+                    binary: false,
+                    bytes: self.inspect_string(value as usize).into_bytes(),
+                },
+            },
+            ValueShape::Compound(fields) => Value::Compound(
+                fields.iter().enumerate().map(
+                    |(i, field)| self.read_value(self.data[value as usize + i], field)
+                ).collect(),
+            ),
+            // `alloc_value` of `Value::Scalar(0)` is 0.
+            ValueShape::Nullable(_) if value == 0 => Value::Scalar(0),
+            ValueShape::Nullable(inner) => self.read_value(value, inner),
+        }
+    }
+
+    pub fn alloc_constants(&mut self, constants: &[Value]) {
+        self.pinning = true;
+
+        for constant in constants.iter() {
+            let ptr = self.alloc_value(constant);
+            self.constants.push(ptr);
+        }
+
+        self.pinning = false;
+    }
+
     // It implicitly `inc_rc` after allocating memory.
     // `size` is of data, not block. A block has 1 scalar for header,
     // 1 scalar for ref_count, and scalars for data. If you call `alloc(8)`,
@@ -234,7 +252,12 @@ impl Heap {
             }
 
             else if let Some(ptr) = self.freelist_large.pop() {
-                todo!()
+                // this block is too big. I'll just use the quarter of this block.
+                self.divide_block(ptr);
+
+                self.data[ptr - 2] |= 0x8000_0000;
+                self.data[ptr - 1] = 1;
+                ptr
             }
 
             else {
@@ -244,8 +267,26 @@ impl Heap {
             }
         }
 
+        // Large blocks are not divided. If there's no free block that's big enough,
+        // it creates a new block that fits `size`.
         else {
-            todo!()
+            match self.freelist_large.iter().position(
+                |ptr| (self.data[ptr - 2] as usize) >= size
+            ) {
+                Some(i) => {
+                    let ptr = self.freelist_large.swap_remove(i);
+                    self.data[ptr - 2] |= 0x8000_0000;
+                    self.data[ptr - 1] = 1;
+                    ptr
+                },
+                None => {
+                    let block_size = size.max(LARGE_BLOCK_SIZE);
+                    self.data.push(block_size as u32 | 0x8000_0000);
+                    self.data.push(1);
+                    self.data.extend(vec![0; block_size]);
+                    self.data.len() - block_size
+                },
+            }
         };
 
         #[cfg(feature="debug-heap")] {
@@ -253,6 +294,10 @@ impl Heap {
             self.heap_debug_info.allocations.insert(result, block_size);
         }
 
+        if self.pinning {
+            self.data[result - 1] = PINNED_REF_COUNT;
+        }
+
         result
     }
 
@@ -279,6 +324,12 @@ impl Heap {
         }
     }
 
+    // Nobody calls `inc_rc` yet, so it doesn't mean that nobody else can see the block.
+    // It only tells that the block is not pinned.
+    pub fn is_unique(&self, ptr: usize) -> bool {
+        self.data[ptr - 1] == 1
+    }
+
     pub fn inc_rc(&mut self, ptr: usize) {
         self.data[ptr - 1] += 1;
     }
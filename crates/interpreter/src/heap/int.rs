@@ -0,0 +1,119 @@
+use super::Heap;
+use std::borrow::Cow;
+
+// An `Int` is either a small integer or a pointer to a big integer.
+//
+// If the most significant bit of the value is 1, it's a small integer. The other 31 bits
+// are the integer in two's complement, so a small integer is in `-2^30..2^30`. Small integers
+// don't live in the heap, so counters and indexes don't have to allocate anything.
+// A heap pointer is always less than 0x8000_0000, so it can never be confused with a small integer.
+//
+// If the most significant bit is 0, it's a pointer to a big integer.
+//
+// heap:   hhh  rrr  d00  d01  d02 ...
+//                   ^
+//                   *-- pointer points to this
+//
+// `hhh` and `rrr` are memory allocator's metadata.
+// `d00` is the integer's metadata.
+//
+// The most significant bit of `d00` is whether the integer is negative or not.
+// If it's negative the most significant bit is 1.
+// The other bits is the length of the integer (how many scalar values).
+// `d01` and the remaining are the actual data.
+// `d01` is the least significant part of the integer.
+// So, the integer's value is `d01 + d02 * 4294967296 + d03 * 18446744073709551616 + ...`.
+//
+// If the integer is -5000000000, it'd be represented like this:
+//
+// heap: [hhh, rrr, 0x8000_0002, 0x2a05f200, 0x1]
+//                  ^
+//                  *-- the most significant bit is 1 because it's negative.
+//                  |
+//                  *-- pointer points to this
+//
+// An integer is a big integer only if it doesn't fit in a small integer. `alloc_int`
+// checks this, so that an integer always has the same representation, and the
+// interpreter can use the fast path again after a big integer becomes small.
+const SMALL_INT_TAG: u32 = 0x8000_0000;
+const SMALL_INT_MIN: i64 = -(1 << 30);
+const SMALL_INT_MAX: i64 = (1 << 30) - 1;
+
+pub fn small_int(n: u32) -> Option<i64> {
+    if n & SMALL_INT_TAG != 0 {
+        // sign-extends the 31 bits
+        Some((((n << 1) as i32) >> 1) as i64)
+    }
+
+    else {
+        None
+    }
+}
+
+pub fn try_into_small_int(n: i64) -> Option<u32> {
+    if SMALL_INT_MIN <= n && n <= SMALL_INT_MAX {
+        Some(n as u32 | SMALL_INT_TAG)
+    }
+
+    else {
+        None
+    }
+}
+
+impl Heap {
+    // It allocates a big integer only if `n` doesn't fit in a small integer.
+    pub fn alloc_int_from_i64(&mut self, n: i64) -> u32 {
+        match try_into_small_int(n) {
+            Some(n) => n,
+            None => {
+                let abs = n.unsigned_abs();
+
+                if abs > 0xffff_ffff {
+                    self.alloc_big_int(n < 0, &[abs as u32, (abs >> 32) as u32])
+                }
+
+                else {
+                    self.alloc_big_int(n < 0, &[abs as u32])
+                }
+            },
+        }
+    }
+
+    // `nums` must not have trailing zeros, like the results of `sodigy_number`'s functions.
+    pub fn alloc_int(&mut self, is_neg: bool, nums: &[u32]) -> u32 {
+        match nums {
+            [n] => {
+                let n = if is_neg { -(*n as i64) } else { *n as i64 };
+                self.alloc_int_from_i64(n)
+            },
+            _ => self.alloc_big_int(is_neg, nums),
+        }
+    }
+
+    // It returns the sign and the absolute value of the integer, like `sodigy_number::BigInt`.
+    pub fn inspect_int(&self, n: u32) -> (bool, Cow<'_, [u32]>) {
+        match small_int(n) {
+            Some(n) => (n < 0, Cow::Owned(vec![n.unsigned_abs() as u32])),
+            None => {
+                let ptr = n as usize;
+                let metadata = self.data[ptr];
+                let is_neg = metadata > 0x7fff_ffff;
+                let length = metadata & 0x7fff_ffff;
+                (is_neg, Cow::Borrowed(&self.data[(ptr + 1)..(ptr + 1 + length as usize)]))
+            },
+        }
+    }
+
+    fn alloc_big_int(&mut self, is_neg: bool, nums: &[u32]) -> u32 {
+        let ptr = self.alloc(nums.len() + 1);
+        let mut metadata = nums.len() as u32;
+
+        if is_neg {
+            metadata |= 0x8000_0000;
+        }
+
+        self.data[ptr] = metadata;
+        self.data[(ptr + 1)..(ptr + 1 + nums.len())].copy_from_slice(nums);
+        ptr as u32
+    }
+}
@@ -0,0 +1,416 @@
+use super::Heap;
+
+// A list is a slice of a buffer. Many slices can share the same buffer.
+//
+// slice:   hhh  rrr  d00  d01  d02  d03
+//                    ^
+//                    *-- pointer points here
+//
+// `d00` is a pointer to the buffer.
+// `d01` is a scalar value. It's the start index of the slice (in the buffer, in units).
+// `d02` is a scalar value. It's the length of the slice (number of elements).
+// `d03` is a scalar value. It's the size of the slice (in units).
+//
+// buffer:  hhh  rrr  lo  hi  kind  e00  e01  e02  ...
+//                    ^
+//                    *-- pointer points here
+//
+// `kind` is `ListKind` of the buffer. If the most significant bit of `kind` is set,
+// the buffer is read-only (e.g. constants).
+//
+// A unit is how elements are stored in a buffer. If the buffer is `ListKind::Words`, a
+// unit is a `u32`, and an element is a unit. Otherwise, a unit is a byte and 4 units are
+// packed in a `u32` (little endian). In `ListKind::Bytes`, an element is a unit, and in
+// `ListKind::Utf8`, an element is a char encoded in 1 ~ 4 units. So the length and the
+// size of a slice are the same unless it's a non-ascii string.
+//
+// The buffer's capacity is the size of its block (minus 3 for `lo`, `hi` and `kind`), in units.
+// `e(lo)..e(hi)` are the units that are used by at least one slice. The other
+// units are garbage, and no slice can see them.
+//
+// Sodigy values are immutable, so we cannot modify a unit that a slice can see.
+// But we can write a unit at `e(hi)` and increment `hi`, because no slice can
+// see `e(hi)`. So, if a slice ends at `e(hi)` and the buffer has a free space, we
+// can append an element to the slice without copying it. It's the same for `e(lo - 1)`
+// and prepending. If there's no free space, it copies the slice to a new buffer
+// which is twice as large as the slice.
+//
+// This makes appending/prepending to the most recent version of a list amortized O(1),
+// which is how recursive list-building functions (e.g. `concat_list` in std) use lists.
+// Appending to an older version of a list (whose end is not `hi`) still copies it.
+// Slicing never copies the elements: it only allocates a new slice.
+//
+// Indexing and slicing a `ListKind::Utf8` list have to find the offset of the char.
+// It's O(1) if the slice is ascii, and O(n) otherwise. But `[c] ++ cs` patterns, which
+// are how std iterates strings, only look at the first char, so they're still O(1).
+pub const LIST_BUFFER_HEADER: usize = 3;
+
+// A new buffer is at least this big (in units), so that appending to an empty list
+// or a short list doesn't copy the list every time.
+const MIN_LIST_CAPACITY: usize = 6;
+
+const READ_ONLY: u32 = 0x8000_0000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ListKind {
+    // It's for all lists except `String` and `Bytes` constants.
+    Words = 0,
+
+    // `Bytes` constants
+    Bytes = 1,
+
+    // `String` constants
+    Utf8 = 2,
+}
+
+impl ListKind {
+    pub fn from_u32(n: u32) -> Option<ListKind> {
+        match n & !READ_ONLY {
+            0 => Some(ListKind::Words),
+            1 => Some(ListKind::Bytes),
+            2 => Some(ListKind::Utf8),
+            _ => None,
+        }
+    }
+
+    // How many units does `element` take?
+    fn units(&self, element: u32) -> usize {
+        match self {
+            ListKind::Words | ListKind::Bytes => 1,
+            ListKind::Utf8 => char::from_u32(element).expect("invalid char point").len_utf8(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Reserve {
+    // If the list is copied to a new buffer, it puts the elements at the front of the buffer.
+    Back,
+
+    // If the list is copied to a new buffer, it puts the elements at the end of the buffer.
+    Front,
+}
+
+impl Heap {
+    // It allocates a list with `length` elements. The elements are not initialized,
+    // so the caller has to write the elements with `Memory::List`.
+    pub fn alloc_list(&mut self, length: usize) -> u32 {
+        let buffer_ptr = self.alloc_list_buffer(ListKind::Words, length, length, Reserve::Back);
+        self.alloc_slice(buffer_ptr, 0, length as u32, length as u32)
+    }
+
+    // `bytes` must be a valid utf-8 if `kind` is `ListKind::Utf8`.
+    pub fn alloc_packed_list(&mut self, kind: ListKind, bytes: &[u8]) -> u32 {
+        let length = match kind {
+            ListKind::Words => unreachable!(),
+            ListKind::Bytes => bytes.len(),
+            ListKind::Utf8 => std::str::from_utf8(bytes).expect("invalid utf-8").chars().count(),
+        };
+        let buffer_ptr = self.alloc_list_buffer(kind, bytes.len(), bytes.len(), Reserve::Back);
+
+        for (i, b) in bytes.iter().enumerate() {
+            self.write_byte(buffer_ptr, i, *b);
+        }
+
+        self.alloc_slice(buffer_ptr, 0, length as u32, bytes.len() as u32)
+    }
+
+    pub fn list_buffer_and_start(&self, slice_ptr: usize) -> (usize, usize) {
+        (self.data[slice_ptr] as usize, self.data[slice_ptr + 1] as usize)
+    }
+
+    pub fn list_len(&self, slice_ptr: usize) -> u32 {
+        self.data[slice_ptr + 2]
+    }
+
+    pub fn list_kind(&self, slice_ptr: usize) -> ListKind {
+        let buffer_ptr = self.data[slice_ptr] as usize;
+        ListKind::from_u32(self.data[buffer_ptr + 2]).unwrap()
+    }
+
+    // Index of the `index`th element of the list in `self.data`.
+    // It only works with `ListKind::Words`.
+    pub fn list_element_index(&self, slice_ptr: usize, index: usize) -> usize {
+        debug_assert_eq!(self.list_kind(slice_ptr), ListKind::Words);
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        buffer_ptr + LIST_BUFFER_HEADER + start + index
+    }
+
+    pub fn index_list(&self, slice_ptr: usize, index: usize) -> u32 {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+
+        match self.list_kind(slice_ptr) {
+            ListKind::Words => self.data[buffer_ptr + LIST_BUFFER_HEADER + start + index],
+            ListKind::Bytes => self.read_byte(buffer_ptr, start + index) as u32,
+            ListKind::Utf8 => {
+                let offset = self.utf8_offset(slice_ptr, index);
+                self.decode_utf8(buffer_ptr, start + offset)
+            },
+        }
+    }
+
+    pub fn inspect_list(&self, slice_ptr: usize) -> Vec<u32> {
+        match self.list_kind(slice_ptr) {
+            ListKind::Words => {
+                let start = self.list_element_index(slice_ptr, 0);
+                let length = self.list_len(slice_ptr) as usize;
+                self.data[start..(start + length)].to_vec()
+            },
+            ListKind::Bytes => self.inspect_bytes(slice_ptr).into_iter().map(|b| b as u32).collect(),
+            ListKind::Utf8 => self.inspect_string(slice_ptr).chars().map(|ch| ch as u32).collect(),
+        }
+    }
+
+    // It reads `[Char]`.
+    pub fn inspect_string(&self, slice_ptr: usize) -> String {
+        match self.list_kind(slice_ptr) {
+            // It's safe because `alloc_packed_list` and `append_list` only write valid utf-8 sequences.
+            ListKind::Utf8 => String::from_utf8(self.inspect_bytes(slice_ptr)).unwrap(),
+            _ => self.inspect_list(slice_ptr).iter().map(
+                |ch| char::from_u32(*ch).expect("invalid char point")
+            ).collect(),
+        }
+    }
+
+    // `ls[start..end]`
+    pub fn slice_list(&mut self, slice_ptr: usize, start: u32, end: u32) -> u32 {
+        let (buffer_ptr, curr_start) = self.list_buffer_and_start(slice_ptr);
+        let (unit_start, unit_end) = match self.list_kind(slice_ptr) {
+            ListKind::Words | ListKind::Bytes => (start as usize, end as usize),
+            ListKind::Utf8 => (
+                self.utf8_offset(slice_ptr, start as usize),
+                self.utf8_offset(slice_ptr, end as usize),
+            ),
+        };
+
+        self.alloc_slice(
+            buffer_ptr,
+            (curr_start + unit_start) as u32,
+            end - start,
+            (unit_end - unit_start) as u32,
+        )
+    }
+
+    pub fn append_list(&mut self, slice_ptr: usize, value: u32) -> u32 {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        let length = self.list_len(slice_ptr) as usize;
+        let size = self.list_size(slice_ptr);
+        let kind = self.list_kind(slice_ptr);
+        let units = kind.units(value);
+        let hi = self.data[buffer_ptr + 1] as usize;
+
+        if start + size == hi && hi + units <= self.list_capacity(buffer_ptr) && !self.is_read_only(buffer_ptr) {
+            self.write_element(buffer_ptr, kind, hi, value);
+            self.data[buffer_ptr + 1] = (hi + units) as u32;
+            return self.alloc_slice(buffer_ptr, start as u32, length as u32 + 1, (size + units) as u32);
+        }
+
+        let new_buffer_ptr = self.copy_list_to_new_buffer(slice_ptr, size + units, Reserve::Back);
+        let lo = self.data[new_buffer_ptr] as usize;
+        self.write_element(new_buffer_ptr, kind, lo + size, value);
+        self.alloc_slice(new_buffer_ptr, lo as u32, length as u32 + 1, (size + units) as u32)
+    }
+
+    pub fn prepend_list(&mut self, value: u32, slice_ptr: usize) -> u32 {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        let length = self.list_len(slice_ptr) as usize;
+        let size = self.list_size(slice_ptr);
+        let kind = self.list_kind(slice_ptr);
+        let units = kind.units(value);
+        let lo = self.data[buffer_ptr] as usize;
+
+        if start == lo && lo >= units && !self.is_read_only(buffer_ptr) {
+            self.write_element(buffer_ptr, kind, lo - units, value);
+            self.data[buffer_ptr] = (lo - units) as u32;
+            return self.alloc_slice(buffer_ptr, (lo - units) as u32, length as u32 + 1, (size + units) as u32);
+        }
+
+        let new_buffer_ptr = self.copy_list_to_new_buffer(slice_ptr, size + units, Reserve::Front);
+        let lo = self.data[new_buffer_ptr] as usize;
+        self.write_element(new_buffer_ptr, kind, lo, value);
+        self.alloc_slice(new_buffer_ptr, lo as u32, length as u32 + 1, (size + units) as u32)
+    }
+
+    fn list_size(&self, slice_ptr: usize) -> usize {
+        self.data[slice_ptr + 3] as usize
+    }
+
+    fn is_read_only(&self, buffer_ptr: usize) -> bool {
+        self.data[buffer_ptr + 2] & READ_ONLY != 0
+    }
+
+    // in units
+    fn list_capacity(&self, buffer_ptr: usize) -> usize {
+        let words = (self.data[buffer_ptr - 2] & 0x7fff_ffff) as usize - LIST_BUFFER_HEADER;
+
+        match ListKind::from_u32(self.data[buffer_ptr + 2]).unwrap() {
+            ListKind::Words => words,
+            ListKind::Bytes | ListKind::Utf8 => words * 4,
+        }
+    }
+
+    fn alloc_slice(&mut self, buffer_ptr: usize, start: u32, length: u32, size: u32) -> u32 {
+        let slice_ptr = self.alloc(4);
+        self.data[slice_ptr] = buffer_ptr as u32;
+        self.data[slice_ptr + 1] = start;
+        self.data[slice_ptr + 2] = length;
+        self.data[slice_ptr + 3] = size;
+        slice_ptr as u32
+    }
+
+    // It allocates a buffer that can hold at least `capacity` units, and
+    // marks `size` units as used. If `reserve` is `Back`, the used units
+    // are at the front of the buffer, so that the buffer has free spaces for appending.
+    fn alloc_list_buffer(&mut self, kind: ListKind, capacity: usize, size: usize, reserve: Reserve) -> usize {
+        let words = match kind {
+            ListKind::Words => capacity,
+            ListKind::Bytes | ListKind::Utf8 => capacity.div_ceil(4),
+        };
+        let buffer_ptr = self.alloc(words + LIST_BUFFER_HEADER);
+        self.data[buffer_ptr + 2] = kind as u32;
+
+        // constants are read-only
+        if self.pinning {
+            self.data[buffer_ptr + 2] |= READ_ONLY;
+        }
+
+        let capacity = self.list_capacity(buffer_ptr);
+        let lo = match reserve {
+            Reserve::Back => 0,
+            Reserve::Front => capacity - size,
+        };
+
+        self.data[buffer_ptr] = lo as u32;
+        self.data[buffer_ptr + 1] = (lo + size) as u32;
+        buffer_ptr
+    }
+
+    // It copies the units of the list to a new buffer. The new buffer marks
+    // `new_size` units as used, and the caller has to initialize the new element.
+    fn copy_list_to_new_buffer(&mut self, slice_ptr: usize, new_size: usize, reserve: Reserve) -> usize {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        let size = self.list_size(slice_ptr);
+        let kind = self.list_kind(slice_ptr);
+        let new_buffer_ptr = self.alloc_list_buffer(
+            kind,
+            (size * 2).max(new_size).max(MIN_LIST_CAPACITY),
+            new_size,
+            reserve,
+        );
+        let dst = self.data[new_buffer_ptr] as usize + match reserve {
+            Reserve::Back => 0,
+            Reserve::Front => new_size - size,
+        };
+
+        match kind {
+            ListKind::Words => {
+                let src = buffer_ptr + LIST_BUFFER_HEADER + start;
+                self.data.copy_within(src..(src + size), new_buffer_ptr + LIST_BUFFER_HEADER + dst);
+            },
+            ListKind::Bytes | ListKind::Utf8 => {
+                for i in 0..size {
+                    let b = self.read_byte(buffer_ptr, start + i);
+                    self.write_byte(new_buffer_ptr, dst + i, b);
+                }
+            },
+        }
+
+        new_buffer_ptr
+    }
+
+    fn write_element(&mut self, buffer_ptr: usize, kind: ListKind, offset: usize, value: u32) {
+        match kind {
+            ListKind::Words => {
+                self.data[buffer_ptr + LIST_BUFFER_HEADER + offset] = value;
+            },
+            ListKind::Bytes => {
+                self.write_byte(buffer_ptr, offset, value as u8);
+            },
+            ListKind::Utf8 => {
+                let mut buffer = [0; 4];
+                let ch = char::from_u32(value).expect("invalid char point");
+
+                for (i, b) in ch.encode_utf8(&mut buffer).bytes().enumerate() {
+                    self.write_byte(buffer_ptr, offset + i, b);
+                }
+            },
+        }
+    }
+
+    fn read_byte(&self, buffer_ptr: usize, offset: usize) -> u8 {
+        let word = self.data[buffer_ptr + LIST_BUFFER_HEADER + offset / 4];
+        (word >> ((offset % 4) * 8)) as u8
+    }
+
+    fn write_byte(&mut self, buffer_ptr: usize, offset: usize, b: u8) {
+        let word = &mut self.data[buffer_ptr + LIST_BUFFER_HEADER + offset / 4];
+        let shift = (offset % 4) * 8;
+        *word = (*word & !(0xff << shift)) | ((b as u32) << shift);
+    }
+
+    fn inspect_bytes(&self, slice_ptr: usize) -> Vec<u8> {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        let size = self.list_size(slice_ptr);
+        (start..(start + size)).map(|i| self.read_byte(buffer_ptr, i)).collect()
+    }
+
+    // Offset of the `index`th char of the slice, in units (relative to the start of the slice).
+    // `index` can be the length of the slice.
+    fn utf8_offset(&self, slice_ptr: usize, index: usize) -> usize {
+        let (buffer_ptr, start) = self.list_buffer_and_start(slice_ptr);
+        let length = self.list_len(slice_ptr) as usize;
+        let size = self.list_size(slice_ptr);
+
+        // ascii
+        if length == size {
+            return index;
+        }
+
+        let is_char_boundary = |offset: usize| self.read_byte(buffer_ptr, start + offset) & 0b1100_0000 != 0b1000_0000;
+
+        if index <= length / 2 {
+            let mut offset = 0;
+
+            for _ in 0..index {
+                offset += 1;
+
+                while !is_char_boundary(offset) {
+                    offset += 1;
+                }
+            }
+
+            offset
+        }
+
+        else {
+            let mut offset = size;
+
+            for _ in index..length {
+                offset -= 1;
+
+                while !is_char_boundary(offset) {
+                    offset -= 1;
+                }
+            }
+
+            offset
+        }
+    }
+
+    // `offset` must be a char boundary.
+    fn decode_utf8(&self, buffer_ptr: usize, offset: usize) -> u32 {
+        let b = self.read_byte(buffer_ptr, offset) as u32;
+        let (units, mut ch) = match b {
+            0..0x80 => { return b; },
+            0xc0..0xe0 => (2, b & 0x1f),
+            0xe0..0xf0 => (3, b & 0x0f),
+            _ => (4, b & 0x07),
+        };
+
+        for i in 1..units {
+            ch = (ch << 6) | (self.read_byte(buffer_ptr, offset + i) as u32 & 0x3f);
+        }
+
+        ch
+    }
+}
@@ -0,0 +1,33 @@
+use super::Heap;
+use sodigy_number::{BigInt, Ratio};
+
+// A `Number` is a pointer to a tuple of 2 `Int`s: numerator and denominator.
+// It's what `std.number.Number` is, and constants are lowered the same way.
+//
+// heap:   hhh  rrr  d00  d01
+//                   ^
+//                   *-- pointer points to this
+//
+// `d00` is the numerator and `d01` is the denominator. Read the comments in
+// `heap/int.rs` for how an `Int` is represented.
+impl Heap {
+    pub fn inspect_number(&self, ptr: u32) -> Ratio {
+        let ptr = ptr as usize;
+        let (numer_neg, numer) = self.inspect_int(self.data[ptr]);
+        let (denom_neg, denom) = self.inspect_int(self.data[ptr + 1]);
+
+        Ratio {
+            numer: BigInt { is_neg: numer_neg, nums: numer.to_vec() },
+            denom: BigInt { is_neg: denom_neg, nums: denom.to_vec() },
+        }
+    }
+
+    pub fn alloc_number(&mut self, n: &Ratio) -> u32 {
+        let numer = self.alloc_int(n.numer.is_neg, &n.numer.nums);
+        let denom = self.alloc_int(n.denom.is_neg, &n.denom.nums);
+        let ptr = self.alloc(2);
+        self.data[ptr] = numer;
+        self.data[ptr + 1] = denom;
+        ptr as u32
+    }
+}
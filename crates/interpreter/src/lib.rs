@@ -4,39 +4,53 @@ use sodigy_bytecode::{
     Label,
     Memory,
     Offset,
-    Value,
 };
 use sodigy_mir::Intrinsic;
 use sodigy_number::{
-    BigInt,
     add_bi,
+    add_ratio,
+    cmp_ratio,
     div_bi,
+    div_ratio,
     eq_bi,
     gt_bi,
     ilog2_ubi,
     lt_bi,
     mul_bi,
+    mul_ratio,
     neg_bi,
+    normalize_ratio,
+    ratio_to_string,
     rem_bi,
     shl_ubi,
     shr_ubi,
     sub_bi,
+    sub_ratio,
 };
+use std::cmp::Ordering;
 use sodigy_span::RenderSpanSession;
 
 #[cfg(feature="debug-bytecode")]
 mod debug;
 
+mod const_eval;
 mod heap;
+mod memo;
 mod stack;
 
-pub use heap::Heap;
+use const_eval::ConstEval;
+use memo::call_memoized;
+
+pub use const_eval::{ConstAssertResult, eval_const, eval_const_assert};
+pub use heap::{Heap, LIST_BUFFER_HEADER, ListKind, small_int, try_into_small_int};
+pub use memo::MemoCache;
 pub use stack::Stack;
 
 pub fn interpret(executable: &Executable, label: usize, intermediate_dir: &str) -> Result<(), ()> {
     let mut heap = Heap::new();
+    heap.alloc_constants(&executable.constants);
     let mut render_span_session = RenderSpanSession::new(intermediate_dir);
-    let result = call(Stack::new(), &mut heap, executable, label, &mut render_span_session);
+    let result = call(Stack::new(), &mut heap, executable, label, &mut None, &mut render_span_session);
 
     #[cfg(feature="debug-heap")] {
         heap.check_integrity();
@@ -56,16 +70,27 @@ fn call(
     executable: &Executable,
     label: usize,
 
+    // It's set if the compiler is evaluating a top-level `let` (`eval_const`).
+    const_eval: &mut Option<ConstEval>,
+
     // only used for `debug::debug`.
     render_span_session: &mut RenderSpanSession,
 ) -> Result<u32, ()> {
     let mut cursor = label;
 
+    if let Some(const_eval) = const_eval {
+        const_eval.enter()?;
+    }
+
     loop {
         #[cfg(feature="debug-bytecode")] {
             debug::debug(&stack, heap, &executable.bytecodes, cursor, render_span_session);
         }
 
+        if let Some(const_eval) = const_eval {
+            const_eval.step(&executable.bytecodes[cursor])?;
+        }
+
         match &executable.bytecodes[cursor] {
             Bytecode::Const { value, dst, debug_info: _ } => {
                 let value = heap.alloc_value(value);
@@ -90,40 +115,40 @@ fn call(
                 },
                 _ => unreachable!(),
             },
-            Bytecode::Call { func, args, dst, debug_info: _, effect: _ } => {
+            Bytecode::Call { args, dst, .. } |
+            Bytecode::CallDynamic { args, dst, .. } => {
                 let new_stack = Stack::from_args(args, &stack);
-                let pc = match func {
-                    Label::Flatten(i) => *i,
+                let pc = match &executable.bytecodes[cursor] {
+                    Bytecode::Call { func: Label::Flatten(i), .. } => *i,
+                    Bytecode::CallDynamic { func, .. } => read(func, &stack, heap) as usize,
                     _ => unreachable!(),
                 };
 
-                match dst {
-                    Some(dst) => {
-                        let value = call(new_stack, heap, executable, pc as usize, render_span_session)?;
+                match (dst, executable.memoized.get(&pc)) {
+                    (Some(dst), None) => {
+                        let value = call(new_stack, heap, executable, pc, const_eval, render_span_session)?;
                         update(dst, value, &mut stack, heap);
                     },
                     // tail call
-                    None => {
+                    (None, None) => {
                         stack = new_stack;
-                        cursor = pc as usize;
+                        cursor = pc;
                         continue;
                     },
-                }
-            },
-            Bytecode::CallDynamic { func, args, dst, debug_info: _, effect: _ } => {
-                let new_stack = Stack::from_args(args, &stack);
-                let pc = read(func, &stack, heap);
-
-                match dst {
-                    Some(dst) => {
-                        let value = call(new_stack, heap, executable, pc as usize, render_span_session)?;
+                    (Some(dst), Some(memoize)) => {
+                        let value = call_memoized(new_stack, heap, executable, pc, memoize, const_eval, render_span_session)?;
                         update(dst, value, &mut stack, heap);
                     },
-                    // tail call
-                    None => {
-                        stack = new_stack;
-                        cursor = pc as usize;
-                        continue;
+                    // A call to a memoized function cannot be a tail call because
+                    // it has to store the result in the cache after the call.
+                    (None, Some(memoize)) => {
+                        let value = call_memoized(new_stack, heap, executable, pc, memoize, const_eval, render_span_session)?;
+
+                        if let Some(const_eval) = const_eval {
+                            const_eval.leave();
+                        }
+
+                        return Ok(value);
                     },
                 }
             },
@@ -152,7 +177,7 @@ fn call(
                 } else {
                     match func {
                         Label::Flatten(i) => {
-                            stack.r#return = call(Stack::new(), heap, executable, *i, render_span_session)?;
+                            stack.r#return = call(Stack::new(), heap, executable, *i, const_eval, render_span_session)?;
                         },
                         _ => unreachable!(),
                     }
@@ -160,31 +185,46 @@ fn call(
             },
             Bytecode::Label(_) => unreachable!(),
             Bytecode::Return(i) => {
+                if let Some(const_eval) = const_eval {
+                    const_eval.leave();
+                }
+
                 return Ok(*stack.ssa.get(i).unwrap());
             },
-            Bytecode::Update { src, size, index, value, dst } => {
+            Bytecode::Update { src, size, index, value, dst, in_place } => {
                 let ptr = *stack.ssa.get(src).unwrap() as usize;
-                let new_tuple = heap.alloc(*size);
 
-                for (i, v) in heap.data[ptr..(ptr + size)].to_vec().iter().enumerate() {
-                    heap.data[new_tuple + i] = *v;
-                }
+                // The compiler has proven that nobody else can see `src`. The ref count is checked
+                // too, so that it never mutates a pinned block (e.g. a constant).
+                let new_tuple = if *in_place && heap.is_unique(ptr) {
+                    ptr
+                } else {
+                    let new_tuple = heap.alloc(*size);
+
+                    for (i, v) in heap.data[ptr..(ptr + size)].to_vec().iter().enumerate() {
+                        heap.data[new_tuple + i] = *v;
+                    }
+
+                    new_tuple
+                };
 
                 heap.data[new_tuple + index] = *stack.ssa.get(value).unwrap();
                 update(dst, new_tuple as u32, &mut stack, heap);
             },
             Bytecode::Intrinsic { intrinsic, args, dst, debug_info: _ } => match intrinsic {
                 Intrinsic::NegInt => {
-                    let rhs_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (rhs_neg, rhs) = inspect_int(&heap.data, rhs_ptr);
-                    let (is_neg, nums) = neg_bi(rhs_neg, rhs);
-
-                    let v = Value::Int(BigInt {
-                        is_neg,
-                        nums,
-                    });
-                    let ptr = heap.alloc_value(&v);
-                    update(dst, ptr, &mut stack, heap);
+                    let rhs = *stack.ssa.get(&args[0]).unwrap();
+
+                    let result = match small_int(rhs) {
+                        Some(rhs) => heap.alloc_int_from_i64(-rhs),
+                        None => {
+                            let (rhs_neg, rhs) = heap.inspect_int(rhs);
+                            let (is_neg, nums) = neg_bi(rhs_neg, &rhs);
+                            heap.alloc_int(is_neg, &nums)
+                        },
+                    };
+
+                    update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::AddInt |
                 Intrinsic::SubInt |
@@ -194,37 +234,51 @@ fn call(
                 Intrinsic::LtInt |
                 Intrinsic::EqInt |
                 Intrinsic::GtInt => {
-                    let lhs_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (lhs_neg, lhs) = inspect_int(&heap.data, lhs_ptr);
-
-                    let rhs_ptr = *stack.ssa.get(&args[1]).unwrap() as usize;
-                    let (rhs_neg, rhs) = inspect_int(&heap.data, rhs_ptr);
+                    let lhs = *stack.ssa.get(&args[0]).unwrap();
+                    let rhs = *stack.ssa.get(&args[1]).unwrap();
 
-                    let result = match intrinsic {
-                        Intrinsic::AddInt |
-                        Intrinsic::SubInt |
-                        Intrinsic::MulInt |
-                        Intrinsic::DivInt |
-                        Intrinsic::RemInt => {
-                            let (is_neg, nums) = match intrinsic {
-                                Intrinsic::AddInt => add_bi(lhs_neg, lhs, rhs_neg, rhs),
-                                Intrinsic::SubInt => sub_bi(lhs_neg, lhs, rhs_neg, rhs),
-                                Intrinsic::MulInt => mul_bi(lhs_neg, lhs, rhs_neg, rhs),
-                                Intrinsic::DivInt => div_bi(lhs_neg, lhs, rhs_neg, rhs),
-                                Intrinsic::RemInt => rem_bi(lhs_neg, lhs, rhs_neg, rhs),
+                    let result = match (small_int(lhs), small_int(rhs)) {
+                        // Fast path: it doesn't touch the heap unless the result is too big.
+                        // It can't overflow `i64` because small integers are 31 bits.
+                        // Division by zero takes the slow path, so that it behaves the same.
+                        (Some(lhs), Some(rhs)) if rhs != 0 || !matches!(intrinsic, Intrinsic::DivInt | Intrinsic::RemInt) => match intrinsic {
+                            // Sodigy uses truncated division, which is what Rust does.
+                            Intrinsic::AddInt => heap.alloc_int_from_i64(lhs + rhs),
+                            Intrinsic::SubInt => heap.alloc_int_from_i64(lhs - rhs),
+                            Intrinsic::MulInt => heap.alloc_int_from_i64(lhs * rhs),
+                            Intrinsic::DivInt => heap.alloc_int_from_i64(lhs / rhs),
+                            Intrinsic::RemInt => heap.alloc_int_from_i64(lhs % rhs),
+                            Intrinsic::LtInt => (lhs < rhs) as u32,
+                            Intrinsic::EqInt => (lhs == rhs) as u32,
+                            Intrinsic::GtInt => (lhs > rhs) as u32,
+                            _ => unreachable!(),
+                        },
+                        _ => {
+                            let (lhs_neg, lhs) = heap.inspect_int(lhs);
+                            let (rhs_neg, rhs) = heap.inspect_int(rhs);
+
+                            match intrinsic {
+                                Intrinsic::AddInt |
+                                Intrinsic::SubInt |
+                                Intrinsic::MulInt |
+                                Intrinsic::DivInt |
+                                Intrinsic::RemInt => {
+                                    let (is_neg, nums) = match intrinsic {
+                                        Intrinsic::AddInt => add_bi(lhs_neg, &lhs, rhs_neg, &rhs),
+                                        Intrinsic::SubInt => sub_bi(lhs_neg, &lhs, rhs_neg, &rhs),
+                                        Intrinsic::MulInt => mul_bi(lhs_neg, &lhs, rhs_neg, &rhs),
+                                        Intrinsic::DivInt => div_bi(lhs_neg, &lhs, rhs_neg, &rhs),
+                                        Intrinsic::RemInt => rem_bi(lhs_neg, &lhs, rhs_neg, &rhs),
+                                        _ => unreachable!(),
+                                    };
+                                    heap.alloc_int(is_neg, &nums)
+                                },
+                                Intrinsic::LtInt => if lt_bi(lhs_neg, &lhs, rhs_neg, &rhs) { 1 } else { 0 },
+                                Intrinsic::EqInt => if eq_bi(lhs_neg, &lhs, rhs_neg, &rhs) { 1 } else { 0 },
+                                Intrinsic::GtInt => if gt_bi(lhs_neg, &lhs, rhs_neg, &rhs) { 1 } else { 0 },
                                 _ => unreachable!(),
-                            };
-                            let v = Value::Int(BigInt {
-                                is_neg,
-                                nums,
-                            });
-                            let ptr = heap.alloc_value(&v);
-                            ptr
+                            }
                         },
-                        Intrinsic::LtInt => if lt_bi(lhs_neg, lhs, rhs_neg, rhs) { 1 } else { 0 },
-                        Intrinsic::EqInt => if eq_bi(lhs_neg, lhs, rhs_neg, rhs) { 1 } else { 0 },
-                        Intrinsic::GtInt => if gt_bi(lhs_neg, lhs, rhs_neg, rhs) { 1 } else { 0 },
-                        _ => unreachable!(),
                     };
 
                     update(dst, result, &mut stack, heap);
@@ -232,26 +286,34 @@ fn call(
                 Intrinsic::BitAndInt => todo!(),
                 Intrinsic::BitOrInt => todo!(),
                 Intrinsic::ShrInt | Intrinsic::ShlInt => {
-                    let lhs_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (is_neg, lhs) = inspect_int(&heap.data, lhs_ptr);
+                    let lhs = *stack.ssa.get(&args[0]).unwrap();
                     let rhs = *stack.ssa.get(&args[1]).unwrap();
 
-                    let nums = match intrinsic {
-                        Intrinsic::ShrInt => shr_ubi(lhs, rhs),
-                        Intrinsic::ShlInt => shl_ubi(lhs, rhs),
-                        _ => unreachable!(),
+                    let result = match (small_int(lhs), intrinsic) {
+                        // It shifts the absolute value, like `shr_ubi`.
+                        (Some(lhs), Intrinsic::ShrInt) => {
+                            let abs = lhs.unsigned_abs().checked_shr(rhs).unwrap_or(0) as i64;
+                            heap.alloc_int_from_i64(if lhs < 0 { -abs } else { abs })
+                        },
+                        (Some(lhs), Intrinsic::ShlInt) if rhs < 32 => heap.alloc_int_from_i64(lhs << rhs),
+                        _ => {
+                            let (is_neg, lhs) = heap.inspect_int(lhs);
+
+                            let nums = match intrinsic {
+                                Intrinsic::ShrInt => shr_ubi(&lhs, rhs),
+                                Intrinsic::ShlInt => shl_ubi(&lhs, rhs),
+                                _ => unreachable!(),
+                            };
+                            heap.alloc_int(is_neg, &nums)
+                        },
                     };
-                    let v = Value::Int(BigInt {
-                        is_neg,
-                        nums,
-                    });
-                    let result = heap.alloc_value(&v);
+
                     update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::Ilog2Int => {
-                    let lhs = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (_, rhs) = inspect_int(&heap.data, lhs);
-                    let result = ilog2_ubi(rhs);
+                    let lhs = *stack.ssa.get(&args[0]).unwrap();
+                    let (_, rhs) = heap.inspect_int(lhs);
+                    let result = ilog2_ubi(&rhs);
                     update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::LtScalar |
@@ -271,76 +333,51 @@ fn call(
                 Intrinsic::BitOrScalar => todo!(),
                 Intrinsic::ScalarToInt => {
                     let lhs = *stack.ssa.get(&args[0]).unwrap();
-                    let result = heap.alloc_int_from_u32(lhs);
+                    let result = heap.alloc_int_from_i64(lhs as i64);
                     update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::IntToScalar => {
-                    let lhs = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (_, n) = inspect_int(&heap.data, lhs);
+                    let lhs = *stack.ssa.get(&args[0]).unwrap();
+                    let (_, n) = heap.inspect_int(lhs);
                     update(dst, n[0], &mut stack, heap);
                 },
                 Intrinsic::IndexList => {
                     let slice_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
                     let index = *stack.ssa.get(&args[1]).unwrap() as usize;
-                    let buffer_ptr = heap.data[slice_ptr] as usize;
-                    let start = heap.data[slice_ptr + 1] as usize;
-                    let result = heap.data[buffer_ptr + start + index + 1];
+                    let result = heap.index_list(slice_ptr, index);
                     update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::LenList => {
                     let slice_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let result = heap.data[slice_ptr + 2];
+                    let result = heap.list_len(slice_ptr);
                     update(dst, result, &mut stack, heap);
                 },
                 Intrinsic::SliceList => {
                     let slice_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
                     let slice_start = *stack.ssa.get(&args[1]).unwrap();
                     let slice_end = *stack.ssa.get(&args[2]).unwrap();
-                    let buffer_ptr = heap.data[slice_ptr];
-                    let start = heap.data[slice_ptr + 1];
-
-                    let new_slice_ptr = heap.alloc(3);
-                    heap.data[new_slice_ptr] = buffer_ptr as u32;
-                    heap.data[new_slice_ptr + 1] = start + slice_start;
-                    heap.data[new_slice_ptr + 2] = slice_end - slice_start;
-                    update(dst, new_slice_ptr as u32, &mut stack, heap);
+                    let new_slice_ptr = heap.slice_list(slice_ptr, slice_start, slice_end);
+                    update(dst, new_slice_ptr, &mut stack, heap);
                 },
                 Intrinsic::SliceRightList => {
                     let slice_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
                     let slice_start = *stack.ssa.get(&args[1]).unwrap();
-                    let buffer_ptr = heap.data[slice_ptr];
-                    let start = heap.data[slice_ptr + 1];
-                    let length = heap.data[slice_ptr + 2];
-
-                    let new_slice_ptr = heap.alloc(3);
-                    heap.data[new_slice_ptr] = buffer_ptr as u32;
-                    heap.data[new_slice_ptr + 1] = start + slice_start;
-                    heap.data[new_slice_ptr + 2] = length - slice_start;
-                    update(dst, new_slice_ptr as u32, &mut stack, heap);
+                    let length = heap.list_len(slice_ptr);
+                    let new_slice_ptr = heap.slice_list(slice_ptr, slice_start, length);
+                    update(dst, new_slice_ptr, &mut stack, heap);
                 },
                 Intrinsic::AppendList => {
                     let slice_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
                     let value = *stack.ssa.get(&args[1]).unwrap();
-
-                    // TODO: I don't want to call `.to_vec()`, but the borrow checker forces me to do so.
-                    let curr_list = inspect_list(&heap.data, slice_ptr).to_vec();
-
-                    let new_buffer = heap.alloc(curr_list.len() + 2);
-                    heap.data[new_buffer] = curr_list.len() as u32 + 1;
-                    heap.data[new_buffer + curr_list.len() + 1] = value;
-
-                    for (i, v) in curr_list.iter().enumerate() {
-                        heap.data[new_buffer + i + 1] = *v;
-                    }
-
-                    let new_slice_ptr = heap.alloc(3);
-                    heap.data[new_slice_ptr] = new_buffer as u32;
-                    heap.data[new_slice_ptr + 1] = 0;
-                    heap.data[new_slice_ptr + 2] = curr_list.len() as u32 + 1;
-
-                    update(dst, new_slice_ptr as u32, &mut stack, heap);
+                    let new_slice_ptr = heap.append_list(slice_ptr, value);
+                    update(dst, new_slice_ptr, &mut stack, heap);
+                },
+                Intrinsic::PrependList => {
+                    let value = *stack.ssa.get(&args[0]).unwrap();
+                    let slice_ptr = *stack.ssa.get(&args[1]).unwrap() as usize;
+                    let new_slice_ptr = heap.prepend_list(value, slice_ptr);
+                    update(dst, new_slice_ptr, &mut stack, heap);
                 },
-                Intrinsic::PrependList => todo!(),
                 Intrinsic::Exit => {
                     // TODO: clean up stack and heap
                     return Ok(0);
@@ -349,12 +386,11 @@ fn call(
                     // TODO: clean up stack and heap
                     return Err(());
                 },
+                // It doesn't print anything at compile time. Read the comments in `ConstEval`.
+                Intrinsic::Print | Intrinsic::EPrint if const_eval.is_some() => {},
                 Intrinsic::Print | Intrinsic::EPrint => {
                     let chars_ptr = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let chars = inspect_list(&heap.data, chars_ptr);
-                    let chars = chars.iter().map(
-                        |ch| char::from_u32(*ch).expect("invalid char point")
-                    ).collect::<Vec<_>>().into_iter().collect::<String>();
+                    let chars = heap.inspect_string(chars_ptr);
 
                     match intrinsic {
                         Intrinsic::Print => {
@@ -368,8 +404,8 @@ fn call(
                 },
                 Intrinsic::RandomInt => todo!(),
                 Intrinsic::Sleep => {
-                    let n = *stack.ssa.get(&args[0]).unwrap() as usize;
-                    let (_, ns) = inspect_int(&heap.data, n);
+                    let n = *stack.ssa.get(&args[0]).unwrap();
+                    let (_, ns) = heap.inspect_int(n);
                     let n = match (ns.get(0), ns.get(1), ns.get(2)) {
                         (Some(n), None, _) => *n as u64,
                         (Some(a), Some(b), None) => *a as u64 | ((*b as u64) << 32),
@@ -378,6 +414,43 @@ fn call(
 
                     std::thread::sleep(std::time::Duration::from_millis(n));
                 },
+                Intrinsic::AddNumber |
+                Intrinsic::SubNumber |
+                Intrinsic::MulNumber |
+                Intrinsic::DivNumber => {
+                    let lhs = heap.inspect_number(*stack.ssa.get(&args[0]).unwrap());
+                    let rhs = heap.inspect_number(*stack.ssa.get(&args[1]).unwrap());
+                    let result = match intrinsic {
+                        Intrinsic::AddNumber => add_ratio(&lhs, &rhs),
+                        Intrinsic::SubNumber => sub_ratio(&lhs, &rhs),
+                        Intrinsic::MulNumber => mul_ratio(&lhs, &rhs),
+                        Intrinsic::DivNumber => div_ratio(&lhs, &rhs),
+                        _ => unreachable!(),
+                    };
+                    let result = heap.alloc_number(&result);
+                    update(dst, result, &mut stack, heap);
+                },
+                Intrinsic::LtNumber | Intrinsic::GtNumber => {
+                    let lhs = heap.inspect_number(*stack.ssa.get(&args[0]).unwrap());
+                    let rhs = heap.inspect_number(*stack.ssa.get(&args[1]).unwrap());
+                    let result = match intrinsic {
+                        Intrinsic::LtNumber => cmp_ratio(&lhs, &rhs) == Ordering::Less,
+                        Intrinsic::GtNumber => cmp_ratio(&lhs, &rhs) == Ordering::Greater,
+                        _ => unreachable!(),
+                    };
+                    update(dst, result as u32, &mut stack, heap);
+                },
+                Intrinsic::NormalizeNumber => {
+                    let n = heap.inspect_number(*stack.ssa.get(&args[0]).unwrap());
+                    let result = heap.alloc_number(&normalize_ratio(&n));
+                    update(dst, result, &mut stack, heap);
+                },
+                Intrinsic::NumberToString => {
+                    let n = heap.inspect_number(*stack.ssa.get(&args[0]).unwrap());
+                    let s = ratio_to_string(&n);
+                    let result = heap.alloc_packed_list(ListKind::Utf8, s.as_bytes());
+                    update(dst, result, &mut stack, heap);
+                },
                 Intrinsic::Nop0 => {},
                 Intrinsic::Nop1 => {
                     let v = *stack.ssa.get(&args[0]).unwrap();
@@ -389,13 +462,8 @@ fn call(
                 update(dst, ptr as u32, &mut stack, heap);
             },
             Bytecode::InitList { elements, dst, debug_info: _ } => {
-                let data_ptr = heap.alloc(*elements + 1);
-                heap.data[data_ptr] = *elements as u32;
-                let slice_ptr = heap.alloc(3);
-                heap.data[slice_ptr] = data_ptr as u32;
-                heap.data[slice_ptr + 1] = 0;
-                heap.data[slice_ptr + 2] = *elements as u32;
-                update(dst, slice_ptr as u32, &mut stack, heap);
+                let slice_ptr = heap.alloc_list(*elements);
+                update(dst, slice_ptr, &mut stack, heap);
             },
             Bytecode::PushDebugInfo { kind, src } => {
                 let src = read(src, &stack, heap);
@@ -422,7 +490,14 @@ fn read(src: &Memory, stack: &Stack, heap: &Heap) -> u32 {
             };
             heap.data[(ptr + offset) as usize]
         },
-        Memory::List { ptr, offset } => todo!(),
+        Memory::List { ptr, offset } => {
+            let ptr = *stack.ssa.get(ptr).unwrap() as usize;
+            let offset = match offset {
+                Offset::Static(i) => *i,
+                Offset::Dynamic(p) => read(p, stack, heap),
+            };
+            heap.index_list(ptr, offset as usize)
+        },
         Memory::Global(s) => *heap.global_values.get(s).expect("global should be initialized before used"),
     }
 }
@@ -450,27 +525,11 @@ fn update(dst: &Memory, value: u32, stack: &mut Stack, heap: &mut Heap) {
                 Offset::Static(i) => *i,
                 Offset::Dynamic(p) => read(p, stack, heap),
             };
-            let data_ptr = heap.data[ptr];
-            let start = heap.data[ptr + 1];
-            heap.data[(data_ptr + start + offset + 1) as usize] = value;
+            let index = heap.list_element_index(ptr, offset as usize);
+            heap.data[index] = value;
         },
         Memory::Global(s) => {
             heap.global_values.insert(s.clone(), value);
         },
     }
 }
-
-fn inspect_int(heap: &[u32], ptr: usize) -> (bool, &[u32]) {
-    let metadata = heap[ptr];
-    let is_neg = metadata > 0x7fff_ffff;
-    let length = metadata & 0x7fff_ffff;
-    let nums = &heap[(ptr + 1)..(ptr + 1 + length as usize)];
-    (is_neg, nums)
-}
-
-fn inspect_list(heap: &[u32], ptr: usize) -> &[u32] {
-    let slice_ptr = heap[ptr] as usize;
-    let start = heap[ptr + 1] as usize;
-    let length = heap[ptr + 2] as usize;
-    &heap[(slice_ptr + start + 1)..(slice_ptr + start + length + 1)]
-}
@@ -0,0 +1,57 @@
+use crate::{Heap, Stack, call};
+use crate::const_eval::ConstEval;
+use sodigy_bytecode::{Executable, Memoize, SSA, Value};
+use sodigy_endec::Endec;
+use sodigy_span::RenderSpanSession;
+use std::collections::{HashMap, VecDeque};
+
+// A cache of a memoized function (`#[memoize]`).
+pub struct MemoCache {
+    // key: encoded arguments, value: the returned value
+    values: HashMap<Vec<u8>, u32>,
+
+    // The keys of `values`, from the oldest to the newest.
+    keys: VecDeque<Vec<u8>>,
+}
+
+// It calls the memoized function at `label`, unless the result is already in the cache.
+// The arguments are read back from the heap and encoded, so that the same values have
+// the same key even if they're in different blocks.
+//
+// It's safe to share the cached values because
+//   1. The runtime never frees a block yet.
+//   2. A value returned by a function is never updated in place (read the comments in `in_place.rs`).
+pub(crate) fn call_memoized(
+    stack: Stack,
+    heap: &mut Heap,
+    executable: &Executable,
+    label: usize,
+    memoize: &Memoize,
+    const_eval: &mut Option<ConstEval>,
+    render_span_session: &mut RenderSpanSession,
+) -> Result<u32, ()> {
+    let key = memoize.params.iter().enumerate().map(
+        |(i, shape)| heap.read_value(*stack.ssa.get(&SSA::from_u32(i as u32)).unwrap(), shape)
+    ).collect::<Vec<Value>>().encode();
+
+    if let Some(value) = heap.memo_caches.get(&label).and_then(|cache| cache.values.get(&key)) {
+        return Ok(*value);
+    }
+
+    let value = call(stack, heap, executable, label, const_eval, render_span_session)?;
+    let cache = heap.memo_caches.entry(label).or_insert_with(
+        || MemoCache {
+            values: HashMap::new(),
+            keys: VecDeque::new(),
+        }
+    );
+
+    // It evicts the oldest entry.
+    if let Some(max) = memoize.max && cache.keys.len() >= max && let Some(oldest) = cache.keys.pop_front() {
+        cache.values.remove(&oldest);
+    }
+
+    cache.keys.push_back(key.clone());
+    cache.values.insert(key, value);
+    Ok(value)
+}
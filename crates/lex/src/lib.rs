@@ -75,6 +75,7 @@ pub fn lex(
         state: LexState::Init,
         cursor: 0,
         tokens: vec![],
+        comments: vec![],
         intermediate_dir,
         is_std,
         group_stack: vec![],
@@ -147,6 +148,7 @@ impl Session {
                             kind: ErrorKind::DotDotDot,
                             spans: Span::range(self.file, self.cursor as u32 + 1, 3).simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -175,6 +177,7 @@ impl Session {
                         kind: ErrorKind::InvalidNumberLiteral,
                         spans: Span::range(self.file, self.cursor as u32 + 1, 1).simple_error(),
                         note: Some(format!("`{}` is not a valid prefix. Valid ones are `x`, `X`, `o`, `O`, `b` and `B`.", *y as char)),
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'0'), Some(b'.'), _) => {
@@ -190,6 +193,7 @@ impl Session {
                         kind: ErrorKind::InvalidNumberLiteral,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: Some(String::from("Leading zeros in decimal literals are not permitted.")),
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'0'), _, _) => {
@@ -259,6 +263,7 @@ impl Session {
                                         kind: ErrorKind::InvalidByteLiteral,
                                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                                         note: Some(base.invalid_digit_error_message(*x)),
+                                        suggestions: vec![],
                                     });
                                 }
 
@@ -283,6 +288,7 @@ impl Session {
                                 (self.cursor - token_start) as u32,
                             ).simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -315,6 +321,7 @@ impl Session {
                                     (self.cursor - token_start) as u32,
                                 ).simple_error(),
                                 note: Some(String::from("A byte must be in range #0..=#255.")),
+                                suggestions: vec![],
                             });
                         },
                     }
@@ -323,9 +330,11 @@ impl Session {
                     let top_level = *z == b'!';
                     self.token_start = self.cursor;
                     self.state = LexState::DocComment { top_level };
+                    self.buffer1.clear();
                     self.cursor += 3;
                 },
                 (Some(b'/'), Some(b'/'), _) => {
+                    self.token_start = self.cursor;
                     self.state = LexState::LineComment;
                     self.cursor += 2;
                 },
@@ -397,6 +406,7 @@ impl Session {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         });
                     },
                     None => {
@@ -407,6 +417,7 @@ impl Session {
                             },
                             spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     },
                 },
@@ -497,6 +508,7 @@ impl Session {
                         kind: ErrorKind::UnexpectedByte(*x),
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (None, _, _) => {
@@ -505,6 +517,7 @@ impl Session {
                             kind: ErrorKind::UnclosedDelimiter(delim),
                             spans: span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -531,6 +544,7 @@ impl Session {
                         },
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(x @ b'b'), Some(y @ b'f'), Some(z @ (b'"' | b'\''))) |
@@ -543,6 +557,7 @@ impl Session {
                         },
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'b'), Some(b'r'), Some(b'"')) |
@@ -567,6 +582,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteralPrefix(vec![*x, *y]),
                         spans: error_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'f'), Some(b'r'), Some(b'"')) |
@@ -586,6 +602,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteralPrefix(vec![*x, *y]),
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'r'), Some(b'e'), Some(b'"')) => {
@@ -603,6 +620,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteralPrefix(vec![b'r', b'e']),
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(x @ (b'b' | b'f' | b'r')), Some(b'"'), _) => {
@@ -628,6 +646,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteralPrefix(vec![*x]),
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'"' | b'\''), _, _) => {
@@ -647,6 +666,7 @@ impl Session {
                         },
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(x @ b'a'..=b'z'), Some(y @ (b'"' | b'\'')), _) => {
@@ -658,6 +678,7 @@ impl Session {
                         },
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 _ => unreachable!(),
@@ -680,6 +701,7 @@ impl Session {
                             spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
 
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -704,8 +726,8 @@ impl Session {
                                 kind: token_kind,
                                 span: Span::range(
                                     self.file,
-                                    self.cursor as u32,
-                                    quote_count as u32,
+                                    self.token_start as u32,
+                                    (self.cursor + quote_count - self.token_start) as u32,
                                 ),
                             });
                             self.state = LexState::Init;
@@ -723,6 +745,7 @@ impl Session {
                                     quote_count as u32,
                                 ).simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             });
                         },
 
@@ -757,7 +780,7 @@ impl Session {
                     };
                     self.tokens.push(Token {
                         kind: token_kind,
-                        span: Span::range(self.file, self.cursor as u32, 2),
+                        span: Span::range(self.file, self.token_start as u32, (self.cursor + 2 - self.token_start) as u32),
                     });
                     self.state = LexState::Init;
                     self.cursor += 2;
@@ -768,6 +791,7 @@ impl Session {
                         kind: ErrorKind::EmptyCharLiteral,
                         spans: Span::range(self.file, self.cursor as u32, 2).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'"'), _, _) => {
@@ -818,6 +842,7 @@ impl Session {
                         kind: ErrorKind::UnmatchedBraceInFormattedString,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'"'), _, _) if quote_count == 1 => {
@@ -905,6 +930,7 @@ impl Session {
                             quote_count as u32,
                         ).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
             },
@@ -940,6 +966,7 @@ impl Session {
                         kind: ErrorKind::UnmatchedBraceInFormattedString,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 // valid escape
@@ -964,6 +991,7 @@ impl Session {
                         kind: ErrorKind::InvalidEscape,
                         spans: Span::range(self.file, self.cursor as u32 + 1, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'"'), _, _, _) if quote_count == 1 => {
@@ -991,7 +1019,7 @@ impl Session {
                             span: Span::range(
                                 self.file,
                                 self.token_start as u32,
-                                (self.cursor - self.token_start) as u32,
+                                (self.cursor + 1 - self.token_start) as u32,
                             ),
                         });
                     }
@@ -1044,6 +1072,7 @@ impl Session {
                         kind: ErrorKind::InvalidUtf8,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (None, _, _, _) => {
@@ -1055,6 +1084,7 @@ impl Session {
                             quote_count as u32,
                         ).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
             },
@@ -1143,6 +1173,7 @@ impl Session {
                                     (self.cursor + 5 - self.token_start) as u32,
                                 ).simple_error(),
                                 note: Some(format!("A byte char literal must be an ascii char. Perhaps you mean `#{}`?", n1 * 16 + n2)),
+                                suggestions: vec![],
                             });
                         }
                     }
@@ -1186,6 +1217,7 @@ impl Session {
                                         kind: ErrorKind::InvalidUnicodeCharacter,
                                         spans: Span::range(self.file, escape_start as u32, 1).simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                 }
                             },
@@ -1198,6 +1230,7 @@ impl Session {
                                     kind: ErrorKind::InvalidUnicodeEscape,
                                     spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 });
                             },
                             None => {
@@ -1205,6 +1238,7 @@ impl Session {
                                     kind: ErrorKind::UnclosedDelimiter(b'}'),
                                     spans: Span::range(self.file, delimiter_start as u32, 1).simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 });
                             },
                         }
@@ -1247,6 +1281,7 @@ impl Session {
                                     (self.cursor - self.token_start) as u32,
                                 ).simple_error(),
                                 note: Some(error_note),
+                                suggestions: vec![],
                             });
                         }
                     }
@@ -1267,6 +1302,7 @@ impl Session {
                         kind: ErrorKind::InvalidEscape,
                         spans: Span::range(self.file, self.cursor as u32 + 1, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (Some(b'\r' | b'\n' | b'\t' | b'\''), _, _, _, _) => {
@@ -1274,6 +1310,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteral,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 // valid char (utf-8)
@@ -1335,6 +1372,7 @@ impl Session {
                                             (self.cursor + l + 1 - self.token_start) as u32,
                                         ).simple_error(),
                                         note: Some(error_note),
+                                        suggestions: vec![],
                                     });
                                 }
                             }
@@ -1361,6 +1399,7 @@ impl Session {
                                 spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
 
                                 note: None,
+                                suggestions: vec![],
                             });
                         },
                     }
@@ -1371,6 +1410,7 @@ impl Session {
                         kind: ErrorKind::InvalidUtf8,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 // etc error (probably multi-character literal)
@@ -1379,6 +1419,7 @@ impl Session {
                         kind: ErrorKind::InvalidCharLiteral,
                         spans: Span::range(self.file, self.token_start as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 (None, _, _, _, _) => {
@@ -1386,6 +1427,7 @@ impl Session {
                         kind: ErrorKind::UnterminatedCharLiteral,
                         spans: Span::range(self.file, self.token_start as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
             },
@@ -1422,6 +1464,7 @@ impl Session {
                         kind: ErrorKind::InvalidUtf8,
                         spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 _ => {
@@ -1453,6 +1496,7 @@ impl Session {
                                     (self.cursor - self.token_start) as u32,
                                 ).simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             });
                         },
                         _ => {
@@ -1476,6 +1520,7 @@ impl Session {
                                             spans: Span::range(self.file, self.token_start as u32, 1).simple_error(),
 
                                             note: None,
+                                            suggestions: vec![],
                                         });
                                     },
                                 }
@@ -1543,6 +1588,7 @@ impl Session {
                             kind: ErrorKind::InvalidNumberLiteral,
                             spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                             note: Some(base.invalid_digit_error_message(*x)),
+                            suggestions: vec![],
                         });
                     }
 
@@ -1560,6 +1606,7 @@ impl Session {
                         kind: ErrorKind::DotDotDot,
                         spans: Span::range(self.file, self.cursor as u32, 3).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
 
@@ -1595,6 +1642,7 @@ impl Session {
                             kind: ErrorKind::InvalidNumberLiteral,
                             spans: Span::range(self.file, self.cursor as u32, 1).simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     },
                 },
@@ -1715,16 +1763,18 @@ impl Session {
                 self.state = LexState::Init;
             },
             LexState::LineComment => match self.input_bytes.get(self.cursor) {
-                Some(b'\n') => {
+                Some(b'\n') | None => {
+                    self.comments.push(Span::range(
+                        self.file,
+                        self.token_start as u32,
+                        (self.cursor - self.token_start) as u32,
+                    ));
                     self.state = LexState::Init;
                     self.cursor += 1;
                 },
                 Some(_) => {
                     self.cursor += 1;
                 },
-                None => {
-                    self.state = LexState::Init;
-                },
             },
             LexState::DocComment { top_level } => match self.input_bytes.get(self.cursor) {
                 Some(b'\n') | None => {
@@ -1751,6 +1801,11 @@ impl Session {
             },
             LexState::BlockComment => match (self.input_bytes.get(self.cursor), self.input_bytes.get(self.cursor + 1)) {
                 (Some(b'*'), Some(b'/')) => {
+                    self.comments.push(Span::range(
+                        self.file,
+                        self.token_start as u32,
+                        (self.cursor + 2 - self.token_start) as u32,
+                    ));
                     self.state = LexState::Init;
                     self.cursor += 2;
                 },
@@ -1765,6 +1820,7 @@ impl Session {
                         spans: Span::range(self.file, self.token_start as u32, 2).simple_error(),
 
                         note: None,
+                        suggestions: vec![],
                     });
                 },
             },
@@ -1784,6 +1840,7 @@ impl Session {
                         kind: ErrorKind::NotAllowedCharInFormattedString(*x),
                         spans: Span::range(self.file, i as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
                 Some(b':') => {
@@ -1799,6 +1856,7 @@ impl Session {
                         kind: ErrorKind::UnterminatedStringLiteral,
                         spans: Span::range(self.file, self.token_start as u32, 1).simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 },
             }
@@ -1826,6 +1884,7 @@ impl Session {
                     (value_end + 1 - self.cursor) as u32,
                 ).simple_error(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
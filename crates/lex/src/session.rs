@@ -10,6 +10,10 @@ pub struct Session {
     pub(crate) state: LexState,
     pub(crate) cursor: usize,
     pub tokens: Vec<Token>,
+
+    // Spans of `//` and `/* */` comments. The compiler doesn't care about
+    // them, but the formatter has to preserve them. Doc comments are tokens.
+    pub comments: Vec<Span>,
     pub intermediate_dir: String,
     pub is_std: bool,
 
@@ -81,6 +81,8 @@ fn validate_spans_worker(
                     assert_eq!(&span_code[0..2], b"f\"");
                 }
 
+                assert_eq!(span_code[span_code.len() - 1], b'"');
+
                 for element in elements.iter() {
                     match element {
                         TokensOrString::Tokens { tokens, span } => {
@@ -374,6 +374,15 @@ pub fn dump_field<S: SodigySession>(fields: &[Field], dotfish: &[Option<Dotfish>
             Field::SelfAsScalar => {
                 lines.push("__SELF_AS_SCALAR__");
             },
+            Field::NicheDiscriminant { null_variant } => {
+                lines.push(&format!("__NICHE_DISCRIMINANT_{null_variant}__"));
+            },
+            Field::UnboxedDiscriminant => {
+                lines.push("__UNBOXED_DISCRIMINANT__");
+            },
+            Field::UnboxedPayload { variant, payload } => {
+                lines.push(&format!("__UNBOXED_PAYLOAD_{variant}_{payload}__"));
+            },
         }
 
         if let Some(dotfish) = dotfish {
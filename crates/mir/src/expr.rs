@@ -8,7 +8,7 @@ use crate::{
     Type,
     lower_hir_if,
 };
-use sodigy_error::{EnumFieldKind, Error, ErrorKind, NotXBut, comma_list_strs, to_ordinal};
+use sodigy_error::{EnumFieldKind, Error, ErrorKind, NotXBut, comma_list_strs, did_you_mean, to_ordinal};
 use sodigy_hir::{self as hir, Generic};
 use sodigy_name_analysis::{IdentWithOrigin, NameKind, NameOrigin};
 use sodigy_parse::{ConversionKind, Field, merge_field_spans};
@@ -320,10 +320,17 @@ impl Expr {
                                                 given_keyword_args_[i] = Some((*keyword, keyword_span.clone()));
                                             },
                                             None => {
+                                                let param_names = params.iter().map(
+                                                    |param| param.name.unintern_or_default(&session.intermediate_dir)
+                                                ).collect::<Vec<_>>();
                                                 session.errors.push(Error {
                                                     kind: ErrorKind::InvalidKeywordArg(*keyword),
                                                     spans: keyword_span.simple_error(),
-                                                    note: None,
+                                                    note: did_you_mean(
+                                                        &keyword.unintern_or_default(&session.intermediate_dir),
+                                                        &param_names,
+                                                    ),
+                                                    suggestions: vec![],
                                                 });
                                                 has_error = true;
                                             },
@@ -357,6 +364,7 @@ impl Expr {
                                     kind: ErrorKind::KeywordArgRepeated(keyword),
                                     spans: error_spans,
                                     note: None,
+                                    suggestions: vec![],
                                 });
                             }
 
@@ -433,6 +441,7 @@ impl Expr {
                                         kind: ErrorKind::KeywordArgNotAllowed,
                                         spans: keyword_span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                     has_error = true;
                                 },
@@ -610,6 +619,7 @@ impl Expr {
                         },
                         spans: call_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                     return Err(());
                 }
@@ -623,6 +633,7 @@ impl Expr {
                         kind: ErrorKind::NotStruct { id: constructor.id.id, tuple_struct: false, but },
                         spans: call_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
 
                     return Err(());
@@ -756,6 +767,7 @@ impl Expr {
                             kind: ErrorKind::StructFieldRepeated(field_name),
                             spans: error_spans,
                             note: None,
+                            suggestions: vec![],
                         });
                         has_error = true;
                     }
@@ -819,6 +831,7 @@ impl Expr {
                             kind: ErrorKind::MissingStructFields { struct_name, enum_name, missing_fields: names },
                             spans,
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -846,6 +859,7 @@ impl Expr {
                                     "and",
                                 ),
                             )),
+                            suggestions: vec![],
                         });
                     }
 
@@ -1,6 +1,6 @@
 use crate::{Expr, Session, Type};
 use sodigy_error::FuncEffect;
-use sodigy_hir::{self as hir, FuncOrigin, FuncParam, FuncShape, Generic};
+use sodigy_hir::{self as hir, FuncOrigin, FuncParam, FuncShape, Generic, Memoize};
 use sodigy_span::Span;
 use sodigy_string::InternedString;
 use std::collections::hash_map::{Entry, HashMap};
@@ -19,6 +19,7 @@ pub struct Func {
     pub value: Expr,
     pub built_in: bool,
     pub origin: FuncOrigin,
+    pub memoize: Option<Memoize>,
 
     // Spans of `hir::Type::Wildcard`. It has to be monomorphized later.
     // Let's say there's an expression `foo.<_, Int>()`. The wildcard type
@@ -135,6 +136,7 @@ impl Func {
                 value: value.unwrap(),
                 built_in: hir_func.built_in,
                 origin: hir_func.origin,
+                memoize: hir_func.memoize.clone(),
                 wildcard_spans: session.wildcard_spans.drain(..).collect(),
             })
         }
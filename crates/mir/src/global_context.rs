@@ -1,4 +1,4 @@
-use crate::Type;
+use crate::{Func, Type};
 use sodigy_hir::{EnumShape, FuncShape, ItemShape, Poly, StructShape};
 use sodigy_inter_hir as inter_hir;
 use sodigy_span::{Span, SpanId};
@@ -23,6 +23,12 @@ pub struct GlobalContext<'hir, 'mir> {
     pub built_in_funcs: Option<&'hir HashSet<Span>>,
 
     pub types: Option<Arc<RwLock<HashMap<Span, Type>>>>,
+
+    // It's `def_span -> Func` map of every function in the project, after post-mir.
+    // Mir-optimizer reads this map when inlining functions in other modules.
+    // It's initialized only when optimization is enabled.
+    pub funcs: Option<Arc<RwLock<HashMap<Span, Func>>>>,
+
     pub generic_args: Option<&'mir HashMap<(Span, Span), Type>>,
     pub span_string_map: Option<&'mir HashMap<SpanId, InternedString>>,
 }
@@ -39,6 +45,7 @@ impl<'hir> GlobalContext<'hir, '_> {
             lang_items: None,
             built_in_funcs: None,
             types: None,
+            funcs: None,
             generic_args: None,
             span_string_map: None,
         }
@@ -55,6 +62,7 @@ impl<'hir> GlobalContext<'hir, '_> {
             lang_items: Some(&session.lang_items),
             built_in_funcs: Some(&session.built_in_funcs),
             types: None,
+            funcs: None,
             generic_args: None,
             span_string_map: None,
         }
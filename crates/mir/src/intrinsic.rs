@@ -109,6 +109,17 @@ intrinsics!(
     (Sleep           , "built_in.sleep"             , 32   , 1   , Proc    ),
     (Nop0            , "built_in.nop0"              , 33   , 0   , Fn      ),
     (Nop1            , "built_in.nop1"              , 34   , 1   , Fn      ),
+
+    // `Number` is a struct in the sodigy std, but the arithmetic is done by
+    // `sodigy_number::Ratio`, which is much faster than the Sodigy implementation.
+    (AddNumber       , "built_in.add_number"        , 35   , 2   , Fn      ),
+    (SubNumber       , "built_in.sub_number"        , 36   , 2   , Fn      ),
+    (MulNumber       , "built_in.mul_number"        , 37   , 2   , Fn      ),
+    (DivNumber       , "built_in.div_number"        , 38   , 2   , Fn      ),
+    (LtNumber        , "built_in.lt_number"         , 39   , 2   , Fn      ),
+    (GtNumber        , "built_in.gt_number"         , 40   , 2   , Fn      ),
+    (NormalizeNumber , "built_in.normalize_number"  , 41   , 1   , Fn      ),
+    (NumberToString  , "built_in.number_to_string"  , 42   , 1   , Fn      ),
 //   ^^^^              ^^^^^^^^^^^^^^^                ^^     ^     ^^
 //   |                 |                              |      |     |
 //  (0)               (1)                            (2)    (3)   (4)
@@ -224,6 +224,7 @@ impl Type {
                             kind: ErrorKind::InvalidFnType,
                             spans: fn_span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                         return Err(());
                     },
@@ -726,10 +727,27 @@ pub fn type_of(expr: &Expr, global_context: GlobalContext) -> Option<Type> {
                 _ => None,
             },
             Callable::StructInit { def_span, .. } => global_context.get_type(def_span),
-            Callable::EnumInit { variant_def_span, kind, .. } => match (global_context.get_type(variant_def_span), kind) {
-                (Some(r#type), EnumFieldKind::None) => Some(r#type),
-                (Some(Type::Func { r#return, .. }), EnumFieldKind::Tuple | EnumFieldKind::Struct) => Some(*r#return.clone()),
-                _ => None,
+            Callable::EnumInit { variant_def_span, kind, span, .. } => {
+                let mut r#type = match (global_context.get_type(variant_def_span), kind) {
+                    (Some(r#type), EnumFieldKind::None) => r#type,
+                    (Some(Type::Func { r#return, .. }), EnumFieldKind::Tuple | EnumFieldKind::Struct) => *r#return.clone(),
+                    _ => { return None; },
+                };
+
+                // The type of a variant of a generic enum is generic (e.g. `Option<T>`),
+                // so it has to use the generic args that inter-mir has solved.
+                if let Some(generic_args) = global_context.generic_args {
+                    let mut generic_params = HashSet::new();
+                    r#type.substitute_generic_param_for_arg(span, &mut generic_params);
+
+                    for generic_param in generic_params.into_iter() {
+                        if let Some(generic_arg) = generic_args.get(&(span.clone(), generic_param.clone())) {
+                            r#type.substitute(&Type::GenericArg { call: span.clone(), generic: generic_param }, generic_arg);
+                        }
+                    }
+                }
+
+                Some(r#type)
             },
             Callable::TupleInit { .. } => {
                 let mut arg_types = Vec::with_capacity(args.len());
@@ -818,8 +836,9 @@ pub fn type_of_field(r#type: &Type, field: &[Field], global_context: GlobalConte
 
                 else if let Some(enum_shape) = global_context.enum_shapes.unwrap().get(&def_span) {
                     match (&field[0], enum_shape.representation) {
-                        (Field::EnumDiscriminant, _) | (Field::Index(0), EnumRepr::Compound) => scalar_type(global_context.lang_items.as_ref().unwrap()),
-                        (Field::EnumPayload { variant, payload }, _) => {
+                        (Field::EnumDiscriminant | Field::NicheDiscriminant { .. } | Field::UnboxedDiscriminant, _) |
+                        (Field::Index(0), EnumRepr::Compound) => scalar_type(global_context.lang_items.as_ref().unwrap()),
+                        (Field::EnumPayload { variant, payload } | Field::UnboxedPayload { variant, payload }, _) => {
                             let variant = &enum_shape.variants[*variant];
 
                             match variant.fields {
@@ -1,15 +1,21 @@
 use sodigy_error::{Error, ErrorKind};
 use sodigy_number::{
+    BigInt,
     InternedNumber,
     add_ratio,
+    cmp_ratio,
+    div_bi,
     div_ratio,
+    intern_big_int,
     intern_ratio,
     mul_ratio,
+    rem_bi,
     sub_ratio,
     unintern_number,
 };
 use sodigy_span::Span;
 use sodigy_token::{InfixOp, PrefixOp};
+use std::cmp::Ordering;
 
 pub fn eval_number_prefix_op(
     op: PrefixOp,
@@ -22,6 +28,7 @@ pub fn eval_number_prefix_op(
             kind: ErrorKind::CannotEvaluateConst,
             spans: op_span.simple_error(),
             note: Some(String::from("Const-eval is not implemented for `!` operator.")),
+            suggestions: vec![],
         }]),
         PrefixOp::Range { inclusive } => Err(vec![Error {
             kind: ErrorKind::CannotEvaluateConst,
@@ -30,6 +37,7 @@ pub fn eval_number_prefix_op(
                 "Const-eval is not implemented for `{}` operator.",
                 if inclusive { "..=" } else { ".." },
             )),
+            suggestions: vec![],
         }]),
     }
 }
@@ -51,6 +59,7 @@ pub fn eval_number_infix_op(
                 if lhs.is_integer() { "an integer" } else { "a number" },
                 if rhs.is_integer() { "an integer" } else { "a number" },
             )),
+            suggestions: vec![],
         }]);
     }
 
@@ -62,18 +71,55 @@ pub fn eval_number_infix_op(
         InfixOp::Add => Ok(intern_ratio(&add_ratio(&lhs_ratio, &rhs_ratio), is_integer, intermediate_dir).unwrap()),
         InfixOp::Sub => Ok(intern_ratio(&sub_ratio(&lhs_ratio, &rhs_ratio), is_integer, intermediate_dir).unwrap()),
         InfixOp::Mul => Ok(intern_ratio(&mul_ratio(&lhs_ratio, &rhs_ratio), is_integer, intermediate_dir).unwrap()),
-        InfixOp::Div => {
-            let value = intern_ratio(&div_ratio(&lhs_ratio, &rhs_ratio), is_integer, intermediate_dir).unwrap();
-
-            if lhs.is_integer() {
-                // We have to truncate the result!
-                todo!()
+        // Integer division is truncated, like the runtime.
+        // The denominators of integers are always 1.
+        InfixOp::Div | InfixOp::Rem if is_integer => {
+            if rhs_ratio.numer.is_zero() {
+                return Err(vec![Error {
+                    kind: ErrorKind::CannotEvaluateConst,
+                    spans: op_span.simple_error(),
+                    note: Some(String::from("Rhs is zero.")),
+                    suggestions: vec![],
+                }]);
             }
 
-            else {
-                Ok(value)
-            }
+            let f = if let InfixOp::Div = op { div_bi } else { rem_bi };
+            let (is_neg, nums) = f(
+                lhs_ratio.numer.is_neg,
+                &lhs_ratio.numer.nums,
+                rhs_ratio.numer.is_neg,
+                &rhs_ratio.numer.nums,
+            );
+            Ok(intern_big_int(&BigInt { is_neg, nums }, true, intermediate_dir).unwrap())
         },
+        InfixOp::Div => Ok(intern_ratio(&div_ratio(&lhs_ratio, &rhs_ratio), is_integer, intermediate_dir).unwrap()),
         _ => Err(vec![Error::todo(89470, "more const eval", op_span)]),
     }
 }
+
+pub fn eval_number_comparison(
+    op: InfixOp,
+    op_span: Span,
+    lhs: &InternedNumber,
+    rhs: &InternedNumber,
+    intermediate_dir: &str,
+) -> Result<bool, Vec<Error>> {
+    let lhs = unintern_number(*lhs, intermediate_dir).unwrap();
+    let rhs = unintern_number(*rhs, intermediate_dir).unwrap();
+    let ordering = cmp_ratio(&lhs, &rhs);
+
+    match op {
+        InfixOp::Lt => Ok(ordering == Ordering::Less),
+        InfixOp::Eq => Ok(ordering == Ordering::Equal),
+        InfixOp::Gt => Ok(ordering == Ordering::Greater),
+        InfixOp::Leq => Ok(ordering != Ordering::Greater),
+        InfixOp::Neq => Ok(ordering != Ordering::Equal),
+        InfixOp::Geq => Ok(ordering != Ordering::Less),
+        _ => Err(vec![Error {
+            kind: ErrorKind::CannotEvaluateConst,
+            spans: op_span.simple_error(),
+            note: Some(format!("`{}` is not a comparison operator.", op.render_error())),
+            suggestions: vec![],
+        }]),
+    }
+}
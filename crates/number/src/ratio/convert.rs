@@ -29,7 +29,7 @@ pub fn ratio_to_string(r: &Ratio) -> String {
     let is_neg = r.numer.is_neg;
     let mut integer = div_ubi(&r.numer.nums, &r.denom.nums);
     let frac = rem_ubi(&r.numer.nums, &r.denom.nums);
-    let frac_nine_digits = mul_ubi(&frac, &[4_000_000_000]);
+    let frac_nine_digits = div_ubi(&mul_ubi(&frac, &[4_000_000_000]), &r.denom.nums);
     let mut frac_nine_digits = i64::try_from(&BigInt { is_neg: false, nums: frac_nine_digits }).unwrap() as u32;
 
     frac_nine_digits = if frac_nine_digits % 4 < 2 {
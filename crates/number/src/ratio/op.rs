@@ -38,7 +38,29 @@ pub fn div_ratio(lhs: &Ratio, rhs: &Ratio) -> Ratio {
     reduce_and_return(res_numer, res_denom)
 }
 
+// `numer` and `denom` don't have to be coprime, and `denom` can be negative.
+// It makes a valid ratio out of them (read the comments in `ratio.rs`).
+pub fn normalize_ratio(r: &Ratio) -> Ratio {
+    reduce_and_return(
+        (r.numer.is_neg, r.numer.nums.clone()),
+        (r.denom.is_neg, r.denom.nums.clone()),
+    )
+}
+
 fn reduce_and_return(mut numer: (bool, Vec<u32>), mut denom: (bool, Vec<u32>)) -> Ratio {
+    // `0 / 0` (e.g. `inf - inf` or `0 * inf`) is 0, like the other `0 / n`s.
+    if numer.1 == [0] {
+        return Ratio {
+            numer: BigInt::zero(),
+            denom: BigInt::one(),
+        };
+    }
+
+    if denom.0 {
+        numer.0 = !numer.0;
+        denom.0 = false;
+    }
+
     let r = gcd_ubi(&numer.1, &denom.1);
 
     if r != [1] {
@@ -1,8 +1,16 @@
 use crate::{
     InternedNumber,
-    // BigInt,
-    // Ratio,
+    BigInt,
+    Ratio,
+    add_ratio,
+    cmp_ratio,
+    div_ratio,
+    mul_ratio,
+    normalize_ratio,
+    ratio_to_string,
+    sub_ratio,
 };
+use std::cmp::Ordering;
 use std::mem::size_of;
 
 #[test]
@@ -38,3 +46,40 @@ fn interned_small_integer() {
     assert_eq!(u32::try_from(n).unwrap() as i32, 0);
     assert_eq!(u64::try_from(n).unwrap() as i32, 0);
 }
+
+fn ratio(numer: i64, denom: i64) -> Ratio {
+    Ratio {
+        numer: BigInt::from(numer),
+        denom: BigInt::from(denom),
+    }
+}
+
+#[test]
+fn ratio_arithmetic() {
+    let inf = ratio(1, 0);
+    let neg_inf = ratio(-1, 0);
+
+    assert_eq!(add_ratio(&ratio(1, 10), &ratio(1, 5)), ratio(3, 10));
+    assert_eq!(sub_ratio(&ratio(1, 10), &ratio(1, 5)), ratio(-1, 10));
+    assert_eq!(mul_ratio(&ratio(3, 2), &ratio(-2, 3)), ratio(-1, 1));
+    assert_eq!(div_ratio(&ratio(1, 2), &ratio(-3, 1)), ratio(-1, 6));
+    assert_eq!(div_ratio(&ratio(-1, 2), &ratio(-3, 1)), ratio(1, 6));
+    assert_eq!(div_ratio(&ratio(-3, 1), &ratio(0, 1)), neg_inf);
+    assert_eq!(add_ratio(&inf, &ratio(3, 1)), inf);
+    assert_eq!(sub_ratio(&inf, &inf), ratio(0, 1));
+    assert_eq!(mul_ratio(&ratio(0, 1), &inf), ratio(0, 1));
+    assert_eq!(normalize_ratio(&ratio(6, -4)), ratio(-3, 2));
+    assert_eq!(normalize_ratio(&ratio(0, -4)), ratio(0, 1));
+    assert_eq!(normalize_ratio(&ratio(-7, 0)), neg_inf);
+
+    assert_eq!(cmp_ratio(&ratio(1, 3), &ratio(1, 2)), Ordering::Less);
+    assert_eq!(cmp_ratio(&ratio(-1, 3), &ratio(-1, 2)), Ordering::Greater);
+    assert_eq!(cmp_ratio(&ratio(2, 4), &ratio(1, 2)), Ordering::Equal);
+    assert_eq!(cmp_ratio(&inf, &ratio(1_000_000, 1)), Ordering::Greater);
+    assert_eq!(cmp_ratio(&neg_inf, &ratio(-1_000_000, 1)), Ordering::Less);
+
+    assert_eq!(ratio_to_string(&ratio(3, 4)), "0.75");
+    assert_eq!(ratio_to_string(&ratio(-1, 8)), "-0.125");
+    assert_eq!(ratio_to_string(&ratio(7, 1)), "7");
+    assert_eq!(ratio_to_string(&neg_inf), "-inf");
+}
@@ -1,19 +1,24 @@
 use crate::OptimizeLevel;
-use sodigy_bytecode::{Bytecode, Label, Memory, Session, SSA, Value};
+use sodigy_bytecode::{Bytecode, Memory, Offset, Session, SSA, Value};
 use sodigy_endec::Endec;
 use sodigy_mir::Intrinsic;
 use sodigy_string::hash;
+use std::collections::HashSet;
 use std::collections::hash_map::{Entry, HashMap};
 
+mod cfg;
+mod renumber;
+mod value_number;
+
 #[cfg(test)]
 mod tests;
 
-struct LocalContext {
-    // If there's `_5 = _7;`, we can replace all `_5` with `_7` and remove this bytecode.
-    //
-    // So, all `Bytecode::Move`s will be gone in the optimized bytecodes.
-    ssa_alias: HashMap<SSA, SSA>,
+use renumber::renumber_ssa;
+use value_number::number_values;
 
+// Copies (`_5 = _7;`) and common expressions are handled by `value_number`,
+// which knows the control flow.
+struct LocalContext {
     // `*(_2 + 1) = _3; _5 = *(_2 + 1);` -> `_5 = _3;`
     heap_ssa_alias: HashMap<(SSA, u32), SSA>,
 
@@ -31,27 +36,34 @@ struct LocalContext {
     // `*(_2 + 1)` is not used again. Then we can remove `*(_2 + 1) = _5;`.
     heap_use_counts: HashMap<(SSA, u32), usize>,
 
-    // It's a `expr -> SSA` map. Let's say there are `_x = expr1;` and `_y = expr2;`. If `expr1` and `expr2` are the same,
-    // this map will remember the fact and will later remove `_y = expr2;` and replace all `_y` with `_x`.
-    common_expression: HashMap<ExprHash, Vec<SSA>>,
+    // Tuples and lists that are initialized in this function. We can't apply sroa to
+    // the other pointers (e.g. parameters) because we don't know who wrote the elements.
+    allocations: HashSet<SSA>,
+
+    // The smallest SSA that's not used by the function.
     free_ssa: SSA,
 }
 
 impl LocalContext {
-    pub fn new() -> LocalContext {
+    pub fn new(free_ssa: SSA) -> LocalContext {
         LocalContext {
-            ssa_alias: HashMap::new(),
             heap_ssa_alias: HashMap::new(),
             sroa: HashMap::new(),
             use_counts: HashMap::new(),
             indirect_use_counts: HashMap::new(),
             heap_use_counts: HashMap::new(),
-            common_expression: HashMap::new(),
-            free_ssa: SSA::from_u32(1000),
+            allocations: HashSet::new(),
+            free_ssa,
         }
     }
 
     pub fn count_use(&mut self, memory: &Memory) {
+        // We don't know which element is read, so the entire pointer is used.
+        if let Memory::Heap { ptr, offset: Offset::Dynamic(offset) } | Memory::List { ptr, offset: Offset::Dynamic(offset) } = memory {
+            self.count_use(&Memory::SSA(*ptr));
+            self.count_use(offset);
+        }
+
         if let Memory::SSA(ssa) = memory {
             match self.use_counts.entry(*ssa) {
                 Entry::Occupied(mut e) => {
@@ -84,49 +96,12 @@ impl LocalContext {
         }
     }
 
-    pub fn register_expression(&mut self, expr: ExprHash, ssa: SSA) {
-        match self.common_expression.entry(expr) {
-            Entry::Occupied(mut e) => {
-                e.get_mut().push(ssa);
-            },
-            Entry::Vacant(e) => {
-                e.insert(vec![ssa]);
-            },
-        }
-    }
-
     pub fn finalize(&mut self) {
-        // If there's `_10 = _0; _20 = _10;`, we have to connect `_20` and `_0`.
-        // There must be a better algorithm, but I'm not smart enough to figure that out...
-        loop {
-            let mut new_connection = HashMap::new();
-
-            for (dst, src) in self.ssa_alias.iter() {
-                if let Some(s2) = self.ssa_alias.get(src) {
-                    if let Some(s3) = self.ssa_alias.get(s2) {
-                        new_connection.insert(*dst, *s3);
-                    }
-
-                    else {
-                        new_connection.insert(*dst, *s2);
-                    }
-                }
-            }
-
-            if new_connection.is_empty() {
-                break;
-            }
-
-            for (dst, src) in new_connection.drain() {
-                self.ssa_alias.insert(dst, src);
-            }
-        }
-
         // We have to sort this before inserting, so that the result is deterministic.
         let mut sroa_list = vec![];
 
         for (a, b) in self.heap_use_counts.keys() {
-            if let Some(0) | None = self.use_counts.get(a) {
+            if let Some(0) | None = self.use_counts.get(a) && self.allocations.contains(a) {
                 sroa_list.push((*a, *b));
             }
         }
@@ -155,28 +130,6 @@ impl ExprHash {
         ExprHash(hash(&encoded))
     }
 
-    pub fn from_func_call(f: &Label, args: &[SSA]) -> ExprHash {
-        let mut encoded = vec![1];
-        f.encode_impl(&mut encoded);
-
-        for arg in args.iter() {
-            arg.encode_impl(&mut encoded);
-        }
-
-        ExprHash(hash(&encoded))
-    }
-
-    pub fn from_dynamic_func_call(f: &Memory, args: &[SSA]) -> ExprHash {
-        let mut encoded = vec![2];
-        f.encode_impl(&mut encoded);
-
-        for arg in args.iter() {
-            arg.encode_impl(&mut encoded);
-        }
-
-        ExprHash(hash(&encoded))
-    }
-
     pub fn from_intrinsic(f: Intrinsic, args: &[SSA]) -> ExprHash {
         let mut encoded = vec![3];
         f.encode_impl(&mut encoded);
@@ -190,123 +143,77 @@ impl ExprHash {
 }
 
 fn optimize_local(bytecodes: &mut Vec<Bytecode>) {
-    let mut context = LocalContext::new();
-    let mut max_ssa = SSA::from_u32(1000);
+    let max_ssa = bytecodes.iter().flat_map(|bytecode| bytecode.used_ssa_indexes()).max();
+    let mut context = LocalContext::new(max_ssa.map(|mut ssa| { ssa.increment(); ssa }).unwrap_or(SSA::from_u32(0)));
 
     for bytecode in bytecodes.iter() {
         match bytecode {
-            Bytecode::Const { value, dst, .. } => {
-                if let Memory::SSA(a) = dst {
-                    context.register_expression(ExprHash::from_const(value), *a);
-                    max_ssa = max_ssa.max(*a);
-                }
-            },
             Bytecode::Move { src, dst } => {
                 context.count_use(src);
 
-                if let Memory::SSA(a) = dst {
-                    if let Memory::SSA(b) = src {
-                        context.ssa_alias.insert(*a, *b);
-                    }
-
-                    max_ssa = max_ssa.max(*a);
-                }
-
                 if let Some((a, b)) = dst.get_heap_index() && let Memory::SSA(c) = src {
                     context.heap_ssa_alias.insert((a, b), *c);
                 }
             },
-            Bytecode::Phi { pair: (a, b), dst } => {
+            Bytecode::Phi { pair: (a, b), .. } => {
                 context.count_use(&Memory::SSA(*a));
                 context.count_use(&Memory::SSA(*b));
-
-                if let Memory::SSA(c) = dst {
-                    max_ssa = max_ssa.max(*c);
-                }
             },
-            Bytecode::Jump(_) => {},
-            Bytecode::Call { func, args, dst, .. } => {
+            Bytecode::Call { args, .. } |
+            Bytecode::Intrinsic { args, .. } => {
                 for arg in args.iter() {
                     context.count_use(&Memory::SSA(*arg));
                 }
-
-                if let Some(Memory::SSA(a)) = dst {
-                    context.register_expression(ExprHash::from_func_call(func, args), *a);
-                    max_ssa = max_ssa.max(*a);
-                }
             },
-            Bytecode::CallDynamic { func, args, dst, .. } => {
+            Bytecode::CallDynamic { func, args, .. } => {
                 context.count_use(func);
 
                 for arg in args.iter() {
                     context.count_use(&Memory::SSA(*arg));
                 }
-
-                if let Some(Memory::SSA(a)) = dst {
-                    context.register_expression(ExprHash::from_dynamic_func_call(func, args), *a);
-                    max_ssa = max_ssa.max(*a);
-                }
             },
             Bytecode::JumpIf { value, .. } => {
                 context.count_use(value);
             },
-            Bytecode::InitOrJump { .. } => {},
-            Bytecode::Label(_) => {},
             Bytecode::Return(a) => {
                 context.count_use(&Memory::SSA(*a));
             },
-            Bytecode::Update { .. } => todo!(),
-            Bytecode::Intrinsic { intrinsic, args, dst, .. } => {
-                for arg in args.iter() {
-                    context.count_use(&Memory::SSA(*arg));
-                }
-
-                if let Memory::SSA(a) = dst {
-                    context.register_expression(ExprHash::from_intrinsic(*intrinsic, args), *a);
-                    max_ssa = max_ssa.max(*a);
-                }
+            Bytecode::Update { src, value, .. } => {
+                context.count_use(&Memory::SSA(*src));
+                context.count_use(&Memory::SSA(*value));
             },
-            Bytecode::InitTuple { dst, .. } => {
-                if let Memory::SSA(a) = dst {
-                    max_ssa = max_ssa.max(*a);
-                }
+            Bytecode::PushDebugInfo { src, .. } => {
+                context.count_use(src);
             },
+            Bytecode::InitTuple { dst, .. } |
             Bytecode::InitList { dst, .. } => {
                 if let Memory::SSA(a) = dst {
-                    max_ssa = max_ssa.max(*a);
+                    context.allocations.insert(*a);
                 }
             },
-            Bytecode::PushDebugInfo { src, .. } => {
-                context.count_use(src);
-            },
+            Bytecode::Const { .. } |
+            Bytecode::Jump(_) |
+            Bytecode::InitOrJump { .. } |
+            Bytecode::Label(_) |
             Bytecode::PopDebugInfo => {},
         }
+
+        // `*(_2 + *_3) = _4;` uses `_2` and `_3`.
+        if let Some(dst @ (Memory::Heap { offset: Offset::Dynamic(_), .. } | Memory::List { offset: Offset::Dynamic(_), .. })) = bytecode.get_dst() {
+            context.count_use(dst);
+        }
     }
 
-    context.free_ssa = max_ssa;
     context.finalize();
 
+    // Reads from the sroa-ed elements are replaced with the new SSAs.
+    let ssa_alias = HashMap::new();
+    let mut heap_ssa_alias = context.sroa.clone();
+    heap_ssa_alias.extend(context.heap_ssa_alias.iter().map(|(k, v)| (*k, *v)));
+
     let mut new_bytecodes: Vec<Bytecode> = Vec::with_capacity(bytecodes.len());
 
     for mut bytecode in bytecodes.drain(..) {
-        if let Bytecode::Move { src, dst } = &bytecode {
-            if let Memory::SSA(_) = src && let Memory::SSA(_) = dst {
-                // `ssa_alias` must cover all the aliases.
-                continue;
-            }
-
-            if let Some((a, b)) = src.get_heap_index() {
-                match context.heap_ssa_alias.get(&(a, b)) {
-                    Some(c) => {
-                        let alias = context.ssa_alias.get(c).unwrap_or(c);
-                        new_bytecodes.push(Bytecode::Move { src: Memory::SSA(*alias), dst: dst.clone() });
-                        continue;
-                    },
-                    None => {},
-                }
-            }
-        }
-
         if let Some(dst) = bytecode.get_dst() {
             if let Memory::SSA(a) = dst {
                 match (context.use_counts.get(a), context.indirect_use_counts.get(a)) {
@@ -320,45 +227,49 @@ fn optimize_local(bytecodes: &mut Vec<Bytecode>) {
                 }
             }
 
-            if let Some((a, b)) = dst.get_heap_index() {
-                if let Some(0) | None = context.use_counts.get(&a) && bytecode.is_observable() {
-                    continue;
-                }
-
+            if let Some((a, b)) = dst.get_heap_index() && let Some(0) | None = context.use_counts.get(&a) && context.allocations.contains(&a) {
                 if let Some(alias) = context.sroa.get(&(a, b)) {
                     bytecode.set_dst(Memory::SSA(*alias));
                 }
+
+                // Nobody reads this element.
+                else if bytecode.is_observable() {
+                    continue;
+                }
             }
         }
 
-        bytecode.apply_ssa_alias(&context.ssa_alias, &context.heap_ssa_alias);
+        bytecode.apply_ssa_alias(&ssa_alias, &heap_ssa_alias);
         new_bytecodes.push(bytecode);
     }
 
     *bytecodes = new_bytecodes;
 }
 
-pub fn optimize_bytecode<'hir, 'mir>(mut session: Session<'hir, 'mir>, level: OptimizeLevel) -> Session<'hir, 'mir> {
-    match level {
-        OptimizeLevel::None => session,
-        OptimizeLevel::Mild => {
-            for func in session.funcs.iter_mut() {
-                optimize_local(&mut func.bytecodes);
-                optimize_local(&mut func.bytecodes);
-            }
+// 1. `optimize_local` removes the unused values and replaces tuples with SSAs (if possible).
+// 2. `number_values` removes copies and common expressions.
+// 3. After all the rounds, `renumber_ssa` fills the holes in the SSA numbers.
+fn optimize_func(bytecodes: &mut Vec<Bytecode>, params: usize, rounds: usize) {
+    for _ in 0..rounds {
+        optimize_local(bytecodes);
+        number_values(bytecodes);
+    }
 
-            session
-        },
-        OptimizeLevel::Extreme => {
-            for func in session.funcs.iter_mut() {
-                optimize_local(&mut func.bytecodes);
-                optimize_local(&mut func.bytecodes);
-                optimize_local(&mut func.bytecodes);
-                optimize_local(&mut func.bytecodes);
-                optimize_local(&mut func.bytecodes);
-            }
+    renumber_ssa(bytecodes, params);
+}
 
-            session
+pub fn optimize_bytecode<'hir, 'mir>(mut session: Session<'hir, 'mir>, level: OptimizeLevel) -> Session<'hir, 'mir> {
+    let rounds = match level {
+        OptimizeLevel::None => {
+            return session;
         },
+        OptimizeLevel::Mild => 2,
+        OptimizeLevel::Extreme => 5,
+    };
+
+    for func in session.funcs.iter_mut() {
+        optimize_func(&mut func.bytecodes, func.params, rounds);
     }
+
+    session
 }
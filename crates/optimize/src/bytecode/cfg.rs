@@ -0,0 +1,225 @@
+use sodigy_bytecode::Bytecode;
+use std::collections::HashMap;
+
+// A control flow graph of a function. A basic block is `bytecodes[start..end]`,
+// and the control flow enters a block only at `start` and leaves only at `end - 1`.
+//
+// `blocks[0]` is the entry block. The blocks are sorted by `start`.
+#[derive(Debug)]
+pub struct Cfg {
+    pub blocks: Vec<BasicBlock>,
+
+    // Immediate dominator of each block. It's `None` for the entry block and
+    // the blocks that are not reachable from the entry block.
+    pub idom: Vec<Option<usize>>,
+}
+
+#[derive(Debug)]
+pub struct BasicBlock {
+    pub start: usize,
+    pub end: usize,
+    pub succs: Vec<usize>,
+    pub preds: Vec<usize>,
+}
+
+impl Cfg {
+    pub fn new(bytecodes: &[Bytecode]) -> Cfg {
+        let mut starts = vec![];
+
+        for (i, bytecode) in bytecodes.iter().enumerate() {
+            match bytecode {
+                // Consecutive labels are in the same block.
+                Bytecode::Label(_) if i > 0 && matches!(bytecodes[i - 1], Bytecode::Label(_)) => {},
+                Bytecode::Label(_) if starts.last() != Some(&i) => {
+                    starts.push(i);
+                },
+                _ if i == 0 => {
+                    starts.push(0);
+                },
+                _ => {},
+            }
+
+            if is_terminator(bytecode) && i + 1 < bytecodes.len() {
+                starts.push(i + 1);
+            }
+        }
+
+        let mut blocks = Vec::with_capacity(starts.len());
+
+        // `Label::Global`s are not in this map: jumping to a global label is a tail call.
+        let mut label_to_block = HashMap::new();
+
+        for (i, start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(bytecodes.len());
+
+            for bytecode in bytecodes[*start..end].iter() {
+                match bytecode {
+                    Bytecode::Label(label) => {
+                        label_to_block.insert(label.clone(), i);
+                    },
+                    _ => {
+                        break;
+                    },
+                }
+            }
+
+            blocks.push(BasicBlock {
+                start: *start,
+                end,
+                succs: vec![],
+                preds: vec![],
+            });
+        }
+
+        for i in 0..blocks.len() {
+            let last = &bytecodes[blocks[i].end - 1];
+            let fallthrough = if i + 1 < blocks.len() { Some(i + 1) } else { None };
+            let mut succs = vec![];
+
+            match last {
+                Bytecode::Jump(label) => {
+                    succs.extend(label_to_block.get(label));
+                },
+                Bytecode::JumpIf { label, .. } |
+                Bytecode::InitOrJump { label, .. } => {
+                    succs.extend(fallthrough);
+                    succs.extend(label_to_block.get(label));
+                },
+                Bytecode::Return(_) |
+                Bytecode::Call { dst: None, .. } |
+                Bytecode::CallDynamic { dst: None, .. } => {},
+                _ => {
+                    succs.extend(fallthrough);
+                },
+            }
+
+            succs.dedup();
+
+            for succ in succs.iter() {
+                blocks[*succ].preds.push(i);
+            }
+
+            blocks[i].succs = succs;
+        }
+
+        let mut cfg = Cfg { idom: vec![None; blocks.len()], blocks };
+        cfg.init_dominators();
+        cfg
+    }
+
+    // "A Simple, Fast Dominance Algorithm" by Cooper, Harvey and Kennedy.
+    fn init_dominators(&mut self) {
+        if self.blocks.is_empty() {
+            return;
+        }
+
+        let postorder = self.postorder();
+        let mut postorder_index = vec![None; self.blocks.len()];
+
+        for (i, block) in postorder.iter().enumerate() {
+            postorder_index[*block] = Some(i);
+        }
+
+        // The entry block is its own dominator while iterating. It's reset to `None` at the end.
+        let mut idom: Vec<Option<usize>> = vec![None; self.blocks.len()];
+        idom[0] = Some(0);
+
+        loop {
+            let mut changed = false;
+
+            for block in postorder.iter().rev().skip(1) {
+                let mut new_idom = None;
+
+                for pred in self.blocks[*block].preds.iter() {
+                    if idom[*pred].is_none() {
+                        continue;
+                    }
+
+                    new_idom = match new_idom {
+                        None => Some(*pred),
+                        Some(new_idom) => Some(intersect(*pred, new_idom, &idom, &postorder_index)),
+                    };
+                }
+
+                if new_idom.is_some() && idom[*block] != new_idom {
+                    idom[*block] = new_idom;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        idom[0] = None;
+        self.idom = idom;
+    }
+
+    fn postorder(&self) -> Vec<usize> {
+        let mut visited = vec![false; self.blocks.len()];
+        let mut postorder = Vec::with_capacity(self.blocks.len());
+
+        // (block, index of the next successor to visit)
+        let mut stack = vec![(0, 0)];
+        visited[0] = true;
+
+        while let Some((block, succ_index)) = stack.last_mut() {
+            match self.blocks[*block].succs.get(*succ_index) {
+                Some(succ) => {
+                    *succ_index += 1;
+
+                    if !visited[*succ] {
+                        visited[*succ] = true;
+                        stack.push((*succ, 0));
+                    }
+                },
+                None => {
+                    postorder.push(*block);
+                    stack.pop();
+                },
+            }
+        }
+
+        postorder
+    }
+
+    // Children of each block in the dominator tree.
+    pub fn dominator_tree(&self) -> Vec<Vec<usize>> {
+        let mut children = vec![vec![]; self.blocks.len()];
+
+        for (block, idom) in self.idom.iter().enumerate() {
+            if let Some(idom) = idom {
+                children[*idom].push(block);
+            }
+        }
+
+        children
+    }
+}
+
+fn intersect(mut a: usize, mut b: usize, idom: &[Option<usize>], postorder_index: &[Option<usize>]) -> usize {
+    while a != b {
+        while postorder_index[a] < postorder_index[b] {
+            a = idom[a].unwrap();
+        }
+
+        while postorder_index[b] < postorder_index[a] {
+            b = idom[b].unwrap();
+        }
+    }
+
+    a
+}
+
+fn is_terminator(bytecode: &Bytecode) -> bool {
+    matches!(
+        bytecode,
+        Bytecode::Jump(_) |
+        Bytecode::JumpIf { .. } |
+        Bytecode::InitOrJump { .. } |
+        Bytecode::Return(_) |
+        Bytecode::Call { dst: None, .. } |
+        Bytecode::CallDynamic { dst: None, .. },
+    )
+}
@@ -0,0 +1,26 @@
+use sodigy_bytecode::{Bytecode, SSA};
+use std::collections::hash_map::{Entry, HashMap};
+
+// The optimizer removes a lot of SSAs and creates new ones, so there are holes
+// in the SSA numbers. It renumbers the SSAs so that they're dense again.
+//
+// `_0..params` are the parameters of the function, so they're not renumbered.
+// The others are numbered in the order they appear.
+pub fn renumber_ssa(bytecodes: &mut [Bytecode], params: usize) {
+    let mut renumbered: HashMap<SSA, SSA> = (0..params as u32).map(
+        |i| (SSA::from_u32(i), SSA::from_u32(i))
+    ).collect();
+    let mut next_ssa = SSA::from_u32(params as u32);
+
+    for bytecode in bytecodes.iter_mut() {
+        bytecode.rename_ssa(&mut |ssa| match renumbered.entry(ssa) {
+            Entry::Occupied(e) => *e.get(),
+            Entry::Vacant(e) => {
+                let new_ssa = next_ssa;
+                e.insert(new_ssa);
+                next_ssa.increment();
+                new_ssa
+            },
+        });
+    }
+}
@@ -1,5 +1,46 @@
-use super::optimize_local;
-use sodigy_bytecode::{Bytecode, Memory, Offset, SSA};
+use super::cfg::Cfg;
+use super::optimize_func;
+use super::renumber::renumber_ssa;
+use super::value_number::number_values;
+use sodigy_bytecode::{Bytecode, Label, Memory, Offset, SSA, Value};
+use sodigy_mir::Intrinsic;
+
+fn ssa(n: u32) -> Memory {
+    Memory::SSA(SSA::from_u32(n))
+}
+
+fn intrinsic(intrinsic: Intrinsic, args: &[u32], dst: u32) -> Bytecode {
+    Bytecode::Intrinsic {
+        intrinsic,
+        args: args.iter().map(|arg| SSA::from_u32(*arg)).collect(),
+        dst: ssa(dst),
+        debug_info: None,
+    }
+}
+
+fn assert_bytecodes(unoptimized: &[Bytecode], optimized: &[Bytecode], expected: &[Bytecode]) {
+    let unoptimized = unoptimized.iter().map(|b| b.to_string()).collect::<Vec<_>>().join("\n");
+    let optimized = optimized.iter().map(|b| b.to_string()).collect::<Vec<_>>().join("\n");
+    let expected = expected.iter().map(|b| b.to_string()).collect::<Vec<_>>().join("\n");
+
+    if optimized != expected {
+        panic!("--- unoptimized ---\n{unoptimized}\n--- optimized ---\n{optimized}\n--- expected ---\n{expected}");
+    }
+}
+
+// `_5 = if _2 { _0 } else { _1 }; return _5;`
+fn if_else() -> Vec<Bytecode> {
+    vec![
+        Bytecode::JumpIf { value: ssa(2), label: Label::Local(0), debug_info: None },
+        Bytecode::Move { src: ssa(1), dst: ssa(3) },
+        Bytecode::Jump(Label::Local(1)),
+        Bytecode::Label(Label::Local(0)),
+        Bytecode::Move { src: ssa(0), dst: ssa(4) },
+        Bytecode::Label(Label::Local(1)),
+        Bytecode::Phi { pair: (SSA::from_u32(4), SSA::from_u32(3)), dst: ssa(5) },
+        Bytecode::Return(SSA::from_u32(5)),
+    ]
+}
 
 #[test]
 fn t1() {
@@ -35,9 +76,7 @@ fn t1() {
     ];
     let mut optimized = unoptimized.clone();
 
-    for _ in 0..5 {
-        optimize_local(&mut optimized);
-    }
+    optimize_func(&mut optimized, 2, 5);
 
     let expected: Vec<Bytecode> = vec![
         Bytecode::Return(SSA::from_u32(0)),
@@ -54,3 +93,108 @@ fn t1() {
         panic!("--- unoptimized ---\n{unoptimized}\n--- optimized ---\n{optimized}\n--- expected ---\n{expected}");
     }
 }
+
+#[test]
+fn cfg_if_else() {
+    let cfg = Cfg::new(&if_else());
+    let blocks = cfg.blocks.iter().map(
+        |block| (block.start, block.end, block.succs.clone())
+    ).collect::<Vec<_>>();
+
+    assert_eq!(blocks, vec![
+        (0, 1, vec![1, 2]),
+        (1, 3, vec![3]),
+        (3, 5, vec![3]),
+        (5, 8, vec![]),
+    ]);
+    assert_eq!(cfg.idom, vec![None, Some(0), Some(0), Some(0)]);
+    assert_eq!(cfg.dominator_tree(), vec![vec![1, 2, 3], vec![], vec![], vec![]]);
+}
+
+#[test]
+fn cfg_unreachable_block() {
+    let cfg = Cfg::new(&[
+        Bytecode::Return(SSA::from_u32(0)),
+        Bytecode::Return(SSA::from_u32(1)),
+        Bytecode::Label(Label::Local(0)),
+        Bytecode::Label(Label::Local(1)),
+        Bytecode::Return(SSA::from_u32(2)),
+    ]);
+
+    // The second and the third blocks are not reachable, so they have no dominators.
+    assert_eq!(cfg.blocks.len(), 3);
+    assert_eq!(cfg.idom, vec![None, None, None]);
+}
+
+#[test]
+fn value_numbering_across_blocks() {
+    let unoptimized = vec![
+        intrinsic(Intrinsic::AddInt, &[0, 1], 2),
+        intrinsic(Intrinsic::EqInt, &[2, 0], 3),
+        Bytecode::JumpIf { value: ssa(3), label: Label::Local(0), debug_info: None },
+
+        // dominated by `_2`
+        intrinsic(Intrinsic::AddInt, &[0, 1], 4),
+        intrinsic(Intrinsic::MulInt, &[4, 4], 5),
+        Bytecode::Return(SSA::from_u32(5)),
+        Bytecode::Label(Label::Local(0)),
+
+        // not dominated by `_5`
+        intrinsic(Intrinsic::MulInt, &[2, 2], 6),
+        Bytecode::Move { src: ssa(6), dst: ssa(7) },
+        Bytecode::Return(SSA::from_u32(7)),
+    ];
+    let mut optimized = unoptimized.clone();
+    number_values(&mut optimized);
+
+    let expected = vec![
+        intrinsic(Intrinsic::AddInt, &[0, 1], 2),
+        intrinsic(Intrinsic::EqInt, &[2, 0], 3),
+        Bytecode::JumpIf { value: ssa(3), label: Label::Local(0), debug_info: None },
+        intrinsic(Intrinsic::MulInt, &[2, 2], 5),
+        Bytecode::Return(SSA::from_u32(5)),
+        Bytecode::Label(Label::Local(0)),
+        intrinsic(Intrinsic::MulInt, &[2, 2], 6),
+        Bytecode::Return(SSA::from_u32(6)),
+    ];
+
+    assert_bytecodes(&unoptimized, &optimized, &expected);
+}
+
+// Operands of `phi` must not be replaced, otherwise `phi` would always pick `_0`.
+#[test]
+fn value_numbering_phi() {
+    let mut unoptimized = if_else();
+    unoptimized.insert(0, Bytecode::Const { value: Value::Scalar(1), dst: ssa(6), debug_info: None });
+    unoptimized[2] = Bytecode::Const { value: Value::Scalar(1), dst: ssa(3), debug_info: None };
+    let mut optimized = unoptimized.clone();
+    number_values(&mut optimized);
+
+    let mut expected = unoptimized.clone();
+    expected[2] = Bytecode::Move { src: ssa(6), dst: ssa(3) };
+
+    assert_bytecodes(&unoptimized, &optimized, &expected);
+
+    let mut optimized = if_else();
+    optimize_func(&mut optimized, 3, 5);
+    assert_bytecodes(&if_else(), &optimized, &if_else());
+}
+
+#[test]
+fn renumber() {
+    let unoptimized = vec![
+        intrinsic(Intrinsic::AddInt, &[0, 1], 10),
+        intrinsic(Intrinsic::MulInt, &[10, 1], 7),
+        Bytecode::Return(SSA::from_u32(7)),
+    ];
+    let mut optimized = unoptimized.clone();
+    renumber_ssa(&mut optimized, 2);
+
+    let expected = vec![
+        intrinsic(Intrinsic::AddInt, &[0, 1], 2),
+        intrinsic(Intrinsic::MulInt, &[2, 1], 3),
+        Bytecode::Return(SSA::from_u32(3)),
+    ];
+
+    assert_bytecodes(&unoptimized, &optimized, &expected);
+}
@@ -0,0 +1,144 @@
+use super::ExprHash;
+use super::cfg::{BasicBlock, Cfg};
+use sodigy_bytecode::{Bytecode, Memory, SSA};
+use sodigy_error::FuncEffect;
+use std::collections::{HashMap, HashSet};
+
+// Dominator-based value numbering and copy propagation.
+//
+// 1. `_5 = _3;` is removed, and all the `_5`s are replaced with `_3`.
+// 2. If `_4 = AddInt(_0, _1);` dominates `_7 = AddInt(_0, _1);`, `_7 = ...` is removed and all the `_7`s
+//    are replaced with `_4`. Only the constants and the pure intrinsics are numbered. A `Fn` can still
+//    print something with `blackbox`, so function calls are never merged.
+//
+// The definition of a SSA dominates all its uses, so it's okay to replace `_7` with `_4` everywhere.
+// There's an exception: `phi(_a, _b)` picks whichever is defined at runtime, so `_a` and `_b` must not
+// be replaced with an SSA that's defined before the branch. If their definition is a copy or a common
+// expression, it becomes a `Move` from the other SSA.
+pub fn number_values(bytecodes: &mut Vec<Bytecode>) {
+    let cfg = Cfg::new(bytecodes);
+    let mut def_counts: HashMap<SSA, usize> = HashMap::new();
+    let mut phi_operands: HashSet<SSA> = HashSet::new();
+
+    for bytecode in bytecodes.iter() {
+        if let Some(Memory::SSA(dst)) = bytecode.get_dst() {
+            *def_counts.entry(*dst).or_insert(0) += 1;
+        }
+
+        if let Bytecode::Phi { pair: (a, b), .. } = bytecode {
+            phi_operands.insert(*a);
+            phi_operands.insert(*b);
+        }
+    }
+
+    // An SSA that's defined more than once is not an SSA, and we can't do anything with it.
+    let is_single_def = |ssa: &SSA| def_counts.get(ssa).copied().unwrap_or(0) <= 1;
+    let mut alias: HashMap<SSA, SSA> = HashMap::new();
+    let mut expressions: HashMap<ExprHash, SSA> = HashMap::new();
+
+    // Expressions in `expressions` are only visible to the blocks dominated by the block
+    // that defined them. When the traversal leaves a block, the expressions are removed.
+    let mut undo_log: Vec<ExprHash> = vec![];
+    let mut removed: HashSet<usize> = HashSet::new();
+    let mut replaced: HashMap<usize, Bytecode> = HashMap::new();
+    let dominator_tree = cfg.dominator_tree();
+
+    // (block, length of `undo_log` when it entered the block)
+    // The length is `None` if it's not entered yet.
+    let mut stack: Vec<(usize, Option<usize>)> = if cfg.blocks.is_empty() { vec![] } else { vec![(0, None)] };
+
+    while let Some((block, undo_log_len)) = stack.pop() {
+        if let Some(undo_log_len) = undo_log_len {
+            for expr in undo_log.drain(undo_log_len..) {
+                expressions.remove(&expr);
+            }
+
+            continue;
+        }
+
+        stack.push((block, Some(undo_log.len())));
+
+        let BasicBlock { start, end, .. } = &cfg.blocks[block];
+
+        for (i, bytecode) in bytecodes.iter().enumerate().take(*end).skip(*start) {
+            let dst = match bytecode.get_dst() {
+                Some(Memory::SSA(dst)) if is_single_def(dst) => *dst,
+                _ => {
+                    continue;
+                },
+            };
+            let resolve = |ssa: &SSA| *alias.get(ssa).unwrap_or(ssa);
+
+            let prev = match bytecode {
+                Bytecode::Move { src: Memory::SSA(src), .. } if is_single_def(src) => resolve(src),
+                Bytecode::Const { value, .. } => {
+                    let expr = ExprHash::from_const(value);
+
+                    match expressions.get(&expr) {
+                        Some(prev) => *prev,
+                        None => {
+                            expressions.insert(expr, dst);
+                            undo_log.push(expr);
+                            continue;
+                        },
+                    }
+                },
+                Bytecode::Intrinsic { intrinsic, args, .. } if bytecode.is_observable() && matches!(intrinsic.effect(), FuncEffect::Fn) && args.iter().all(is_single_def) => {
+                    let args = args.iter().map(resolve).collect::<Vec<_>>();
+                    let expr = ExprHash::from_intrinsic(*intrinsic, &args);
+
+                    match expressions.get(&expr) {
+                        Some(prev) => *prev,
+                        None => {
+                            expressions.insert(expr, dst);
+                            undo_log.push(expr);
+                            continue;
+                        },
+                    }
+                },
+                _ => {
+                    continue;
+                },
+            };
+
+            if phi_operands.contains(&dst) {
+                // The `Move` is already there.
+                if let Bytecode::Move { .. } = bytecode {
+                    continue;
+                }
+
+                replaced.insert(i, Bytecode::Move { src: Memory::SSA(prev), dst: Memory::SSA(dst) });
+            }
+
+            else {
+                alias.insert(dst, prev);
+                removed.insert(i);
+            }
+        }
+
+        for child in dominator_tree[block].iter().rev() {
+            stack.push((*child, None));
+        }
+    }
+
+    if removed.is_empty() && replaced.is_empty() {
+        return;
+    }
+
+    let mut new_bytecodes = Vec::with_capacity(bytecodes.len() - removed.len());
+
+    for (i, mut bytecode) in bytecodes.drain(..).enumerate() {
+        if removed.contains(&i) {
+            continue;
+        }
+
+        if let Some(new_bytecode) = replaced.remove(&i) {
+            bytecode = new_bytecode;
+        }
+
+        bytecode.rename_ssa(&mut |ssa| *alias.get(&ssa).unwrap_or(&ssa));
+        new_bytecodes.push(bytecode);
+    }
+
+    *bytecodes = new_bytecodes;
+}
@@ -2,7 +2,7 @@ mod bytecode;
 mod mir;
 
 pub use bytecode::optimize_bytecode;
-pub use mir::optimize_mir;
+pub use mir::{AccumulatorLog, ConsKind, InlineLog, InlineReason, MirOptimizeLog, optimize_mir};
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OptimizeLevel {
@@ -1,7 +1,60 @@
 use crate::OptimizeLevel;
-use sodigy_mir::Session;
+use sodigy_mir::{Func, Session};
+use sodigy_span::Span;
+use std::collections::HashMap;
 
-pub fn optimize_mir<'hir, 'mir>(session: Session<'hir, 'mir>, level: OptimizeLevel) -> Session<'hir, 'mir> {
-    // TODO: optimize
-    session
+mod accumulator;
+mod fold;
+mod inline;
+
+pub use accumulator::{AccumulatorLog, ConsKind};
+pub use inline::{InlineLog, InlineReason};
+
+#[derive(Clone, Debug, Default)]
+pub struct MirOptimizeLog {
+    pub inlines: Vec<InlineLog>,
+    pub accumulators: Vec<AccumulatorLog>,
+}
+
+// If `dump_log` is set, it returns what were inlined and rewritten, and why.
+pub fn optimize_mir<'hir, 'mir>(
+    mut session: Session<'hir, 'mir>,
+    level: OptimizeLevel,
+    dump_log: bool,
+) -> (Session<'hir, 'mir>, Option<MirOptimizeLog>) {
+    if level == OptimizeLevel::None {
+        return (session, if dump_log { Some(MirOptimizeLog::default()) } else { None });
+    }
+
+    // The passes read the bodies of the callees from `global_context.funcs`, which has
+    // every function in the project. If the session is not compiled by the driver,
+    // there's no global map and they can only see the functions in this module.
+    let shared_funcs = session.global_context.funcs.clone();
+    let shared_funcs_guard;
+    let local_funcs: HashMap<Span, Func>;
+    let funcs = match &shared_funcs {
+        Some(funcs) => {
+            shared_funcs_guard = funcs.read().expect("global context poisoned");
+            &*shared_funcs_guard
+        },
+        None => {
+            local_funcs = session.funcs.iter().map(
+                |func| (func.name_span.clone(), func.clone())
+            ).collect();
+            &local_funcs
+        },
+    };
+
+    let inline_log = inline::inline_funcs(&mut session, funcs, level, dump_log);
+    fold::fold_consts(&mut session, funcs);
+
+    // It creates new functions, which are not in `funcs`, so it has to be the last pass.
+    let accumulator_log = accumulator::add_accumulators(&mut session, dump_log);
+
+    let log = match (inline_log, accumulator_log) {
+        (Some(inlines), Some(accumulators)) => Some(MirOptimizeLog { inlines, accumulators }),
+        _ => None,
+    };
+
+    (session, log)
 }
@@ -0,0 +1,319 @@
+use sodigy_hir::FuncParam;
+use sodigy_mir::{Callable, Expr, Session, Type};
+use sodigy_name_analysis::{IdentWithOrigin, NameOrigin};
+use sodigy_span::{Span, SpanDeriveKind};
+use sodigy_string::{InternedString, intern_string};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+#[derive(Clone, Debug)]
+pub struct AccumulatorLog {
+    pub func: InternedString,
+    pub func_span: Span,
+    pub kind: ConsKind,
+
+    // spans of the recursive calls that became tail calls
+    pub call_spans: Vec<Span>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConsKind {
+    // `x +> f(..)`
+    Prepend,
+
+    // `f(..) <+ x`
+    Append,
+}
+
+impl fmt::Display for ConsKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConsKind::Prepend => write!(f, "`x +> f(..)`"),
+            ConsKind::Append => write!(f, "`f(..) <+ x`"),
+        }
+    }
+}
+
+// `fn range(n) = if n == 0 { [] } else { n +> range(n - 1) };` is not tail-recursive. It has to
+// prepend `n` after the recursive call returns. It's rewritten to
+//
+// ```
+// fn range(n) = range_acc(n, []);
+// fn range_acc(n, acc) = if n == 0 { acc } else { range_acc(n - 1, acc <+ n) };
+// ```
+//
+// , and the recursive call in `range_acc` is lowered to a tail call (`Call { dst: None }`).
+//
+// Every tail position of the function must be one of
+//   1. `x +> f(..)` (or `f(..) <+ x`, but the function can't have both).
+//   2. `f(..)`, which is already a tail call.
+//   3. a list literal: the accumulator is the elements before (or after) the literal, so it becomes `acc <+ a <+ b`.
+//   4. a call that never returns (e.g. `panic`), which is left as it is.
+// Otherwise, it'd need `acc ++ value` in the base case, but `concat_list` is generic and might not be monomorphized.
+//
+// The elements are evaluated in the same order as before. `x` in `x +> f(..)` is evaluated before the
+// recursive call in both versions. `x` in `f(..) <+ x` is evaluated after the recursive call, so it has to be
+// a name or a constant, which can be evaluated at any time.
+pub fn add_accumulators(session: &mut Session, dump_log: bool) -> Option<Vec<AccumulatorLog>> {
+    let context = AccumulatorContext {
+        prepend_span: session.global_context.get_lang_item_span("built_in.prepend_list"),
+        append_span: session.global_context.get_lang_item_span("built_in.append_list"),
+        acc_name: intern_string(b"acc", &session.intermediate_dir).unwrap(),
+        types: session.global_context.types.clone(),
+    };
+    let mut new_funcs = vec![];
+    let mut log = if dump_log { Some(vec![]) } else { None };
+
+    for func in session.funcs.iter_mut() {
+        // The recursive calls of a memoized function have to go through the cache.
+        if func.built_in || func.memoize.is_some() {
+            continue;
+        }
+
+        let mut positions = TailPositions::default();
+
+        if !context.collect_tail_positions(&func.value, &func.name_span, &mut positions) {
+            continue;
+        }
+
+        let kind = match (positions.prepends.is_empty(), positions.appends.is_empty()) {
+            (false, true) => ConsKind::Prepend,
+            (true, false) => ConsKind::Append,
+            _ => {
+                continue;
+            },
+        };
+
+        let acc_func_span = func.name_span.derive(SpanDeriveKind::AccumulatorFunc);
+        let acc_param_span = func.name_span.derive(SpanDeriveKind::AccumulatorParam);
+        let acc = Expr::Ident {
+            id: IdentWithOrigin {
+                id: context.acc_name,
+                span: acc_param_span.clone(),
+                origin: NameOrigin::FuncParam { index: func.params.len() },
+                def_span: acc_param_span.clone(),
+            },
+            dotfish: None,
+        };
+
+        let mut acc_func = func.clone();
+        acc_func.name_span = acc_func_span.clone();
+        acc_func.params.push(FuncParam {
+            name: context.acc_name,
+            name_span: acc_param_span.clone(),
+            type_annot: None,
+            default_value: None,
+            unused_name: None,
+        });
+        context.rewrite_tail_positions(&mut acc_func.value, &func.name_span, &acc_func_span, &acc, kind);
+
+        // `fn range(n) = range_acc(n, []);`
+        let mut args = func.params.iter().enumerate().map(
+            |(index, param)| Expr::Ident {
+                id: IdentWithOrigin {
+                    id: param.name,
+                    span: param.name_span.clone(),
+                    origin: NameOrigin::FuncParam { index },
+                    def_span: param.name_span.clone(),
+                },
+                dotfish: None,
+            }
+        ).collect::<Vec<_>>();
+        args.push(call(Callable::ListInit { group_span: func.name_span.clone() }, vec![]));
+        func.value = call(Callable::Static { def_span: acc_func_span.clone(), span: func.name_span.clone() }, args);
+
+        // The accumulator has the same type as the return value.
+        if let Some(types) = &context.types {
+            let mut types = types.write().expect("global context poisoned");
+
+            if let Some(Type::Func { fn_span, group_span, params, r#return, effect }) = types.get(&func.name_span).cloned() {
+                let mut params = params;
+                params.push(*r#return.clone());
+                types.insert(acc_param_span, *r#return.clone());
+                types.insert(acc_func_span, Type::Func { fn_span, group_span, params, r#return, effect });
+            }
+        }
+
+        if let Some(log) = &mut log {
+            log.push(AccumulatorLog {
+                func: func.name,
+                func_span: func.name_span.clone(),
+                kind,
+                call_spans: match kind {
+                    ConsKind::Prepend => positions.prepends,
+                    ConsKind::Append => positions.appends,
+                },
+            });
+        }
+
+        new_funcs.push(acc_func);
+    }
+
+    session.funcs.extend(new_funcs);
+    log
+}
+
+struct AccumulatorContext {
+    prepend_span: Span,
+    append_span: Span,
+    acc_name: InternedString,
+    types: Option<Arc<RwLock<HashMap<Span, Type>>>>,
+}
+
+// Spans of the recursive calls in tail positions.
+#[derive(Default)]
+struct TailPositions {
+    prepends: Vec<Span>,
+    appends: Vec<Span>,
+}
+
+impl AccumulatorContext {
+    // It returns false if there's a tail position that it cannot rewrite.
+    fn collect_tail_positions(&self, expr: &Expr, func_span: &Span, positions: &mut TailPositions) -> bool {
+        match expr {
+            Expr::If(r#if) => {
+                self.collect_tail_positions(&r#if.true_value, func_span, positions) &&
+                self.collect_tail_positions(&r#if.false_value, func_span, positions)
+            },
+            Expr::Block(block) => self.collect_tail_positions(&block.value, func_span, positions),
+            Expr::Call { func: Callable::Static { def_span, .. }, args, .. } => {
+                if def_span == func_span || self.never_returns(def_span) {
+                    true
+                }
+
+                else if *def_span == self.prepend_span && let Some(span) = self_call_span(&args[1], func_span) {
+                    positions.prepends.push(span);
+                    true
+                }
+
+                else if *def_span == self.append_span &&
+                    let Some(span) = self_call_span(&args[0], func_span) &&
+                    matches!(&args[1], Expr::Ident { .. } | Expr::Constant(_))
+                {
+                    positions.appends.push(span);
+                    true
+                }
+
+                else {
+                    false
+                }
+            },
+            Expr::Call { func: Callable::ListInit { .. }, .. } => true,
+            _ => false,
+        }
+    }
+
+    fn rewrite_tail_positions(
+        &self,
+        expr: &mut Expr,
+        func_span: &Span,
+        acc_func_span: &Span,
+        acc: &Expr,
+        kind: ConsKind,
+    ) {
+        match expr {
+            Expr::If(r#if) => {
+                self.rewrite_tail_positions(&mut r#if.true_value, func_span, acc_func_span, acc, kind);
+                self.rewrite_tail_positions(&mut r#if.false_value, func_span, acc_func_span, acc, kind);
+            },
+            Expr::Block(block) => {
+                self.rewrite_tail_positions(&mut block.value, func_span, acc_func_span, acc, kind);
+            },
+            Expr::Call { func: Callable::Static { def_span, span }, args, .. } => {
+                // `f(x)` -> `f_acc(x, acc)`
+                if def_span == func_span {
+                    *def_span = acc_func_span.clone();
+                    args.push(acc.clone());
+                }
+
+                else if self.never_returns(def_span) {}
+
+                else {
+                    let cons_span = span.clone();
+                    let mut args = std::mem::take(args);
+
+                    // `x +> f(y)` -> `f_acc(y, acc <+ x)`
+                    // `f(y) <+ x` -> `f_acc(y, x +> acc)`
+                    let (elem, mut self_call) = match kind {
+                        ConsKind::Prepend => {
+                            let self_call = args.pop().unwrap();
+                            (args.pop().unwrap(), self_call)
+                        },
+                        ConsKind::Append => {
+                            let elem = args.pop().unwrap();
+                            (elem, args.pop().unwrap())
+                        },
+                    };
+                    let new_acc = self.cons(acc.clone(), elem, kind, cons_span);
+
+                    if let Expr::Call { func: Callable::Static { def_span, .. }, args, .. } = &mut self_call {
+                        *def_span = acc_func_span.clone();
+                        args.push(new_acc);
+                    }
+
+                    *expr = self_call;
+                }
+            },
+            // `[a, b]` -> `acc <+ a <+ b` or `a +> b +> acc`
+            Expr::Call { func: Callable::ListInit { group_span }, args, .. } => {
+                let group_span = group_span.clone();
+                let elems = std::mem::take(args);
+
+                *expr = match kind {
+                    ConsKind::Prepend => elems.into_iter().fold(
+                        acc.clone(),
+                        |acc, elem| self.cons(acc, elem, kind, group_span.clone()),
+                    ),
+                    ConsKind::Append => elems.into_iter().rev().fold(
+                        acc.clone(),
+                        |acc, elem| self.cons(acc, elem, kind, group_span.clone()),
+                    ),
+                };
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    fn never_returns(&self, def_span: &Span) -> bool {
+        match self.types.as_ref().map(|types| types.read()) {
+            Some(Ok(types)) => matches!(
+                types.get(def_span),
+                Some(Type::Func { r#return, .. }) if matches!(**r#return, Type::Never(_)),
+            ),
+            Some(Err(_)) => panic!("global context poisoned"),
+            None => false,
+        }
+    }
+
+    // It pushes `elem` to the end of the accumulator if the original function prepends
+    // elements, and vice versa.
+    fn cons(&self, acc: Expr, elem: Expr, kind: ConsKind, span: Span) -> Expr {
+        match kind {
+            ConsKind::Prepend => call(Callable::Static { def_span: self.append_span.clone(), span }, vec![acc, elem]),
+            ConsKind::Append => call(Callable::Static { def_span: self.prepend_span.clone(), span }, vec![elem, acc]),
+        }
+    }
+}
+
+fn self_call_span(expr: &Expr, func_span: &Span) -> Option<Span> {
+    match expr {
+        Expr::Call { func: Callable::Static { def_span, span }, .. } if def_span == func_span => Some(span.clone()),
+        _ => None,
+    }
+}
+
+fn call(func: Callable, args: Vec<Expr>) -> Expr {
+    let arg_group_span = match &func {
+        Callable::Static { span, .. } | Callable::ListInit { group_span: span } => span.clone(),
+        _ => Span::None,
+    };
+
+    Expr::Call {
+        func,
+        args,
+        arg_group_span,
+        types: None,
+        given_keyword_args: vec![],
+    }
+}
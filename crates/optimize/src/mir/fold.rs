@@ -0,0 +1,542 @@
+use sodigy_error::FuncEffect;
+use sodigy_mir::{Callable, Expr, Func, Intrinsic, Session, false_value, true_value};
+use sodigy_name_analysis::{NameKind, NameOrigin};
+use sodigy_number::{InternedNumber, unintern_number};
+use sodigy_number_eval::{eval_number_comparison, eval_number_infix_op};
+use sodigy_span::{Span, SpanDeriveKind, SpanId};
+use sodigy_string::{intern_string, unintern_string};
+use sodigy_token::{Constant, InfixOp};
+use std::collections::{HashMap, HashSet};
+
+// 1. It evaluates calls to intrinsics (and `++` of string literals) when all the arguments are constants.
+// 2. It propagates constant `let`s and picks a branch of an `if` if the condition is a constant.
+//    Assertions in blocks that are always true are removed.
+// 3. Then, it removes `let`s that are not used. Functions are pure, so it can remove a `let` if its
+//    value doesn't call any `proc`, `ndet` or `blackbox` (directly or indirectly).
+//
+// It doesn't touch top-level `let`s. They're evaluated at compile time (if possible) or removed by the linker.
+pub fn fold_consts(session: &mut Session, funcs: &HashMap<Span, Func>) {
+    let mut context = FoldContext {
+        funcs,
+        intrinsics: Intrinsic::ALL_WITH_LANG_ITEM.iter().map(
+            |(intrinsic, lang_item)| (session.global_context.get_lang_item_span(lang_item), *intrinsic)
+        ).collect(),
+        blackbox_span: session.global_context.get_lang_item_span_id("fn.blackbox"),
+        concat_span: session.global_context.get_lang_item_span_id("fn.concat_list"),
+        true_span: session.global_context.get_lang_item_span("variant.Bool.True"),
+        false_span: session.global_context.get_lang_item_span("variant.Bool.False"),
+        true_value: true_value(session),
+        false_value: false_value(session),
+        purity: HashMap::new(),
+        intermediate_dir: session.intermediate_dir.clone(),
+    };
+
+    for func in session.funcs.iter_mut() {
+        context.fold_expr(&mut func.value, &mut ConstEnv::default());
+    }
+
+    for r#let in session.lets.iter_mut() {
+        context.fold_expr(&mut r#let.value, &mut ConstEnv::default());
+    }
+
+    for assert in session.asserts.iter_mut() {
+        if let Some(note) = &mut assert.note {
+            context.fold_expr(note, &mut ConstEnv::default());
+        }
+
+        context.fold_expr(&mut assert.value, &mut ConstEnv::default());
+    }
+}
+
+struct FoldContext<'f> {
+    funcs: &'f HashMap<Span, Func>,
+    intrinsics: HashMap<Span, Intrinsic>,
+    blackbox_span: SpanId,
+    concat_span: SpanId,
+    true_span: Span,
+    false_span: Span,
+    true_value: Expr,
+    false_value: Expr,
+
+    // `def_span -> is_pure`
+    purity: HashMap<Span, bool>,
+    intermediate_dir: String,
+}
+
+// Constant values of local `let`s in scope.
+// Post-mir creates `let`s without name spans (`Span::None`). They're resolved by scope
+// (the inner-most one), so `unnamed` is a stack.
+#[derive(Default)]
+struct ConstEnv {
+    named: HashMap<Span, Option<Expr>>,
+    unnamed: Vec<Option<Expr>>,
+}
+
+impl FoldContext<'_> {
+    fn fold_expr(&mut self, expr: &mut Expr, env: &mut ConstEnv) {
+        match expr {
+            Expr::Ident { id, .. } => {
+                if is_local_value(&id.origin) {
+                    let value = if id.def_span == Span::None {
+                        env.unnamed.last().cloned().flatten()
+                    } else {
+                        env.named.get(&id.def_span).cloned().flatten()
+                    };
+
+                    if let Some(value) = value {
+                        *expr = value;
+                    }
+                }
+            },
+            Expr::Constant(_) => {},
+            Expr::If(r#if) => {
+                self.fold_expr(&mut r#if.cond, env);
+
+                match self.as_bool(&r#if.cond) {
+                    Some(cond) => {
+                        let mut value = if cond {
+                            std::mem::replace(r#if.true_value.as_mut(), Expr::dummy())
+                        } else {
+                            std::mem::replace(r#if.false_value.as_mut(), Expr::dummy())
+                        };
+
+                        self.fold_expr(&mut value, env);
+                        *expr = value;
+                    },
+                    None => {
+                        self.fold_expr(&mut r#if.true_value, env);
+                        self.fold_expr(&mut r#if.false_value, env);
+                    },
+                }
+            },
+            Expr::Block(block) => {
+                // A name can be bound multiple times (e.g. in different arms of a `match`),
+                // so it restores the shadowed values when it leaves the block.
+                let mut shadowed = vec![];
+                let unnamed_len = env.unnamed.len();
+
+                for r#let in block.lets.iter_mut() {
+                    self.fold_expr(&mut r#let.value, env);
+                    let value = if self.is_const(&r#let.value) { Some(r#let.value.clone()) } else { None };
+
+                    if r#let.name_span == Span::None {
+                        env.unnamed.push(value);
+                    }
+
+                    else {
+                        let prev = env.named.insert(r#let.name_span.clone(), value);
+                        shadowed.push((r#let.name_span.clone(), prev));
+                    }
+                }
+
+                for assert in block.asserts.iter_mut() {
+                    if let Some(note) = &mut assert.note {
+                        self.fold_expr(note, env);
+                    }
+
+                    self.fold_expr(&mut assert.value, env);
+                }
+
+                // An assertion that's always true does nothing (e.g. zero-division checks in std).
+                block.asserts.retain(|assert| self.as_bool(&assert.value) != Some(true));
+
+                for r#do in block.dos.iter_mut() {
+                    self.fold_expr(&mut r#do.value, env);
+                }
+
+                self.fold_expr(&mut block.value, env);
+
+                for (name, prev) in shadowed.into_iter().rev() {
+                    match prev {
+                        Some(prev) => {
+                            env.named.insert(name, prev);
+                        },
+                        None => {
+                            env.named.remove(&name);
+                        },
+                    }
+                }
+
+                env.unnamed.truncate(unnamed_len);
+                self.remove_dead_lets(block);
+
+                if block.lets.is_empty() && block.asserts.is_empty() && block.dos.is_empty() {
+                    *expr = std::mem::replace(block.value.as_mut(), Expr::dummy());
+                }
+            },
+            Expr::Field { lhs, .. } => {
+                self.fold_expr(lhs, env);
+            },
+            Expr::FieldUpdate { lhs, rhs, .. } => {
+                self.fold_expr(lhs, env);
+                self.fold_expr(rhs, env);
+            },
+            Expr::Call { func, args, .. } => {
+                if let Callable::Dynamic(f) = func {
+                    self.fold_expr(f, env);
+                }
+
+                for arg in args.iter_mut() {
+                    self.fold_expr(arg, env);
+                }
+
+                if let Some(value) = self.eval_call(expr) {
+                    *expr = value;
+                }
+            },
+            Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+        }
+    }
+
+    // It returns None if it cannot evaluate the call at compile time.
+    fn eval_call(&self, expr: &Expr) -> Option<Expr> {
+        let Expr::Call { func: Callable::Static { def_span, span }, args, .. } = expr else {
+            return None;
+        };
+        let span = span.derive(SpanDeriveKind::ConstEval);
+
+        if def_span.id_equals(self.concat_span) {
+            return match &args[..] {
+                [
+                    Expr::Constant(Constant::String { binary: lhs_binary, s: lhs, .. }),
+                    Expr::Constant(Constant::String { binary: rhs_binary, s: rhs, .. }),
+                ] if lhs_binary == rhs_binary => {
+                    let mut s = unintern_string(*lhs, &self.intermediate_dir).ok()??;
+                    s.extend(unintern_string(*rhs, &self.intermediate_dir).ok()??);
+
+                    Some(Expr::Constant(Constant::String {
+                        binary: *lhs_binary,
+                        s: intern_string(&s, &self.intermediate_dir).ok()?,
+                        span,
+                    }))
+                },
+                _ => None,
+            };
+        }
+
+        let intrinsic = self.intrinsics.get(def_span)?;
+
+        match (intrinsic, &args[..]) {
+            // `-n` is `0 - n`, which also works for big integers.
+            (Intrinsic::NegInt, [Expr::Constant(Constant::Number { n, .. })]) if n.is_integer() => {
+                let n = eval_number_infix_op(InfixOp::Sub, span.clone(), &InternedNumber::from_u32(0, true), n, &self.intermediate_dir).ok()?;
+                Some(Expr::Constant(Constant::Number { n, span }))
+            },
+            (
+                Intrinsic::AddInt | Intrinsic::SubInt | Intrinsic::MulInt | Intrinsic::DivInt | Intrinsic::RemInt |
+                Intrinsic::LtInt | Intrinsic::EqInt | Intrinsic::GtInt,
+                [Expr::Constant(Constant::Number { n: lhs, .. }), Expr::Constant(Constant::Number { n: rhs, .. })],
+            ) if lhs.is_integer() && rhs.is_integer() => {
+                let op = match intrinsic {
+                    Intrinsic::AddInt => InfixOp::Add,
+                    Intrinsic::SubInt => InfixOp::Sub,
+                    Intrinsic::MulInt => InfixOp::Mul,
+                    Intrinsic::DivInt => InfixOp::Div,
+                    Intrinsic::RemInt => InfixOp::Rem,
+                    Intrinsic::LtInt => InfixOp::Lt,
+                    Intrinsic::EqInt => InfixOp::Eq,
+                    Intrinsic::GtInt => InfixOp::Gt,
+                    _ => unreachable!(),
+                };
+
+                match op {
+                    InfixOp::Lt | InfixOp::Eq | InfixOp::Gt => {
+                        let result = eval_number_comparison(op, span, lhs, rhs, &self.intermediate_dir).ok()?;
+                        Some(self.bool_value(result))
+                    },
+                    _ => {
+                        // `DivInt` and `RemInt` don't check zero-divisions. It's std's job, so we just don't touch it.
+                        let n = eval_number_infix_op(op, span.clone(), lhs, rhs, &self.intermediate_dir).ok()?;
+                        Some(Expr::Constant(Constant::Number { n, span }))
+                    },
+                }
+            },
+            (
+                Intrinsic::LtScalar | Intrinsic::EqScalar | Intrinsic::GtScalar,
+                [Expr::Constant(lhs), Expr::Constant(rhs)],
+            ) => {
+                let (lhs, rhs) = (as_scalar(lhs)?, as_scalar(rhs)?);
+                let result = match intrinsic {
+                    Intrinsic::LtScalar => lhs < rhs,
+                    Intrinsic::EqScalar => lhs == rhs,
+                    Intrinsic::GtScalar => lhs > rhs,
+                    _ => unreachable!(),
+                };
+
+                Some(self.bool_value(result))
+            },
+
+            // `transmute` between chars, bytes and scalars. Their runtime representations are the same.
+            (Intrinsic::Nop1, [Expr::Constant(c)]) => Some(Expr::Constant(Constant::Scalar(as_scalar(c)?))),
+            (Intrinsic::ScalarToInt, [Expr::Constant(c)]) => Some(Expr::Constant(Constant::Number {
+                n: InternedNumber::from_u32(as_scalar(c)?, true),
+                span,
+            })),
+            (Intrinsic::IntToScalar, [Expr::Constant(Constant::Number { n, .. })]) if n.is_integer() => {
+                let n = unintern_number(*n, &self.intermediate_dir).ok()?;
+                let n = u32::try_from(i128::try_from(&n.numer).ok()?).ok()?;
+                Some(Expr::Constant(Constant::Scalar(n)))
+            },
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self, expr: &Expr) -> Option<bool> {
+        match expr {
+            Expr::Call { func: Callable::EnumInit { variant_def_span, .. }, args, .. } if args.is_empty() => {
+                if *variant_def_span == self.true_span {
+                    Some(true)
+                }
+
+                else if *variant_def_span == self.false_span {
+                    Some(false)
+                }
+
+                else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn bool_value(&self, b: bool) -> Expr {
+        if b { self.true_value.clone() } else { self.false_value.clone() }
+    }
+
+    // Values that are propagated to the uses of the `let`.
+    fn is_const(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::Constant(_)) || self.as_bool(expr).is_some()
+    }
+
+    // A `let` has to be kept if its name is used after it, or its value has a side effect.
+    fn remove_dead_lets(&mut self, block: &mut sodigy_mir::Block) {
+        let mut used = HashSet::new();
+        let mut unnamed_used = false;
+
+        for assert in block.asserts.iter() {
+            if let Some(note) = &assert.note {
+                collect_uses(note, &mut used, &mut unnamed_used);
+            }
+
+            collect_uses(&assert.value, &mut used, &mut unnamed_used);
+        }
+
+        for r#do in block.dos.iter() {
+            collect_uses(&r#do.value, &mut used, &mut unnamed_used);
+        }
+
+        collect_uses(&block.value, &mut used, &mut unnamed_used);
+        let mut lets = Vec::with_capacity(block.lets.len());
+
+        // An unnamed `let` shadows the previous unnamed `let`s, so it iterates in reverse order.
+        for r#let in std::mem::take(&mut block.lets).into_iter().rev() {
+            let is_used = if r#let.name_span == Span::None {
+                std::mem::replace(&mut unnamed_used, false)
+            } else {
+                used.contains(&r#let.name_span)
+            };
+
+            if is_used || !self.is_pure(&r#let.value) {
+                collect_uses(&r#let.value, &mut used, &mut unnamed_used);
+                lets.push(r#let);
+            }
+        }
+
+        lets.reverse();
+        block.lets = lets;
+    }
+
+    fn is_pure(&mut self, expr: &Expr) -> bool {
+        let mut callees = vec![];
+        self.has_no_side_effect(expr, &mut callees) && callees.iter().all(|callee| self.is_pure_func(callee))
+    }
+
+    // A function is pure if it (and the functions it calls) doesn't call any `proc`, `ndet`, `blackbox`
+    // or a functor, and doesn't have `assert` or `do`.
+    fn is_pure_func(&mut self, def_span: &Span) -> bool {
+        if let Some(is_pure) = self.purity.get(def_span) {
+            return *is_pure;
+        }
+
+        let mut stack = vec![def_span.clone()];
+        let mut visited = HashSet::new();
+        visited.insert(def_span.clone());
+        let mut is_pure = true;
+
+        while let Some(curr) = stack.pop() {
+            match self.purity.get(&curr) {
+                Some(true) => {
+                    continue;
+                },
+                Some(false) => {
+                    is_pure = false;
+                    break;
+                },
+                None => {},
+            }
+
+            let mut callees = vec![];
+
+            if !self.has_no_side_effect(&self.funcs.get(&curr).unwrap().value, &mut callees) {
+                is_pure = false;
+                break;
+            }
+
+            for callee in callees.into_iter() {
+                if visited.insert(callee.clone()) {
+                    stack.push(callee);
+                }
+            }
+        }
+
+        // If it's pure, every function it can reach is also pure.
+        if is_pure {
+            for def_span in visited.into_iter() {
+                self.purity.insert(def_span, true);
+            }
+        }
+
+        else {
+            self.purity.insert(def_span.clone(), false);
+        }
+
+        is_pure
+    }
+
+    // It doesn't look into the static callees. It pushes them to `callees` instead.
+    fn has_no_side_effect(&self, expr: &Expr, callees: &mut Vec<Span>) -> bool {
+        match expr {
+            Expr::Ident { .. } | Expr::Constant(_) => true,
+            Expr::If(r#if) => {
+                self.has_no_side_effect(&r#if.cond, callees) &&
+                self.has_no_side_effect(&r#if.true_value, callees) &&
+                self.has_no_side_effect(&r#if.false_value, callees)
+            },
+            Expr::Block(block) => {
+                block.asserts.is_empty() &&
+                block.dos.is_empty() &&
+                block.lets.iter().all(|r#let| self.has_no_side_effect(&r#let.value, callees)) &&
+                self.has_no_side_effect(&block.value, callees)
+            },
+            Expr::Field { lhs, .. } => self.has_no_side_effect(lhs, callees),
+            Expr::FieldUpdate { lhs, rhs, .. } => {
+                self.has_no_side_effect(lhs, callees) &&
+                self.has_no_side_effect(rhs, callees)
+            },
+            Expr::Call { func, args, .. } => {
+                let func = match func {
+                    Callable::Static { def_span, .. } => match self.intrinsics.get(def_span) {
+                        // `Print` and `EPrint` are `Fn`s for debugging, but they're not pure.
+                        Some(intrinsic) => intrinsic.effect() == FuncEffect::Fn && !matches!(
+                            intrinsic,
+                            Intrinsic::Panic | Intrinsic::Print | Intrinsic::EPrint,
+                        ),
+                        None if def_span.id_equals(self.blackbox_span) => false,
+                        None => match self.funcs.get(def_span) {
+                            Some(func) if func.effect == FuncEffect::Fn => {
+                                callees.push(def_span.clone());
+                                true
+                            },
+                            _ => false,
+                        },
+                    },
+                    Callable::StructInit { .. } |
+                    Callable::EnumInit { .. } |
+                    Callable::TupleInit { .. } |
+                    Callable::ListInit { .. } => true,
+                    Callable::Dynamic(_) => false,
+                };
+
+                func && args.iter().all(|arg| self.has_no_side_effect(arg, callees))
+            },
+            Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+        }
+    }
+}
+
+// Names that are bound by block `let`s. Post-mir lowers name bindings in patterns and
+// pipelines to `let`s, but the identifiers keep their original origins.
+fn is_local_value(origin: &NameOrigin) -> bool {
+    matches!(
+        origin,
+        NameOrigin::Local { kind: NameKind::Let { is_top_level: false } | NameKind::PatternNameBind | NameKind::Pipeline },
+    )
+}
+
+fn as_scalar(c: &Constant) -> Option<u32> {
+    match c {
+        Constant::Char { ch, .. } => Some(*ch),
+        Constant::Byte { b, .. } => Some(*b as u32),
+        Constant::Scalar(n) => Some(*n),
+        _ => None,
+    }
+}
+
+// It collects def_spans of local `let`s that are used in `expr`.
+// `unnamed_used` is set if `expr` uses the inner-most unnamed `let` outside `expr`.
+fn collect_uses(expr: &Expr, used: &mut HashSet<Span>, unnamed_used: &mut bool) {
+    match expr {
+        Expr::Ident { id, .. } => {
+            if is_local_value(&id.origin) {
+                if id.def_span == Span::None {
+                    *unnamed_used = true;
+                }
+
+                else {
+                    used.insert(id.def_span.clone());
+                }
+            }
+        },
+        Expr::Constant(_) => {},
+        Expr::If(r#if) => {
+            collect_uses(&r#if.cond, used, unnamed_used);
+            collect_uses(&r#if.true_value, used, unnamed_used);
+            collect_uses(&r#if.false_value, used, unnamed_used);
+        },
+        Expr::Block(block) => {
+            // Once there's an unnamed `let` in this block, the unnamed names
+            // after that don't refer to the `let` outside.
+            let mut shadowed = false;
+            let mut dummy = false;
+
+            for r#let in block.lets.iter() {
+                collect_uses(&r#let.value, used, if shadowed { &mut dummy } else { &mut *unnamed_used });
+                shadowed |= r#let.name_span == Span::None;
+            }
+
+            let unnamed_used = if shadowed { &mut dummy } else { unnamed_used };
+
+            for assert in block.asserts.iter() {
+                if let Some(note) = &assert.note {
+                    collect_uses(note, used, unnamed_used);
+                }
+
+                collect_uses(&assert.value, used, unnamed_used);
+            }
+
+            for r#do in block.dos.iter() {
+                collect_uses(&r#do.value, used, unnamed_used);
+            }
+
+            collect_uses(&block.value, used, unnamed_used);
+        },
+        Expr::Field { lhs, .. } => {
+            collect_uses(lhs, used, unnamed_used);
+        },
+        Expr::FieldUpdate { lhs, rhs, .. } => {
+            collect_uses(lhs, used, unnamed_used);
+            collect_uses(rhs, used, unnamed_used);
+        },
+        Expr::Call { func, args, .. } => {
+            if let Callable::Dynamic(f) = func {
+                collect_uses(f, used, unnamed_used);
+            }
+
+            for arg in args.iter() {
+                collect_uses(arg, used, unnamed_used);
+            }
+        },
+        Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+    }
+}
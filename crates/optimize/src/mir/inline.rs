@@ -0,0 +1,564 @@
+use crate::OptimizeLevel;
+use sodigy_error::FuncEffect;
+use sodigy_hir::LetOrigin;
+use sodigy_mir::{Block, Callable, Expr, Func, Let, Session, Type};
+use sodigy_name_analysis::{IdentWithOrigin, NameKind, NameOrigin};
+use sodigy_span::{Span, SpanDeriveKind, SpanId};
+use sodigy_string::InternedString;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+// It doesn't inline anything to a function that's already this big.
+const MAX_CALLER_SIZE: usize = 2048;
+
+#[derive(Clone, Debug)]
+pub struct InlineLog {
+    pub call_span: Span,
+    pub callee: InternedString,
+    pub callee_span: Span,
+    pub reason: InlineReason,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum InlineReason {
+    // The body of the function is just a call to another function, and the
+    // arguments are parameters, constants or such calls (e.g. `fn len(ls) = len_list(ls) as <Int>;`).
+    // Inlining it removes a call without making the code larger.
+    Wrapper,
+
+    // `size` is the number of nodes in the body, where a branch that never returns (e.g. `panic`) counts as 1 node.
+    Small { size: usize, limit: usize },
+}
+
+impl fmt::Display for InlineReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InlineReason::Wrapper => write!(f, "it's a wrapper of another function"),
+            InlineReason::Small { size, limit } => write!(f, "its body is small ({size} nodes, limit: {limit})"),
+        }
+    }
+}
+
+// Functions are pure, so `f(x)` is always `{ let param = x; body }`.
+//
+// It only inlines static calls to non-recursive `fn`s. `funcs` may have
+// functions in other modules, so it can inline them too.
+pub fn inline_funcs(
+    session: &mut Session,
+    funcs: &HashMap<Span, Func>,
+    level: OptimizeLevel,
+    dump_log: bool,
+) -> Option<Vec<InlineLog>> {
+    let (size_limit, rounds) = match level {
+        OptimizeLevel::None => unreachable!(),
+        OptimizeLevel::Mild => (32, 3),
+        OptimizeLevel::Extreme => (96, 5),
+    };
+    let mut context = InlineContext {
+        funcs,
+        size_limit,
+        types: session.global_context.types.clone(),
+        blackbox_span: session.global_context.get_lang_item_span_id("fn.blackbox"),
+        candidates: HashMap::new(),
+        callees: HashMap::new(),
+        next_id: 0,
+        type_copies: vec![],
+        log: if dump_log { Some(vec![]) } else { None },
+    };
+
+    // Each round inlines the calls in the original code, and the next round
+    // inlines the calls that were in the inlined bodies.
+    for _ in 0..rounds {
+        let mut inlined = false;
+
+        for func in session.funcs.iter_mut() {
+            if context.size_of(&func.value) < MAX_CALLER_SIZE {
+                inlined |= context.inline_expr(&mut func.value);
+            }
+        }
+
+        for r#let in session.lets.iter_mut() {
+            inlined |= context.inline_expr(&mut r#let.value);
+        }
+
+        for assert in session.asserts.iter_mut() {
+            inlined |= context.inline_expr(&mut assert.value);
+        }
+
+        if !inlined {
+            break;
+        }
+    }
+
+    // The renamed parameters and `let`s have the same types as the original ones.
+    if let Some(types) = &session.global_context.types {
+        let mut types = types.write().expect("global context poisoned");
+
+        for (original, renamed) in context.type_copies.drain(..) {
+            if let Some(r#type) = types.get(&original).cloned() {
+                types.insert(renamed, r#type);
+            }
+        }
+    }
+
+    context.log
+}
+
+struct InlineContext<'f> {
+    funcs: &'f HashMap<Span, Func>,
+    size_limit: usize,
+    types: Option<Arc<RwLock<HashMap<Span, Type>>>>,
+    blackbox_span: SpanId,
+
+    // `def_span -> Some(reason)` if the function can be inlined.
+    candidates: HashMap<Span, Option<InlineReason>>,
+
+    // `def_span -> def_spans of the functions it calls`
+    callees: HashMap<Span, Vec<Span>>,
+
+    // for `SpanDeriveKind::Inline`
+    next_id: u32,
+
+    // `(original def_span, renamed def_span)`
+    type_copies: Vec<(Span, Span)>,
+
+    log: Option<Vec<InlineLog>>,
+}
+
+// An argument of an inlined call.
+enum Substitute {
+    // Constants and names are cheap to evaluate, so it just replaces the parameter with the argument.
+    Expr(Box<Expr>),
+
+    // Otherwise, the argument is bound to a `let` with this name span.
+    Let(Span),
+}
+
+impl InlineContext<'_> {
+    // It returns true if anything's inlined.
+    fn inline_expr(&mut self, expr: &mut Expr) -> bool {
+        let mut inlined = false;
+
+        match expr {
+            Expr::Ident { .. } | Expr::Constant(_) => {},
+            Expr::If(r#if) => {
+                inlined |= self.inline_expr(&mut r#if.cond);
+                inlined |= self.inline_expr(&mut r#if.true_value);
+                inlined |= self.inline_expr(&mut r#if.false_value);
+            },
+            Expr::Block(block) => {
+                for r#let in block.lets.iter_mut() {
+                    inlined |= self.inline_expr(&mut r#let.value);
+                }
+
+                for assert in block.asserts.iter_mut() {
+                    inlined |= self.inline_expr(&mut assert.value);
+                }
+
+                for r#do in block.dos.iter_mut() {
+                    inlined |= self.inline_expr(&mut r#do.value);
+                }
+
+                inlined |= self.inline_expr(&mut block.value);
+            },
+            Expr::Field { lhs, .. } => {
+                inlined |= self.inline_expr(lhs);
+            },
+            Expr::FieldUpdate { lhs, rhs, .. } => {
+                inlined |= self.inline_expr(lhs);
+                inlined |= self.inline_expr(rhs);
+            },
+            Expr::Call { func, args, .. } => {
+                if let Callable::Dynamic(f) = func {
+                    inlined |= self.inline_expr(f);
+                }
+
+                for arg in args.iter_mut() {
+                    inlined |= self.inline_expr(arg);
+                }
+            },
+            Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+        }
+
+        if let Expr::Call { func: Callable::Static { def_span, span }, args, arg_group_span, .. } = expr &&
+            let Some(reason) = self.get_inline_reason(def_span) &&
+            let Some(callee) = self.funcs.get(def_span) &&
+            callee.params.len() == args.len()
+        {
+            if let Some(log) = &mut self.log {
+                log.push(InlineLog {
+                    call_span: span.clone(),
+                    callee: callee.name,
+                    callee_span: callee.name_span.clone(),
+                    reason,
+                });
+            }
+
+            *expr = self.instantiate(callee, std::mem::take(args), span.clone(), arg_group_span.clone());
+            inlined = true;
+        }
+
+        inlined
+    }
+
+    fn get_inline_reason(&mut self, def_span: &Span) -> Option<InlineReason> {
+        if let Some(reason) = self.candidates.get(def_span) {
+            return *reason;
+        }
+
+        let reason = match self.funcs.get(def_span) {
+            // A call that never returns is cold. There's no point in inlining it.
+            // An inlined call to a memoized function would bypass the cache.
+            Some(func) if func.effect == FuncEffect::Fn && func.memoize.is_none() && !func.name_span.id_equals(self.blackbox_span) && !self.never_returns(def_span) => {
+                let size = self.size_of(&func.value);
+
+                if is_wrapper(&func.value, &mut HashSet::new()) {
+                    Some(InlineReason::Wrapper)
+                }
+
+                else if size <= self.size_limit {
+                    Some(InlineReason::Small { size, limit: self.size_limit })
+                }
+
+                else {
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let reason = match reason {
+            Some(_) if self.is_recursive(def_span) => None,
+            _ => reason,
+        };
+
+        self.candidates.insert(def_span.clone(), reason);
+        reason
+    }
+
+    // It's recursive if it can reach itself by static calls.
+    fn is_recursive(&mut self, def_span: &Span) -> bool {
+        let mut stack = vec![def_span.clone()];
+        let mut visited = HashSet::new();
+
+        while let Some(curr) = stack.pop() {
+            if !self.callees.contains_key(&curr) {
+                let mut callees = vec![];
+
+                if let Some(func) = self.funcs.get(&curr) {
+                    collect_static_callees(&func.value, &mut callees);
+                }
+
+                self.callees.insert(curr.clone(), callees);
+            }
+
+            for callee in self.callees.get(&curr).unwrap().iter() {
+                if callee == def_span {
+                    return true;
+                }
+
+                if visited.insert(callee.clone()) {
+                    stack.push(callee.clone());
+                }
+            }
+        }
+
+        false
+    }
+
+    // A branch that never returns (e.g. `panic`) is cold, so it counts as 1 node,
+    // no matter how big the panic message is.
+    fn size_of(&self, expr: &Expr) -> usize {
+        match expr {
+            Expr::Ident { .. } | Expr::Constant(_) => 1,
+            Expr::If(r#if) => {
+                let branch_size = |value: &Expr| if self.is_cold(value) { 1 } else { self.size_of(value) };
+                1 + self.size_of(&r#if.cond) + branch_size(&r#if.true_value) + branch_size(&r#if.false_value)
+            },
+            Expr::Block(block) => {
+                1 + block.lets.iter().map(|r#let| self.size_of(&r#let.value)).sum::<usize>()
+                + block.asserts.iter().map(|assert| self.size_of(&assert.value)).sum::<usize>()
+                + block.dos.iter().map(|r#do| self.size_of(&r#do.value)).sum::<usize>()
+                + self.size_of(&block.value)
+            },
+            Expr::Field { lhs, .. } => 1 + self.size_of(lhs),
+            Expr::FieldUpdate { lhs, rhs, .. } => 1 + self.size_of(lhs) + self.size_of(rhs),
+            Expr::Call { func, args, .. } => {
+                let func_size = match func {
+                    Callable::Dynamic(f) => self.size_of(f),
+                    _ => 0,
+                };
+
+                1 + func_size + args.iter().map(|arg| self.size_of(arg)).sum::<usize>()
+            },
+            Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+        }
+    }
+
+    fn is_cold(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Call { func: Callable::Static { def_span, .. }, .. } => self.never_returns(def_span),
+            _ => false,
+        }
+    }
+
+    fn never_returns(&self, def_span: &Span) -> bool {
+        match self.types.as_ref().map(|types| types.read()) {
+            Some(Ok(types)) => matches!(
+                types.get(def_span),
+                Some(Type::Func { r#return, .. }) if matches!(**r#return, Type::Never(_)),
+            ),
+            Some(Err(_)) => panic!("global context poisoned"),
+            None => false,
+        }
+    }
+
+    // `f(x, y)` -> `{ let a = x; let b = y; body }`
+    fn instantiate(&mut self, callee: &Func, args: Vec<Expr>, call_span: Span, arg_group_span: Span) -> Expr {
+        let mut lets = vec![];
+        let mut substitutes = Vec::with_capacity(args.len());
+
+        for (param, arg) in callee.params.iter().zip(args) {
+            let is_cheap = match &arg {
+                Expr::Constant(_) => true,
+                Expr::Ident { id, .. } => matches!(
+                    id.origin,
+                    NameOrigin::FuncParam { .. } |
+                    NameOrigin::Local { kind: NameKind::Let { is_top_level: false } | NameKind::Func } |
+                    NameOrigin::Foreign { kind: NameKind::Func },
+                ),
+                _ => false,
+            };
+
+            if is_cheap {
+                substitutes.push(Substitute::Expr(Box::new(arg)));
+            }
+
+            else {
+                let name_span = self.rename(&param.name_span, &callee.name_span);
+                lets.push(Let {
+                    keyword_span: call_span.clone(),
+                    name: param.name,
+                    name_span: name_span.clone(),
+                    type_annot_span: None,
+                    value: arg,
+                    origin: LetOrigin::Inline,
+                });
+                substitutes.push(Substitute::Let(name_span));
+            }
+        }
+
+        let mut value = callee.value.clone();
+        self.rename_locals(
+            &mut value,
+            &substitutes,
+            &callee.name_span,
+            &mut HashMap::new(),
+            &mut vec![],
+        );
+
+        if lets.is_empty() {
+            value
+        }
+
+        else {
+            Expr::Block(Block {
+                group_span: arg_group_span,
+                lets,
+                asserts: vec![],
+                dos: vec![],
+                value: Box::new(value),
+            })
+        }
+    }
+
+    fn rename(&mut self, span: &Span, callee_span: &Span) -> Span {
+        self.next_id += 1;
+        let renamed = span.or(callee_span).derive(SpanDeriveKind::Inline(self.next_id));
+
+        if *span != Span::None {
+            self.type_copies.push((span.clone(), renamed.clone()));
+        }
+
+        renamed
+    }
+
+    // Every local name in the inlined body gets a new def_span, so that the same function
+    // can be inlined multiple times in a function.
+    //
+    // Post-mir creates `let`s without name spans (`Span::None`). They're resolved by scope
+    // (the inner-most one), so `unnamed_lets` is a stack.
+    fn rename_locals(
+        &mut self,
+        expr: &mut Expr,
+        substitutes: &[Substitute],
+        callee_span: &Span,
+        renamed: &mut HashMap<Span, Span>,
+        unnamed_lets: &mut Vec<Span>,
+    ) {
+        match expr {
+            Expr::Ident { id, .. } => match id.origin {
+                NameOrigin::FuncParam { index } => match &substitutes[index] {
+                    Substitute::Expr(e) => {
+                        *expr = *e.clone();
+                    },
+                    Substitute::Let(def_span) => {
+                        id.origin = NameOrigin::Local { kind: NameKind::Let { is_top_level: false } };
+                        id.def_span = def_span.clone();
+                    },
+                },
+                _ => {
+                    if id.def_span == Span::None {
+                        if let Some(def_span) = unnamed_lets.last() {
+                            id.def_span = def_span.clone();
+                        }
+                    }
+
+                    else if let Some(def_span) = renamed.get(&id.def_span) {
+                        id.def_span = def_span.clone();
+                    }
+                },
+            },
+            Expr::Constant(_) => {},
+            Expr::If(r#if) => {
+                self.rename_locals(&mut r#if.cond, substitutes, callee_span, renamed, unnamed_lets);
+                self.rename_locals(&mut r#if.true_value, substitutes, callee_span, renamed, unnamed_lets);
+                self.rename_locals(&mut r#if.false_value, substitutes, callee_span, renamed, unnamed_lets);
+            },
+            Expr::Block(block) => {
+                let unnamed_lets_len = unnamed_lets.len();
+
+                for r#let in block.lets.iter_mut() {
+                    self.rename_locals(&mut r#let.value, substitutes, callee_span, renamed, unnamed_lets);
+                    let new_name_span = self.rename(&r#let.name_span, callee_span);
+
+                    if r#let.name_span == Span::None {
+                        unnamed_lets.push(new_name_span.clone());
+                    }
+
+                    else {
+                        renamed.insert(r#let.name_span.clone(), new_name_span.clone());
+                    }
+
+                    r#let.name_span = new_name_span;
+                }
+
+                for assert in block.asserts.iter_mut() {
+                    if let Some(note) = &mut assert.note {
+                        self.rename_locals(note, substitutes, callee_span, renamed, unnamed_lets);
+                    }
+
+                    self.rename_locals(&mut assert.value, substitutes, callee_span, renamed, unnamed_lets);
+                }
+
+                for r#do in block.dos.iter_mut() {
+                    self.rename_locals(&mut r#do.value, substitutes, callee_span, renamed, unnamed_lets);
+                }
+
+                self.rename_locals(&mut block.value, substitutes, callee_span, renamed, unnamed_lets);
+                unnamed_lets.truncate(unnamed_lets_len);
+            },
+            Expr::Field { lhs, .. } => {
+                self.rename_locals(lhs, substitutes, callee_span, renamed, unnamed_lets);
+            },
+            Expr::FieldUpdate { lhs, rhs, .. } => {
+                self.rename_locals(lhs, substitutes, callee_span, renamed, unnamed_lets);
+                self.rename_locals(rhs, substitutes, callee_span, renamed, unnamed_lets);
+            },
+            Expr::Call { func, args, .. } => {
+                for arg in args.iter_mut() {
+                    self.rename_locals(arg, substitutes, callee_span, renamed, unnamed_lets);
+                }
+
+                if let Callable::Dynamic(f) = func {
+                    self.rename_locals(f, substitutes, callee_span, renamed, unnamed_lets);
+
+                    // If a function is passed to a parameter, the dynamic call becomes a static call.
+                    if let Expr::Ident {
+                        id: IdentWithOrigin {
+                            origin: NameOrigin::Local { kind: NameKind::Func } | NameOrigin::Foreign { kind: NameKind::Func },
+                            def_span,
+                            span,
+                            ..
+                        },
+                        ..
+                    } = &**f {
+                        *func = Callable::Static {
+                            def_span: def_span.clone(),
+                            span: span.clone(),
+                        };
+                    }
+                }
+            },
+            Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+        }
+    }
+}
+
+// `f(g(x), 1)` is a wrapper, but `f(x, x)` and `f(x.a)` are not.
+fn is_wrapper(expr: &Expr, used_params: &mut HashSet<usize>) -> bool {
+    match expr {
+        Expr::Call { func: Callable::Static { .. }, args, .. } => args.iter().all(
+            |arg| match arg {
+                Expr::Constant(_) => true,
+                Expr::Ident { id, .. } => match id.origin {
+                    NameOrigin::FuncParam { index } => used_params.insert(index),
+                    _ => false,
+                },
+                Expr::Call { .. } => is_wrapper(arg, used_params),
+                _ => false,
+            }
+        ),
+        _ => false,
+    }
+}
+
+fn collect_static_callees(expr: &Expr, callees: &mut Vec<Span>) {
+    match expr {
+        Expr::Ident { .. } | Expr::Constant(_) => {},
+        Expr::If(r#if) => {
+            collect_static_callees(&r#if.cond, callees);
+            collect_static_callees(&r#if.true_value, callees);
+            collect_static_callees(&r#if.false_value, callees);
+        },
+        Expr::Block(block) => {
+            for r#let in block.lets.iter() {
+                collect_static_callees(&r#let.value, callees);
+            }
+
+            for assert in block.asserts.iter() {
+                collect_static_callees(&assert.value, callees);
+            }
+
+            for r#do in block.dos.iter() {
+                collect_static_callees(&r#do.value, callees);
+            }
+
+            collect_static_callees(&block.value, callees);
+        },
+        Expr::Field { lhs, .. } => {
+            collect_static_callees(lhs, callees);
+        },
+        Expr::FieldUpdate { lhs, rhs, .. } => {
+            collect_static_callees(lhs, callees);
+            collect_static_callees(rhs, callees);
+        },
+        Expr::Call { func, args, .. } => {
+            match func {
+                Callable::Static { def_span, .. } => {
+                    callees.push(def_span.clone());
+                },
+                Callable::Dynamic(f) => {
+                    collect_static_callees(f, callees);
+                },
+                _ => {},
+            }
+
+            for arg in args.iter() {
+                collect_static_callees(arg, callees);
+            }
+        },
+        Expr::Match(_) | Expr::Macro { .. } => unreachable!(),
+    }
+}
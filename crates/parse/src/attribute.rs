@@ -73,6 +73,9 @@ pub struct Decorator {
     // `#[public]` and `#[public()]` are different!
     pub args: Option<Vec<DecoratorArg>>,
     pub arg_group_span: Option<Span>,
+
+    // span of the entire decorator, including `#[` and `]`
+    pub group_span: Span,
 }
 
 // A decorator argument can be an expression or a type annotation,
@@ -156,13 +159,14 @@ impl<'t, 's> Tokens<'t, 's> {
                                 kind: ErrorKind::ModuleDocCommentNotAtTop,
                                 spans: span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             });
                             module_doc_error = true;
                         }
+                    }
 
-                        if top_level && !*top_level_ {
-                            break;
-                        }
+                    else if top_level && !*top_level_ {
+                        break;
                     }
 
                     doc_comments.push(DocCommentLine::new(*doc, span.clone()));
@@ -179,6 +183,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             kind: ErrorKind::ModuleDecoratorNotAtTop,
                             spans: span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -189,7 +194,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     let group_span = span.clone();
                     let mut tokens = Tokens::new(tokens, group_span.end(), false, self.intermediate_dir);
 
-                    match tokens.parse_decorator() {
+                    match tokens.parse_decorator(group_span.clone()) {
                         Ok(decorator) => {
                             decorators.push(decorator);
                         },
@@ -212,6 +217,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 note: Some(String::from("Remove this `;`.")),
                             }],
                             note: Some(String::from("Don't put a semicolon after a decorator.")),
+                            suggestions: vec![],
                         });
                         self.cursor += 1;
                         return Err(errors);
@@ -260,7 +266,7 @@ impl<'t, 's> Tokens<'t, 's> {
         }
     }
 
-    pub fn parse_decorator(&mut self) -> Result<Decorator, Vec<Error>> {
+    pub fn parse_decorator(&mut self, decorator_span: Span) -> Result<Decorator, Vec<Error>> {
         let (name, name_span) = self.pop_name_and_span(false /* allow_wildcard */)?;
 
         match self.peek() {
@@ -273,6 +279,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     name_span,
                     args: Some(args),
                     arg_group_span: Some(group_span),
+                    group_span: decorator_span,
                 };
                 self.cursor += 1;
                 self.empty_or_error()?;
@@ -286,6 +293,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             None => {
@@ -294,6 +302,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     name_span,
                     args: None,
                     arg_group_span: None,
+                    group_span: decorator_span,
                 });
             },
         }
@@ -365,6 +374,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 (None, _) => {
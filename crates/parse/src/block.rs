@@ -16,7 +16,7 @@ use sodigy_error::{Error, ErrorKind, ErrorToken};
 use sodigy_name_analysis::NameKind;
 use sodigy_span::{RenderableSpan, Span};
 use sodigy_string::InternedString;
-use sodigy_token::{Keyword, Token, TokenKind};
+use sodigy_token::{Delim, Keyword, Punct, Token, TokenKind};
 
 #[derive(Clone, Debug)]
 pub struct Block {
@@ -97,8 +97,6 @@ impl<'t, 's> Tokens<'t, 's> {
         &mut self,
 
         // top-level block doesn't have a value
-        // also, there's a heuristic for top-level blocks: it continues parsing even
-        // though there's an error so that it can find more errors
         is_top_level: bool,
         group_span: Span,
     ) -> Result<Block, Vec<Error>> {
@@ -127,6 +125,8 @@ impl<'t, 's> Tokens<'t, 's> {
             None
         };
 
+        // If a statement has a syntax error, it skips the statement and continues parsing
+        // so that it can find more errors. Read `march_until_next_statement`.
         loop {
             let attribute = match self.collect_attribute(false /* top_level */) {
                 Ok(attribute) => attribute,
@@ -138,6 +138,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     Attribute::new()
                 },
             };
+            let statement_start = self.cursor;
 
             // FIXME: the same code is repeated multiple times...
             match self.peek3() {
@@ -159,14 +160,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 // `proc \() ..` is an expression, but `proc foo(...)` is an item.
@@ -181,14 +175,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Struct), .. }), _, _) => match self.parse_struct() {
@@ -198,14 +185,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Enum), .. }), _, _) => match self.parse_enum() {
@@ -215,14 +195,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Assert), .. }), _, _) => {
@@ -233,14 +206,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         Err(e) => {
                             errors.extend(e);
-
-                            if is_top_level {
-                                self.march_until_top_level_statement();
-                            }
-
-                            else {
-                                return Err(errors);
-                            }
+                            self.march_until_next_statement(statement_start);
                         },
                     }
                 },
@@ -250,8 +216,9 @@ impl<'t, 's> Tokens<'t, 's> {
                             kind: ErrorKind::TopLevelDo,
                             spans: span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         });
-                        return Err(errors);
+                        self.march_until_next_statement(statement_start);
                     }
 
                     else {
@@ -262,7 +229,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             Err(e) => {
                                 errors.extend(e);
-                                return Err(errors);
+                                self.march_until_next_statement(statement_start);
                             },
                         }
                     }
@@ -274,14 +241,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Mod), .. }), _, _) => match self.parse_module() {
@@ -291,14 +251,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Use), .. }), _, _) => match self.parse_use() {
@@ -320,14 +273,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     Err(e) => {
                         errors.extend(e);
-
-                        if is_top_level {
-                            self.march_until_top_level_statement();
-                        }
-
-                        else {
-                            return Err(errors);
-                        }
+                        self.march_until_next_statement(statement_start);
                     },
                 },
                 (Some(Token { kind: TokenKind::Keyword(Keyword::Ndet), .. }), Some(Token { kind: TokenKind::Ident(id), span }), _) => {
@@ -338,8 +284,9 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: span.simple_error(),
                         note: Some(format!("If you want to define a non-deterministic function, define it like `ndet fn {}`.", id.unintern_or_default(self.intermediate_dir))),
+                        suggestions: vec![],
                     });
-                    return Err(errors);
+                    self.march_until_next_statement(statement_start);
                 },
                 (Some(t), _, _) => {
                     let initial_token = t.clone();
@@ -368,8 +315,10 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                     spans: expr.error_span_wide().simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 });
-                                return Err(errors);
+                                self.march_until_next_statement(statement_start);
+                                continue;
                             }
 
                             value = Some(expr);
@@ -384,13 +333,13 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                     spans: initial_token.span.simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 });
+                                self.march_until_next_statement(statement_start);
+                                continue;
                             }
 
-                            else {
-                                errors.extend(e);
-                            }
-
+                            errors.extend(e);
                             return Err(errors);
                         },
                     }
@@ -405,11 +354,13 @@ impl<'t, 's> Tokens<'t, 's> {
             }
         }
 
-        if !is_top_level && value.is_none() {
+        // If there's a syntax error, the value might have been skipped.
+        if !is_top_level && value.is_none() && errors.is_empty() {
             errors.push(Error {
                 kind: ErrorKind::BlockWithoutValue,
                 spans: self.span_end.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
@@ -445,23 +396,48 @@ impl<'t, 's> Tokens<'t, 's> {
         }
     }
 
-    // If there's no top-level statement, it marches until the end
-    fn march_until_top_level_statement(&mut self) {
+    // It's called after a statement fails to parse. It skips the rest of the statement:
+    //   1. It skips to the next `;` and the `;` is also skipped.
+    //   2. If it finds a beginning of a statement (e.g. `fn` or `let`) before a `;`, it stops there.
+    //      It's likely that the failed statement is missing a `;`.
+    //   3. If there's nothing left, it stops at the end of the block. Since `self.tokens`
+    //      is the content of a group, that's the closing delimiter of the group.
+    //
+    // It doesn't look inside groups, so a `;` inside a block expression doesn't confuse it.
+    fn march_until_next_statement(&mut self, statement_start: usize) {
+        // If the statement failed at its first token, the token is not a beginning of a statement.
+        if self.cursor == statement_start {
+            self.cursor += 1;
+        }
+
+        // The failed statement has already consumed its `;`.
+        else if let Some(Token { kind: TokenKind::Punct(Punct::Semicolon), .. }) = self.peek_prev() {
+            return;
+        }
+
         loop {
-            match self.peek().map(|t| &t.kind) {
-                Some(TokenKind::Keyword(
+            match self.peek3() {
+                (Some(Token { kind: TokenKind::Punct(Punct::Semicolon), .. }), _, _) => {
+                    self.cursor += 1;
+                    return;
+                },
+                (Some(Token { kind: TokenKind::Keyword(
                     Keyword::Let | Keyword::Fn |
                     Keyword::Struct | Keyword::Enum |
-                    Keyword::Type | Keyword::Assert
-                )) => {
+                    Keyword::Type | Keyword::Assert |
+                    Keyword::Use | Keyword::Mod |
+                    Keyword::Pub | Keyword::Do
+                ), .. }), _, _) |
+                (Some(Token { kind: TokenKind::Keyword(Keyword::Proc), .. }), Some(Token { kind: TokenKind::Ident(_), .. }), _) |
+                (Some(Token { kind: TokenKind::Keyword(Keyword::Ndet), .. }), Some(Token { kind: TokenKind::Keyword(Keyword::Fn | Keyword::Proc), .. }), Some(Token { kind: TokenKind::Ident(_), .. })) |
+                (Some(Token { kind: TokenKind::DocComment { .. }, .. }), _, _) |
+                (Some(Token { kind: TokenKind::Group { delim: Delim::Decorator | Delim::ModuleDecorator, .. }, .. }), _, _) |
+                (None, _, _) => {
                     return;
                 },
-                Some(_) => {
+                (Some(_), _, _) => {
                     self.cursor += 1;
                 },
-                None => {
-                    return;
-                },
             }
         }
     }
@@ -491,6 +467,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: Some(String::from("You can't add a document for an expression.")),
+                suggestions: vec![],
             });
         }
 
@@ -505,6 +482,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             });
         }
     }
@@ -526,6 +504,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: Some(String::from("You can't decorate an expression.")),
+                suggestions: vec![],
             });
         }
 
@@ -540,6 +519,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             });
         }
     }
@@ -561,6 +541,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: Some(String::from("An expression cannot be public.")),
+                suggestions: vec![],
             });
         }
 
@@ -575,6 +556,7 @@ fn attribute_not_allowed(
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             });
         }
     }
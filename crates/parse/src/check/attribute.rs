@@ -92,6 +92,7 @@ fn check_decorator_args(args: &[DecoratorArg], intermediate_dir: &str) -> Result
                     }
                 ).collect(),
                 note: None,
+                suggestions: vec![],
             });
         }
     }
@@ -96,6 +96,7 @@ impl Block {
                     kind: ErrorKind::CannotDeclareInlineModule,
                     spans: module.keyword_span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 });
             }
 
@@ -175,6 +176,7 @@ impl Block {
                     },
                     spans,
                     note,
+                    suggestions: vec![],
                 });
             }
         }
@@ -59,6 +59,7 @@ impl Enum {
                         }
                     ).collect(),
                     note: None,
+                    suggestions: vec![],
                 });
             }
         }
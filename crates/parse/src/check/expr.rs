@@ -127,6 +127,7 @@ impl Expr {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         });
                     }
 
@@ -163,6 +164,7 @@ impl Expr {
                                     }
                                 ).collect(),
                                 note: None,
+                                suggestions: vec![],
                             });
                         }
                     }
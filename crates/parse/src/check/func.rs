@@ -46,6 +46,7 @@ impl Func {
                         },
                     ],
                     note: None,
+                    suggestions: vec![],
                 });
             }
 
@@ -87,6 +88,7 @@ impl Func {
                         }
                     ).collect(),
                     note: None,
+                    suggestions: vec![],
                 });
             }
         }
@@ -167,6 +169,7 @@ pub(crate) fn check_func_args(args: &[FuncArg], intermediate_dir: &str) -> Resul
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             });
         }
 
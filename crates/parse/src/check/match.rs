@@ -10,6 +10,7 @@ impl Match {
                 kind: ErrorKind::EmptyMatchStatement,
                 spans: self.keyword_span.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
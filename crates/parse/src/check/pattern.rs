@@ -45,6 +45,7 @@ impl Pattern {
                             }
                         ).collect(),
                         note: None,
+                        suggestions: vec![],
                     });
                 }
             }
@@ -81,6 +82,7 @@ impl Pattern {
                         kind: ErrorKind::InvalidRangePattern,
                         spans: self.error_span_wide().simple_error(),
                         note: Some(note.to_string()),
+                        suggestions: vec![],
                     }])
                 },
             },
@@ -96,6 +98,7 @@ impl Pattern {
                 } else {
                     None
                 },
+                suggestions: vec![],
             }]),
         }
     }
@@ -155,6 +158,7 @@ impl PatternKind {
                         kind: ErrorKind::InclusiveRangeWithNoEnd,
                         spans: op_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     });
                 }
 
@@ -235,6 +239,7 @@ impl PatternKind {
                             },
                         ],
                         note: Some(String::from("Names must be bound in all patterns.")),
+                        suggestions: vec![],
                     });
                 }
 
@@ -35,6 +35,7 @@ impl Struct {
                 kind: ErrorKind::StructWithoutField,
                 spans: self.name_span.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
@@ -70,6 +71,7 @@ impl Struct {
                         }
                     ).collect(),
                     note: None,
+                    suggestions: vec![],
                 });
             }
         }
@@ -80,6 +80,7 @@ impl Endec for Decorator {
         self.name_span.encode_impl(buffer);
         self.args.encode_impl(buffer);
         self.arg_group_span.encode_impl(buffer);
+        self.group_span.encode_impl(buffer);
     }
 
     fn decode_impl(buffer: &[u8], cursor: usize) -> Result<(Self, usize), DecodeError> {
@@ -87,6 +88,7 @@ impl Endec for Decorator {
         let (name_span, cursor) = Span::decode_impl(buffer, cursor)?;
         let (args, cursor) = Option::<Vec<DecoratorArg>>::decode_impl(buffer, cursor)?;
         let (arg_group_span, cursor) = Option::<Span>::decode_impl(buffer, cursor)?;
+        let (group_span, cursor) = Span::decode_impl(buffer, cursor)?;
 
         Ok((
             Decorator {
@@ -94,6 +96,7 @@ impl Endec for Decorator {
                 name_span,
                 args,
                 arg_group_span,
+                group_span,
             },
             cursor,
         ))
@@ -72,6 +72,18 @@ impl Endec for Field {
             Field::SelfAsScalar => {
                 buffer.push(6);
             },
+            Field::NicheDiscriminant { null_variant } => {
+                buffer.push(7);
+                null_variant.encode_impl(buffer);
+            },
+            Field::UnboxedDiscriminant => {
+                buffer.push(8);
+            },
+            Field::UnboxedPayload { variant, payload } => {
+                buffer.push(9);
+                variant.encode_impl(buffer);
+                payload.encode_impl(buffer);
+            },
         }
     }
 
@@ -109,7 +121,17 @@ impl Endec for Field {
             },
             Some(5) => Ok((Field::ListLength, cursor + 1)),
             Some(6) => Ok((Field::SelfAsScalar, cursor + 1)),
-            Some(n @ 7..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            Some(7) => {
+                let (null_variant, cursor) = usize::decode_impl(buffer, cursor + 1)?;
+                Ok((Field::NicheDiscriminant { null_variant }, cursor))
+            },
+            Some(8) => Ok((Field::UnboxedDiscriminant, cursor + 1)),
+            Some(9) => {
+                let (variant, cursor) = usize::decode_impl(buffer, cursor + 1)?;
+                let (payload, cursor) = usize::decode_impl(buffer, cursor)?;
+                Ok((Field::UnboxedPayload { variant, payload }, cursor))
+            },
+            Some(n @ 10..) => Err(DecodeError::InvalidEnumVariant(*n)),
             None => Err(DecodeError::UnexpectedEof),
         }
     }
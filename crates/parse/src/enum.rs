@@ -139,6 +139,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     }
@@ -178,6 +179,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         },
                                         spans: t.span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     }]);
                                 },
                             }
@@ -208,6 +210,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     }
@@ -220,6 +223,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
             }
@@ -223,10 +223,28 @@ impl<'t, 's> Tokens<'t, 's> {
         self.pratt_parse(0, try_struct_init)
     }
 
+    // If a group (e.g. the arguments of a call) has a syntax error, it continues parsing
+    // after the closing delimiter of the group, so that it can find more errors.
+    // The errors are collected in `recovered_errors`, and it returns the errors at the end.
     fn pratt_parse(
         &mut self,
         min_bp: u32,
         try_struct_init: bool,
+    ) -> Result<Expr, Vec<Error>> {
+        let mut recovered_errors = vec![];
+
+        match self.pratt_parse_worker(min_bp, try_struct_init, &mut recovered_errors) {
+            Ok(expr) if recovered_errors.is_empty() => Ok(expr),
+            Ok(_) => Err(recovered_errors),
+            Err(e) => Err([recovered_errors, e].concat()),
+        }
+    }
+
+    fn pratt_parse_worker(
+        &mut self,
+        min_bp: u32,
+        try_struct_init: bool,
+        recovered_errors: &mut Vec<Error>,
     ) -> Result<Expr, Vec<Error>> {
         let mut lhs = match self.peek3() {
             (Some(Token { kind: TokenKind::Punct(Punct::Dollar), span }), _, _) => {
@@ -258,6 +276,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: punct_span.simple_error(),
                             note: Some(format!("`{}` is not a prefix operator.", p.render_error())),
+                            suggestions: vec![],
                         }]);
                     },
                 }
@@ -288,6 +307,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     kind: ErrorKind::WildcardNotAllowed,
                     spans: span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (Some(Token { kind: TokenKind::Number(n), span }), _, _) => {
@@ -368,7 +388,15 @@ impl<'t, 's> Tokens<'t, 's> {
                 Delim::Parenthesis => {
                     let span = span.clone();
                     let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                    let exprs = tokens.parse_exprs()?;
+                    let exprs = match tokens.parse_exprs() {
+                        Ok(exprs) => exprs,
+
+                        // It becomes an empty tuple, which is just a placeholder.
+                        Err(e) => {
+                            recovered_errors.extend(e);
+                            vec![]
+                        },
+                    };
                     let mut is_tuple = exprs.len() != 1;
 
                     // `(a + b)` is just an expression, but `(a + b,)` is a tuple
@@ -395,7 +423,13 @@ impl<'t, 's> Tokens<'t, 's> {
                 Delim::Brace => {
                     let span = span.clone();
                     let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                    let block = tokens.parse_block(false /* top_level */, span)?;
+                    let block = match tokens.parse_block(false /* top_level */, span.clone()) {
+                        Ok(block) => block,
+                        Err(e) => {
+                            recovered_errors.extend(e);
+                            Block::dummy()
+                        },
+                    };
                     self.cursor += 1;
 
                     Expr::Block(Box::new(block))
@@ -403,7 +437,13 @@ impl<'t, 's> Tokens<'t, 's> {
                 Delim::Bracket => {
                     let span = span.clone();
                     let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                    let exprs = tokens.parse_exprs()?;
+                    let exprs = match tokens.parse_exprs() {
+                        Ok(exprs) => exprs,
+                        Err(e) => {
+                            recovered_errors.extend(e);
+                            vec![]
+                        },
+                    };
                     self.cursor += 1;
 
                     Expr::List {
@@ -416,6 +456,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         kind: ErrorKind::DecoratorNotAllowed,
                         spans: span.simple_error(),
                         note: Some(String::from("You cannot decorate an expression.")),
+                        suggestions: vec![],
                     }]);
                 },
             },
@@ -427,6 +468,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (None, _, _) => {
@@ -467,6 +509,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                             kind: ErrorKind::InclusiveRangeWithNoEnd,
                                             spans: punct_span.simple_error(),
                                             note: None,
+                                            suggestions: vec![],
                                         }]);
                                     }
 
@@ -645,6 +688,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                         Delim::Decorator | Delim::ModuleDecorator => {
@@ -652,6 +696,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 kind: ErrorKind::DecoratorNotAllowed,
                                 spans: span.simple_error(),
                                 note: Some(String::from("You cannot decorate an expression.")),
+                                suggestions: vec![],
                             }]);
                         },
                         // call
@@ -663,7 +708,13 @@ impl<'t, 's> Tokens<'t, 's> {
                             }
 
                             let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                            let args = tokens.parse_func_args()?;
+                            let args = match tokens.parse_func_args() {
+                                Ok(args) => args,
+                                Err(e) => {
+                                    recovered_errors.extend(e);
+                                    vec![]
+                                },
+                            };
                             self.cursor += 1;
                             lhs = Expr::Call {
                                 func: Box::new(lhs),
@@ -687,7 +738,13 @@ impl<'t, 's> Tokens<'t, 's> {
                                 Expr::Path(p) => {
                                     let constructor = p.clone();
                                     let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                                    let fields = tokens.parse_struct_initialization()?;
+                                    let fields = match tokens.parse_struct_initialization() {
+                                        Ok(fields) => fields,
+                                        Err(e) => {
+                                            recovered_errors.extend(e);
+                                            vec![]
+                                        },
+                                    };
                                     self.cursor += 1;
                                     lhs = Expr::StructInit {
                                         constructor,
@@ -710,7 +767,13 @@ impl<'t, 's> Tokens<'t, 's> {
                             }
 
                             let mut tokens = Tokens::new(tokens, span.end(), false, self.intermediate_dir);
-                            let rhs = tokens.parse_expr(true)?;
+                            let rhs = match tokens.parse_expr(true) {
+                                Ok(rhs) => rhs,
+                                Err(e) => {
+                                    recovered_errors.extend(e);
+                                    Expr::Tuple { elements: vec![], group_span: span.clone() }
+                                },
+                            };
 
                             // TODO: make sure that there's no remaining tokens
 
@@ -820,8 +883,21 @@ impl<'t, 's> Tokens<'t, 's> {
             return Ok(exprs);
         }
 
+        let mut errors = vec![];
+
         loop {
-            exprs.push(self.parse_expr(true)?);
+            match self.parse_expr(true) {
+                Ok(expr) => {
+                    exprs.push(expr);
+                },
+
+                // The elements are independent of each other, so it skips to the next
+                // comma and continues parsing, so that it can find more errors.
+                Err(e) => {
+                    errors.extend(e);
+                    self.march_until_comma();
+                },
+            }
 
             match self.peek2() {
                 (
@@ -841,19 +917,27 @@ impl<'t, 's> Tokens<'t, 's> {
                     break;
                 },
                 (Some(t), _) => {
-                    return Err(vec![Error {
+                    errors.push(Error {
                         kind: ErrorKind::UnexpectedToken {
                             expected: ErrorToken::Punct(Punct::Comma),
                             got: (&t.kind).into(),
                         },
                         spans: t.span.simple_error(),
                         note: None,
-                    }]);
+                        suggestions: vec![],
+                    });
+                    return Err(errors);
                 },
             }
         }
 
-        Ok(exprs)
+        if errors.is_empty() {
+            Ok(exprs)
+        }
+
+        else {
+            Err(errors)
+        }
     }
 }
 
@@ -11,6 +11,7 @@ impl Expr {
                 kind: ErrorKind::CannotBindName(name),
                 spans: name_span.simple_error(),
                 note: None,
+                suggestions: vec![],
             });
         }
 
@@ -39,6 +40,7 @@ impl Expr {
                 kind: ErrorKind::CannotEvaluateConst,
                 spans: pattern_kind.error_span_narrow().simple_error(),
                 note: None,
+                suggestions: vec![],
             }]),
             PatternKind::InfixOp { op, lhs, rhs, op_span, .. } => match (
                 Expr::from_pattern(lhs),
@@ -69,6 +69,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (None, _) => {
@@ -95,39 +96,26 @@ impl<'t, 's> Tokens<'t, 's> {
             _ => unreachable!(),
         };
         let mut param_tokens = Tokens::new(param_tokens_inner, param_tokens.span.end(), false, self.intermediate_dir);
-        let params = param_tokens.parse_func_params(true /* allow wildcard */)?;
+        let mut errors = vec![];
 
-        let type_annot = match self.peek() {
-            Some(Token { kind: TokenKind::Punct(Punct::ReturnType), ..}) => {
-                self.cursor += 1;
-                Some(self.parse_type()?)
+        // If the parameters have a syntax error, it continues parsing after the closing parenthesis
+        // so that it can find more errors in the body.
+        let params = match param_tokens.parse_func_params(true /* allow wildcard */) {
+            Ok(params) => params,
+            Err(e) => {
+                errors.extend(e);
+                vec![]
             },
-            _ => None,
         };
 
-        let value = match self.peek() {
-            Some(Token { kind: TokenKind::Punct(Punct::Assign), .. }) => {
-                self.match_and_pop(TokenKind::Punct(Punct::Assign))?;
-                let value = Some(self.parse_expr(true)?);
-                self.match_and_pop(TokenKind::Punct(Punct::Semicolon))?;
-                value
+        let (type_annot, value) = match self.parse_func_type_annot_and_value() {
+            Ok((type_annot, value)) if errors.is_empty() => (type_annot, value),
+            Ok(_) => {
+                return Err(errors);
             },
-            Some(Token { kind: TokenKind::Punct(Punct::Semicolon), .. }) => {
-                self.match_and_pop(TokenKind::Punct(Punct::Semicolon))?;
-                None
-            },
-            Some(t) => {
-                return Err(vec![Error {
-                    kind: ErrorKind::UnexpectedToken {
-                        expected: ErrorToken::AssignOrSemicolon,
-                        got: (&t.kind).into(),
-                    },
-                    spans: t.span.simple_error(),
-                    note: None,
-                }]);
-            },
-            None => {
-                return Err(vec![self.unexpected_end(ErrorToken::AssignOrSemicolon)]);
+            Err(e) => {
+                errors.extend(e);
+                return Err(errors);
             },
         };
 
@@ -186,6 +174,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                 ],
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         }
 
@@ -216,6 +205,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                 ],
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         }
 
@@ -251,6 +241,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: t.span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         }]);
                     },
                 }
@@ -260,6 +251,46 @@ impl<'t, 's> Tokens<'t, 's> {
         Ok(params)
     }
 
+    // `-> Int = x + y;`
+    fn parse_func_type_annot_and_value(&mut self) -> Result<(Option<Type>, Option<Expr>), Vec<Error>> {
+        let type_annot = match self.peek() {
+            Some(Token { kind: TokenKind::Punct(Punct::ReturnType), ..}) => {
+                self.cursor += 1;
+                Some(self.parse_type()?)
+            },
+            _ => None,
+        };
+
+        let value = match self.peek() {
+            Some(Token { kind: TokenKind::Punct(Punct::Assign), .. }) => {
+                self.match_and_pop(TokenKind::Punct(Punct::Assign))?;
+                let value = Some(self.parse_expr(true)?);
+                self.match_and_pop(TokenKind::Punct(Punct::Semicolon))?;
+                value
+            },
+            Some(Token { kind: TokenKind::Punct(Punct::Semicolon), .. }) => {
+                self.match_and_pop(TokenKind::Punct(Punct::Semicolon))?;
+                None
+            },
+            Some(t) => {
+                return Err(vec![Error {
+                    kind: ErrorKind::UnexpectedToken {
+                        expected: ErrorToken::AssignOrSemicolon,
+                        got: (&t.kind).into(),
+                    },
+                    spans: t.span.simple_error(),
+                    note: None,
+                    suggestions: vec![],
+                }]);
+            },
+            None => {
+                return Err(vec![self.unexpected_end(ErrorToken::AssignOrSemicolon)]);
+            },
+        };
+
+        Ok((type_annot, value))
+    }
+
     // (3, 4, x = 4, y = 5)
     pub fn parse_func_args(&mut self) -> Result<Vec<FuncArg>, Vec<Error>> {
         let mut func_args = vec![];
@@ -268,6 +299,8 @@ impl<'t, 's> Tokens<'t, 's> {
             return Ok(func_args);
         }
 
+        let mut errors = vec![];
+
         loop {
             let keyword = match self.peek2() {
                 (
@@ -281,30 +314,47 @@ impl<'t, 's> Tokens<'t, 's> {
                 },
                 _ => None,
             };
-            let arg = self.parse_expr(true)?;
-            func_args.push(FuncArg { keyword, arg });
+
+            match self.parse_expr(true) {
+                Ok(arg) => {
+                    func_args.push(FuncArg { keyword, arg });
+                },
+
+                // It skips the argument and continues parsing, so that it can find more errors.
+                Err(e) => {
+                    errors.extend(e);
+                    self.march_until_comma();
+                },
+            }
 
             match self.peek2() {
                 (Some(Token { kind: TokenKind::Punct(Punct::Comma), .. }), Some(_)) => {
                     self.cursor += 1;
                 },
-                (Some(Token { kind: TokenKind::Punct(Punct::Comma), .. }), None) => {
-                    return Ok(func_args);
+                (Some(Token { kind: TokenKind::Punct(Punct::Comma), .. }), None) | (None, _) => {
+                    break;
                 },
                 (Some(t), _) => {
-                    return Err(vec![Error {
+                    errors.push(Error {
                         kind: ErrorKind::UnexpectedToken {
                             expected: ErrorToken::Punct(Punct::Comma),
                             got: (&t.kind).into(),
                         },
                         spans: t.span.simple_error(),
                         note: None,
-                    }]);
-                },
-                (None, _) => {
-                    return Ok(func_args);
+                        suggestions: vec![],
+                    });
+                    return Err(errors);
                 },
             }
         }
+
+        if errors.is_empty() {
+            Ok(func_args)
+        }
+
+        else {
+            Err(errors)
+        }
     }
 }
@@ -60,6 +60,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t1.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (None, _) => {
@@ -113,6 +114,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t2.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (
@@ -140,6 +142,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                     ],
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (None, _) => {
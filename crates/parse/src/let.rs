@@ -115,6 +115,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                 ],
                 note: None,
+                suggestions: vec![],
             }]),
             1 => Ok(vec![Let {
                 keyword_span: keyword_span.clone(),
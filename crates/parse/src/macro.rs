@@ -119,6 +119,7 @@ pub fn try_parse_macro(
             kind: ErrorKind::UndefinedMacro(id),
             spans: macro_span.simple_error(),
             note: None,
+            suggestions: vec![],
         }]),
     }
 }
@@ -151,6 +152,7 @@ fn parse_string(tokens: Tokens) -> Result<InternedString, Vec<Error>> {
                 },
             ],
             note: None,
+            suggestions: vec![],
         }]),
         (Some(Token { kind: TokenKind::String { .. }, .. }), Some(t)) => Err(vec![Error {
             kind: ErrorKind::UnexpectedToken {
@@ -159,6 +161,7 @@ fn parse_string(tokens: Tokens) -> Result<InternedString, Vec<Error>> {
             },
             spans: t.span.simple_error(),
             note: None,
+            suggestions: vec![],
         }]),
         (Some(t), _) => Err(vec![Error {
             kind: ErrorKind::UnexpectedToken {
@@ -167,6 +170,7 @@ fn parse_string(tokens: Tokens) -> Result<InternedString, Vec<Error>> {
             },
             spans: t.span.simple_error(),
             note: None,
+            suggestions: vec![],
         }]),
         (None, _) => Err(vec![tokens.unexpected_end(ErrorToken::String)]),
     }
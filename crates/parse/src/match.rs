@@ -93,6 +93,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 (None, _) => {
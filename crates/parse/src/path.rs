@@ -96,6 +96,20 @@ pub enum Field {
     // `x.self_as_scalar` is `transmute.<_, Scalar>(x)`.
     // We need this field for `Field::EnumDiscriminant` of `EnumRepr::Scalar`.
     SelfAsScalar,
+
+    // `Field::EnumDiscriminant` of `EnumRepr::Niche`.
+    // It's `null_variant` if the value is 0, and the other variant's index otherwise.
+    NicheDiscriminant { null_variant: usize },
+
+    // `Field::EnumDiscriminant` of `EnumRepr::Unboxed`. It's always 0.
+    UnboxedDiscriminant,
+
+    // `Field::EnumPayload` of `EnumRepr::Niche` and `EnumRepr::Unboxed`.
+    // The payload is the value itself, but it keeps `variant` and `payload` for the type-solver.
+    UnboxedPayload {
+        variant: usize,
+        payload: usize,
+    },
 }
 
 impl Field {
@@ -107,7 +121,10 @@ impl Field {
             Field::EnumDiscriminant |
             Field::EnumPayload { .. } |
             Field::ListLength |
-            Field::SelfAsScalar => None,
+            Field::SelfAsScalar |
+            Field::NicheDiscriminant { .. } |
+            Field::UnboxedDiscriminant |
+            Field::UnboxedPayload { .. } => None,
         }
     }
 
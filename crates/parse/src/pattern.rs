@@ -163,6 +163,7 @@ impl Pattern {
                 kind: ErrorKind::CannotBindName(name),
                 spans: name_span.simple_error(),
                 note: Some(String::from("I see what you're trying to do, and it's perfectly valid, but due to the limitations of the compiler, you cannot bind a name. A `++` pattern is lowered to a single long list, and there's no way to bind a name to a part of a list.")),
+                suggestions: vec![],
             });
         }
 
@@ -222,6 +223,7 @@ impl Pattern {
                         &format!("This cannot be an {} of `++`.", if is_lhs { "lhs" } else { "rhs" }),
                     ),
                     note: None,
+                    suggestions: vec![],
                 });
                 return Err(errors);
             },
@@ -514,6 +516,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         }]);
                     },
                     Delim::Parenthesis | Delim::Bracket => {},
@@ -591,6 +594,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             (None, _) => {
@@ -642,6 +646,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                 },
                                             ],
                                             note: None,
+                                            suggestions: vec![],
                                         });
                                     }
                                 },
@@ -653,6 +658,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         },
                                         spans: op_span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     }]);
                                 },
                             }
@@ -678,6 +684,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                             },
                                         ],
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                 },
                                 Pattern { kind: PatternKind::Path(path), .. } => {
@@ -700,6 +707,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                             },
                                         ],
                                         note: None,
+                                        suggestions: vec![],
                                     });
                                 },
                                 Pattern { kind: PatternKind::InfixOp { .. }, .. } => {
@@ -708,6 +716,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         kind: ErrorKind::CannotBindName(name),
                                         spans: name_span.simple_error(),
                                         note: Some(String::from("You cannot bind a name to a result of an infix operator. All infix operators in patterns are evaluated at compile time, and their intermediate results are gone.")),
+                                        suggestions: vec![],
                                     });
                                 },
                                 _ => {
@@ -821,6 +830,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                     },
                                                 ],
                                                 note: None,
+                                                suggestions: vec![],
                                             }]);
                                         },
                                         (Some(rest), None) => Some(rest),
@@ -865,6 +875,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                             kind: ErrorKind::UnsupportedInfixOpInPattern(op),
                                             spans: op_span.simple_error(),
                                             note: None,
+                                            suggestions: vec![],
                                         }]);
                                     },
                                 };
@@ -924,6 +935,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                         },
                                                     ],
                                                     note: None,
+                                                    suggestions: vec![],
                                                 }]);
                                             },
                                         }
@@ -963,6 +975,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                 },
                                             ],
                                             note: None,
+                                            suggestions: vec![],
                                         }]);
                                     },
                                     _ => todo!(),  // throw nice error message
@@ -1038,6 +1051,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         },
                                     ],
                                     note: None,
+                                    suggestions: vec![],
                                 }]);
                             },
                         },
@@ -1055,6 +1069,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     };
@@ -1102,6 +1117,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     }
@@ -1119,6 +1135,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: t.span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         }]);
                     }
                 },
@@ -1177,6 +1194,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: dot_dot_span.simple_error(),
                             note: Some(String::from("You cannot use a rest pattern (`..`) here. If you intended a range without ends, that's still invalid. A range must have at least one end, otherwise, just use a wildcard pattern.")),
+                            suggestions: vec![],
                         }]);
                     }
 
@@ -1196,6 +1214,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         }]);
                     }
 
@@ -1227,6 +1246,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     }
@@ -1275,6 +1295,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: dollar_span.simple_error(),
                                 note: Some(String::from("There should be no `$` before a name of a field, unless you're using the shorthand syntax.")),
+                                suggestions: vec![],
                             }]);
                         },
                         Some(Token { kind: TokenKind::Punct(Punct::Comma), .. }) | None => {
@@ -1289,6 +1310,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                     }
@@ -1324,6 +1346,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: span.simple_error(),
                         note: Some(String::from("In order to use the shorthand syntax, put a `$` before the field name.")),
+                        suggestions: vec![],
                     }]);
                 },
                 (Some(Token { kind: TokenKind::Punct(Punct::DotDot), span }), _) => {
@@ -1343,6 +1366,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                             ],
                             note: None,
+                            suggestions: vec![],
                         }]);
                     }
 
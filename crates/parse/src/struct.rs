@@ -108,6 +108,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 (None, _) => {
@@ -132,6 +133,7 @@ impl<'t, 's> Tokens<'t, 's> {
                 kind: ErrorKind::AmbiguousCurlyBraces,
                 spans: span.simple_error(),
                 note: None,
+                suggestions: vec![],
             }]),
             _ => Ok(()),
         }
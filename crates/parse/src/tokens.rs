@@ -1,7 +1,7 @@
 use sodigy_error::{Error, ErrorKind, ErrorToken};
 use sodigy_span::Span;
 use sodigy_string::{InternedString, intern_string};
-use sodigy_token::{Token, TokenKind};
+use sodigy_token::{Punct, Token, TokenKind};
 
 pub struct Tokens<'t, 's> {
     pub(crate) tokens: &'t [Token],
@@ -50,6 +50,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     },
                     spans: t.span.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
             None => {
@@ -70,6 +71,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         kind: ErrorKind::WildcardNotAllowed,
                         spans: span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }])
                 }
             },
@@ -85,6 +87,7 @@ impl<'t, 's> Tokens<'t, 's> {
                 },
                 spans: t.span.simple_error(),
                 note: None,
+                suggestions: vec![],
             }]),
             None => Err(vec![self.unexpected_end(ErrorToken::Ident)]),
         }
@@ -101,6 +104,7 @@ impl<'t, 's> Tokens<'t, 's> {
             kind,
             spans: self.span_end.simple_error(),
             note: None,
+            suggestions: vec![],
         }
     }
 
@@ -113,6 +117,7 @@ impl<'t, 's> Tokens<'t, 's> {
                 },
                 spans: t.span.simple_error(),
                 note: None,
+                suggestions: vec![],
             }]),
             None => Ok(()),
         }
@@ -166,6 +171,14 @@ impl<'t, 's> Tokens<'t, 's> {
     pub fn enumerate_forward(&self) -> impl Iterator<Item=(usize, &Token)> {
         EnumerateForward::new(self)
     }
+
+    /// It's used to recover from a syntax error in a comma-separated list.
+    /// It stops at the next comma (without consuming it) or at the end.
+    pub(crate) fn march_until_comma(&mut self) {
+        while let Some(token) = self.peek() && !matches!(token.kind, TokenKind::Punct(Punct::Comma)) {
+            self.cursor += 1;
+        }
+    }
 }
 
 struct EnumerateForward<'t> {
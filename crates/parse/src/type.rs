@@ -54,6 +54,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 (None, _) => {
@@ -207,6 +208,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                         (None, _) => {
@@ -314,6 +316,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: unexpected_type_annot.error_span_wide().simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         }
 
@@ -333,6 +336,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                     spans: group_span.simple_error(),
                                     note: Some(String::from("Please specify the type of the elements of the list. If you don't want to specify, write `[_]`.")),
+                                    suggestions: vec![],
                                 }])
                             },
                         }
@@ -344,6 +348,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: group_span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]),
                 };
 
@@ -367,6 +372,7 @@ impl<'t, 's> Tokens<'t, 's> {
                 },
                 spans: t.span.simple_error(),
                 note: None,
+                suggestions: vec![],
             }]),
             (None, _) => Err(vec![self.unexpected_end(ErrorToken::TypeAnnot)]),
         }
@@ -427,6 +433,7 @@ impl<'t, 's> Tokens<'t, 's> {
                     kind: ErrorKind::AmbiguousAngleBrackets,
                     spans: span_end.simple_error(),
                     note: None,
+                    suggestions: vec![],
                 }]);
             },
         }
@@ -471,6 +478,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 (None, _) => {
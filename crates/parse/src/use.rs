@@ -98,6 +98,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                     },
                                                     spans: span.simple_error(),
                                                     note: Some(String::from("If you want to import multiple names, use another `use` statement.")),
+                                                    suggestions: vec![],
                                                 }]);
                                             }
                                         },
@@ -112,6 +113,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                     },
                                                     spans: span.simple_error(),
                                                     note: None,
+                                                    suggestions: vec![],
                                                 }]);
                                             }
 
@@ -128,6 +130,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                                 },
                                                 spans: span.simple_error(),
                                                 note: None,
+                                                suggestions: vec![],
                                             }]);
                                         },
                                     },
@@ -140,6 +143,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                             },
                                             spans: t.span.simple_error(),
                                             note: None,
+                                            suggestions: vec![],
                                         }]);
                                     },
                                     // `use a.{b, c.{d, e}}` (valid)
@@ -157,6 +161,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                     spans: t.span.simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 }]);
                             },
                             // `use a.` (invalid)
@@ -190,6 +195,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: span.simple_error(),
                                 note: Some(String::from("If you want to import multiple names, use another `use` statement.")),
+                                suggestions: vec![],
                             }]);
                         }
                     },
@@ -204,6 +210,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         }
 
@@ -229,6 +236,7 @@ impl<'t, 's> Tokens<'t, 's> {
                             },
                             spans: span.simple_error(),
                             note: None,
+                            suggestions: vec![],
                         }]);
                     },
                 },
@@ -267,6 +275,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         },
                                         spans: span.simple_error(),
                                         note: Some(String::from("If you want to import multiple names, use another `use` statement.")),
+                                        suggestions: vec![],
                                     }]);
                                 }
                             },
@@ -281,6 +290,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                         },
                                         spans: span.simple_error(),
                                         note: None,
+                                        suggestions: vec![],
                                     }]);
                                 }
 
@@ -297,6 +307,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                     },
                                     spans: span.simple_error(),
                                     note: None,
+                                    suggestions: vec![],
                                 }]);
                             },
                         },
@@ -309,6 +320,7 @@ impl<'t, 's> Tokens<'t, 's> {
                                 },
                                 spans: t.span.simple_error(),
                                 note: None,
+                                suggestions: vec![],
                             }]);
                         },
                         // `use a.{b as c}` (valid)
@@ -327,6 +339,7 @@ impl<'t, 's> Tokens<'t, 's> {
                         },
                         spans: t.span.simple_error(),
                         note: None,
+                        suggestions: vec![],
                     }]);
                 },
                 // `use a.{b, c}` (valid)
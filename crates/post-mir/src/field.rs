@@ -62,14 +62,23 @@ pub(crate) fn lower_fields(lhs: &Expr, fields: &mut Vec<Field>, session: &mut Se
                             EnumRepr::Compound => {
                                 *field = Field::Index(0);
                             },
-                            EnumRepr::Niche => todo!(),
+                            EnumRepr::Niche { null_variant } => {
+                                *field = Field::NicheDiscriminant { null_variant };
+                            },
+                            EnumRepr::Unboxed => {
+                                *field = Field::UnboxedDiscriminant;
+                            },
                         },
 
-                        // TODO: respect niche optimization
-                        Field::EnumPayload { .. } => {
+                        Field::EnumPayload { variant, payload } => match enum_shape.representation {
                             // We're not gonna lower this:
                             //   1. We can use the payload index like `Field::Index`.
                             //   2. We don't need the variant index at runtime, but we need that for type-solving.
+                            EnumRepr::Compound => {},
+                            EnumRepr::Niche { .. } | EnumRepr::Unboxed => {
+                                *field = Field::UnboxedPayload { variant: *variant, payload: *payload };
+                            },
+                            EnumRepr::Scalar => unreachable!(),
                         },
 
                         // Is this reachable?
@@ -156,7 +156,7 @@
 
 use crate::Session;
 use sodigy_endec::IndentedLines;
-use sodigy_error::{Error, ErrorKind, Warning, WarningKind};
+use sodigy_error::{Applicability, Error, ErrorKind, Suggestion, Warning, WarningKind};
 use sodigy_hir::{LetOrigin, Pattern, PatternKind};
 use sodigy_mir::{
     ArmSplit,
@@ -184,6 +184,7 @@ mod dump;
 mod matrix;
 mod range;
 mod tree;
+mod witness;
 
 pub use dump::MatchDump;
 use matrix::{
@@ -206,10 +207,11 @@ use tree::{
     ExprConstructor,
     build_tree,
 };
+use witness::get_witnesses;
 
 // This is like `sodigy_parse::Field`, but for pattern analysis.
 // e.g. with `PatternKind` and `PatternField`, you can get a `PatternConstructor`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PatternField {
     Constructor,
     Index(i64),
@@ -267,8 +269,10 @@ pub(crate) fn lower_match(match_expr: &mut Match, session: &mut Session) -> Resu
 
     check_unreachable_and_exhaustiveness(
         &tree,
+        &scrutinee_type,
         &borrowed_arms,
         match_expr.keyword_span.clone(),
+        match_expr.arms.last().map(|arm| arm.value.error_span_wide()),
         extra_arm_id,
         match_expr.lowered_from_let,
         session,
@@ -697,8 +701,12 @@ pub enum NameBindingOffset {
 // If the extra arm is reachable, the match expression is not exhaustive.
 fn check_unreachable_and_exhaustiveness(
     tree: &DecisionTree,
+    scrutinee_type: &Type,
     arms: &[(usize, &MatchArm)],
     keyword_span: Span,
+
+    // span of the value of the last arm, which is where a new arm goes
+    last_arm_value_span: Option<Span>,
     extra_arm_id: usize,
     is_from_let_pattern: bool,
     session: &mut Session,
@@ -738,27 +746,62 @@ fn check_unreachable_and_exhaustiveness(
                     kind: WarningKind::UnreachableMatchArm,
                     spans: warning_spans,
                     note: None,
+                    suggestions: vec![],
                 });
             }
         }
     }
 
-    // TODO: we can calculate the set of unreachable values
     if reachable_arms.contains(&extra_arm_id) {
+        let witnesses = get_witnesses(tree, scrutinee_type, extra_arm_id, session);
+        let note = render_witnesses_note(&witnesses);
+
         if is_from_let_pattern {
             session.errors.push(Error {
                 kind: ErrorKind::RefutableLetPattern,
                 // TODO: it makes more sense to underline the pattern
                 spans: keyword_span.simple_error(),
-                note: None,
+                note,
+                suggestions: vec![],
             });
         }
 
         else {
+            // Arms are separated by commas, and a trailing comma is optional, so
+            // the new arms start with a comma and don't end with a comma.
+            // If there are too many witnesses, a wildcard is better than a wall of arms.
+            let (new_arms, message) = match witnesses.len() {
+                1..=3 => (
+                    witnesses.iter().map(
+                        |witness| format!(",\n{witness} => std.panic(\"TODO\")")
+                    ).collect::<Vec<_>>().concat(),
+                    format!(
+                        "Add {} that {} the missing pattern{}.",
+                        if witnesses.len() == 1 { "an arm" } else { "arms" },
+                        if witnesses.len() == 1 { "handles" } else { "handle" },
+                        if witnesses.len() == 1 { "" } else { "s" },
+                    ),
+                ),
+                _ => (
+                    String::from(",\n_ => std.panic(\"TODO\")"),
+                    String::from("Add an arm that handles the other cases: `_ => std.panic(\"TODO\")`."),
+                ),
+            };
+            let suggestions = match last_arm_value_span {
+                Some(span) => vec![Suggestion::insert_after(
+                    &span,
+                    new_arms,
+                    Applicability::HasPlaceholders,
+                    message,
+                )],
+                None => vec![],
+            };
+
             session.errors.push(Error {
                 kind: ErrorKind::NonExhaustiveArms,
                 spans: keyword_span.simple_error(),
-                note: None,
+                note,
+                suggestions,
             });
         }
 
@@ -770,6 +813,26 @@ fn check_unreachable_and_exhaustiveness(
     }
 }
 
+// `Patterns `None`, `Some(0)` and 3 more are not covered.`
+fn render_witnesses_note(witnesses: &[String]) -> Option<String> {
+    let rendered = witnesses.iter().take(3).map(|witness| format!("`{witness}`")).collect::<Vec<_>>();
+
+    match witnesses.len() {
+        0 => None,
+        1 => Some(format!("Pattern {} is not covered.", rendered[0])),
+        2 | 3 => Some(format!(
+            "Patterns {} and {} are not covered.",
+            rendered[..(rendered.len() - 1)].join(", "),
+            rendered.last().unwrap(),
+        )),
+        n => Some(format!(
+            "Patterns {} and {} more are not covered.",
+            rendered.join(", "),
+            n - 3,
+        )),
+    }
+}
+
 fn check_arm_reachability(
     tree: &DecisionTree,
     hidden_by: &mut HashMap<usize, HashSet<usize>>,
@@ -0,0 +1,475 @@
+use super::{
+    DecisionTree,
+    DecisionTreeNode,
+    ExprConstructor,
+    LiteralType,
+    PatternField,
+    Range,
+};
+use crate::Session;
+use sodigy_hir::{EnumVariantFields, PRELUDES};
+use sodigy_mir::{Type, get_def_span_from_id};
+use sodigy_number::InternedNumber;
+use std::cmp::Ordering;
+
+// A cartesian product of the witnesses can be very big (e.g. a tuple of 5 enums).
+// We don't need that many witnesses to tell the user what's missing.
+const MAX_WITNESSES: usize = 64;
+
+/// The extra arm (`_ => { .. }`) that the compiler inserted is reachable if the match is
+/// not exhaustive. Each path from the root of the decision tree to the extra arm is a set
+/// of values that no arm covers. It converts the paths to patterns, so that the user can
+/// see what's missing.
+///
+/// ```ignore
+/// match x {  // x: Option<Result<Int, String>>
+///     None => 0,
+///     Some(Ok(0..10)) => 1,
+/// }
+/// ```
+///
+/// The witnesses are `Some(Ok(..0))`, `Some(Ok(10..))` and `Some(Error(_))`.
+pub(crate) fn get_witnesses(
+    tree: &DecisionTree,
+    scrutinee_type: &Type,
+    extra_arm_id: usize,
+    session: &Session,
+) -> Vec<String> {
+    let mut paths = vec![];
+    collect_uncovered_paths(tree, extra_arm_id, &mut vec![], &mut paths);
+    let mut witnesses: Vec<String> = vec![];
+
+    for path in paths.iter() {
+        for witness in render_witness(scrutinee_type, &[], path, session) {
+            if !witnesses.contains(&witness) {
+                witnesses.push(witness);
+            }
+        }
+
+        if witnesses.len() >= MAX_WITNESSES {
+            witnesses.truncate(MAX_WITNESSES);
+            break;
+        }
+    }
+
+    witnesses
+}
+
+// `scrutinee.field` satisfies the condition.
+#[derive(Clone, Debug)]
+enum WitnessCondition {
+    In(Vec<Range>),
+
+    // The wildcard branch of an enum: it's none of the other branches.
+    NotIn(Vec<Range>),
+}
+
+type Path = Vec<(Vec<PatternField>, WitnessCondition)>;
+
+fn collect_uncovered_paths(
+    tree: &DecisionTree,
+    extra_arm_id: usize,
+    curr_path: &mut Path,
+    paths: &mut Vec<Path>,
+) {
+    for branch in tree.branches.iter() {
+        let condition = match (&tree.field, &branch.condition) {
+            (None, _) => None,
+            (Some(field), ExprConstructor::Wildcard) => {
+                let siblings = tree.branches.iter().flat_map(
+                    |branch| flatten_condition(&branch.condition)
+                ).collect::<Vec<_>>();
+
+                if siblings.is_empty() {
+                    None
+                } else {
+                    Some((field.clone(), WitnessCondition::NotIn(siblings)))
+                }
+            },
+            (Some(field), condition) => Some((field.clone(), WitnessCondition::In(flatten_condition(condition)))),
+        };
+        let has_condition = condition.is_some();
+
+        if let Some(condition) = condition {
+            curr_path.push(condition);
+        }
+
+        match &branch.node {
+            DecisionTreeNode::Tree(tree) => {
+                collect_uncovered_paths(tree, extra_arm_id, curr_path, paths);
+            },
+            DecisionTreeNode::Leaf { matched, .. } => {
+                if *matched == extra_arm_id {
+                    paths.push(curr_path.clone());
+                }
+            },
+        }
+
+        if has_condition {
+            curr_path.pop();
+        }
+    }
+}
+
+fn flatten_condition(condition: &ExprConstructor) -> Vec<Range> {
+    match condition {
+        ExprConstructor::Range(r) => vec![*r],
+        ExprConstructor::Or(cs) => cs.iter().flat_map(flatten_condition).collect(),
+        ExprConstructor::Wildcard => vec![],
+    }
+}
+
+fn get_condition<'p>(path: &'p Path, field: &[PatternField]) -> Option<&'p WitnessCondition> {
+    path.iter().find(|(f, _)| f == field).map(|(_, condition)| condition)
+}
+
+fn with_field(prefix: &[PatternField], fields: &[PatternField]) -> Vec<PatternField> {
+    [prefix.to_vec(), fields.to_vec()].concat()
+}
+
+// It returns alternatives: any of the patterns is not covered.
+// It mirrors `get_matrix`: a field of `scrutinee` is constrained only if `get_matrix` generated
+// a row for the field.
+fn render_witness(
+    r#type: &Type,
+    prefix: &[PatternField],
+    path: &Path,
+    session: &Session,
+) -> Vec<String> {
+    // Nothing is known about this value.
+    if !path.iter().any(|(field, _)| field.starts_with(prefix)) {
+        return vec![String::from("_")];
+    }
+
+    let Type::Data { constructor_def_span, args, .. } = r#type else {
+        return vec![String::from("_")];
+    };
+    let real_def_span = get_def_span_from_id(*constructor_def_span, args);
+    let literal_type = [
+        ("type.Int", LiteralType::Int),
+        ("type.Char", LiteralType::Char),
+        ("type.Byte", LiteralType::Byte),
+        ("type.Number", LiteralType::Number),
+        ("type.Scalar", LiteralType::Scalar),
+    ].into_iter().find(
+        |(lang_item, _)| *constructor_def_span == session.get_lang_item_span_id(lang_item)
+    ).map(|(_, literal_type)| literal_type);
+
+    if let Some(literal_type) = literal_type {
+        match get_condition(path, &with_field(prefix, &[PatternField::Constructor])) {
+            Some(WitnessCondition::In(ranges)) => ranges.iter().map(
+                |range| render_range(range, literal_type, session)
+            ).collect(),
+            _ => vec![String::from("_")],
+        }
+    }
+
+    else if *constructor_def_span == session.get_lang_item_span_id("type.Tuple") {
+        let args = args.as_ref().unwrap();
+        let elements = args.iter().enumerate().map(
+            |(i, arg)| render_witness(arg, &with_field(prefix, &[PatternField::Index(i as i64)]), path, session)
+        ).collect::<Vec<_>>();
+
+        cartesian_product(&elements).into_iter().map(
+            |elements| match elements.len() {
+                1 => format!("({},)", elements[0]),
+                _ => format!("({})", elements.join(", ")),
+            }
+        ).collect()
+    }
+
+    else if *constructor_def_span == session.get_lang_item_span_id("type.List") {
+        render_list_witness(&args.as_ref().unwrap()[0], prefix, path, session)
+    }
+
+    else if let Some(struct_shape) = session.global_context.struct_shapes.unwrap().get(&real_def_span) {
+        let mut names = vec![];
+        let mut fields = vec![];
+
+        for (index, field) in struct_shape.fields.iter().enumerate() {
+            let field_prefix = with_field(prefix, &[PatternField::StructField { index, name: field.name }]);
+
+            if !path.iter().any(|(f, _)| f.starts_with(&field_prefix)) {
+                continue;
+            }
+
+            let field_type = session.global_context.get_type(&field.name_span).unwrap();
+            names.push(field.name.unintern_or_default(&session.intermediate_dir));
+            fields.push(render_witness(&field_type, &field_prefix, path, session));
+        }
+
+        let struct_name = struct_shape.name.unintern_or_default(&session.intermediate_dir);
+
+        cartesian_product(&fields).into_iter().map(
+            |fields| format!(
+                "{struct_name} {{ {}.. }}",
+                names.iter().zip(fields.iter()).map(
+                    |(name, field)| format!("{name}: {field}, ")
+                ).collect::<Vec<_>>().concat(),
+            )
+        ).collect()
+    }
+
+    else if let Some(enum_shape) = session.global_context.enum_shapes.unwrap().get(&real_def_span) {
+        let variants = (0..enum_shape.variants.len()).filter(
+            |variant| match get_condition(path, &with_field(prefix, &[PatternField::EnumDiscriminant])) {
+                Some(WitnessCondition::In(ranges)) => ranges.iter().any(|range| range_contains(range, *variant as u32, session)),
+                Some(WitnessCondition::NotIn(ranges)) => !ranges.iter().any(|range| range_contains(range, *variant as u32, session)),
+                None => true,
+            }
+        ).collect::<Vec<_>>();
+
+        // `Option.Some` can be written as `Some`.
+        let enum_name = enum_shape.name.unintern_or_default(&session.intermediate_dir);
+        let is_std = real_def_span.file().map(|file| file.is_std()).unwrap_or(false);
+        let mut result = vec![];
+
+        for variant_index in variants.into_iter() {
+            let variant = &enum_shape.variants[variant_index];
+            let variant_name = variant.name.unintern_or_default(&session.intermediate_dir);
+            let variant_name = if is_std && PRELUDES.contains(&variant_name.as_bytes()) {
+                variant_name
+            } else {
+                format!("{enum_name}.{variant_name}")
+            };
+
+            match &variant.fields {
+                EnumVariantFields::None => {
+                    result.push(variant_name);
+                },
+                EnumVariantFields::Tuple(_) => match session.global_context.get_type(&variant.name_span) {
+                    Some(Type::Func { params, .. }) => {
+                        let payloads = params.iter().enumerate().map(
+                            |(i, param)| render_witness(
+                                param,
+                                &with_field(prefix, &[
+                                    PatternField::EnumPayload,
+                                    PatternField::EnumPayloadIndex { variant: variant_index, payload: i },
+                                ]),
+                                path,
+                                session,
+                            )
+                        ).collect::<Vec<_>>();
+
+                        for payloads in cartesian_product(&payloads).into_iter() {
+                            result.push(format!("{variant_name}({})", payloads.join(", ")));
+                        }
+                    },
+                    _ => {
+                        result.push(format!("{variant_name}(..)"));
+                    },
+                },
+                EnumVariantFields::Struct(_) => {
+                    result.push(format!("{variant_name} {{ .. }}"));
+                },
+            }
+
+            if result.len() >= MAX_WITNESSES {
+                result.truncate(MAX_WITNESSES);
+                break;
+            }
+        }
+
+        result
+    }
+
+    else {
+        vec![String::from("_")]
+    }
+}
+
+// `[_, _, ..]`, `[1, _]`, `[.., 3]`
+fn render_list_witness(
+    elem_type: &Type,
+    prefix: &[PatternField],
+    path: &Path,
+    session: &Session,
+) -> Vec<String> {
+    let lengths = match get_condition(path, &with_field(prefix, &[PatternField::ListLength])) {
+        Some(WitnessCondition::In(ranges)) => ranges.iter().filter_map(
+            |range| {
+                let min = match range.lhs {
+                    Some(lhs) => u32::try_from(lhs).ok()? + if range.lhs_inclusive { 0 } else { 1 },
+                    None => 0,
+                };
+                let max = match range.rhs {
+                    Some(rhs) => Some(u32::try_from(rhs).ok()?.checked_sub(if range.rhs_inclusive { 0 } else { 1 })?),
+                    None => None,
+                };
+
+                // `1..3` is `[_]` or `[_, _]`, and `1..` is `[_, ..]`.
+                // Scalars are bounded, so a very long range is just `[_, ..]`.
+                match max {
+                    Some(max) if max - min < 4 => Some((min..=max).map(|len| (len, false)).collect::<Vec<_>>()),
+                    _ => Some(vec![(min, true)]),
+                }
+            }
+        ).flatten().take(MAX_WITNESSES).collect::<Vec<_>>(),
+        _ => vec![(0, true)],
+    };
+    let elements_prefix = with_field(prefix, &[PatternField::ListElements]);
+
+    // indexes of the elements that are constrained
+    let mut max_front = 0;
+    let mut max_back = 0;
+
+    for (field, _) in path.iter() {
+        if field.starts_with(&elements_prefix) && let Some(PatternField::ListIndex(i)) = field.get(elements_prefix.len()) {
+            if *i >= 0 {
+                max_front = max_front.max(*i as u32 + 1);
+            } else {
+                max_back = max_back.max((-*i) as u32);
+            }
+        }
+    }
+
+    let render_element = |index: i64| render_witness(
+        elem_type,
+        &with_field(&elements_prefix, &[PatternField::ListIndex(index)]),
+        path,
+        session,
+    );
+    let mut result = vec![];
+
+    for (min_len, has_rest) in lengths.into_iter() {
+        let elements = if has_rest {
+            let front = max_front.max(min_len.saturating_sub(max_back));
+            let back = max_back;
+            let mut elements = (0..front).map(|i| render_element(i as i64)).collect::<Vec<_>>();
+            elements.push(vec![String::from("..")]);
+            elements.extend((0..back).rev().map(|i| render_element(-(i as i64) - 1)));
+            elements
+        } else {
+            // The length is known, so a negative index is just another index.
+            (0..min_len).map(
+                |i| {
+                    let back_index = i as i64 - min_len as i64;
+
+                    if path.iter().any(|(field, _)| field.starts_with(&with_field(&elements_prefix, &[PatternField::ListIndex(back_index)]))) {
+                        render_element(back_index)
+                    } else {
+                        render_element(i as i64)
+                    }
+                }
+            ).collect()
+        };
+
+        if let [rest] = &elements[..] && rest[0] == ".." {
+            result.push(String::from("_"));
+        }
+
+        else {
+            for elements in cartesian_product(&elements).into_iter() {
+                result.push(format!("[{}]", elements.join(", ")));
+            }
+        }
+
+        if result.len() >= MAX_WITNESSES {
+            result.truncate(MAX_WITNESSES);
+            break;
+        }
+    }
+
+    result
+}
+
+// `0`, `..0`, `1..5`, `10..`, `'a'..='z'`
+fn render_range(range: &Range, literal_type: LiteralType, session: &Session) -> String {
+    let mut lhs = range.lhs;
+    let mut rhs = range.rhs;
+    let mut rhs_inclusive = range.rhs_inclusive;
+
+    if literal_type.is_int_like() {
+        // `(3, 5)` is `4..5`.
+        if let Some(n) = lhs && !range.lhs_inclusive {
+            lhs = Some(n.add_one());
+        }
+
+        // The smallest/largest value of the type doesn't have to be written.
+        let (min, max) = match literal_type {
+            LiteralType::Byte => (Some(0), Some(256)),
+            LiteralType::Char | LiteralType::Scalar => (Some(0), Some(0x110000)),
+            _ => (None, None),
+        };
+
+        if let (Some(n), Some(min)) = (lhs, min) && u32::try_from(n) == Ok(min) {
+            lhs = None;
+        }
+
+        if let (Some(n), Some(max)) = (rhs, max) {
+            let n = u32::try_from(n).map(|n| if rhs_inclusive { n + 1 } else { n });
+
+            if n == Ok(max) {
+                rhs = None;
+                rhs_inclusive = false;
+            }
+        }
+
+        // `3..4` is `3`.
+        if let (Some(l), Some(r)) = (lhs, rhs) {
+            let r = if rhs_inclusive { r } else { r.negate().add_one().negate() };
+
+            if l.cmp(r, &session.intermediate_dir) == Ordering::Equal {
+                return render_number(l, literal_type, session);
+            }
+        }
+    }
+
+    match (lhs, rhs) {
+        (None, None) => String::from("_"),
+        (lhs, rhs) => format!(
+            "{}..{}{}",
+            lhs.map(|lhs| render_number(lhs, literal_type, session)).unwrap_or_default(),
+            if rhs_inclusive { "=" } else { "" },
+            rhs.map(|rhs| render_number(rhs, literal_type, session)).unwrap_or_default(),
+        ),
+    }
+}
+
+fn render_number(n: InternedNumber, literal_type: LiteralType, session: &Session) -> String {
+    match literal_type {
+        LiteralType::Byte => match u8::try_from(n) {
+            Ok(b) => format!("#{b}"),
+            Err(_) => n.dump(&session.intermediate_dir),
+        },
+        LiteralType::Char => match u32::try_from(n).ok().and_then(char::from_u32) {
+            Some(ch) => format!("{ch:?}"),
+            None => n.dump(&session.intermediate_dir),
+        },
+        _ => n.dump(&session.intermediate_dir),
+    }
+}
+
+fn range_contains(range: &Range, n: u32, session: &Session) -> bool {
+    let n = InternedNumber::from_u32(n, true);
+    let lhs = match range.lhs {
+        Some(lhs) => matches!(
+            (lhs.cmp(n, &session.intermediate_dir), range.lhs_inclusive),
+            (Ordering::Less, _) | (Ordering::Equal, true),
+        ),
+        None => true,
+    };
+    let rhs = match range.rhs {
+        Some(rhs) => matches!(
+            (n.cmp(rhs, &session.intermediate_dir), range.rhs_inclusive),
+            (Ordering::Less, _) | (Ordering::Equal, true),
+        ),
+        None => true,
+    };
+
+    lhs && rhs
+}
+
+fn cartesian_product(alternatives: &[Vec<String>]) -> Vec<Vec<String>> {
+    let mut result = vec![vec![]];
+
+    for alternative in alternatives.iter() {
+        result = result.into_iter().flat_map(
+            |prev: Vec<String>| alternative.iter().map(
+                move |curr| [prev.clone(), vec![curr.clone()]].concat()
+            )
+        ).take(MAX_WITNESSES).collect();
+    }
+
+    result
+}
@@ -45,6 +45,16 @@ pub enum SpanDeriveKind {
     // `"3" as! <Int>` -> `std.convert.try_convert.<_, Int, _>("3").unwrap()`
     // `unwrap`'s derived span has this kind.
     UnwrapTryConvert,
+
+    // `fn double(x) = x + x; double(y)` -> `{ let x = y; x + x }`
+    // Every local name in the inlined body gets a new span, so that the
+    // same function can be inlined multiple times in a function.
+    Inline(u32),
+
+    // `fn rev(ns) = match ns { [] => [], [$n] ++ $ns => rev(ns) <+ n };` ->
+    // `fn rev(ns) = rev_acc(ns, []); fn rev_acc(ns, acc) = match ns { [] => acc, [$n] ++ $ns => rev_acc(ns, n +> acc) };`
+    AccumulatorFunc,  // `rev_acc`
+    AccumulatorParam,  // `acc`
 }
 
 impl SpanDeriveKind {
@@ -68,6 +78,8 @@ impl SpanDeriveKind {
             SpanDeriveKind::FStringConcat => Some("It is desugared to a `++` operator."),
             SpanDeriveKind::ConvertError => None,
             SpanDeriveKind::UnwrapTryConvert => None,
+            SpanDeriveKind::Inline(_) => Some("It is inlined."),
+            SpanDeriveKind::AccumulatorFunc | SpanDeriveKind::AccumulatorParam => Some("It is rewritten to a tail-recursive function with an accumulator."),
         }
     }
 }
@@ -166,6 +166,16 @@ impl Endec for SpanDeriveKind {
             SpanDeriveKind::UnwrapTryConvert => {
                 buffer.push(14);
             },
+            SpanDeriveKind::Inline(id) => {
+                buffer.push(15);
+                id.encode_impl(buffer);
+            },
+            SpanDeriveKind::AccumulatorFunc => {
+                buffer.push(16);
+            },
+            SpanDeriveKind::AccumulatorParam => {
+                buffer.push(17);
+            },
         }
     }
 
@@ -192,7 +202,13 @@ impl Endec for SpanDeriveKind {
             Some(12) => Ok((SpanDeriveKind::FStringConcat, cursor + 1)),
             Some(13) => Ok((SpanDeriveKind::ConvertError, cursor + 1)),
             Some(14) => Ok((SpanDeriveKind::UnwrapTryConvert, cursor + 1)),
-            Some(n @ 15..) => Err(DecodeError::InvalidEnumVariant(*n)),
+            Some(15) => {
+                let (id, cursor) = u32::decode_impl(buffer, cursor + 1)?;
+                Ok((SpanDeriveKind::Inline(id), cursor))
+            },
+            Some(16) => Ok((SpanDeriveKind::AccumulatorFunc, cursor + 1)),
+            Some(17) => Ok((SpanDeriveKind::AccumulatorParam, cursor + 1)),
+            Some(n @ 18..) => Err(DecodeError::InvalidEnumVariant(*n)),
             None => Err(DecodeError::UnexpectedEof),
         }
     }
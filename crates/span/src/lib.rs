@@ -158,6 +158,54 @@ impl Span {
         }
     }
 
+    // A 0-length span at the start of the span. It's used to insert code.
+    pub fn shrink_to_start(&self) -> Self {
+        match self {
+            Span::Range(_) => {
+                let (offset, _) = self.get_offset_and_length().unwrap();
+                Span::range(self.file().unwrap(), offset, 0)
+            },
+            Span::Monomorphize { id, span } => Span::Monomorphize {
+                id: *id,
+                span: Box::new(span.shrink_to_start()),
+            },
+            Span::Derived { kind, span } => Span::Derived {
+                kind: *kind,
+                span: Box::new(span.shrink_to_start()),
+            },
+            Span::Prelude(_) |
+            Span::Poly { .. } |
+            Span::IntermediateTypeVar(_) |
+            Span::Std |
+            Span::Lib => self.clone(),
+            Span::None => Span::None,
+        }
+    }
+
+    // A 0-length span at the end of the span. It's used to insert code.
+    pub fn shrink_to_end(&self) -> Self {
+        match self {
+            Span::Range(_) => {
+                let (offset, length) = self.get_offset_and_length().unwrap();
+                Span::range(self.file().unwrap(), offset + length, 0)
+            },
+            Span::Monomorphize { id, span } => Span::Monomorphize {
+                id: *id,
+                span: Box::new(span.shrink_to_end()),
+            },
+            Span::Derived { kind, span } => Span::Derived {
+                kind: *kind,
+                span: Box::new(span.shrink_to_end()),
+            },
+            Span::Prelude(_) |
+            Span::Poly { .. } |
+            Span::IntermediateTypeVar(_) |
+            Span::Std |
+            Span::Lib => self.clone(),
+            Span::None => Span::None,
+        }
+    }
+
     pub fn file(&self) -> Option<File> {
         match self {
             Span::Range(SpanId(r)) => Some(File(((r >> 64) & 0xffff_ffff) as u32)),
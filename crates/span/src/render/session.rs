@@ -62,29 +62,47 @@ impl Session {
     pub fn get_rect(&mut self, span: &Span) -> Option<(usize, usize, usize, usize)> {
         match (span.file(), span.get_offset_and_length()) {
             (Some(file), Some((offset, length))) => {
-                let line_breaks = match self.line_breaks.entry(file) {
-                    Entry::Occupied(e) => e.get().to_vec(),
-                    Entry::Vacant(e) => match file.read_bytes(&self.intermediate_dir) {
-                        Ok(Some(bytes)) => {
-                            let line_breaks = bytes.iter().enumerate().filter(
-                                |(_, b)| **b == b'\n'
-                            ).map(
-                                |(i, _)| i
-                            ).collect::<Vec<_>>();
-                            e.insert(line_breaks.clone());
-                            line_breaks
-                        },
-                        _ => {
-                            return None;
-                        },
-                    },
-                };
-
+                let line_breaks = self.get_line_breaks(file)?;
                 Some(get_rect(&line_breaks, offset as usize, (offset + length) as usize))
             },
             _ => None,
         }
     }
+
+    // (line, column) of a byte offset. Both are 1-based and the column counts bytes, not chars.
+    pub fn get_line_col(&mut self, file: File, offset: usize) -> Option<(usize, usize)> {
+        let line_breaks = self.get_line_breaks(file)?;
+        Some(get_line_col(&line_breaks, offset))
+    }
+
+    fn get_line_breaks(&mut self, file: File) -> Option<Vec<usize>> {
+        match self.line_breaks.entry(file) {
+            Entry::Occupied(e) => Some(e.get().to_vec()),
+            Entry::Vacant(e) => match file.read_bytes(&self.intermediate_dir) {
+                Ok(Some(bytes)) => {
+                    let line_breaks = bytes.iter().enumerate().filter(
+                        |(_, b)| **b == b'\n'
+                    ).map(
+                        |(i, _)| i
+                    ).collect::<Vec<_>>();
+                    e.insert(line_breaks.clone());
+                    Some(line_breaks)
+                },
+                _ => None,
+            },
+        }
+    }
+}
+
+fn get_line_col(line_breaks: &[usize], offset: usize) -> (usize, usize) {
+    // number of line breaks before `offset`
+    let line_no = match line_breaks.binary_search(&offset) {
+        Ok(n) => n,
+        Err(n) => n,
+    };
+    let line_start = if line_no == 0 { 0 } else { line_breaks[line_no - 1] + 1 };
+
+    (line_no + 1, offset - line_start + 1)
 }
 
 fn get_rect(line_breaks: &[usize], start: usize, end: usize) -> (usize, usize, usize, usize) {
@@ -132,7 +150,24 @@ fn get_rect(line_breaks: &[usize], start: usize, end: usize) -> (usize, usize, u
 
 #[cfg(test)]
 mod tests {
-    use super::get_rect;
+    use super::{get_line_col, get_rect};
+
+    #[test]
+    fn get_line_col_test() {
+        // a  b  c  \n
+        // d  e  \n
+        // f
+        assert_eq!(get_line_col(&[3, 6], 0), (1, 1));
+        assert_eq!(get_line_col(&[3, 6], 2), (1, 3));
+
+        // the line break itself belongs to the line it ends
+        assert_eq!(get_line_col(&[3, 6], 3), (1, 4));
+        assert_eq!(get_line_col(&[3, 6], 4), (2, 1));
+        assert_eq!(get_line_col(&[3, 6], 7), (3, 1));
+
+        // end of file
+        assert_eq!(get_line_col(&[3, 6], 8), (3, 2));
+    }
 
     #[test]
     fn get_rect_test() {
@@ -1,6 +1,7 @@
 use sodigy_driver::{
     Backend,
     ColorWhen,
+    MessageFormat,
     Error,
     OptimizeLevel,
     Profile,
@@ -48,9 +49,11 @@ pub fn runner(data: &[u8], target: &str) {
         false,  // dump-post-mir-log
         true,   // dump-timings
         false,  // dump-bytecodes
+        false,  // check-const-asserts
         0,  // graceful-shutdown
         8,  // jobs
         ColorWhen::Never,
+        MessageFormat::Human,
         true,  // incremental-compilation
         ValidateTokenSpans::Never,
         false,  // verify-built-ins
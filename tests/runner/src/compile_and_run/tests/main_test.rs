@@ -26,6 +26,12 @@ pub struct Directive {
     pub compile_error: Option<(Comparison, usize)>,
     pub compile_warning: Option<(Comparison, usize)>,
     pub run_error: Option<(Comparison, usize)>,
+
+    // `sodigy build --check-const-asserts`
+    pub check_const_asserts: bool,
+
+    // `sodigy build --message-format=json`
+    pub message_format_json: bool,
 }
 
 impl CnrContext {
@@ -78,6 +84,14 @@ impl CnrContext {
             args.push("--dump-post-mir-log");
         }
 
+        if directive.check_const_asserts {
+            args.push("--check-const-asserts");
+        }
+
+        if directive.message_format_json {
+            args.push("--message-format=json");
+        }
+
         // The cnr test runner has to validate the spans of the tokens. But it doesn't
         // have to validate the tokens in std, because they're the same!
         // Ideally, we have to run `--validate-token-spans` only when `self.cnr_seq == 0`,
@@ -209,6 +223,8 @@ fn parse_directive(file_path: &str) -> Result<Directive, FileError> {
     let mut compile_error = None;
     let mut compile_warning = None;
     let mut run_error = None;
+    let mut check_const_asserts = false;
+    let mut message_format_json = false;
 
     for line in s.lines() {
         if line.starts_with("//%") {
@@ -231,6 +247,12 @@ fn parse_directive(file_path: &str) -> Result<Directive, FileError> {
                     Some(_) => error(file_path, line),
                     None => { expected_status = Some(Status::RunFail); },
                 },
+                "check-const-asserts" => {
+                    check_const_asserts = true;
+                },
+                "message-format-json" => {
+                    message_format_json = true;
+                },
                 _ if directive.starts_with("compile-error") || directive.starts_with("compile-warning") || directive.starts_with("run-error") => {
                     let (kind, directive) = match directive {
                         _ if directive.starts_with("compile-error") => ("ce", directive.get(13..).unwrap().trim()),
@@ -303,11 +325,19 @@ fn parse_directive(file_path: &str) -> Result<Directive, FileError> {
         compile_error,
         compile_warning,
         run_error,
+        check_const_asserts,
+        message_format_json,
     })
 }
 
 fn check_compile_output(output: &subprocess::Output, directive: &Directive, expected_output: &ExpectedOutput) -> Result<(), String> {
-    let (compile_errors, compile_warnings) = match count_compile_errors_and_warnings(&String::from_utf8_lossy(&output.stderr)) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let compile_errors_and_warnings = if directive.message_format_json {
+        Some(count_compile_errors_and_warnings_json(&stderr))
+    } else {
+        count_compile_errors_and_warnings(&stderr)
+    };
+    let (compile_errors, compile_warnings) = match compile_errors_and_warnings {
         Some((e, w)) => (e, w),
         None => {
             return Err(String::from("failed to parse the compiler output"));
@@ -329,7 +359,7 @@ fn check_compile_output(output: &subprocess::Output, directive: &Directive, expe
     }
 
     match_lines(&String::from_utf8_lossy(&output.stdout), &expected_output.compile_stdout).map_err(|e| format!("expected compile_stdout and actual stdout do not match\n{e}"))?;
-    match_lines(&String::from_utf8_lossy(&output.stderr), &expected_output.compile_stderr).map_err(|e| format!("expected compile_stderr and actual stderr do not match\n{e}"))?;
+    match_lines(&stderr, &expected_output.compile_stderr).map_err(|e| format!("expected compile_stderr and actual stderr do not match\n{e}"))?;
     Ok(())
 }
 
@@ -366,6 +396,24 @@ fn count_compile_errors_and_warnings(output: &str) -> Option<(usize, usize)> {
     None
 }
 
+// With `--message-format=json`, there's no summary line. Instead, each error/warning is a json object in a line.
+fn count_compile_errors_and_warnings_json(output: &str) -> (usize, usize) {
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for line in output.lines() {
+        if line.starts_with("{\"level\":\"error\"") {
+            errors += 1;
+        }
+
+        else if line.starts_with("{\"level\":\"warning\"") {
+            warnings += 1;
+        }
+    }
+
+    (errors, warnings)
+}
+
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Comparison {
     Gt,